@@ -0,0 +1,172 @@
+//! Baseline Module
+//!
+//! Compares a response with its request's baseline, the example saved under the
+//! name `baseline` (see the examples module). Status, headers, and body are checked.
+//! Headers that change on every call, like `Date`, are left out, and JSON bodies are
+//! pretty-printed on both sides so only real changes show up.
+
+use super::request::format_json;
+use super::types::Response;
+use crate::utils::{diff_lines, DiffLine};
+use std::collections::BTreeMap;
+
+/// Example name the baseline is stored under
+pub const BASELINE_EXAMPLE: &str = "baseline";
+
+/// Headers that differ between identical responses, so never count as a change
+const VOLATILE_HEADERS: &[&str] = &[
+    "date",
+    "age",
+    "expires",
+    "last-modified",
+    "etag",
+    "set-cookie",
+    "content-length",
+    "x-request-id",
+    "cf-ray",
+];
+
+/// A header that differs from the baseline
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderChange {
+    Added(String, String),
+    Removed(String, String),
+    Changed {
+        name: String,
+        old: String,
+        new: String,
+    },
+}
+
+/// How a response differs from the baseline
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BaselineDiff {
+    /// `(baseline, current)` when the status changed
+    pub status: Option<(u16, u16)>,
+    pub headers: Vec<HeaderChange>,
+    pub body: Vec<DiffLine>,
+}
+
+impl BaselineDiff {
+    /// Body lines added or removed
+    pub fn changed_lines(&self) -> usize {
+        self.body
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Same(_)))
+            .count()
+    }
+
+    pub fn is_match(&self) -> bool {
+        self.status.is_none() && self.headers.is_empty() && self.changed_lines() == 0
+    }
+}
+
+/// Headers by lowercase name, repeated ones joined as `a, b`; volatile ones dropped
+fn header_map(headers: &[(String, String)]) -> BTreeMap<String, (String, String)> {
+    let mut map: BTreeMap<String, (String, String)> = BTreeMap::new();
+    for (name, value) in headers {
+        let key = name.to_lowercase();
+        if VOLATILE_HEADERS.contains(&key.as_str()) {
+            continue;
+        }
+        map.entry(key)
+            .and_modify(|(_, v)| {
+                v.push_str(", ");
+                v.push_str(value);
+            })
+            .or_insert_with(|| (name.clone(), value.clone()));
+    }
+    map
+}
+
+pub fn compare(baseline: &Response, current: &Response) -> BaselineDiff {
+    let old = header_map(&baseline.headers);
+    let new = header_map(&current.headers);
+    let mut headers = Vec::new();
+    for (key, (name, value)) in &old {
+        match new.get(key) {
+            None => headers.push(HeaderChange::Removed(name.clone(), value.clone())),
+            Some((_, new_value)) if new_value != value => headers.push(HeaderChange::Changed {
+                name: name.clone(),
+                old: value.clone(),
+                new: new_value.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (key, (name, value)) in &new {
+        if !old.contains_key(key) {
+            headers.push(HeaderChange::Added(name.clone(), value.clone()));
+        }
+    }
+
+    BaselineDiff {
+        status: (baseline.status != current.status).then_some((baseline.status, current.status)),
+        headers,
+        body: diff_lines(&format_json(&baseline.body), &format_json(&current.body)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, headers: &[(&str, &str)], body: &str) -> Response {
+        Response {
+            status,
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            body: body.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_same_response_matches() {
+        let baseline = response(
+            200,
+            &[("Content-Type", "application/json"), ("Date", "Mon")],
+            r#"{"id": 1, "name": "ada"}"#,
+        );
+        // Formatting and volatile headers don't count
+        let current = response(
+            200,
+            &[("content-type", "application/json"), ("Date", "Tue")],
+            r#"{"name":"ada","id":1}"#,
+        );
+        assert!(compare(&baseline, &current).is_match());
+    }
+
+    #[test]
+    fn test_reports_changes() {
+        let baseline = response(
+            200,
+            &[("Content-Type", "application/json"), ("X-Version", "1")],
+            r#"{"id": 1}"#,
+        );
+        let current = response(
+            500,
+            &[("Content-Type", "text/plain"), ("Retry-After", "5")],
+            "oops",
+        );
+        let diff = compare(&baseline, &current);
+
+        assert!(!diff.is_match());
+        assert_eq!(diff.status, Some((200, 500)));
+        assert_eq!(
+            diff.headers,
+            vec![
+                HeaderChange::Changed {
+                    name: "Content-Type".to_string(),
+                    old: "application/json".to_string(),
+                    new: "text/plain".to_string(),
+                },
+                HeaderChange::Removed("X-Version".to_string(), "1".to_string()),
+                HeaderChange::Added("Retry-After".to_string(), "5".to_string()),
+            ]
+        );
+        assert_eq!(diff.changed_lines(), 4);
+    }
+}
@@ -0,0 +1,569 @@
+//! Code Generation Module
+//!
+//! Turns a request/response pair into snippets for other tools, such as cURL
+//! commands, mock server definitions for stubbing an API that isn't ready yet,
+//! or Markdown examples for API docs.
+
+use super::request::{HttpResponse, ResponseType};
+use super::types::HttpMethod;
+use serde_json::{json, Map, Value};
+
+/// Mock tools a response can be copied for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MockFormat {
+    WireMock,
+    Msw,
+    JsonServer,
+}
+
+impl MockFormat {
+    pub const ALL: [MockFormat; 3] = [
+        MockFormat::WireMock,
+        MockFormat::Msw,
+        MockFormat::JsonServer,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MockFormat::WireMock => "WireMock stub",
+            MockFormat::Msw => "MSW handler",
+            MockFormat::JsonServer => "json-server db",
+        }
+    }
+}
+
+/// Headers that describe the original transfer rather than the payload
+const SKIPPED_HEADERS: &[&str] = &[
+    "connection",
+    "content-encoding",
+    "content-length",
+    "date",
+    "keep-alive",
+    "set-cookie",
+    "transfer-encoding",
+];
+
+fn mock_headers(response: &HttpResponse) -> Map<String, Value> {
+    response
+        .headers
+        .iter()
+        .filter(|(k, _)| !SKIPPED_HEADERS.contains(&k.to_lowercase().as_str()))
+        .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+        .collect()
+}
+
+fn json_body(response: &HttpResponse) -> Option<Value> {
+    if response.response_type == ResponseType::Json {
+        serde_json::from_str(&response.body).ok()
+    } else {
+        None
+    }
+}
+
+/// Split a URL into (origin + path, path); the query is never part of the match
+fn split_url(url: &str) -> (String, String) {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => (
+            format!("{}{}", parsed.origin().ascii_serialization(), parsed.path()),
+            parsed.path().to_string(),
+        ),
+        Err(_) => {
+            let without_query = url.split(['?', '#']).next().unwrap_or(url).to_string();
+            (without_query.clone(), without_query)
+        }
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// Render `response` as a mock definition for `format`, matched on `method` and `url`
+pub fn generate_mock(
+    format: MockFormat,
+    method: &HttpMethod,
+    url: &str,
+    response: &HttpResponse,
+) -> String {
+    match format {
+        MockFormat::WireMock => wiremock_stub(method, url, response),
+        MockFormat::Msw => msw_handler(method, url, response),
+        MockFormat::JsonServer => json_server_db(url, response),
+    }
+}
+
+fn wiremock_stub(method: &HttpMethod, url: &str, response: &HttpResponse) -> String {
+    let (_, path) = split_url(url);
+    let mut mock_response = json!({ "status": response.status });
+    let headers = mock_headers(response);
+    if !headers.is_empty() {
+        mock_response["headers"] = Value::Object(headers);
+    }
+    match json_body(response) {
+        Some(body) => mock_response["jsonBody"] = body,
+        None if !response.body.is_empty() => {
+            mock_response["body"] = Value::String(response.body.clone())
+        }
+        None => {}
+    }
+
+    pretty(&json!({
+        "request": { "method": method.as_str(), "urlPath": path },
+        "response": mock_response,
+    }))
+}
+
+fn msw_handler(method: &HttpMethod, url: &str, response: &HttpResponse) -> String {
+    let (target, _) = split_url(url);
+    let handler = match method {
+        HttpMethod::CONNECT | HttpMethod::TRACE => "all".to_string(),
+        other => other.as_str().to_lowercase(),
+    };
+
+    let mut init = json!({ "status": response.status });
+    let headers = mock_headers(response);
+    if !headers.is_empty() {
+        init["headers"] = Value::Object(headers);
+    }
+    let init = indent_continuation(&pretty(&init), "  ");
+
+    let body = match json_body(response) {
+        Some(body) => format!(
+            "HttpResponse.json({}, {})",
+            indent_continuation(&pretty(&body), "  "),
+            init
+        ),
+        // JSON string literals are valid JavaScript strings
+        None => format!(
+            "new HttpResponse({}, {})",
+            Value::String(response.body.clone()),
+            init
+        ),
+    };
+
+    format!(
+        "http.{}({}, () => {{\n  return {}\n}}),",
+        handler,
+        Value::String(target),
+        body
+    )
+}
+
+fn json_server_db(url: &str, response: &HttpResponse) -> String {
+    let (_, path) = split_url(url);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    // `/users/1` returns one item of the `users` collection
+    let is_item = segments
+        .last()
+        .is_some_and(|s| s.chars().all(|c| c.is_ascii_digit()));
+    let resource = segments
+        .iter()
+        .rev()
+        .find(|s| !s.chars().all(|c| c.is_ascii_digit()))
+        .copied()
+        .unwrap_or("data");
+
+    let body = json_body(response).unwrap_or_else(|| Value::String(response.body.clone()));
+    let body = if is_item && body.is_object() {
+        Value::Array(vec![body])
+    } else {
+        body
+    };
+
+    let mut db = Map::new();
+    db.insert(resource.to_string(), body);
+    pretty(&Value::Object(db))
+}
+
+/// What a Markdown example includes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarkdownOptions {
+    pub include_headers: bool,
+    /// Mask credential headers such as `Authorization` and `Set-Cookie`
+    pub redact_secrets: bool,
+}
+
+/// Headers that carry credentials whatever their names say
+const CREDENTIAL_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+];
+
+pub fn is_credential_header(name: &str) -> bool {
+    CREDENTIAL_HEADERS.contains(&name.to_lowercase().as_str())
+        || crate::utils::is_secret_name(&name.replace('-', "_"))
+}
+
+/// The `{{NAME}}` a literal credential is replaced with: `X-Api-Key` gives `X_API_KEY`
+fn placeholder_name(name: &str) -> String {
+    name.trim().to_uppercase().replace('-', "_")
+}
+
+/// Whether `value`, found under `name`, is a literal credential to mask
+fn is_literal_secret(name: &str, value: &str) -> bool {
+    !value.is_empty()
+        && !value.contains("{{")
+        && crate::utils::is_secret_name(&name.replace('-', "_"))
+}
+
+/// `pairs` (`a=1&b=2`, as in a query string or form body) with the values of
+/// secret-named keys replaced by placeholders
+fn mask_pairs(pairs: &str, names: &mut Vec<String>) -> String {
+    pairs
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if is_literal_secret(key, value) => {
+                let placeholder = placeholder_name(key);
+                names.push(placeholder.clone());
+                format!("{}={{{{{}}}}}", key, placeholder)
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Replace string values under secret-named keys, at any depth
+fn mask_json(value: &mut Value, names: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                match field {
+                    Value::String(s) if is_literal_secret(key, s) => {
+                        let placeholder = placeholder_name(key);
+                        *s = format!("{{{{{}}}}}", placeholder);
+                        names.push(placeholder);
+                    }
+                    _ => mask_json(field, names),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| mask_json(item, names)),
+        _ => {}
+    }
+}
+
+/// Replace literal values of credential headers, and of secret-named query
+/// parameters and body fields, with `{{NAME}}` placeholders. Values that
+/// already hold a placeholder are left alone. A JSON body that changes is
+/// pretty-printed. Returns the names of the placeholders added.
+pub fn mask_literal_secrets(
+    url: &mut String,
+    headers: &mut [(String, String)],
+    body: &mut String,
+) -> Vec<String> {
+    let mut names = Vec::new();
+    for (name, value) in headers.iter_mut() {
+        if is_credential_header(name) && !value.is_empty() && !value.contains("{{") {
+            let placeholder = placeholder_name(name);
+            *value = format!("{{{{{}}}}}", placeholder);
+            names.push(placeholder);
+        }
+    }
+
+    if let Some((base, rest)) = url.split_once('?') {
+        let (query, fragment) = match rest.split_once('#') {
+            Some((query, fragment)) => (query, Some(fragment)),
+            None => (rest, None),
+        };
+        let mut masked = format!("{}?{}", base, mask_pairs(query, &mut names));
+        if let Some(fragment) = fragment {
+            masked.push('#');
+            masked.push_str(fragment);
+        }
+        *url = masked;
+    }
+
+    if let Ok(mut value) = serde_json::from_str::<Value>(body) {
+        let before = names.len();
+        mask_json(&mut value, &mut names);
+        if names.len() > before {
+            *body = pretty(&value);
+        }
+    } else if body.contains('=') && !body.contains(char::is_whitespace) {
+        *body = mask_pairs(body, &mut names);
+    }
+    names
+}
+
+/// A request as a cURL command. Variables should already be resolved. A hex body
+/// is piped in as the bytes it spells, in octal escapes any POSIX printf understands.
+pub fn generate_curl(
+    method: &HttpMethod,
+    url: &str,
+    headers: &[(String, String)],
+    body: &str,
+    body_hex: bool,
+) -> String {
+    let mut curl = format!("curl -X {} '{}'", method.as_str(), url);
+
+    for (key, value) in headers {
+        curl.push_str(&format!(" \\\n  -H '{}: {}'", key, value));
+    }
+
+    let hex_bytes = body_hex
+        .then(|| crate::utils::parse_hex(body).ok())
+        .flatten();
+    if let Some(bytes) = hex_bytes.filter(|bytes| !bytes.is_empty()) {
+        let escaped: String = bytes.iter().map(|b| format!("\\{:03o}", b)).collect();
+        curl = format!("printf '{}' | {} \\\n  --data-binary @-", escaped, curl);
+    } else if !body.is_empty() {
+        curl.push_str(&format!(" \\\n  -d '{}'", body.replace('\'', "'\\''")));
+    }
+
+    curl
+}
+
+/// `request` and its `response` as two fenced `http` blocks, ready to paste into
+/// a README. Variables should already be resolved.
+pub fn generate_markdown(
+    method: &HttpMethod,
+    url: &str,
+    request_headers: &[(String, String)],
+    request_body: &str,
+    response: &HttpResponse,
+    options: MarkdownOptions,
+) -> String {
+    let header_lines = |headers: &[(String, String)], skipped: &[&str]| -> String {
+        headers
+            .iter()
+            .filter(|(k, _)| !skipped.contains(&k.to_lowercase().as_str()))
+            .map(|(k, v)| {
+                let value = if options.redact_secrets && is_credential_header(k) {
+                    "<redacted>"
+                } else {
+                    v.as_str()
+                };
+                format!("{}: {}\n", k, value)
+            })
+            .collect()
+    };
+
+    let mut request = format!("{} {}\n", method.as_str(), url);
+    if options.include_headers {
+        request.push_str(&header_lines(request_headers, &[]));
+    }
+    let body = request_body.trim();
+    if !body.is_empty() {
+        request.push('\n');
+        request.push_str(&match serde_json::from_str::<Value>(body) {
+            Ok(value) => pretty(&value),
+            Err(_) => body.to_string(),
+        });
+        request.push('\n');
+    }
+
+    let mut reply = format!("HTTP/1.1 {}\n", response.status_text);
+    if options.include_headers {
+        reply.push_str(&header_lines(&response.headers, SKIPPED_HEADERS));
+    }
+    let body = match json_body(response) {
+        Some(body) => pretty(&body),
+        None => response.body.trim().to_string(),
+    };
+    if !body.is_empty() {
+        reply.push('\n');
+        reply.push_str(&body);
+        reply.push('\n');
+    }
+
+    format!(
+        "**Request**\n\n{}\n**Response**\n\n{}",
+        fenced(&request, "http"),
+        fenced(&reply, "http")
+    )
+}
+
+/// `text` in a code fence longer than any backtick run inside it
+fn fenced(text: &str, language: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}{}\n", fence, language, text, fence)
+}
+
+/// Indent every line after the first, for nesting multi-line JSON in code
+fn indent_continuation(text: &str, indent: &str) -> String {
+    text.replace('\n', &format!("\n{}", indent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &str, response_type: ResponseType) -> HttpResponse {
+        HttpResponse {
+            status: 200,
+            status_text: "200 OK".to_string(),
+            headers: vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("content-length".to_string(), "17".to_string()),
+            ],
+            cookies: Vec::new(),
+            body: body.to_string(),
+            raw_bytes: None,
+            duration_ms: 12,
+            size_bytes: body.len(),
+            content_type: "application/json".to_string(),
+            response_type,
+            lossy_utf8: false,
+        }
+    }
+
+    #[test]
+    fn test_mask_literal_secrets() {
+        let mut url =
+            "https://api.example.com/items?page=2&api_key=abc123&token={{TOKEN}}#top".to_string();
+        let mut headers = vec![
+            ("Authorization".to_string(), "Bearer abc".to_string()),
+            ("X-Api-Key".to_string(), "{{API_KEY}}".to_string()),
+            ("Accept".to_string(), "application/json".to_string()),
+        ];
+        let mut body = "grant_type=password&password=hunter2".to_string();
+        let names = mask_literal_secrets(&mut url, &mut headers, &mut body);
+
+        assert_eq!(names, vec!["AUTHORIZATION", "API_KEY", "PASSWORD"]);
+        assert_eq!(
+            url,
+            "https://api.example.com/items?page=2&api_key={{API_KEY}}&token={{TOKEN}}#top"
+        );
+        assert_eq!(headers[0].1, "{{AUTHORIZATION}}");
+        assert_eq!(headers[1].1, "{{API_KEY}}");
+        assert_eq!(headers[2].1, "application/json");
+        assert_eq!(body, "grant_type=password&password={{PASSWORD}}");
+
+        let mut body = r#"{"user": {"client_secret": "s3cret"}, "tags": []}"#.to_string();
+        let names = mask_literal_secrets(&mut String::new(), &mut [], &mut body);
+        assert_eq!(names, vec!["CLIENT_SECRET"]);
+        let masked: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(masked["user"]["client_secret"], "{{CLIENT_SECRET}}");
+
+        let mut plain = "https://api.example.com/items".to_string();
+        let mut text = "plain text = fine".to_string();
+        assert!(mask_literal_secrets(&mut plain, &mut [], &mut text).is_empty());
+        assert_eq!(plain, "https://api.example.com/items");
+        assert_eq!(text, "plain text = fine");
+    }
+
+    #[test]
+    fn test_wiremock_stub() {
+        let stub = generate_mock(
+            MockFormat::WireMock,
+            &HttpMethod::GET,
+            "https://api.example.com/users/1?expand=true",
+            &response(r#"{"id":1}"#, ResponseType::Json),
+        );
+        let stub: Value = serde_json::from_str(&stub).unwrap();
+
+        assert_eq!(stub["request"]["method"], "GET");
+        assert_eq!(stub["request"]["urlPath"], "/users/1");
+        assert_eq!(stub["response"]["status"], 200);
+        assert_eq!(stub["response"]["jsonBody"]["id"], 1);
+        assert_eq!(
+            stub["response"]["headers"],
+            json!({"content-type": "application/json"})
+        );
+    }
+
+    #[test]
+    fn test_msw_handler() {
+        let handler = generate_mock(
+            MockFormat::Msw,
+            &HttpMethod::POST,
+            "https://api.example.com/users?x=1",
+            &response("created", ResponseType::PlainText),
+        );
+
+        assert!(handler.starts_with("http.post(\"https://api.example.com/users\", () => {"));
+        assert!(handler.contains("new HttpResponse(\"created\", {"));
+        assert!(handler.ends_with("}),"));
+    }
+
+    #[test]
+    fn test_json_server_db() {
+        let db = generate_mock(
+            MockFormat::JsonServer,
+            &HttpMethod::GET,
+            "https://api.example.com/users/1",
+            &response(r#"{"id":1}"#, ResponseType::Json),
+        );
+        let db: Value = serde_json::from_str(&db).unwrap();
+
+        assert_eq!(db, json!({"users": [{"id": 1}]}));
+    }
+
+    #[test]
+    fn test_curl_command() {
+        let headers = vec![("Accept".to_string(), "application/json".to_string())];
+        let curl = generate_curl(
+            &HttpMethod::POST,
+            "https://api.example.com/notes",
+            &headers,
+            "{\"note\":\"it's\"}",
+            false,
+        );
+        assert_eq!(
+            curl,
+            "curl -X POST 'https://api.example.com/notes' \\\n  -H 'Accept: application/json' \\\n  -d '{\"note\":\"it'\\''s\"}'"
+        );
+
+        let curl = generate_curl(&HttpMethod::PUT, "https://x.test", &[], "0a ff", true);
+        assert_eq!(
+            curl,
+            "printf '\\012\\377' | curl -X PUT 'https://x.test' \\\n  --data-binary @-"
+        );
+    }
+
+    #[test]
+    fn test_markdown_example() {
+        let headers = vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("Authorization".to_string(), "Bearer abc123".to_string()),
+            ("X-Api-Key".to_string(), "k-42".to_string()),
+        ];
+        let options = MarkdownOptions {
+            include_headers: true,
+            redact_secrets: true,
+        };
+        let markdown = generate_markdown(
+            &HttpMethod::POST,
+            "https://api.example.com/users",
+            &headers,
+            r#"{"name":"Ada"}"#,
+            &response(r#"{"id":1}"#, ResponseType::Json),
+            options,
+        );
+
+        assert!(markdown.starts_with(
+            "**Request**\n\n```http\nPOST https://api.example.com/users\nContent-Type: application/json\n"
+        ));
+        assert!(markdown.contains("Authorization: <redacted>\nX-Api-Key: <redacted>\n"));
+        assert!(!markdown.contains("abc123"));
+        assert!(markdown.contains("{\n  \"name\": \"Ada\"\n}\n```\n"));
+        assert!(markdown.contains(
+            "**Response**\n\n```http\nHTTP/1.1 200 OK\ncontent-type: application/json\n\n{\n  \"id\": 1\n}\n```\n"
+        ));
+
+        // Without headers, and a body holding a fence of its own
+        let markdown = generate_markdown(
+            &HttpMethod::GET,
+            "https://api.example.com/readme",
+            &headers,
+            "",
+            &response("```sh\nls\n```", ResponseType::PlainText),
+            MarkdownOptions {
+                include_headers: false,
+                redact_secrets: false,
+            },
+        );
+        assert!(!markdown.contains("Content-Type"));
+        assert!(markdown.contains("````http\nHTTP/1.1 200 OK\n\n```sh"));
+        assert!(markdown.ends_with("```\n````\n"));
+    }
+}
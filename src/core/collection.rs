@@ -0,0 +1,418 @@
+//! Collection Module
+//!
+//! Builds the sidebar tree from a workspace folder. Symlinked folders are
+//! followed, but one that leads back to a folder already in the tree is skipped,
+//! so a link loop can't recurse forever or list the same requests twice.
+//! Paths matched by the workspace's `.mercuryignore` are left out.
+
+use super::constants::{IGNORE_FILE, MAX_SCAN_DEPTH};
+use super::types::{CollectionItem, HttpFileEntry, JsonRequest};
+use crate::parser::{parse_http_file, parse_request_file};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The scanned tree, plus any symlinks that pointed back at one of their own
+/// parent folders and were skipped
+#[derive(Debug, Default)]
+pub struct CollectionScan {
+    pub items: Vec<CollectionItem>,
+    pub loops: Vec<PathBuf>,
+}
+
+/// The workspace's `.mercuryignore` rules; empty if there's no such file.
+/// Lines that aren't valid patterns are skipped.
+pub fn workspace_ignore(workspace: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(workspace);
+    let path = workspace.join(IGNORE_FILE);
+    if path.is_file() {
+        builder.add(path);
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Whether `ignore` leaves `path` out, or a folder it's in. Paths are compared
+/// as given and then with links resolved, so a workspace opened through a
+/// symlink matches the paths the OS reports for it.
+pub fn is_ignored(ignore: &Gitignore, path: &Path, is_dir: bool) -> bool {
+    if ignore.is_empty() {
+        return false;
+    }
+    let relative = match path.strip_prefix(ignore.path()) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => {
+            let (Some(root), Some(path)) = (resolve(ignore.path()), resolve(path)) else {
+                return false;
+            };
+            match path.strip_prefix(&root) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => return false,
+            }
+        }
+    };
+    ignore
+        .matched_path_or_any_parents(relative, is_dir)
+        .is_ignore()
+}
+
+/// `path` with links resolved; for a path that no longer exists (a deleted
+/// file), its folder is resolved instead
+fn resolve(path: &Path) -> Option<PathBuf> {
+    path.canonicalize().ok().or_else(|| {
+        let parent = path.parent()?.canonicalize().ok()?;
+        Some(parent.join(path.file_name()?))
+    })
+}
+
+/// The workspace and every folder under it that `ignore` doesn't leave out,
+/// for the file watcher to register one by one. Links are followed as in
+/// `scan_collection`, each folder listed once.
+pub fn watched_dirs(workspace: &Path, ignore: &Gitignore) -> Vec<PathBuf> {
+    let mut dirs = vec![workspace.to_path_buf()];
+    let mut visited: HashSet<PathBuf> = workspace.canonicalize().into_iter().collect();
+    let mut next = 0;
+    while next < dirs.len() {
+        let dir = dirs[next].clone();
+        next += 1;
+        if dir.components().count() > workspace.components().count() + MAX_SCAN_DEPTH {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.is_dir()
+                && !ignore.matched(&path, true).is_ignore()
+                && path.canonicalize().is_ok_and(|real| visited.insert(real))
+            {
+                dirs.push(path);
+            }
+        }
+    }
+    dirs
+}
+
+/// Scan `dir` into tree items, folders first. A folder is expanded if it's in
+/// `expanded`, or always when `expanded` is empty (first load). Paths `ignore`
+/// matches are skipped.
+pub fn scan_collection(
+    dir: &Path,
+    expanded: &HashSet<PathBuf>,
+    ignore: &Gitignore,
+) -> CollectionScan {
+    let mut scan = CollectionScan::default();
+    let mut visited = HashSet::new();
+    let mut ancestors = Vec::new();
+    if let Ok(root) = dir.canonicalize() {
+        visited.insert(root.clone());
+        ancestors.push(root);
+    }
+    scan.items = scan_dir(
+        dir,
+        expanded,
+        ignore,
+        &mut visited,
+        &mut ancestors,
+        &mut scan.loops,
+    );
+    scan
+}
+
+fn scan_dir(
+    dir: &Path,
+    expanded: &HashSet<PathBuf>,
+    ignore: &Gitignore,
+    visited: &mut HashSet<PathBuf>,
+    ancestors: &mut Vec<PathBuf>,
+    loops: &mut Vec<PathBuf>,
+) -> Vec<CollectionItem> {
+    let mut folders = Vec::new();
+    let mut requests = Vec::new();
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return folders;
+    };
+    let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.path());
+
+    for entry in entries {
+        let path = entry.path();
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        // Skip hidden files and env files, and whatever `.mercuryignore` lists
+        if name.starts_with('.') || ignore.matched(&path, path.is_dir()).is_ignore() {
+            continue;
+        }
+
+        if path.is_dir() {
+            let Ok(canonical) = path.canonicalize() else {
+                continue;
+            };
+            if ancestors.contains(&canonical) {
+                loops.push(path);
+                continue;
+            }
+            // Only links can reach a folder twice; it stays where it was first found
+            let is_link = entry.file_type().is_ok_and(|t| t.is_symlink());
+            if (!visited.insert(canonical.clone()) && is_link) || ancestors.len() > MAX_SCAN_DEPTH {
+                continue;
+            }
+
+            ancestors.push(canonical);
+            let children = scan_dir(&path, expanded, ignore, visited, ancestors, loops);
+            ancestors.pop();
+
+            folders.push(CollectionItem::Folder {
+                name,
+                expanded: expanded.contains(&path) || expanded.is_empty(),
+                path,
+                children,
+            });
+        } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            let parsed = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| parse_request_file(&content).ok());
+            let (method, url, tags) = match parsed {
+                Some(request) => (Some(request.method), request.url, request.tags),
+                None => (None, String::new(), Vec::new()),
+            };
+
+            requests.push(CollectionItem::Request {
+                name,
+                path,
+                method,
+                url,
+                tags,
+            });
+        } else if is_http_file(&path) {
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            let entries = parse_http_file(&content)
+                .into_iter()
+                .map(|entry| HttpFileEntry {
+                    name: entry.name.unwrap_or_else(|| {
+                        format!("{} {}", entry.request.method.as_str(), entry.request.url)
+                    }),
+                    method: entry.request.method,
+                    url: entry.request.url,
+                })
+                .collect();
+
+            requests.push(CollectionItem::HttpFile {
+                name,
+                expanded: expanded.contains(&path),
+                path,
+                requests: entries,
+            });
+        }
+    }
+
+    // Combine folders first, then requests
+    folders.extend(requests);
+    folders
+}
+
+/// REST Client files (`.http` or `.rest`), shown in the tree with their requests
+pub fn is_http_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|s| s.to_str()),
+        Some("http" | "rest")
+    )
+}
+
+/// Paths of every request file in `items`, in tree order; `.http` files are left out
+pub fn request_paths(items: &[CollectionItem]) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for item in items {
+        match item {
+            CollectionItem::Folder { children, .. } => paths.extend(request_paths(children)),
+            CollectionItem::Request { path, .. } => paths.push(path.clone()),
+            CollectionItem::HttpFile { .. } => {}
+        }
+    }
+    paths
+}
+
+/// The requests at `paths` that carry docs, such as those imported from OpenAPI
+pub fn documented_requests(paths: &[PathBuf]) -> Vec<(PathBuf, JsonRequest)> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let request = parse_request_file(&fs::read_to_string(path).ok()?).ok()?;
+            request.docs.is_some().then(|| (path.clone(), request))
+        })
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    fn folder_names(items: &[CollectionItem]) -> Vec<String> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                CollectionItem::Folder { name, .. } => Some(name.clone()),
+                CollectionItem::Request { .. } | CollectionItem::HttpFile { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_skips_symlink_loop() {
+        let workspace = TempDir::new().unwrap();
+        let users = workspace.path().join("users");
+        fs::create_dir_all(&users).unwrap();
+        fs::write(
+            users.join("list.json"),
+            r#"{"method": "GET", "url": "/users"}"#,
+        )
+        .unwrap();
+        // users/back -> workspace root, users/self -> users
+        symlink(workspace.path(), users.join("back")).unwrap();
+        symlink(&users, users.join("self")).unwrap();
+
+        let scan = scan_collection(workspace.path(), &HashSet::new(), &Gitignore::empty());
+
+        assert_eq!(folder_names(&scan.items), vec!["users"]);
+        let CollectionItem::Folder { children, .. } = &scan.items[0] else {
+            panic!("expected a folder");
+        };
+        assert!(folder_names(children).is_empty());
+        assert_eq!(children.len(), 1);
+        let CollectionItem::Request { url, .. } = &children[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(url, "/users");
+        assert_eq!(scan.loops, vec![users.join("back"), users.join("self")]);
+    }
+
+    #[test]
+    fn test_scan_follows_links_once() {
+        let workspace = TempDir::new().unwrap();
+        let shared = TempDir::new().unwrap();
+        fs::write(
+            shared.path().join("health.json"),
+            r#"{"method": "GET", "url": "/health"}"#,
+        )
+        .unwrap();
+        symlink(shared.path(), workspace.path().join("a-shared")).unwrap();
+        symlink(shared.path(), workspace.path().join("b-shared")).unwrap();
+
+        let scan = scan_collection(workspace.path(), &HashSet::new(), &Gitignore::empty());
+
+        assert_eq!(folder_names(&scan.items), vec!["a-shared"]);
+        assert!(scan.loops.is_empty());
+    }
+
+    #[test]
+    fn test_scan_lists_http_file_requests() {
+        let workspace = TempDir::new().unwrap();
+        fs::write(
+            workspace.path().join("users.http"),
+            "### List users\nGET /users\n\n###\nPOST /users\n",
+        )
+        .unwrap();
+
+        let scan = scan_collection(workspace.path(), &HashSet::new(), &Gitignore::empty());
+
+        let CollectionItem::HttpFile {
+            name,
+            expanded,
+            requests,
+            ..
+        } = &scan.items[0]
+        else {
+            panic!("expected a .http file");
+        };
+        assert_eq!(name, "users.http");
+        assert!(!expanded);
+        let names: Vec<_> = requests.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["List users", "POST /users"]);
+        assert!(request_paths(&scan.items).is_empty());
+    }
+
+    #[test]
+    fn test_scan_honors_mercuryignore() {
+        let workspace = TempDir::new().unwrap();
+        let root = workspace.path();
+        for dir in ["api", "node_modules/pkg", "build"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let request = r#"{"method": "GET", "url": "/"}"#;
+        for file in [
+            "api/users.json",
+            "api/draft.json",
+            "node_modules/pkg/package.json",
+            "build/out.json",
+            "keep.json",
+        ] {
+            fs::write(root.join(file), request).unwrap();
+        }
+        fs::write(
+            root.join(IGNORE_FILE),
+            "# Not requests\nnode_modules/\n/build\ndraft*.json\n",
+        )
+        .unwrap();
+
+        let ignore = workspace_ignore(root);
+        let scan = scan_collection(root, &HashSet::new(), &ignore);
+        let paths: Vec<_> = request_paths(&scan.items)
+            .into_iter()
+            .map(|path| path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("api/users.json"), PathBuf::from("keep.json")]
+        );
+
+        assert!(is_ignored(
+            &ignore,
+            &root.join("node_modules/pkg/package.json"),
+            false
+        ));
+        assert!(!is_ignored(&ignore, &root.join("api/users.json"), false));
+        assert!(!is_ignored(&ignore, Path::new("/elsewhere/build"), true));
+
+        // Ignored folders aren't registered with the watcher at all
+        let watched: Vec<_> = watched_dirs(root, &ignore)
+            .into_iter()
+            .map(|path| path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(watched, vec![PathBuf::new(), PathBuf::from("api")]);
+
+        // No file, no exclusions
+        let empty = TempDir::new().unwrap();
+        let none = workspace_ignore(empty.path());
+        assert!(none.is_empty());
+        assert!(!is_ignored(&none, &root.join("build"), true));
+    }
+
+    #[test]
+    fn test_ignore_through_symlinked_workspace() {
+        let real = TempDir::new().unwrap();
+        fs::create_dir_all(real.path().join("node_modules/pkg")).unwrap();
+        fs::write(real.path().join(IGNORE_FILE), "node_modules/\n").unwrap();
+        let links = TempDir::new().unwrap();
+        let link = links.path().join("workspace");
+        symlink(real.path(), &link).unwrap();
+
+        // Opened through the link, while events name the real folder
+        let ignore = workspace_ignore(&link);
+        let real_root = real.path().canonicalize().unwrap();
+        // Deleted files are matched by their folder
+        assert!(is_ignored(
+            &ignore,
+            &real_root.join("node_modules/pkg/gone.json"),
+            false
+        ));
+        assert!(is_ignored(&ignore, &link.join("node_modules"), true));
+        assert!(!is_ignored(&ignore, &real_root.join("users.json"), false));
+    }
+}
@@ -25,12 +25,71 @@ pub fn get_docs_url() -> String {
     )
 }
 
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+/// Unreachable hosts fail after this long, however long responses may take
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+/// Sent as `User-Agent` when a request doesn't set its own
+pub const DEFAULT_USER_AGENT: &str = concat!("Mercury/", env!("CARGO_PKG_VERSION"));
+
+/// Folder runs default to one request at a time, at most 5 per second per host
+pub const DEFAULT_RUN_CONCURRENCY: usize = 1;
+pub const DEFAULT_RUN_RATE_LIMIT: u32 = 5;
+pub const MAX_RUN_CONCURRENCY: usize = 16;
+/// Shortest wait after a 429, even for `Retry-After: 0` or a date already past,
+/// so resending when the window ends can't hammer the server every frame
+pub const MIN_RETRY_AFTER_SECS: u64 = 1;
+
+/// Workspace settings file, hidden from the collection tree like `.env` files
+pub const WORKSPACE_CONFIG_FILE: &str = ".mercury.json";
+/// Paths to leave out of the collection tree, in `.gitignore` syntax
+pub const IGNORE_FILE: &str = ".mercuryignore";
+/// Headers and variables shared by the requests in a folder and its subfolders
+pub const FOLDER_DEFAULTS_FILE: &str = ".folder.json";
+/// Summary of a folder run written next to its saved responses
+pub const RUN_MANIFEST_FILE: &str = "manifest.json";
+/// Base environment every `.env.<name>` inherits from
+pub const BASE_ENV_FILE: &str = ".env";
+/// Encrypted secrets layered over whichever environment is selected
+pub const SECRETS_FILE: &str = ".env.enc";
+/// Variables every environment is expected to set, with descriptions and defaults
+pub const ENV_SCHEMA_FILE: &str = ".env.schema";
+
+/// Command-line flag that starts Mercury as an extra window on a workspace
+pub const WINDOW_ARG: &str = "--window";
+
+/// The request log is moved to `<name>.1` when it reaches this size
+pub const REQUEST_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
 pub const MAX_TIMELINE_ENTRIES: usize = 50;
+/// Response times shown in a request's sparkline
+pub const SPARKLINE_SAMPLES: usize = 20;
+/// Bytes of a binary response formatted at a time in the hex view
+pub const HEX_PAGE_BYTES: usize = 4096;
+pub const MAX_RECENT_WORKSPACES: usize = 10;
+/// Request files remembered for switching back with "Previous Request"
+pub const MAX_RECENT_FILES: usize = 10;
+/// Deepest folder nesting scanned into the sidebar
+pub const MAX_SCAN_DEPTH: usize = 64;
+pub const MAX_URL_SUGGESTIONS: usize = 8;
+/// Requests listed by a search of request contents
+pub const MAX_CONTENT_SEARCH_RESULTS: usize = 100;
+/// Characters of the matching line shown under each content search result
+pub const SEARCH_SNIPPET_CHARS: usize = 60;
 pub const URL_TRUNCATE_LENGTH: usize = 35;
 pub const HISTORY_URL_TRUNCATE_LENGTH: usize = 25;
 pub const STATUS_MSG_TRUNCATE_LENGTH: usize = 60;
 pub const COPY_CONFIRM_DURATION_SECONDS: f64 = 1.0;
 pub const FADE_DURATION_SECONDS: f64 = 5.0; // Increased from 3.0 for better readability
+/// How long a toast stays before giving way to the next queued one
+pub const TOAST_MIN_SECONDS: f64 = 2.0;
+pub const MAX_QUEUED_TOASTS: usize = 5;
+pub const HISTORY_PREVIEW_CHARS: usize = 500;
+/// Longest variable value shown when hovering `{{name}}` in an editor
+pub const VARIABLE_PREVIEW_CHARS: usize = 200;
+/// NDJSON responses with this many records or fewer start expanded
+pub const NDJSON_OPEN_RECORDS: usize = 3;
+pub const NDJSON_TITLE_CHARS: usize = 80;
+pub const HISTORY_PREVIEW_HOVER_DELAY_SECONDS: f64 = 0.3; // Debounce before reading from disk
 pub const HISTORY_EXPIRY_SECONDS: f64 = 7.0 * 24.0 * 60.0 * 60.0; // 7 days
 
 // Response Size Limits
@@ -48,3 +107,12 @@ pub const MAX_RESPONSE_SIZE: usize = 10 * 1024 * 1024; // 10MB
 /// This keeps the UI at 60fps - character-by-character highlighting is expensive.
 /// Also used as the threshold for ResponseType::LargeText classification.
 pub const MAX_HIGHLIGHT_SIZE: usize = 100_000; // 100KB
+
+/// Longest line the Pretty view lays out as is. Longer lines, such as minified
+/// JSON on one line, are broken up for display since one huge line is slow to
+/// lay out even well under `MAX_HIGHLIGHT_SIZE`.
+pub const MAX_DISPLAY_LINE_CHARS: usize = 2_000;
+
+/// Largest file loaded into a hex body; its hex is three times the size, and
+/// the editor lays all of it out
+pub const MAX_HEX_BODY_BYTES: usize = 64 * 1024;
@@ -0,0 +1,162 @@
+//! Environment Comparison Module
+//!
+//! Sends one request to several environments at once and lines the responses up
+//! against the first environment in the list that got one. Differences use the
+//! baseline comparison, so volatile headers and JSON formatting don't count.
+
+use super::baseline::{self, BaselineDiff};
+use super::runner::RunStatus;
+use super::types::{HttpMethod, Response};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Whether an environment file looks like production (`.env.prod`, `.env.production`)
+pub fn is_production(env_name: &str) -> bool {
+    env_name.to_lowercase().contains("prod")
+}
+
+/// One environment's column in a comparison
+#[derive(Debug, Clone)]
+pub struct EnvColumn {
+    pub env: String,
+    pub status: RunStatus,
+    pub response: Option<Response>,
+    /// Difference from the reference column; `None` for the reference itself
+    pub diff: Option<BaselineDiff>,
+}
+
+/// A comparison in progress (or finished, until dismissed)
+pub struct EnvComparison {
+    pub id: u64,
+    pub method: HttpMethod,
+    pub request_name: String,
+    pub columns: Vec<EnvColumn>,
+    pub cancel: Arc<AtomicBool>,
+}
+
+impl EnvComparison {
+    pub fn new(
+        id: u64,
+        method: HttpMethod,
+        request_name: String,
+        envs: Vec<String>,
+        cancel: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            id,
+            method,
+            request_name,
+            columns: envs
+                .into_iter()
+                .map(|env| EnvColumn {
+                    env,
+                    status: RunStatus::Pending,
+                    response: None,
+                    diff: None,
+                })
+                .collect(),
+            cancel,
+        }
+    }
+
+    /// Done when every environment answered, or when stopped and nothing is in flight
+    pub fn is_done(&self) -> bool {
+        let running = self.columns.iter().any(|c| c.status == RunStatus::Running);
+        self.columns.iter().all(|c| c.status.is_finished())
+            || (self.cancel.load(Ordering::Relaxed) && !running)
+    }
+
+    /// Index of the column the others are compared with: the first with a response
+    pub fn reference(&self) -> Option<usize> {
+        self.columns.iter().position(|c| c.response.is_some())
+    }
+
+    /// Record a column's progress and refresh every diff against the reference
+    pub fn update(&mut self, index: usize, status: RunStatus, response: Option<Response>) {
+        let Some(column) = self.columns.get_mut(index) else {
+            return;
+        };
+        column.status = status;
+        if response.is_some() {
+            column.response = response;
+        }
+
+        let reference = self.reference();
+        let expected = reference.and_then(|i| self.columns[i].response.clone());
+        for (i, column) in self.columns.iter_mut().enumerate() {
+            column.diff = match (&expected, &column.response) {
+                (Some(expected), Some(response)) if Some(i) != reference => {
+                    Some(baseline::compare(expected, response))
+                }
+                _ => None,
+            };
+        }
+    }
+
+    /// Whether every environment that answered matches the reference
+    pub fn all_match(&self) -> bool {
+        self.columns
+            .iter()
+            .filter_map(|c| c.diff.as_ref())
+            .all(BaselineDiff::is_match)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, body: &str) -> Response {
+        Response {
+            status,
+            body: body.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn done(status: u16) -> RunStatus {
+        RunStatus::Done {
+            status,
+            duration_ms: 10,
+        }
+    }
+
+    #[test]
+    fn test_is_production() {
+        assert!(is_production(".env.prod"));
+        assert!(is_production(".env.Production"));
+        assert!(!is_production(".env.staging"));
+    }
+
+    #[test]
+    fn test_diffs_against_first_response() {
+        let mut comparison = EnvComparison::new(
+            1,
+            HttpMethod::GET,
+            "users".to_string(),
+            vec![
+                ".env.dev".to_string(),
+                ".env.staging".to_string(),
+                ".env.prod".to_string(),
+            ],
+            Arc::new(AtomicBool::new(false)),
+        );
+
+        // Staging answers first and is the reference until dev does
+        comparison.update(1, done(200), Some(response(200, r#"{"id": 1}"#)));
+        assert_eq!(comparison.reference(), Some(1));
+        assert!(comparison.columns[1].diff.is_none());
+
+        comparison.update(0, done(200), Some(response(200, r#"{"id":1}"#)));
+        comparison.update(2, done(500), Some(response(500, "oops")));
+        assert_eq!(comparison.reference(), Some(0));
+        assert!(comparison.columns[0].diff.is_none());
+        assert!(comparison.columns[1].diff.as_ref().unwrap().is_match());
+        assert_eq!(
+            comparison.columns[2].diff.as_ref().unwrap().status,
+            Some((200, 500))
+        );
+        assert!(!comparison.all_match());
+        assert!(comparison.is_done());
+    }
+}
@@ -12,14 +12,22 @@ pub enum MercuryError {
     // =========================================================================
     // Network Errors
     // =========================================================================
-    /// Connection to server failed (DNS, refused, unreachable)
+    /// Connection to server failed (refused, reset)
     #[error("Connection failed: {0}")]
     ConnectionFailed(String),
 
+    /// Host could not be reached: DNS lookup failed or the network is down
+    #[error("Host unreachable: {0}")]
+    HostUnreachable(String),
+
     /// Request exceeded timeout duration
     #[error("Request timed out after {0}ms")]
     Timeout(u64),
 
+    /// No connection to the host within the connect timeout; nothing was sent
+    #[error("Connection timed out after {0}ms")]
+    ConnectTimeout(u64),
+
     /// SSL/TLS certificate or handshake error
     #[error("SSL/TLS error: {0}")]
     TlsError(String),
@@ -28,6 +36,10 @@ pub enum MercuryError {
     #[error("Request failed: {0}")]
     RequestFailed(String),
 
+    /// Request was cancelled by the user before it finished
+    #[error("Request cancelled")]
+    Cancelled,
+
     // =========================================================================
     // File/IO Errors
     // =========================================================================
@@ -47,6 +59,10 @@ pub enum MercuryError {
     #[error("{kind} already exists: {name}")]
     AlreadyExists { kind: String, name: String },
 
+    /// A folder added to the workspace inside itself
+    #[error("Can't add '{0}' inside itself")]
+    AddIntoItself(String),
+
     /// Failed to delete file or folder
     #[error("Failed to delete '{path}': {reason}")]
     DeleteFailed { path: String, reason: String },
@@ -70,6 +86,14 @@ pub enum MercuryError {
     #[error("Invalid JSON: {0}")]
     JsonError(String),
 
+    /// A hex body that doesn't spell whole bytes
+    #[error("Invalid hex body: {0}")]
+    InvalidHexBody(String),
+
+    /// A JSON Patch or Merge Patch body that doesn't match its Content-Type
+    #[error("Invalid patch body: {0}")]
+    InvalidPatchBody(String),
+
     /// Invalid URL format
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
@@ -78,6 +102,21 @@ pub enum MercuryError {
     #[error("Invalid cURL command: {0}")]
     CurlParseError(String),
 
+    /// Invalid fetch call
+    #[error("Invalid fetch call: {0}")]
+    FetchParseError(String),
+
+    /// Invalid or unsupported `.proto` file
+    #[error("Invalid proto file: {0}")]
+    ProtoParseError(String),
+
+    // =========================================================================
+    // gRPC Errors
+    // =========================================================================
+    /// Message could not be encoded/decoded, or the call is not supported
+    #[error("gRPC error: {0}")]
+    GrpcError(String),
+
     // =========================================================================
     // Import Errors
     // =========================================================================
@@ -89,6 +128,10 @@ pub enum MercuryError {
     #[error("Insomnia import failed: {0}")]
     InsomniaImportError(String),
 
+    /// OpenAPI or Swagger spec import failed
+    #[error("OpenAPI import failed: {0}")]
+    OpenApiImportError(String),
+
     // =========================================================================
     // Workspace Errors
     // =========================================================================
@@ -111,6 +154,22 @@ pub enum MercuryError {
     #[error("Failed to create environment: {0}")]
     EnvironmentCreateFailed(String),
 
+    /// No environment is selected to write to
+    #[error("No environment selected")]
+    NoEnvironment,
+
+    /// OS keychain entry could not be read or written
+    #[error("Keychain error: {0}")]
+    KeychainError(String),
+
+    /// Encrypted secrets file could not be read, unlocked, or written
+    #[error("Secrets error: {0}")]
+    SecretsError(String),
+
+    /// Another window could not be started
+    #[error("Failed to open window: {0}")]
+    WindowFailed(String),
+
     // =========================================================================
     // File System Watcher Errors
     // =========================================================================
@@ -147,7 +206,11 @@ impl From<serde_json::Error> for MercuryError {
 
 impl From<reqwest::Error> for MercuryError {
     fn from(err: reqwest::Error) -> Self {
-        if err.is_timeout() {
+        // The connect timeout in effect isn't known here, so a connect timeout
+        // doesn't claim a duration; requests report it with the configured one
+        if err.is_connect() && err.is_timeout() {
+            MercuryError::ConnectionFailed(err.to_string())
+        } else if err.is_timeout() {
             MercuryError::Timeout(30000) // Default timeout
         } else if err.is_connect() {
             MercuryError::ConnectionFailed(err.to_string())
@@ -172,15 +235,22 @@ impl MercuryError {
             MercuryError::ConnectionFailed(_) => {
                 "Could not connect to the server. Check your internet connection and the URL."
             }
+            MercuryError::HostUnreachable(_) => {
+                "Can't reach the host. You may be offline, or the host name doesn't resolve. Check your connection and the URL."
+            }
             MercuryError::Timeout(_) => {
                 "The server took too long to respond. Try again or increase the timeout."
             }
+            MercuryError::ConnectTimeout(_) => {
+                "Could not connect to the host in time. It may be down, or a firewall may be dropping the connection. Check the URL and your network."
+            }
             MercuryError::TlsError(_) => {
                 "SSL/TLS certificate error. The server's certificate may be invalid or expired."
             }
             MercuryError::RequestFailed(_) => {
                 "The request could not be completed. Check the URL and try again."
             }
+            MercuryError::Cancelled => "The request was cancelled.",
 
             // File
             MercuryError::FileRead { .. } => {
@@ -195,6 +265,9 @@ impl MercuryError {
             MercuryError::AlreadyExists { .. } => {
                 "An item with this name already exists. Choose a different name."
             }
+            MercuryError::AddIntoItself(_) => {
+                "A folder can't be added inside itself or one of its subfolders. Choose another destination."
+            }
             MercuryError::DeleteFailed { .. } => {
                 "Could not delete the item. It may be in use or protected."
             }
@@ -207,12 +280,29 @@ impl MercuryError {
                 "Invalid HTTP file format. Expected: METHOD URL on the first line."
             }
             MercuryError::JsonError(_) => "The content is not valid JSON. Check for syntax errors.",
+            MercuryError::InvalidHexBody(_) => {
+                "The body is not valid hex. Use pairs of hex digits, like 48 65 6c 6c 6f."
+            }
+            MercuryError::InvalidPatchBody(_) => {
+                "The body doesn't match its patch Content-Type. A JSON Patch is an array of operations, each with op and path."
+            }
             MercuryError::InvalidUrl(_) => {
                 "Please enter a valid URL (e.g., https://api.example.com)."
             }
             MercuryError::CurlParseError(_) => {
                 "Could not parse the cURL command. Ensure it's a valid cURL command."
             }
+            MercuryError::FetchParseError(_) => {
+                "Could not parse the fetch call. Copy it again with \"Copy as fetch\" in DevTools."
+            }
+            MercuryError::ProtoParseError(_) => {
+                "Could not read the .proto file. Check its syntax and that all types are defined in it."
+            }
+
+            // gRPC
+            MercuryError::GrpcError(_) => {
+                "The gRPC call could not be made. Check the service, method, and JSON message."
+            }
 
             // Import
             MercuryError::PostmanImportError(_) => {
@@ -221,18 +311,29 @@ impl MercuryError {
             MercuryError::InsomniaImportError(_) => {
                 "Could not import the Insomnia collection. Ensure it's a valid export file."
             }
+            MercuryError::OpenApiImportError(_) => {
+                "Could not import the OpenAPI spec. Ensure it's an OpenAPI 3 or Swagger 2 document."
+            }
 
             // Workspace
             MercuryError::NoWorkspace => "No workspace is open. Create or open a workspace first.",
             MercuryError::WorkspaceNotFound(_) => {
                 "The workspace folder was not found. It may have been moved or deleted."
             }
+            MercuryError::WindowFailed(_) => "Could not open a new Mercury window.",
 
             // Environment
             MercuryError::EnvironmentExists(_) => "An environment with this name already exists.",
             MercuryError::EnvironmentCreateFailed(_) => {
                 "Could not create the environment file. Check write permissions."
             }
+            MercuryError::NoEnvironment => "No environment is selected. Select an environment first.",
+            MercuryError::KeychainError(_) => {
+                "Could not access the OS keychain. Check that the entry exists and the keychain is unlocked."
+            }
+            MercuryError::SecretsError(_) => {
+                "Could not unlock the encrypted secrets. Check the passphrase."
+            }
 
             // File Watcher
             MercuryError::FileWatcherError(_) => {
@@ -242,13 +343,13 @@ impl MercuryError {
     }
 
     /// Returns true if this error is recoverable (user can retry)
-    /// Future use: show "Retry" button on recoverable errors
-    #[allow(dead_code)]
     pub fn is_recoverable(&self) -> bool {
         matches!(
             self,
             MercuryError::ConnectionFailed(_)
+                | MercuryError::HostUnreachable(_)
                 | MercuryError::Timeout(_)
+                | MercuryError::ConnectTimeout(_)
                 | MercuryError::RequestFailed(_)
         )
     }
@@ -289,12 +390,18 @@ mod tests {
     fn test_user_message() {
         let err = MercuryError::Timeout(30000);
         assert!(err.user_message().contains("took too long"));
+
+        let err = MercuryError::ConnectTimeout(10000);
+        assert_eq!(err.to_string(), "Connection timed out after 10000ms");
+        assert!(err.user_message().contains("connect to the host"));
     }
 
     #[test]
     fn test_is_recoverable() {
         assert!(MercuryError::Timeout(1000).is_recoverable());
+        assert!(MercuryError::ConnectTimeout(1000).is_recoverable());
         assert!(MercuryError::ConnectionFailed("test".to_string()).is_recoverable());
+        assert!(MercuryError::HostUnreachable("test".to_string()).is_recoverable());
         assert!(!MercuryError::FileNotFound("test".to_string()).is_recoverable());
     }
 
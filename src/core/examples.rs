@@ -0,0 +1,138 @@
+//! Example Responses Module
+//!
+//! Named example responses stored in a `.examples/` sidecar folder next to the request:
+//! `users/get-user.json` -> `users/.examples/get-user/<name>.json`.
+//! The sidecar is a dot-folder, so the collection tree never shows it.
+
+use super::error::MercuryError;
+use super::types::Response;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sidecar folder name, also used by the file watcher to ignore example writes
+pub const EXAMPLES_DIR: &str = ".examples";
+
+/// Folder holding the examples for one request file
+pub fn examples_dir(request_path: &Path) -> PathBuf {
+    let parent = request_path.parent().unwrap_or(Path::new(""));
+    let stem = request_path.file_stem().unwrap_or_default();
+    parent.join(EXAMPLES_DIR).join(stem)
+}
+
+/// File for one named example; path separators in the name are neutralized
+fn example_path(request_path: &Path, name: &str) -> PathBuf {
+    examples_dir(request_path).join(format!("{}.json", name.replace(['/', '\\'], "-")))
+}
+
+/// Example names for a request, sorted alphabetically
+pub fn list_examples(request_path: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(examples_dir(request_path)) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect();
+    names.sort();
+    names
+}
+
+pub fn load_example(request_path: &Path, name: &str) -> Option<Response> {
+    let content = fs::read_to_string(example_path(request_path, name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Save (or overwrite) a named example for a request
+pub fn save_example(
+    request_path: &Path,
+    name: &str,
+    response: &Response,
+) -> Result<(), MercuryError> {
+    let dir = examples_dir(request_path);
+    let path = example_path(request_path, name);
+    let write_error = |e: std::io::Error| MercuryError::FileWrite {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    };
+
+    fs::create_dir_all(&dir).map_err(write_error)?;
+    let content = serde_json::to_string_pretty(response)?;
+    fs::write(&path, content).map_err(write_error)
+}
+
+pub fn delete_example(request_path: &Path, name: &str) -> Result<(), MercuryError> {
+    let path = example_path(request_path, name);
+    fs::remove_file(&path).map_err(|e| MercuryError::DeleteFailed {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// Keep a request's examples with it when the request file is renamed
+pub fn rename_examples(old_request_path: &Path, new_request_path: &Path) {
+    let old_dir = examples_dir(old_request_path);
+    if old_dir.is_dir() {
+        let _ = fs::rename(old_dir, examples_dir(new_request_path));
+    }
+}
+
+/// Remove a request's examples along with the request file
+pub fn delete_all_examples(request_path: &Path) {
+    let _ = fs::remove_dir_all(examples_dir(request_path));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_response() -> Response {
+        Response {
+            status: 404,
+            status_text: "Not Found".to_string(),
+            body: r#"{"error": "missing"}"#.to_string(),
+            response_type: "Json".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_examples_dir_is_sidecar() {
+        let dir = examples_dir(Path::new("/ws/users/get-user.json"));
+        assert_eq!(dir, PathBuf::from("/ws/users/.examples/get-user"));
+    }
+
+    #[test]
+    fn test_save_list_load_delete() {
+        let dir = TempDir::new().unwrap();
+        let request_path = dir.path().join("get-user.json");
+        fs::write(&request_path, "{}").unwrap();
+
+        save_example(&request_path, "not-found", &sample_response()).unwrap();
+        save_example(&request_path, "success", &Response::default()).unwrap();
+        assert_eq!(list_examples(&request_path), vec!["not-found", "success"]);
+
+        let loaded = load_example(&request_path, "not-found").unwrap();
+        assert_eq!(loaded.status, 404);
+        assert_eq!(loaded.body, sample_response().body);
+
+        delete_example(&request_path, "success").unwrap();
+        assert_eq!(list_examples(&request_path), vec!["not-found"]);
+    }
+
+    #[test]
+    fn test_rename_examples_follows_request() {
+        let dir = TempDir::new().unwrap();
+        let old_path = dir.path().join("old.json");
+        let new_path = dir.path().join("new.json");
+        save_example(&old_path, "ok", &sample_response()).unwrap();
+
+        rename_examples(&old_path, &new_path);
+
+        assert!(list_examples(&old_path).is_empty());
+        assert_eq!(list_examples(&new_path), vec!["ok"]);
+    }
+}
@@ -0,0 +1,199 @@
+//! Folder Defaults Module
+//!
+//! A folder can carry a `.folder.json` with headers and variables shared by every
+//! request inside it, subfolders included. Files are merged from the workspace
+//! root down, so a nested folder's value wins over its parent's. A request's own
+//! headers win over all of them, and the environment wins over folder variables.
+
+use super::constants::FOLDER_DEFAULTS_FILE;
+use super::types::FolderDefaults;
+use crate::parser::substitute_variables;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A header or variable and the folder whose `.folder.json` set it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inherited {
+    pub name: String,
+    pub value: String,
+    pub folder: PathBuf,
+}
+
+/// Everything a request inherits from the folders above it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InheritedDefaults {
+    pub headers: Vec<Inherited>,
+    pub variables: Vec<Inherited>,
+}
+
+/// Set `name`, replacing an entry already there; `same` decides what counts as one
+fn upsert(list: &mut Vec<Inherited>, item: Inherited, same: impl Fn(&str, &str) -> bool) {
+    match list.iter_mut().find(|i| same(&i.name, &item.name)) {
+        Some(existing) => *existing = item,
+        None => list.push(item),
+    }
+}
+
+/// Defaults for the request at `request`, from every folder between it and
+/// `workspace`. Files that are missing or aren't valid JSON are skipped.
+pub fn load_inherited(request: &Path, workspace: &Path) -> InheritedDefaults {
+    let mut folders: Vec<&Path> = request
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(workspace))
+        .collect();
+    folders.reverse();
+
+    let mut inherited = InheritedDefaults::default();
+    for folder in folders {
+        let Some(defaults) = fs::read_to_string(folder.join(FOLDER_DEFAULTS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<FolderDefaults>(&content).ok())
+        else {
+            continue;
+        };
+        for (name, value) in defaults.headers {
+            let item = Inherited {
+                name,
+                value,
+                folder: folder.to_path_buf(),
+            };
+            upsert(&mut inherited.headers, item, |a, b| {
+                a.eq_ignore_ascii_case(b)
+            });
+        }
+        for (name, value) in defaults.variables {
+            let item = Inherited {
+                name,
+                value,
+                folder: folder.to_path_buf(),
+            };
+            upsert(&mut inherited.variables, item, |a, b| a == b);
+        }
+    }
+    inherited
+}
+
+impl InheritedDefaults {
+    /// A copy with `{{variables}}` in header names and values filled in
+    pub fn substituted(&self, variables: &HashMap<String, String>) -> Self {
+        let substitute = |item: &Inherited| Inherited {
+            name: substitute_variables(&item.name, variables),
+            value: substitute_variables(&item.value, variables),
+            folder: item.folder.clone(),
+        };
+        Self {
+            headers: self.headers.iter().map(substitute).collect(),
+            variables: self.variables.clone(),
+        }
+    }
+
+    /// Add inherited headers the request doesn't set itself (in any case)
+    pub fn apply_headers(&self, headers: &mut HashMap<String, String>) {
+        for header in &self.headers {
+            if !headers
+                .keys()
+                .any(|k| k.trim().eq_ignore_ascii_case(&header.name))
+            {
+                headers.insert(header.name.clone(), header.value.clone());
+            }
+        }
+    }
+
+    /// Add inherited variables the environment doesn't define
+    pub fn apply_variables(&self, variables: &mut HashMap<String, String>) {
+        for variable in &self.variables {
+            variables
+                .entry(variable.name.clone())
+                .or_insert_with(|| variable.value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_inner_folders_override_outer() {
+        let workspace = TempDir::new().unwrap();
+        let admin = workspace.path().join("users").join("admin");
+        fs::create_dir_all(&admin).unwrap();
+        fs::write(
+            workspace.path().join(FOLDER_DEFAULTS_FILE),
+            r#"{"headers": {"Accept": "application/json", "X-Team": "core"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            workspace.path().join("users").join(FOLDER_DEFAULTS_FILE),
+            r#"{"headers": {"x-team": "users"}, "variables": {"PREFIX": "/v2/users"}}"#,
+        )
+        .unwrap();
+        // Broken files are skipped, not fatal
+        fs::write(admin.join(FOLDER_DEFAULTS_FILE), "{ not json").unwrap();
+
+        let inherited = load_inherited(&admin.join("list.json"), workspace.path());
+
+        let headers: Vec<_> = inherited
+            .headers
+            .iter()
+            .map(|h| (h.name.as_str(), h.value.as_str()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![("Accept", "application/json"), ("x-team", "users")]
+        );
+        assert_eq!(inherited.headers[1].folder, workspace.path().join("users"));
+        assert_eq!(inherited.variables[0].value, "/v2/users");
+    }
+
+    #[test]
+    fn test_request_and_environment_win() {
+        let inherited = InheritedDefaults {
+            headers: vec![Inherited {
+                name: "Authorization".to_string(),
+                value: "Bearer folder".to_string(),
+                folder: PathBuf::from("users"),
+            }],
+            variables: vec![
+                Inherited {
+                    name: "HOST".to_string(),
+                    value: "folder.example.com".to_string(),
+                    folder: PathBuf::from("users"),
+                },
+                Inherited {
+                    name: "PREFIX".to_string(),
+                    value: "/v2".to_string(),
+                    folder: PathBuf::from("users"),
+                },
+            ],
+        };
+
+        let mut headers = HashMap::from([("authorization".to_string(), "Bearer own".to_string())]);
+        inherited.apply_headers(&mut headers);
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers["authorization"], "Bearer own");
+
+        let mut variables = HashMap::from([("HOST".to_string(), "env.example.com".to_string())]);
+        inherited.apply_variables(&mut variables);
+        assert_eq!(variables["HOST"], "env.example.com");
+        assert_eq!(variables["PREFIX"], "/v2");
+    }
+
+    #[test]
+    fn test_nothing_outside_the_workspace() {
+        let outer = TempDir::new().unwrap();
+        let workspace = outer.path().join("workspace");
+        fs::create_dir_all(&workspace).unwrap();
+        fs::write(
+            outer.path().join(FOLDER_DEFAULTS_FILE),
+            r#"{"headers": {"X-Outside": "1"}}"#,
+        )
+        .unwrap();
+
+        let inherited = load_inherited(&workspace.join("health.json"), &workspace);
+        assert_eq!(inherited, InheritedDefaults::default());
+    }
+}
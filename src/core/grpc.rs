@@ -0,0 +1,1112 @@
+//! gRPC-Web Module
+//!
+//! Unary gRPC calls over gRPC-Web (text mode): JSON <-> protobuf encoding driven by a
+//! parsed `.proto` file, message framing, and trailer/status handling.
+//! Text mode base64-encodes the frames, so the regular HTTP executor can carry them.
+
+use super::error::MercuryError;
+use super::request::{execute_request, HttpResponse, ResponseType};
+use super::types::{GrpcCall, HttpMethod, JsonRequest, Timeouts};
+use crate::parser::proto::{FieldDef, FieldType, ProtoFile, Scalar};
+use base64::prelude::*;
+use serde_json::{Map, Number, Value};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+const WIRE_VARINT: u8 = 0;
+const WIRE_FIXED64: u8 = 1;
+const WIRE_LEN: u8 = 2;
+const WIRE_FIXED32: u8 = 5;
+
+/// Deepest message nesting decoded, as in protobuf's own decoders; a recursive
+/// type could otherwise nest deep enough to overflow the stack
+const MAX_MESSAGE_DEPTH: usize = 100;
+
+/// Frame flag marking the trailers frame in a gRPC-Web response
+const TRAILER_FLAG: u8 = 0x80;
+
+fn grpc_error(message: impl Into<String>) -> MercuryError {
+    MercuryError::GrpcError(message.into())
+}
+
+/// Canonical name for a gRPC status code
+pub fn grpc_status_name(code: u32) -> &'static str {
+    match code {
+        0 => "OK",
+        1 => "CANCELLED",
+        2 => "UNKNOWN",
+        3 => "INVALID_ARGUMENT",
+        4 => "DEADLINE_EXCEEDED",
+        5 => "NOT_FOUND",
+        6 => "ALREADY_EXISTS",
+        7 => "PERMISSION_DENIED",
+        8 => "RESOURCE_EXHAUSTED",
+        9 => "FAILED_PRECONDITION",
+        10 => "ABORTED",
+        11 => "OUT_OF_RANGE",
+        12 => "UNIMPLEMENTED",
+        13 => "INTERNAL",
+        14 => "UNAVAILABLE",
+        15 => "DATA_LOSS",
+        16 => "UNAUTHENTICATED",
+        _ => "UNKNOWN",
+    }
+}
+
+/// gRPC status carried in response headers or trailers, if any
+pub fn grpc_status_from_headers(headers: &[(String, String)]) -> Option<(u32, String)> {
+    let find = |name: &str| {
+        headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.trim().to_string())
+    };
+    let code = find("grpc-status")?.parse().ok()?;
+    Some((code, find("grpc-message").unwrap_or_default()))
+}
+
+// =============================================================================
+// Wire Encoding
+// =============================================================================
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_tag(buf: &mut Vec<u8>, number: u32, wire_type: u8) {
+    write_varint(buf, ((number as u64) << 3) | wire_type as u64);
+}
+
+fn write_len_delimited(buf: &mut Vec<u8>, number: u32, bytes: &[u8]) {
+    write_tag(buf, number, WIRE_LEN);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn json_i64(value: &Value) -> Result<i64, MercuryError> {
+    match value {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| grpc_error(format!("Expected an integer, got {}", value)))
+}
+
+fn json_u64(value: &Value) -> Result<u64, MercuryError> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| grpc_error(format!("Expected an unsigned integer, got {}", value)))
+}
+
+fn json_f64(value: &Value) -> Result<f64, MercuryError> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| grpc_error(format!("Expected a number, got {}", value)))
+}
+
+fn scalar_wire_type(scalar: Scalar) -> u8 {
+    match scalar {
+        Scalar::Double | Scalar::Fixed64 | Scalar::Sfixed64 => WIRE_FIXED64,
+        Scalar::Float | Scalar::Fixed32 | Scalar::Sfixed32 => WIRE_FIXED32,
+        Scalar::String | Scalar::Bytes => WIRE_LEN,
+        _ => WIRE_VARINT,
+    }
+}
+
+/// Encode a scalar's payload (without tag) into `buf`
+fn encode_scalar(buf: &mut Vec<u8>, scalar: Scalar, value: &Value) -> Result<(), MercuryError> {
+    match scalar {
+        Scalar::Int32 | Scalar::Int64 => write_varint(buf, json_i64(value)? as u64),
+        Scalar::Uint32 | Scalar::Uint64 => write_varint(buf, json_u64(value)?),
+        Scalar::Sint32 | Scalar::Sint64 => {
+            let n = json_i64(value)?;
+            write_varint(buf, ((n << 1) ^ (n >> 63)) as u64);
+        }
+        Scalar::Bool => {
+            let b = value
+                .as_bool()
+                .ok_or_else(|| grpc_error(format!("Expected a boolean, got {}", value)))?;
+            write_varint(buf, b as u64);
+        }
+        Scalar::Fixed32 => buf.extend_from_slice(&(json_u64(value)? as u32).to_le_bytes()),
+        Scalar::Sfixed32 => buf.extend_from_slice(&(json_i64(value)? as i32).to_le_bytes()),
+        Scalar::Float => buf.extend_from_slice(&(json_f64(value)? as f32).to_le_bytes()),
+        Scalar::Fixed64 => buf.extend_from_slice(&json_u64(value)?.to_le_bytes()),
+        Scalar::Sfixed64 => buf.extend_from_slice(&json_i64(value)?.to_le_bytes()),
+        Scalar::Double => buf.extend_from_slice(&json_f64(value)?.to_le_bytes()),
+        Scalar::String => {
+            let s = value
+                .as_str()
+                .ok_or_else(|| grpc_error(format!("Expected a string, got {}", value)))?;
+            write_varint(buf, s.len() as u64);
+            buf.extend_from_slice(s.as_bytes());
+        }
+        Scalar::Bytes => {
+            let bytes = value
+                .as_str()
+                .and_then(|s| BASE64_STANDARD.decode(s).ok())
+                .ok_or_else(|| grpc_error(format!("Expected base64 bytes, got {}", value)))?;
+            write_varint(buf, bytes.len() as u64);
+            buf.extend_from_slice(&bytes);
+        }
+    }
+    Ok(())
+}
+
+fn enum_number(proto: &ProtoFile, enum_name: &str, value: &Value) -> Result<i64, MercuryError> {
+    if let Some(name) = value.as_str() {
+        return proto
+            .enums
+            .get(enum_name)
+            .and_then(|def| def.values.iter().find(|(n, _)| n == name))
+            .map(|(_, number)| *number as i64)
+            .ok_or_else(|| grpc_error(format!("Unknown {} value '{}'", enum_name, name)));
+    }
+    json_i64(value)
+}
+
+/// Encode one (non-repeated) value with its tag
+fn encode_field_value(
+    proto: &ProtoFile,
+    buf: &mut Vec<u8>,
+    number: u32,
+    field_type: &FieldType,
+    value: &Value,
+) -> Result<(), MercuryError> {
+    match field_type {
+        FieldType::Scalar(scalar) => {
+            write_tag(buf, number, scalar_wire_type(*scalar));
+            encode_scalar(buf, *scalar, value)
+        }
+        FieldType::Enum(name) => {
+            write_tag(buf, number, WIRE_VARINT);
+            write_varint(buf, enum_number(proto, name, value)? as u64);
+            Ok(())
+        }
+        FieldType::Message(name) => {
+            let nested = encode_message(proto, name, value)?;
+            write_len_delimited(buf, number, &nested);
+            Ok(())
+        }
+        FieldType::Map(_, _) => Err(grpc_error("Nested maps are not supported")),
+    }
+}
+
+fn encode_field(
+    proto: &ProtoFile,
+    buf: &mut Vec<u8>,
+    field: &FieldDef,
+    value: &Value,
+) -> Result<(), MercuryError> {
+    if value.is_null() {
+        return Ok(());
+    }
+
+    if let FieldType::Map(key_type, value_type) = &field.field_type {
+        let entries = value
+            .as_object()
+            .ok_or_else(|| grpc_error(format!("Field '{}' expects an object", field.name)))?;
+        for (key, entry_value) in entries {
+            // Map keys are always strings in JSON; numeric/bool keys are parsed back
+            let key_value = match key_type.as_ref() {
+                FieldType::Scalar(Scalar::String) => Value::String(key.clone()),
+                FieldType::Scalar(Scalar::Bool) => Value::Bool(key == "true"),
+                _ => Value::String(key.clone()),
+            };
+            let mut entry = Vec::new();
+            encode_field_value(proto, &mut entry, 1, key_type, &key_value)?;
+            encode_field_value(proto, &mut entry, 2, value_type, entry_value)?;
+            write_len_delimited(buf, field.number, &entry);
+        }
+        return Ok(());
+    }
+
+    if !field.repeated {
+        return encode_field_value(proto, buf, field.number, &field.field_type, value);
+    }
+
+    let items = value
+        .as_array()
+        .ok_or_else(|| grpc_error(format!("Field '{}' expects an array", field.name)))?;
+
+    // Numeric repeated fields are packed, as proto3 does by default
+    let packable = match &field.field_type {
+        FieldType::Scalar(scalar) => scalar_wire_type(*scalar) != WIRE_LEN,
+        FieldType::Enum(_) => true,
+        _ => false,
+    };
+    if packable {
+        let mut packed = Vec::new();
+        for item in items {
+            match &field.field_type {
+                FieldType::Scalar(scalar) => encode_scalar(&mut packed, *scalar, item)?,
+                FieldType::Enum(name) => {
+                    write_varint(&mut packed, enum_number(proto, name, item)? as u64)
+                }
+                _ => unreachable!("only scalars and enums are packable"),
+            }
+        }
+        write_len_delimited(buf, field.number, &packed);
+    } else {
+        for item in items {
+            encode_field_value(proto, buf, field.number, &field.field_type, item)?;
+        }
+    }
+    Ok(())
+}
+
+/// Encode a JSON object as the named protobuf message.
+/// Keys may use either the proto field name or its lowerCamelCase JSON name.
+pub fn encode_message(
+    proto: &ProtoFile,
+    message_name: &str,
+    value: &Value,
+) -> Result<Vec<u8>, MercuryError> {
+    let message = proto
+        .messages
+        .get(message_name)
+        .ok_or_else(|| grpc_error(format!("Unknown message '{}'", message_name)))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| grpc_error(format!("{} must be a JSON object", message_name)))?;
+
+    let mut buf = Vec::new();
+    for (key, field_value) in object {
+        let field = message
+            .fields
+            .iter()
+            .find(|f| &f.name == key || &f.json_name() == key)
+            .ok_or_else(|| grpc_error(format!("Unknown field '{}' in {}", key, message_name)))?;
+        encode_field(proto, &mut buf, field, field_value)?;
+    }
+    Ok(buf)
+}
+
+// =============================================================================
+// Wire Decoding
+// =============================================================================
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn varint(&mut self) -> Result<u64, MercuryError> {
+        let mut result = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .bytes
+                .get(self.pos)
+                .ok_or_else(|| grpc_error("Truncated varint"))?;
+            self.pos += 1;
+            result |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        Err(grpc_error("Varint too long"))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], MercuryError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| grpc_error("Truncated message"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn fixed32(&mut self) -> Result<[u8; 4], MercuryError> {
+        Ok(self.take(4)?.try_into().expect("length checked"))
+    }
+
+    fn fixed64(&mut self) -> Result<[u8; 8], MercuryError> {
+        Ok(self.take(8)?.try_into().expect("length checked"))
+    }
+
+    fn skip(&mut self, wire_type: u8) -> Result<(), MercuryError> {
+        match wire_type {
+            WIRE_VARINT => self.varint().map(|_| ()),
+            WIRE_FIXED64 => self.take(8).map(|_| ()),
+            WIRE_LEN => {
+                let len = self.varint()? as usize;
+                self.take(len).map(|_| ())
+            }
+            WIRE_FIXED32 => self.take(4).map(|_| ()),
+            other => Err(grpc_error(format!("Unsupported wire type {}", other))),
+        }
+    }
+}
+
+fn float_value(f: f64) -> Value {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .unwrap_or_else(|| Value::String(f.to_string()))
+}
+
+/// Decode one scalar payload; 64-bit integers become strings per the proto3 JSON mapping
+fn decode_scalar(reader: &mut Reader, scalar: Scalar) -> Result<Value, MercuryError> {
+    Ok(match scalar {
+        Scalar::Int32 => Value::from(reader.varint()? as i32),
+        Scalar::Int64 => Value::String((reader.varint()? as i64).to_string()),
+        Scalar::Uint32 => Value::from(reader.varint()? as u32),
+        Scalar::Uint64 => Value::String(reader.varint()?.to_string()),
+        Scalar::Sint32 | Scalar::Sint64 => {
+            let raw = reader.varint()?;
+            let n = (raw >> 1) as i64 ^ -((raw & 1) as i64);
+            if scalar == Scalar::Sint32 {
+                Value::from(n as i32)
+            } else {
+                Value::String(n.to_string())
+            }
+        }
+        Scalar::Bool => Value::Bool(reader.varint()? != 0),
+        Scalar::Fixed32 => Value::from(u32::from_le_bytes(reader.fixed32()?)),
+        Scalar::Sfixed32 => Value::from(i32::from_le_bytes(reader.fixed32()?)),
+        Scalar::Float => float_value(f32::from_le_bytes(reader.fixed32()?) as f64),
+        Scalar::Fixed64 => Value::String(u64::from_le_bytes(reader.fixed64()?).to_string()),
+        Scalar::Sfixed64 => Value::String(i64::from_le_bytes(reader.fixed64()?).to_string()),
+        Scalar::Double => float_value(f64::from_le_bytes(reader.fixed64()?)),
+        Scalar::String => {
+            let len = reader.varint()? as usize;
+            Value::String(String::from_utf8_lossy(reader.take(len)?).into_owned())
+        }
+        Scalar::Bytes => {
+            let len = reader.varint()? as usize;
+            Value::String(BASE64_STANDARD.encode(reader.take(len)?))
+        }
+    })
+}
+
+fn decode_enum(proto: &ProtoFile, enum_name: &str, number: i32) -> Value {
+    proto
+        .enums
+        .get(enum_name)
+        .and_then(|def| def.values.iter().find(|(_, n)| *n == number))
+        .map(|(name, _)| Value::String(name.clone()))
+        .unwrap_or_else(|| Value::from(number))
+}
+
+fn decode_field_value(
+    proto: &ProtoFile,
+    reader: &mut Reader,
+    field_type: &FieldType,
+    depth: usize,
+) -> Result<Value, MercuryError> {
+    match field_type {
+        FieldType::Scalar(scalar) => decode_scalar(reader, *scalar),
+        FieldType::Enum(name) => Ok(decode_enum(proto, name, reader.varint()? as i32)),
+        FieldType::Message(name) => {
+            let len = reader.varint()? as usize;
+            decode_nested(proto, name, reader.take(len)?, depth + 1)
+        }
+        FieldType::Map(_, _) => Err(grpc_error("Nested maps are not supported")),
+    }
+}
+
+/// Decode a map entry message into its (key, value) pair
+fn decode_map_entry(
+    proto: &ProtoFile,
+    bytes: &[u8],
+    key_type: &FieldType,
+    value_type: &FieldType,
+    depth: usize,
+) -> Result<(String, Value), MercuryError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let mut key = String::new();
+    let mut value = Value::Null;
+    while !reader.is_empty() {
+        let tag = reader.varint()?;
+        match tag >> 3 {
+            1 => {
+                key = match decode_field_value(proto, &mut reader, key_type, depth)? {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                }
+            }
+            2 => value = decode_field_value(proto, &mut reader, value_type, depth)?,
+            _ => reader.skip((tag & 7) as u8)?,
+        }
+    }
+    Ok((key, value))
+}
+
+/// Decode a protobuf message into JSON, using lowerCamelCase field names.
+/// Unknown fields are skipped; fields absent on the wire are omitted.
+pub fn decode_message(
+    proto: &ProtoFile,
+    message_name: &str,
+    bytes: &[u8],
+) -> Result<Value, MercuryError> {
+    decode_nested(proto, message_name, bytes, 0)
+}
+
+/// Decode a message found `depth` levels inside the top-level one
+fn decode_nested(
+    proto: &ProtoFile,
+    message_name: &str,
+    bytes: &[u8],
+    depth: usize,
+) -> Result<Value, MercuryError> {
+    if depth > MAX_MESSAGE_DEPTH {
+        return Err(grpc_error(format!(
+            "Message nested more than {} levels deep",
+            MAX_MESSAGE_DEPTH
+        )));
+    }
+    let message = proto
+        .messages
+        .get(message_name)
+        .ok_or_else(|| grpc_error(format!("Unknown message '{}'", message_name)))?;
+
+    let mut object = Map::new();
+    let mut reader = Reader { bytes, pos: 0 };
+
+    while !reader.is_empty() {
+        let tag = reader.varint()?;
+        let number = (tag >> 3) as u32;
+        let wire_type = (tag & 7) as u8;
+
+        let Some(field) = message.fields.iter().find(|f| f.number == number) else {
+            reader.skip(wire_type)?;
+            continue;
+        };
+        let key = field.json_name();
+
+        if let FieldType::Map(key_type, value_type) = &field.field_type {
+            let len = reader.varint()? as usize;
+            let (map_key, map_value) =
+                decode_map_entry(proto, reader.take(len)?, key_type, value_type, depth)?;
+            object
+                .entry(key)
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .expect("map fields decode to objects")
+                .insert(map_key, map_value);
+            continue;
+        }
+
+        if !field.repeated {
+            let value = decode_field_value(proto, &mut reader, &field.field_type, depth)?;
+            object.insert(key, value);
+            continue;
+        }
+
+        let items = object
+            .entry(key)
+            .or_insert_with(|| Value::Array(Vec::new()))
+            .as_array_mut()
+            .expect("repeated fields decode to arrays");
+
+        // Packed numeric values arrive length-delimited; accept unpacked too
+        let is_packed_scalar = wire_type == WIRE_LEN
+            && match &field.field_type {
+                FieldType::Scalar(scalar) => scalar_wire_type(*scalar) != WIRE_LEN,
+                FieldType::Enum(_) => true,
+                _ => false,
+            };
+        if is_packed_scalar {
+            let len = reader.varint()? as usize;
+            let mut packed = Reader {
+                bytes: reader.take(len)?,
+                pos: 0,
+            };
+            while !packed.is_empty() {
+                items.push(decode_field_value(
+                    proto,
+                    &mut packed,
+                    &field.field_type,
+                    depth,
+                )?);
+            }
+        } else {
+            items.push(decode_field_value(
+                proto,
+                &mut reader,
+                &field.field_type,
+                depth,
+            )?);
+        }
+    }
+
+    Ok(Value::Object(object))
+}
+
+// =============================================================================
+// gRPC-Web Framing
+// =============================================================================
+
+/// Wrap a message in a gRPC-Web data frame: flag byte, big-endian length, payload
+pub fn frame_message(payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 5);
+    frame.push(0);
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Decode a grpc-web-text body. Servers may send several independently padded
+/// base64 chunks, so each 4-character quantum is decoded on its own.
+pub fn decode_text_body(body: &str) -> Result<Vec<u8>, MercuryError> {
+    let cleaned: Vec<u8> = body.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let mut bytes = Vec::new();
+    for quantum in cleaned.chunks(4) {
+        let decoded = BASE64_STANDARD
+            .decode(quantum)
+            .map_err(|e| grpc_error(format!("Invalid grpc-web-text body: {}", e)))?;
+        bytes.extend(decoded);
+    }
+    Ok(bytes)
+}
+
+/// Message payloads and trailers of a gRPC-Web response
+type Frames = (Vec<Vec<u8>>, Vec<(String, String)>);
+
+/// Split a gRPC-Web response into message payloads and trailers
+pub fn split_frames(bytes: &[u8]) -> Result<Frames, MercuryError> {
+    let mut messages = Vec::new();
+    let mut trailers = Vec::new();
+    let mut reader = Reader { bytes, pos: 0 };
+
+    while !reader.is_empty() {
+        let flag = reader.take(1)?[0];
+        let len = u32::from_be_bytes(reader.take(4)?.try_into().expect("length checked"));
+        let payload = reader.take(len as usize)?;
+
+        if flag & TRAILER_FLAG != 0 {
+            for line in String::from_utf8_lossy(payload).lines() {
+                if let Some((key, value)) = line.split_once(':') {
+                    trailers.push((key.trim().to_lowercase(), value.trim().to_string()));
+                }
+            }
+        } else {
+            messages.push(payload.to_vec());
+        }
+    }
+
+    Ok((messages, trailers))
+}
+
+// =============================================================================
+// Error Details
+// =============================================================================
+
+/// Frame flag marking the end-of-stream message in a Connect response
+const CONNECT_END_STREAM_FLAG: u8 = 0x02;
+
+/// Frame flag marking a compressed frame, in both gRPC-Web and Connect
+const COMPRESSED_FLAG: u8 = 0x01;
+
+/// Why a gRPC-Web or Connect call failed, read from its framed response
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrpcErrorDetails {
+    /// Canonical status name, e.g. `NOT_FOUND`
+    pub code: String,
+    pub message: String,
+    /// Each detail as `{"type": ..., "value": ...}`, plus whatever else the
+    /// server sent with it
+    pub details: Vec<Value>,
+}
+
+/// The error a framed gRPC-Web (`application/grpc-web+proto`, or base64 as
+/// `application/grpc-web-text`) or Connect (`application/connect+json`) response
+/// carries; `None` if the call succeeded or the body can't be parsed, so it's
+/// shown as binary
+pub fn grpc_error_details(response: &HttpResponse) -> Option<GrpcErrorDetails> {
+    let content_type = response.content_type.to_lowercase();
+    let bytes = response.raw_bytes.as_deref().unwrap_or_default();
+    if content_type.starts_with("application/grpc-web-text") {
+        let text = match &response.raw_bytes {
+            Some(bytes) => String::from_utf8_lossy(bytes),
+            None => response.body.as_str().into(),
+        };
+        grpc_web_error(&response.headers, &decode_text_body(&text).ok()?)
+    } else if content_type.starts_with("application/grpc") {
+        grpc_web_error(&response.headers, bytes)
+    } else if content_type.starts_with("application/connect+") {
+        connect_error(bytes)
+    } else {
+        None
+    }
+}
+
+fn grpc_web_error(headers: &[(String, String)], bytes: &[u8]) -> Option<GrpcErrorDetails> {
+    let (_, trailers) = split_frames(bytes).ok()?;
+    let mut headers = headers.to_vec();
+    headers.extend(trailers);
+
+    let (code, message) = grpc_status_from_headers(&headers)?;
+    if code == 0 {
+        return None;
+    }
+    let details = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("grpc-status-details-bin"))
+        .and_then(|(_, v)| {
+            BASE64_STANDARD_NO_PAD
+                .decode(v.trim().trim_end_matches('='))
+                .ok()
+        })
+        .and_then(|status| decode_status_details(&status).ok())
+        .unwrap_or_default();
+
+    Some(GrpcErrorDetails {
+        code: grpc_status_name(code).to_string(),
+        message: percent_decode(&message),
+        details,
+    })
+}
+
+/// `grpc-message` is percent-encoded UTF-8
+fn percent_decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// The `details` of a `google.rpc.Status` message: repeated `Any` in field 3
+fn decode_status_details(bytes: &[u8]) -> Result<Vec<Value>, MercuryError> {
+    let mut details = Vec::new();
+    let mut reader = Reader { bytes, pos: 0 };
+
+    while !reader.is_empty() {
+        let tag = reader.varint()?;
+        let number = (tag >> 3) as u32;
+        let wire_type = (tag & 7) as u8;
+        if number != 3 || wire_type != WIRE_LEN {
+            reader.skip(wire_type)?;
+            continue;
+        }
+        let len = reader.varint()? as usize;
+        details.push(decode_any(reader.take(len)?)?);
+    }
+
+    Ok(details)
+}
+
+/// A `google.protobuf.Any` as its type name and base64 value, the same shape
+/// Connect uses for error details
+fn decode_any(bytes: &[u8]) -> Result<Value, MercuryError> {
+    let mut type_url = String::new();
+    let mut value = Vec::new();
+    let mut reader = Reader { bytes, pos: 0 };
+
+    while !reader.is_empty() {
+        let tag = reader.varint()?;
+        let wire_type = (tag & 7) as u8;
+        match ((tag >> 3) as u32, wire_type) {
+            (1, WIRE_LEN) => {
+                let len = reader.varint()? as usize;
+                type_url = String::from_utf8_lossy(reader.take(len)?).into_owned();
+            }
+            (2, WIRE_LEN) => {
+                let len = reader.varint()? as usize;
+                value = reader.take(len)?.to_vec();
+            }
+            _ => reader.skip(wire_type)?,
+        }
+    }
+
+    // `type.googleapis.com/google.rpc.ErrorInfo` -> `google.rpc.ErrorInfo`
+    let type_name = type_url.rsplit('/').next().unwrap_or_default();
+    let mut detail = Map::new();
+    detail.insert("type".to_string(), Value::String(type_name.to_string()));
+    detail.insert(
+        "value".to_string(),
+        Value::String(BASE64_STANDARD_NO_PAD.encode(value)),
+    );
+    Ok(Value::Object(detail))
+}
+
+/// A Connect streaming response ends with a JSON frame holding the error, if any
+fn connect_error(bytes: &[u8]) -> Option<GrpcErrorDetails> {
+    let mut reader = Reader { bytes, pos: 0 };
+    while !reader.is_empty() {
+        let flag = reader.take(1).ok()?[0];
+        let len = u32::from_be_bytes(reader.take(4).ok()?.try_into().ok()?);
+        let payload = reader.take(len as usize).ok()?;
+        if flag & CONNECT_END_STREAM_FLAG == 0 {
+            continue;
+        }
+        if flag & COMPRESSED_FLAG != 0 {
+            return None;
+        }
+
+        let end: Value = serde_json::from_slice(payload).ok()?;
+        let error = end.get("error")?;
+        let text = |key: &str| {
+            error
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let code = text("code");
+        return Some(GrpcErrorDetails {
+            code: if code.is_empty() {
+                "UNKNOWN".to_string()
+            } else {
+                code.to_uppercase()
+            },
+            message: text("message"),
+            details: error
+                .get("details")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default(),
+        });
+    }
+    None
+}
+
+// =============================================================================
+// Execution
+// =============================================================================
+
+/// Make a unary gRPC-Web call. `request.url` is the server base URL and
+/// `request.body` the JSON request message; the reply is shown as JSON.
+pub fn execute_grpc_call(
+    request: &JsonRequest,
+    call: &GrpcCall,
+    proto: &ProtoFile,
+    timeouts: Timeouts,
+    shared_client: Option<&reqwest::blocking::Client>,
+    cookie_jar: Option<&Arc<reqwest::cookie::Jar>>,
+    cancel: Option<&AtomicBool>,
+) -> Result<HttpResponse, MercuryError> {
+    let service = proto
+        .service(&call.service)
+        .ok_or_else(|| grpc_error(format!("Service '{}' not found", call.service)))?;
+    let method = service
+        .methods
+        .iter()
+        .find(|m| m.name == call.method)
+        .ok_or_else(|| grpc_error(format!("Method '{}' not found", call.method)))?;
+    if method.client_streaming || method.server_streaming {
+        return Err(grpc_error("Only unary calls are supported"));
+    }
+
+    let message: Value = if request.body.trim().is_empty() {
+        Value::Object(Map::new())
+    } else {
+        serde_json::from_str(&request.body)?
+    };
+    let payload = encode_message(proto, &method.input, &message)?;
+
+    let mut headers = request.headers.clone();
+    headers.insert(
+        "Content-Type".to_string(),
+        "application/grpc-web-text".to_string(),
+    );
+    headers.insert(
+        "Accept".to_string(),
+        "application/grpc-web-text".to_string(),
+    );
+    headers.insert("X-Grpc-Web".to_string(), "1".to_string());
+
+    let http_request = JsonRequest {
+        method: HttpMethod::POST,
+        url: format!(
+            "{}/{}/{}",
+            request.url.trim_end_matches('/'),
+            service.name,
+            method.name
+        ),
+        headers,
+        body: BASE64_STANDARD.encode(frame_message(&payload)),
+        resolve: request.resolve.clone(),
+        timeout_secs: request.timeout_secs,
+        delay_ms: request.delay_ms,
+        ..Default::default()
+    };
+
+    let mut response = execute_request(
+        &http_request,
+        timeouts,
+        true,
+        shared_client,
+        cookie_jar,
+        cancel,
+    )?;
+
+    // Non-gRPC replies (proxy errors, HTML pages) are shown as-is
+    if !response.content_type.starts_with("application/grpc-web") {
+        return Ok(response);
+    }
+
+    let bytes = match response.raw_bytes.take() {
+        Some(bytes) => bytes,
+        None => decode_text_body(&response.body)?,
+    };
+    let (messages, trailers) = split_frames(&bytes)?;
+    response.headers.extend(trailers);
+
+    let replies = messages
+        .iter()
+        .map(|bytes| decode_message(proto, &method.output, bytes))
+        .collect::<Result<Vec<_>, _>>()?;
+    response.body = match replies.len() {
+        0 => String::new(),
+        1 => serde_json::to_string_pretty(&replies[0])?,
+        _ => serde_json::to_string_pretty(&replies)?,
+    };
+    response.response_type = if response.body.is_empty() {
+        ResponseType::Empty
+    } else {
+        ResponseType::Json
+    };
+    response.content_type = "application/json".to_string();
+    response.size_bytes = messages.iter().map(Vec::len).sum();
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_proto;
+    use serde_json::json;
+
+    const PROTO: &str = r#"
+syntax = "proto3";
+package demo;
+
+service Users {
+  rpc Get (GetUser) returns (User);
+}
+
+message GetUser { int64 id = 1; }
+
+message User {
+  int64 id = 1;
+  string display_name = 2;
+  repeated int32 scores = 3;
+  Role role = 4;
+  map<string, string> labels = 5;
+  Address address = 6;
+  bool active = 7;
+  double rating = 8;
+  sint32 offset = 9;
+}
+
+message Address { string city = 1; }
+
+enum Role {
+  ROLE_UNSPECIFIED = 0;
+  ADMIN = 1;
+}
+"#;
+
+    #[test]
+    fn test_encode_known_bytes() {
+        let proto = parse_proto(PROTO).unwrap();
+        // id = 150 -> field 1 varint: 08 96 01
+        let bytes = encode_message(&proto, "demo.GetUser", &json!({"id": 150})).unwrap();
+        assert_eq!(bytes, vec![0x08, 0x96, 0x01]);
+    }
+
+    #[test]
+    fn test_roundtrip_message() {
+        let proto = parse_proto(PROTO).unwrap();
+        let user = json!({
+            "id": "42",
+            "display_name": "Ada",
+            "scores": [1, 2, 300],
+            "role": "ADMIN",
+            "labels": {"team": "core"},
+            "address": {"city": "London"},
+            "active": true,
+            "rating": 4.5,
+            "offset": -3
+        });
+
+        let bytes = encode_message(&proto, "demo.User", &user).unwrap();
+        let decoded = decode_message(&proto, "demo.User", &bytes).unwrap();
+
+        assert_eq!(
+            decoded,
+            json!({
+                "id": "42",
+                "displayName": "Ada",
+                "scores": [1, 2, 300],
+                "role": "ADMIN",
+                "labels": {"team": "core"},
+                "address": {"city": "London"},
+                "active": true,
+                "rating": 4.5,
+                "offset": -3
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_is_rejected() {
+        let proto = parse_proto(PROTO).unwrap();
+        let result = encode_message(&proto, "demo.GetUser", &json!({"nope": 1}));
+        assert!(matches!(result, Err(MercuryError::GrpcError(_))));
+    }
+
+    #[test]
+    fn test_decode_stops_at_max_depth() {
+        let proto = parse_proto("syntax = \"proto3\";\nmessage Node { Node child = 1; }").unwrap();
+        let nest = |levels: usize| {
+            let mut bytes = Vec::new();
+            for _ in 0..levels {
+                let mut outer = vec![0x0a];
+                write_varint(&mut outer, bytes.len() as u64);
+                outer.extend_from_slice(&bytes);
+                bytes = outer;
+            }
+            bytes
+        };
+
+        assert!(decode_message(&proto, "Node", &nest(MAX_MESSAGE_DEPTH)).is_ok());
+        let result = decode_message(&proto, "Node", &nest(MAX_MESSAGE_DEPTH + 1));
+        assert!(matches!(result, Err(MercuryError::GrpcError(_))));
+    }
+
+    #[test]
+    fn test_frames_and_trailers() {
+        let mut body = frame_message(&[0x08, 0x01]);
+        let trailer = b"grpc-status: 5\r\ngrpc-message: not found\r\n";
+        body.push(TRAILER_FLAG);
+        body.extend_from_slice(&(trailer.len() as u32).to_be_bytes());
+        body.extend_from_slice(trailer);
+
+        // Servers may base64 each frame separately
+        let text = format!(
+            "{}{}",
+            BASE64_STANDARD.encode(&body[..7]),
+            BASE64_STANDARD.encode(&body[7..])
+        );
+        let (messages, trailers) = split_frames(&decode_text_body(&text).unwrap()).unwrap();
+
+        assert_eq!(messages, vec![vec![0x08, 0x01]]);
+        assert_eq!(
+            grpc_status_from_headers(&trailers),
+            Some((5, "not found".to_string()))
+        );
+        assert_eq!(grpc_status_name(5), "NOT_FOUND");
+    }
+
+    fn framed_response(content_type: &str, body: Vec<u8>) -> HttpResponse {
+        HttpResponse {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: vec![("content-type".to_string(), content_type.to_string())],
+            cookies: Vec::new(),
+            size_bytes: body.len(),
+            body: String::new(),
+            raw_bytes: Some(body),
+            duration_ms: 0,
+            content_type: content_type.to_string(),
+            response_type: ResponseType::Binary,
+            lossy_utf8: false,
+        }
+    }
+
+    #[test]
+    fn test_grpc_web_error_details() {
+        // google.rpc.Status { details: [Any { type_url, value: [0x0a, 0x01, 0x78] }] }
+        let type_url = b"type.googleapis.com/google.rpc.ErrorInfo";
+        let mut any = vec![0x0a, type_url.len() as u8];
+        any.extend_from_slice(type_url);
+        any.extend_from_slice(&[0x12, 0x03, 0x0a, 0x01, 0x78]);
+        let mut status = vec![0x08, 0x05, 0x1a, any.len() as u8];
+        status.extend(any);
+
+        let trailer = format!(
+            "grpc-status: 5\r\ngrpc-message: user%2042%20not%20found\r\ngrpc-status-details-bin: {}\r\n",
+            BASE64_STANDARD_NO_PAD.encode(&status)
+        );
+        let mut body = vec![TRAILER_FLAG];
+        body.extend_from_slice(&(trailer.len() as u32).to_be_bytes());
+        body.extend_from_slice(trailer.as_bytes());
+
+        let error =
+            grpc_error_details(&framed_response("application/grpc-web+proto", body.clone()))
+                .unwrap();
+        assert_eq!(error.code, "NOT_FOUND");
+        assert_eq!(error.message, "user 42 not found");
+        assert_eq!(
+            error.details,
+            vec![json!({"type": "google.rpc.ErrorInfo", "value": "CgF4"})]
+        );
+
+        // The same frames as base64 text
+        let text = BASE64_STANDARD.encode(&body).into_bytes();
+        let text_error =
+            grpc_error_details(&framed_response("application/grpc-web-text", text)).unwrap();
+        assert_eq!(text_error, error);
+
+        // Successful calls and unparseable bodies fall back to the binary view
+        let mut ok = vec![TRAILER_FLAG, 0, 0, 0, 16];
+        ok.extend_from_slice(b"grpc-status: 0\r\n");
+        assert_eq!(
+            grpc_error_details(&framed_response("application/grpc-web+proto", ok)),
+            None
+        );
+        assert_eq!(
+            grpc_error_details(&framed_response(
+                "application/grpc-web+proto",
+                vec![0, 0, 0, 9, 1]
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_connect_error_details() {
+        let mut body = frame_message(&[0x08, 0x01]);
+        let end = br#"{"error": {"code": "permission_denied", "message": "no access", "details": [{"type": "google.rpc.ErrorInfo", "value": "CgF4", "debug": {"reason": "x"}}]}}"#;
+        body.push(CONNECT_END_STREAM_FLAG);
+        body.extend_from_slice(&(end.len() as u32).to_be_bytes());
+        body.extend_from_slice(end);
+
+        let error = grpc_error_details(&framed_response("application/connect+json", body)).unwrap();
+        assert_eq!(error.code, "PERMISSION_DENIED");
+        assert_eq!(error.message, "no access");
+        assert_eq!(error.details[0]["debug"]["reason"], "x");
+
+        let mut ok = frame_message(b"{}");
+        ok.extend_from_slice(&[CONNECT_END_STREAM_FLAG, 0, 0, 0, 2]);
+        ok.extend_from_slice(b"{}");
+        assert_eq!(
+            grpc_error_details(&framed_response("application/connect+json", ok)),
+            None
+        );
+    }
+}
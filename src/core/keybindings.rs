@@ -0,0 +1,315 @@
+//! Keybindings Module
+//!
+//! Remappable shortcuts for the core actions. Bindings are stored as strings such as
+//! `Cmd+Shift+O`, where `Cmd` is ⌘ on macOS and Ctrl elsewhere; key names follow egui's.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// An action that can be bound to a key combination
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    SendRequest,
+    RetryLast,
+    StopAndEditUrl,
+    NewRequest,
+    SaveRequest,
+    FocusSearch,
+    FocusUrlBar,
+    PreviousRequest,
+    CopyAsCurl,
+    OpenFolder,
+    RecentWorkspaces,
+    NewWindow,
+    ToggleRawView,
+    CycleEnvironment,
+    History,
+    FocusMode,
+    CollapseRequest,
+    CollapseResponse,
+    Settings,
+}
+
+impl Action {
+    pub const ALL: [Action; 19] = [
+        Action::SendRequest,
+        Action::RetryLast,
+        Action::StopAndEditUrl,
+        Action::NewRequest,
+        Action::SaveRequest,
+        Action::FocusSearch,
+        Action::FocusUrlBar,
+        Action::PreviousRequest,
+        Action::CopyAsCurl,
+        Action::OpenFolder,
+        Action::RecentWorkspaces,
+        Action::NewWindow,
+        Action::ToggleRawView,
+        Action::CycleEnvironment,
+        Action::History,
+        Action::FocusMode,
+        Action::CollapseRequest,
+        Action::CollapseResponse,
+        Action::Settings,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::SendRequest => "Send Request",
+            Action::RetryLast => "Retry Last Request",
+            Action::StopAndEditUrl => "Stop and Edit URL",
+            Action::NewRequest => "New Request",
+            Action::SaveRequest => "Save Request",
+            Action::FocusSearch => "Search",
+            Action::FocusUrlBar => "Focus URL Bar",
+            Action::PreviousRequest => "Previous Request",
+            Action::CopyAsCurl => "Copy as cURL",
+            Action::OpenFolder => "Open Folder",
+            Action::RecentWorkspaces => "Recent Workspaces",
+            Action::NewWindow => "New Window",
+            Action::ToggleRawView => "Toggle Raw View",
+            Action::CycleEnvironment => "Switch Environment",
+            Action::History => "History",
+            Action::FocusMode => "Focus Mode",
+            Action::CollapseRequest => "Collapse Request",
+            Action::CollapseResponse => "Collapse Response",
+            Action::Settings => "Settings",
+        }
+    }
+}
+
+/// A key plus modifiers, e.g. `Cmd+Shift+O`
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyCombo {
+    /// ⌘ on macOS, Ctrl elsewhere
+    pub command: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// egui key name, e.g. `Enter`, `N`, `Comma`
+    pub key: String,
+}
+
+impl KeyCombo {
+    pub fn cmd(key: &str) -> Self {
+        Self {
+            command: true,
+            alt: false,
+            shift: false,
+            key: key.to_string(),
+        }
+    }
+
+    pub fn cmd_shift(key: &str) -> Self {
+        Self {
+            shift: true,
+            ..Self::cmd(key)
+        }
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (held, name) in [
+            (self.command, "Cmd"),
+            (self.alt, "Alt"),
+            (self.shift, "Shift"),
+        ] {
+            if held {
+                write!(f, "{}+", name)?;
+            }
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut combo = KeyCombo {
+            command: false,
+            alt: false,
+            shift: false,
+            key: String::new(),
+        };
+        let parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let (key, modifiers) = parts.split_last().ok_or("Empty key binding")?;
+        for modifier in modifiers {
+            match modifier.to_lowercase().as_str() {
+                "cmd" | "ctrl" | "command" | "control" => combo.command = true,
+                "alt" | "option" => combo.alt = true,
+                "shift" => combo.shift = true,
+                other => return Err(format!("Unknown modifier '{}' in '{}'", other, s)),
+            }
+        }
+        if key.is_empty() {
+            return Err(format!("Missing key in '{}'", s));
+        }
+        combo.key = key.to_string();
+        Ok(combo)
+    }
+}
+
+impl TryFrom<String> for KeyCombo {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<KeyCombo> for String {
+    fn from(combo: KeyCombo) -> Self {
+        combo.to_string()
+    }
+}
+
+/// Starting sets of bindings
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeymapPreset {
+    Mercury,
+    Postman,
+    Insomnia,
+}
+
+impl KeymapPreset {
+    pub const ALL: [KeymapPreset; 3] = [
+        KeymapPreset::Mercury,
+        KeymapPreset::Postman,
+        KeymapPreset::Insomnia,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeymapPreset::Mercury => "Mercury",
+            KeymapPreset::Postman => "Postman",
+            KeymapPreset::Insomnia => "Insomnia",
+        }
+    }
+}
+
+/// The bound combination for each action
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Keymap(BTreeMap<Action, KeyCombo>);
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(KeymapPreset::Mercury)
+    }
+}
+
+impl Keymap {
+    pub fn preset(preset: KeymapPreset) -> Self {
+        let mut bindings: BTreeMap<Action, KeyCombo> = [
+            (Action::SendRequest, KeyCombo::cmd("Enter")),
+            (Action::RetryLast, KeyCombo::cmd_shift("Enter")),
+            (Action::StopAndEditUrl, KeyCombo::cmd("Period")),
+            (Action::NewRequest, KeyCombo::cmd("N")),
+            (Action::SaveRequest, KeyCombo::cmd("S")),
+            (Action::FocusSearch, KeyCombo::cmd("K")),
+            (Action::FocusUrlBar, KeyCombo::cmd("L")),
+            (Action::PreviousRequest, KeyCombo::cmd("B")),
+            (Action::CopyAsCurl, KeyCombo::cmd_shift("C")),
+            (Action::OpenFolder, KeyCombo::cmd("O")),
+            (Action::RecentWorkspaces, KeyCombo::cmd_shift("O")),
+            (Action::NewWindow, KeyCombo::cmd_shift("N")),
+            (Action::ToggleRawView, KeyCombo::cmd("R")),
+            (Action::CycleEnvironment, KeyCombo::cmd("E")),
+            (Action::History, KeyCombo::cmd("H")),
+            (Action::FocusMode, KeyCombo::cmd_shift("F")),
+            (Action::CollapseRequest, KeyCombo::cmd_shift("Left")),
+            (Action::CollapseResponse, KeyCombo::cmd_shift("Right")),
+            (Action::Settings, KeyCombo::cmd("Comma")),
+        ]
+        .into_iter()
+        .collect();
+
+        let overrides = match preset {
+            KeymapPreset::Mercury => vec![],
+            // New tab, and the sidebar toggle
+            KeymapPreset::Postman => vec![
+                (Action::NewRequest, KeyCombo::cmd("T")),
+                (Action::FocusMode, KeyCombo::cmd("Backslash")),
+            ],
+            // Quick switcher, environment switcher, and the sidebar toggle
+            KeymapPreset::Insomnia => vec![
+                (Action::FocusSearch, KeyCombo::cmd("P")),
+                (Action::CycleEnvironment, KeyCombo::cmd_shift("E")),
+                (Action::FocusMode, KeyCombo::cmd("Backslash")),
+            ],
+        };
+        bindings.extend(overrides);
+        Self(bindings)
+    }
+
+    /// The combination bound to `action`; actions missing from older settings
+    /// files use the default
+    pub fn get(&self, action: Action) -> KeyCombo {
+        self.0
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| Self::default().0[&action].clone())
+    }
+
+    pub fn set(&mut self, action: Action, combo: KeyCombo) {
+        self.0.insert(action, combo);
+    }
+
+    /// Another action bound to the same combination as `action`, if any
+    pub fn conflict(&self, action: Action) -> Option<Action> {
+        let combo = self.get(action);
+        Action::ALL
+            .into_iter()
+            .find(|other| *other != action && self.get(*other) == combo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_combo_roundtrip() {
+        let combo: KeyCombo = "Cmd+Shift+O".parse().unwrap();
+        assert_eq!(combo, KeyCombo::cmd_shift("O"));
+        assert_eq!(combo.to_string(), "Cmd+Shift+O");
+
+        // Ctrl is the same modifier as Cmd
+        assert_eq!(
+            "ctrl+Enter".parse::<KeyCombo>().unwrap(),
+            KeyCombo::cmd("Enter")
+        );
+        assert!("Hyper+K".parse::<KeyCombo>().is_err());
+        assert!("Cmd+".parse::<KeyCombo>().is_err());
+    }
+
+    #[test]
+    fn test_keymap_conflicts() {
+        let mut keymap = Keymap::default();
+        assert!(Action::ALL.iter().all(|a| keymap.conflict(*a).is_none()));
+
+        keymap.set(Action::History, KeyCombo::cmd("K"));
+        assert_eq!(keymap.conflict(Action::History), Some(Action::FocusSearch));
+        assert_eq!(keymap.conflict(Action::FocusSearch), Some(Action::History));
+
+        for preset in KeymapPreset::ALL {
+            let keymap = Keymap::preset(preset);
+            assert!(Action::ALL.iter().all(|a| keymap.conflict(*a).is_none()));
+        }
+    }
+
+    #[test]
+    fn test_keymap_serde_fills_missing() {
+        let keymap: Keymap = serde_json::from_str(r#"{"send_request": "Alt+Enter"}"#).unwrap();
+        assert_eq!(keymap.get(Action::SendRequest).to_string(), "Alt+Enter");
+        assert_eq!(keymap.get(Action::NewRequest), KeyCombo::cmd("N"));
+
+        let json = serde_json::to_string(&Keymap::default()).unwrap();
+        assert!(json.contains(r#""settings":"Cmd+Comma""#));
+    }
+}
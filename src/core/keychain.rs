@@ -0,0 +1,235 @@
+//! Keychain Module
+//!
+//! Resolves `{{keychain:service/account}}` tokens from the OS credential store at
+//! send time, so secrets don't have to live in `.env` files. Entries live in the
+//! platform's credential store: Keychain on macOS, Credential Manager on Windows,
+//! and the Secret Service (GNOME Keyring, KWallet) on Linux.
+
+use super::error::MercuryError;
+use super::types::JsonRequest;
+use std::collections::HashMap;
+
+/// Variable name prefix marking a keychain reference
+pub const TOKEN_PREFIX: &str = "keychain:";
+
+/// A keychain entry, addressed by service and account
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeychainRef {
+    pub service: String,
+    pub account: String,
+}
+
+impl KeychainRef {
+    /// Parse the inside of a `{{...}}` token, e.g. `keychain:github/me`
+    pub fn parse(name: &str) -> Option<Self> {
+        let (service, account) = name.trim().strip_prefix(TOKEN_PREFIX)?.split_once('/')?;
+        let (service, account) = (service.trim(), account.trim());
+        if service.is_empty() || account.is_empty() {
+            return None;
+        }
+        Some(Self {
+            service: service.to_string(),
+            account: account.to_string(),
+        })
+    }
+
+    /// The `{{keychain:service/account}}` token for this entry
+    pub fn token(&self) -> String {
+        format!("{{{{{}{}/{}}}}}", TOKEN_PREFIX, self.service, self.account)
+    }
+}
+
+/// Replace every keychain token in `text` using `lookup`. A malformed token is an
+/// error rather than being sent as literal text.
+fn resolve_tokens(
+    text: &str,
+    lookup: &mut impl FnMut(&KeychainRef) -> Result<String, MercuryError>,
+) -> Result<String, MercuryError> {
+    let open = format!("{{{{{}", TOKEN_PREFIX);
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(&open) {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = &rest[start + 2..start + len];
+        let reference = KeychainRef::parse(name).ok_or_else(|| {
+            MercuryError::KeychainError(format!(
+                "'{{{{{}}}}}' should look like {{{{keychain:service/account}}}}",
+                name
+            ))
+        })?;
+        result.push_str(&rest[..start]);
+        result.push_str(&lookup(&reference)?);
+        rest = &rest[start + len + 2..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Resolve keychain tokens in the URL, headers, and body of `request`.
+/// Returns `None` when there are none, so the request can be sent as is.
+pub fn resolve_request(request: &JsonRequest) -> Result<Option<JsonRequest>, MercuryError> {
+    resolve_request_with(request, read_secret)
+}
+
+fn resolve_request_with(
+    request: &JsonRequest,
+    mut read: impl FnMut(&KeychainRef) -> Result<String, MercuryError>,
+) -> Result<Option<JsonRequest>, MercuryError> {
+    let open = format!("{{{{{}", TOKEN_PREFIX);
+    let has_tokens = request.url.contains(&open)
+        || request.body.contains(&open)
+        || request
+            .headers
+            .iter()
+            .any(|(k, v)| k.contains(&open) || v.contains(&open));
+    if !has_tokens {
+        return Ok(None);
+    }
+
+    // Each entry is read once, however often it's referenced
+    let mut cache: HashMap<KeychainRef, String> = HashMap::new();
+    let mut lookup = |reference: &KeychainRef| match cache.get(reference) {
+        Some(secret) => Ok(secret.clone()),
+        None => {
+            let secret = read(reference)?;
+            cache.insert(reference.clone(), secret.clone());
+            Ok(secret)
+        }
+    };
+
+    let mut headers = HashMap::new();
+    for (key, value) in &request.headers {
+        headers.insert(
+            resolve_tokens(key, &mut lookup)?,
+            resolve_tokens(value, &mut lookup)?,
+        );
+    }
+    Ok(Some(JsonRequest {
+        url: resolve_tokens(&request.url, &mut lookup)?,
+        headers,
+        body: resolve_tokens(&request.body, &mut lookup)?,
+        ..request.clone()
+    }))
+}
+
+/// The credential store entry for `reference`
+fn entry(reference: &KeychainRef) -> Result<keyring::Entry, MercuryError> {
+    keyring::Entry::new(&reference.service, &reference.account).map_err(|e| {
+        MercuryError::KeychainError(format!(
+            "Can't use {}/{}: {}",
+            reference.service, reference.account, e
+        ))
+    })
+}
+
+/// Read the secret stored for `reference`
+pub fn read_secret(reference: &KeychainRef) -> Result<String, MercuryError> {
+    match entry(reference)?.get_password() {
+        Ok(secret) if !secret.is_empty() => Ok(secret),
+        Ok(_) | Err(keyring::Error::NoEntry) => Err(MercuryError::KeychainError(format!(
+            "No keychain entry for {}/{}",
+            reference.service, reference.account
+        ))),
+        Err(e) => Err(MercuryError::KeychainError(format!(
+            "Could not read {}/{}: {}",
+            reference.service, reference.account, e
+        ))),
+    }
+}
+
+/// Create or replace the entry for `reference`
+pub fn store_secret(reference: &KeychainRef, secret: &str) -> Result<(), MercuryError> {
+    entry(reference)?.set_password(secret).map_err(|e| {
+        MercuryError::KeychainError(format!(
+            "Could not store {}/{}: {}",
+            reference.service, reference.account, e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reference() {
+        assert_eq!(
+            KeychainRef::parse("keychain:github/octocat"),
+            Some(KeychainRef {
+                service: "github".to_string(),
+                account: "octocat".to_string(),
+            })
+        );
+        // Accounts may contain slashes; the service may not
+        assert_eq!(
+            KeychainRef::parse("keychain:api/team/ci").map(|r| r.account),
+            Some("team/ci".to_string())
+        );
+        assert_eq!(KeychainRef::parse("keychain:github"), None);
+        assert_eq!(KeychainRef::parse("keychain:/octocat"), None);
+        assert_eq!(KeychainRef::parse("API_TOKEN"), None);
+
+        let reference = KeychainRef::parse("keychain:github/octocat").unwrap();
+        assert_eq!(reference.token(), "{{keychain:github/octocat}}");
+    }
+
+    #[test]
+    fn test_resolve_request() {
+        let request = JsonRequest {
+            url: "https://api.example.com/{{keychain:api/path}}".to_string(),
+            headers: [(
+                "Authorization".to_string(),
+                "Bearer {{keychain:github/octocat}}".to_string(),
+            )]
+            .into_iter()
+            .collect(),
+            body: r#"{"a":"{{keychain:github/octocat}}"}"#.to_string(),
+            ..Default::default()
+        };
+
+        let mut reads = 0;
+        let resolved = resolve_request_with(&request, |r| {
+            reads += 1;
+            Ok(format!("<{}>", r.account))
+        })
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(resolved.url, "https://api.example.com/<path>");
+        assert_eq!(resolved.headers["Authorization"], "Bearer <octocat>");
+        assert_eq!(resolved.body, r#"{"a":"<octocat>"}"#);
+        assert_eq!(reads, 2);
+
+        let plain = JsonRequest {
+            url: "https://api.example.com/{{BASE}}".to_string(),
+            ..Default::default()
+        };
+        assert!(resolve_request_with(&plain, |_| unreachable!())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let request = JsonRequest {
+            url: "https://api.example.com/?key={{keychain:missing/me}}".to_string(),
+            ..Default::default()
+        };
+        let err = resolve_request_with(&request, |_| {
+            Err(MercuryError::KeychainError("not found".to_string()))
+        })
+        .unwrap_err();
+        assert!(matches!(err, MercuryError::KeychainError(_)));
+
+        let malformed = JsonRequest {
+            url: "https://api.example.com/?key={{keychain:nope}}".to_string(),
+            ..Default::default()
+        };
+        let err = resolve_request_with(&malformed, |_| Ok(String::new())).unwrap_err();
+        assert!(err.to_string().contains("keychain:service/account"));
+    }
+}
@@ -2,12 +2,30 @@
 //!
 //! Core business logic: types, persistence, constants, error handling, and HTTP execution.
 
+pub mod baseline;
+pub mod codegen;
+pub mod collection;
 pub mod constants;
+pub mod env_compare;
 pub mod error;
+pub mod examples;
+pub mod folder_defaults;
+pub mod grpc;
+pub mod keybindings;
+pub mod keychain;
+pub mod patch;
 pub mod persistence;
 pub mod request;
+pub mod request_log;
+pub mod runner;
+pub mod search;
+pub mod secrets;
 pub mod types;
+pub mod windows;
 
 // Re-export commonly used items
 pub use error::MercuryError;
-pub use request::{execute_request, format_json, format_xml, HttpResponse, ResponseType};
+pub use request::{
+    check_xml, execute_request, format_json, format_json_prefix, format_json_with, format_xml,
+    format_xml_body, HttpResponse, ResponseType,
+};
@@ -0,0 +1,354 @@
+//! Patch Module
+//!
+//! Helpers for the two standard PATCH bodies: JSON Merge Patch (RFC 7386), which
+//! is just the fields to change, and JSON Patch (RFC 6902), a list of operations.
+//! Bodies sent with either media type are checked before the request goes out.
+
+use serde_json::{Map, Value};
+
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+/// JSON Patch operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchOpKind {
+    Add,
+    Remove,
+    Replace,
+    Move,
+    Copy,
+    Test,
+}
+
+impl PatchOpKind {
+    pub const ALL: [PatchOpKind; 6] = [
+        PatchOpKind::Add,
+        PatchOpKind::Remove,
+        PatchOpKind::Replace,
+        PatchOpKind::Move,
+        PatchOpKind::Copy,
+        PatchOpKind::Test,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PatchOpKind::Add => "add",
+            PatchOpKind::Remove => "remove",
+            PatchOpKind::Replace => "replace",
+            PatchOpKind::Move => "move",
+            PatchOpKind::Copy => "copy",
+            PatchOpKind::Test => "test",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.as_str() == s)
+    }
+
+    /// `add`, `replace`, and `test` carry a `value`
+    pub fn takes_value(&self) -> bool {
+        matches!(
+            self,
+            PatchOpKind::Add | PatchOpKind::Replace | PatchOpKind::Test
+        )
+    }
+
+    /// `move` and `copy` carry a `from` path
+    pub fn takes_from(&self) -> bool {
+        matches!(self, PatchOpKind::Move | PatchOpKind::Copy)
+    }
+
+    /// Whether a builder row for this op shows the member `key`
+    fn shows(&self, key: &str) -> bool {
+        match key {
+            "op" | "path" => true,
+            "from" => self.takes_from(),
+            "value" => self.takes_value(),
+            _ => false,
+        }
+    }
+}
+
+/// One row of the JSON Patch builder. `value` is JSON text as typed and is
+/// written out as-is, so text that isn't JSON makes the body invalid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchOp {
+    pub op: PatchOpKind,
+    pub path: String,
+    pub from: String,
+    pub value: String,
+    /// Members the row doesn't show, kept so rewriting the body doesn't drop them
+    pub extra: Map<String, Value>,
+}
+
+impl Default for PatchOp {
+    fn default() -> Self {
+        Self {
+            op: PatchOpKind::Replace,
+            path: String::new(),
+            from: String::new(),
+            value: String::new(),
+            extra: Map::new(),
+        }
+    }
+}
+
+impl PatchOp {
+    /// Why `value` can't be sent, for operations that carry one
+    pub fn value_error(&self) -> Option<&'static str> {
+        let invalid = self.op.takes_value() && serde_json::from_str::<Value>(&self.value).is_err();
+        invalid.then_some("Not JSON; put text in quotes, like \"Alice\"")
+    }
+}
+
+/// Which patch format `content_type` names, if either; parameters are ignored
+pub fn patch_media_type(content_type: &str) -> Option<&'static str> {
+    let media_type = content_type.split(';').next().unwrap_or("").trim();
+    [MERGE_PATCH_CONTENT_TYPE, JSON_PATCH_CONTENT_TYPE]
+        .into_iter()
+        .find(|known| media_type.eq_ignore_ascii_case(known))
+}
+
+/// The builder rows for a JSON Patch body. An empty body has none.
+pub fn parse_json_patch(body: &str) -> Result<Vec<PatchOp>, String> {
+    if body.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    check_json_patch(body)?;
+    let Ok(Value::Array(ops)) = serde_json::from_str::<Value>(body) else {
+        return Err("A JSON Patch must be an array of operations".to_string());
+    };
+    Ok(ops
+        .iter()
+        .filter_map(|op| {
+            let text = |key: &str| {
+                op.get(key)
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_string()
+            };
+            let kind = PatchOpKind::from_str(op.get("op")?.as_str()?)?;
+            Some(PatchOp {
+                op: kind,
+                path: text("path"),
+                from: text("from"),
+                value: op.get("value").map(Value::to_string).unwrap_or_default(),
+                extra: op
+                    .as_object()?
+                    .iter()
+                    .filter(|(key, _)| !kind.shows(key))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            })
+        })
+        .collect())
+}
+
+/// `ops` as a JSON Patch document, one operation per line, with the members
+/// each row keeps but doesn't show after the ones it does
+pub fn to_json_patch(ops: &[PatchOp]) -> String {
+    if ops.is_empty() {
+        return "[]".to_string();
+    }
+    let quote = |s: &str| Value::String(s.to_string()).to_string();
+    let lines: Vec<String> = ops
+        .iter()
+        .map(|op| {
+            let mut line = format!(
+                "  {{\"op\": {}, \"path\": {}",
+                quote(op.op.as_str()),
+                quote(&op.path)
+            );
+            if op.op.takes_from() {
+                line.push_str(&format!(", \"from\": {}", quote(&op.from)));
+            }
+            if op.op.takes_value() {
+                let value = serde_json::from_str::<Value>(&op.value)
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|_| op.value.trim().to_string());
+                line.push_str(&format!(", \"value\": {}", value));
+            }
+            for (key, value) in &op.extra {
+                if !op.op.shows(key) {
+                    line.push_str(&format!(", {}: {}", quote(key), value));
+                }
+            }
+            line.push('}');
+            line
+        })
+        .collect();
+    format!("[\n{}\n]", lines.join(",\n"))
+}
+
+/// Why `body` isn't a valid JSON Patch document, naming the first bad operation
+pub fn check_json_patch(body: &str) -> Result<(), String> {
+    let doc: Value = serde_json::from_str(body).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let Value::Array(ops) = doc else {
+        return Err("A JSON Patch must be an array of operations".to_string());
+    };
+    let is_pointer = |p: &Value| {
+        p.as_str()
+            .is_some_and(|p| p.is_empty() || p.starts_with('/'))
+    };
+    for (i, op) in ops.iter().enumerate() {
+        let n = i + 1;
+        let Value::Object(fields) = op else {
+            return Err(format!("Operation {} is not an object", n));
+        };
+        let kind = fields
+            .get("op")
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("Operation {} has no \"op\"", n))?;
+        let kind = PatchOpKind::from_str(kind)
+            .ok_or_else(|| format!("Operation {}: unknown op \"{}\"", n, kind))?;
+        match fields.get("path") {
+            None => return Err(format!("Operation {} has no \"path\"", n)),
+            Some(path) if !is_pointer(path) => {
+                return Err(format!("Operation {}: \"path\" must start with /", n))
+            }
+            _ => {}
+        }
+        if kind.takes_value() && !fields.contains_key("value") {
+            return Err(format!(
+                "Operation {} ({}) needs a \"value\"",
+                n,
+                kind.as_str()
+            ));
+        }
+        if kind.takes_from() && !fields.get("from").is_some_and(is_pointer) {
+            return Err(format!(
+                "Operation {} ({}) needs a \"from\" path starting with /",
+                n,
+                kind.as_str()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check a body against the patch format its `Content-Type` names; bodies of
+/// any other type pass
+pub fn check_patch_body(content_type: Option<&str>, body: &str) -> Result<(), String> {
+    match content_type.and_then(patch_media_type) {
+        Some(JSON_PATCH_CONTENT_TYPE) => check_json_patch(body),
+        Some(_) => serde_json::from_str::<Value>(body)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid JSON: {}", e)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_patch_roundtrip() {
+        let ops = vec![
+            PatchOp {
+                op: PatchOpKind::Replace,
+                path: "/name".to_string(),
+                value: "\"Alice\"".to_string(),
+                ..Default::default()
+            },
+            PatchOp {
+                op: PatchOpKind::Remove,
+                path: "/age".to_string(),
+                value: "ignored".to_string(),
+                ..Default::default()
+            },
+            PatchOp {
+                op: PatchOpKind::Move,
+                path: "/b".to_string(),
+                from: "/a".to_string(),
+                ..Default::default()
+            },
+            PatchOp {
+                op: PatchOpKind::Add,
+                path: "/tags/-".to_string(),
+                value: "[1, 2]".to_string(),
+                ..Default::default()
+            },
+        ];
+        let body = to_json_patch(&ops);
+        assert_eq!(
+            body,
+            "[\n  {\"op\": \"replace\", \"path\": \"/name\", \"value\": \"Alice\"},\n  {\"op\": \"remove\", \"path\": \"/age\"},\n  {\"op\": \"move\", \"path\": \"/b\", \"from\": \"/a\"},\n  {\"op\": \"add\", \"path\": \"/tags/-\", \"value\": [1,2]}\n]"
+        );
+        assert!(check_json_patch(&body).is_ok());
+
+        let parsed = parse_json_patch(&body).unwrap();
+        assert_eq!(parsed.len(), 4);
+        assert_eq!(parsed[0].value, "\"Alice\"");
+        assert_eq!(parsed[2].from, "/a");
+        assert_eq!(parse_json_patch("").unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn test_json_patch_value_not_coerced() {
+        let op = PatchOp {
+            op: PatchOpKind::Add,
+            path: "/note".to_string(),
+            value: "plain text".to_string(),
+            ..Default::default()
+        };
+        assert!(op.value_error().is_some());
+        let body = to_json_patch(std::slice::from_ref(&op));
+        assert!(body.contains("\"value\": plain text"));
+        assert!(check_json_patch(&body).is_err());
+
+        let remove = PatchOp {
+            op: PatchOpKind::Remove,
+            ..op
+        };
+        assert_eq!(remove.value_error(), None);
+    }
+
+    #[test]
+    fn test_json_patch_keeps_unknown_members() {
+        let body = r#"[{"op": "test", "path": "/a", "value": 1, "comment": "guard"}, {"op": "remove", "path": "/b", "from": "/c"}]"#;
+        let ops = parse_json_patch(body).unwrap();
+        assert_eq!(ops[0].extra.get("comment"), Some(&Value::from("guard")));
+        assert_eq!(ops[1].extra.get("from"), Some(&Value::from("/c")));
+
+        let rewritten: Value = serde_json::from_str(&to_json_patch(&ops)).unwrap();
+        let original: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(rewritten, original);
+    }
+
+    #[test]
+    fn test_check_json_patch() {
+        assert!(check_json_patch("[]").is_ok());
+        assert_eq!(
+            check_json_patch(r#"{"op": "add"}"#).unwrap_err(),
+            "A JSON Patch must be an array of operations"
+        );
+        assert_eq!(
+            check_json_patch(r#"[{"op": "remove", "path": "/a"}, {"op": "rename", "path": "/b"}]"#)
+                .unwrap_err(),
+            "Operation 2: unknown op \"rename\""
+        );
+        assert_eq!(
+            check_json_patch(r#"[{"op": "replace", "path": "name", "value": 1}]"#).unwrap_err(),
+            "Operation 1: \"path\" must start with /"
+        );
+        assert_eq!(
+            check_json_patch(r#"[{"op": "test", "path": "/a"}]"#).unwrap_err(),
+            "Operation 1 (test) needs a \"value\""
+        );
+        assert!(check_json_patch(r#"[{"op": "copy", "path": "/a"}]"#).is_err());
+        assert!(check_json_patch("[{")
+            .unwrap_err()
+            .starts_with("Invalid JSON"));
+    }
+
+    #[test]
+    fn test_check_patch_body_by_content_type() {
+        let merge = Some("application/merge-patch+json; charset=utf-8");
+        assert!(check_patch_body(merge, r#"{"name": null}"#).is_ok());
+        assert!(check_patch_body(merge, "{name: 1}").is_err());
+        assert!(check_patch_body(Some("application/json-patch+json"), "{}").is_err());
+        assert!(check_patch_body(Some("text/plain"), "{name: 1}").is_ok());
+        assert!(check_patch_body(None, "anything").is_ok());
+    }
+}
@@ -1,12 +1,17 @@
 //! Persistence Module
 //!
 //! Handles saving and loading application state to disk.
-//! All data is stored in ~/.mercury/ directory.
+//! All data is stored in ~/.mercury/ directory. Every window is its own
+//! process, so history and recent requests are written under a file lock and
+//! through a temp file, and a reader never sees half a file.
 
-use super::constants::{HISTORY_EXPIRY_SECONDS, MAX_TIMELINE_ENTRIES};
-use super::types::{AppState, RecentRequest, TimelineEntry};
-use std::fs;
-use std::path::PathBuf;
+use super::constants::{HISTORY_EXPIRY_SECONDS, MAX_TIMELINE_ENTRIES, WORKSPACE_CONFIG_FILE};
+use super::types::{AppState, RecentRequest, TimelineEntry, WorkspaceConfig};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
 
 /// Get the Mercury config directory (~/.mercury)
 fn get_config_dir() -> PathBuf {
@@ -22,6 +27,31 @@ fn ensure_config_dir() {
     let _ = fs::create_dir_all(&dir);
 }
 
+/// Replace the JSON list at `path` with `update` applied to it, holding a lock
+/// that other Mercury processes wait on. A file that exists but doesn't parse is
+/// left as it is rather than replaced by `update`'s result alone.
+fn update_json_list<T: Serialize + DeserializeOwned>(
+    path: &Path,
+    update: impl FnOnce(&mut Vec<T>),
+) -> io::Result<()> {
+    let lock = File::create(path.with_extension("lock"))?;
+    lock.lock()?;
+
+    let mut items = match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    update(&mut items);
+
+    // Renaming over the old file is atomic, unlike writing into it
+    let json = serde_json::to_string_pretty(&items)?;
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, json)?;
+    fs::rename(&temp, path)
+}
+
 // ============ Recent Requests ============
 
 pub fn get_recent_file_path() -> PathBuf {
@@ -45,12 +75,11 @@ pub fn save_recent_requests(requests: &[RecentRequest]) {
     let path = get_recent_file_path();
 
     let skip = requests.len().saturating_sub(50);
-    let to_save: Vec<_> = requests.iter().skip(skip).cloned().collect();
-
-    if let Ok(json) = serde_json::to_string_pretty(&to_save) {
-        if let Err(e) = fs::write(&path, json) {
-            eprintln!("Failed to save recent requests: {}", e);
-        }
+    let result = update_json_list(&path, |saved: &mut Vec<RecentRequest>| {
+        *saved = requests[skip..].to_vec();
+    });
+    if let Err(e) = result {
+        eprintln!("Failed to save recent requests: {}", e);
     }
 }
 
@@ -81,6 +110,23 @@ pub fn save_state(state: &AppState) {
     }
 }
 
+// ============ Workspace Config ============
+
+/// Load `.mercury.json` from a workspace root. Missing or invalid files yield defaults.
+pub fn load_workspace_config(workspace: &Path) -> WorkspaceConfig {
+    fs::read_to_string(workspace.join(WORKSPACE_CONFIG_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+// ============ Request Log ============
+
+/// Where the request log goes when Settings doesn't name a file
+pub fn get_request_log_path() -> PathBuf {
+    get_config_dir().join("requests.log")
+}
+
 // ============ History ============
 
 pub fn get_history_file_path() -> PathBuf {
@@ -129,44 +175,28 @@ pub fn load_history_entry(timestamp: f64) -> Option<TimelineEntry> {
 /// Loads existing history, adds new entry, enforces limits, and saves.
 pub fn append_history_entry(entry: &TimelineEntry) {
     ensure_config_dir();
-    let path = get_history_file_path();
+    if let Err(e) = append_to_history(&get_history_file_path(), entry) {
+        eprintln!("Failed to save history: {}", e);
+    }
+}
 
-    // Load existing entries
-    let mut entries: Vec<TimelineEntry> = if path.exists() {
-        fs::read_to_string(&path)
-            .ok()
-            .and_then(|content| serde_json::from_str(&content).ok())
-            .unwrap_or_default()
-    } else {
-        Vec::new()
-    };
+fn append_to_history(path: &Path, entry: &TimelineEntry) -> io::Result<()> {
+    update_json_list(path, |entries: &mut Vec<TimelineEntry>| {
+        entries.push(entry.clone());
 
-    // Add new entry
-    entries.push(entry.clone());
-
-    // Apply expiry and limits
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs_f64();
-    let cutoff = now - HISTORY_EXPIRY_SECONDS;
-
-    // Filter by expiry, take most recent entries, preserve chronological order
-    let mut to_save: Vec<_> = entries
-        .into_iter()
-        .filter(|e| e.timestamp > cutoff)
-        .collect();
-
-    // Keep only the most recent MAX_TIMELINE_ENTRIES
-    if to_save.len() > MAX_TIMELINE_ENTRIES {
-        to_save = to_save.split_off(to_save.len() - MAX_TIMELINE_ENTRIES);
-    }
+        // Apply expiry and limits
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+        let cutoff = now - HISTORY_EXPIRY_SECONDS;
+        entries.retain(|e| e.timestamp > cutoff);
 
-    if let Ok(json) = serde_json::to_string_pretty(&to_save) {
-        if let Err(e) = fs::write(&path, json) {
-            eprintln!("Failed to save history: {}", e);
+        // Keep only the most recent MAX_TIMELINE_ENTRIES, in chronological order
+        if entries.len() > MAX_TIMELINE_ENTRIES {
+            entries.drain(..entries.len() - MAX_TIMELINE_ENTRIES);
         }
-    }
+    })
 }
 
 /// Clear all history entries from disk.
@@ -176,3 +206,79 @@ pub fn clear_history() {
         let _ = fs::remove_file(&path);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::HttpMethod;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_workspace_config() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_workspace_config(dir.path()).request_defaults.is_none());
+
+        fs::write(
+            dir.path().join(WORKSPACE_CONFIG_FILE),
+            r#"{"request_defaults": {"method": "POST", "headers": "Accept: application/json"}}"#,
+        )
+        .unwrap();
+        let defaults = load_workspace_config(dir.path()).request_defaults.unwrap();
+        assert_eq!(defaults.method, HttpMethod::POST);
+        assert_eq!(defaults.headers, "Accept: application/json");
+    }
+
+    #[test]
+    fn test_state_without_settings_uses_defaults() {
+        let state: AppState = serde_json::from_str(
+            r#"{"workspace_path": null, "method": "GET", "url": "", "headers_text": "",
+                "body_text": "", "auth_text": "", "selected_tab": 0, "selected_env": 0}"#,
+        )
+        .unwrap();
+        assert!(state.settings.auto_save);
+        assert_eq!(state.settings.timeout_secs, 30);
+        assert!(state.current_file.is_none());
+        assert!(!state.show_timeline);
+        assert_eq!(state.response_tab, crate::core::types::ResponseTab::Body);
+
+        // Settings saved by an older version may lack newer fields
+        let settings: crate::core::types::Settings =
+            serde_json::from_str(r#"{"timeout_secs": 5}"#).unwrap();
+        assert_eq!(settings.timeout_secs, 5);
+        assert_eq!(settings.connect_timeout_secs, 10);
+        assert!(settings.follow_redirects);
+        assert_eq!(settings.response_view, None);
+
+        let settings: crate::core::types::Settings =
+            serde_json::from_str(r#"{"response_view": "raw"}"#).unwrap();
+        assert_eq!(
+            settings.response_view,
+            Some(crate::core::types::ResponseView::Raw)
+        );
+    }
+
+    #[test]
+    fn test_append_history_keeps_unreadable_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.json");
+        let entry = TimelineEntry {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64(),
+            request: Default::default(),
+            response: Default::default(),
+        };
+
+        append_to_history(&path, &entry).unwrap();
+        append_to_history(&path, &entry).unwrap();
+        let saved: Vec<TimelineEntry> =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.len(), 2);
+
+        // Half a file, as another window might have left it, isn't replaced
+        fs::write(&path, "[{\"timestamp\": 1").unwrap();
+        assert!(append_to_history(&path, &entry).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[{\"timestamp\": 1");
+    }
+}
@@ -4,9 +4,19 @@
 
 use super::constants::MAX_RESPONSE_SIZE;
 use super::error::MercuryError;
-use crate::core::types::{HttpMethod, JsonRequest};
+use super::keychain;
+use crate::core::types::{HttpMethod, JsonFormat, JsonIndent, JsonRequest, Response, Timeouts};
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
 use serde_json::Value;
-use std::time::Instant;
+use std::io::Read;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Chunk size for streaming response bodies; the cancel flag is checked between chunks
+const BODY_CHUNK_SIZE: usize = 16 * 1024;
 
 /// Classification of response content for rendering
 #[derive(Debug, Clone, PartialEq)]
@@ -15,11 +25,13 @@ pub enum ResponseType {
     Xml,
     Html,
     PlainText,
-    Image,     // Raw image bytes stored in raw_bytes
-    Binary,    // Non-displayable binary data
-    TooLarge,  // Exceeded MAX_RESPONSE_SIZE
-    LargeText, // Text content too large for inline display (>1000KB)
-    Empty,     // 204 No Content or empty body
+    FormUrlEncoded, // application/x-www-form-urlencoded, shown as fields
+    Ndjson,         // One JSON value per line, shown as records
+    Image,          // Raw image bytes stored in raw_bytes
+    Binary,         // Non-displayable binary data
+    TooLarge,       // Exceeded MAX_RESPONSE_SIZE
+    LargeText,      // Text content too large for inline display (>1000KB)
+    Empty,          // 204 No Content or empty body
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +46,90 @@ pub struct HttpResponse {
     pub size_bytes: usize,
     pub content_type: String,
     pub response_type: ResponseType,
+    /// The body had bytes that aren't valid in its charset (UTF-8 when it's
+    /// missing or unknown), and they were replaced
+    pub lossy_utf8: bool,
+}
+
+impl ResponseType {
+    /// Parse the variant name stored on disk (history, examples)
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "Json" => ResponseType::Json,
+            "Xml" => ResponseType::Xml,
+            "Html" => ResponseType::Html,
+            "PlainText" => ResponseType::PlainText,
+            "FormUrlEncoded" => ResponseType::FormUrlEncoded,
+            "Ndjson" => ResponseType::Ndjson,
+            "Image" => ResponseType::Image,
+            "Binary" => ResponseType::Binary,
+            "TooLarge" => ResponseType::TooLarge,
+            "LargeText" => ResponseType::LargeText,
+            "Empty" => ResponseType::Empty,
+            _ => ResponseType::PlainText,
+        }
+    }
+}
+
+/// Storable form of a response (drops cookies and raw bytes)
+impl From<&HttpResponse> for Response {
+    fn from(response: &HttpResponse) -> Self {
+        Self {
+            status: response.status,
+            status_text: response.status_text.clone(),
+            headers: response.headers.clone(),
+            body: response.body.clone(),
+            content_type: response.content_type.clone(),
+            response_type: format!("{:?}", response.response_type),
+            size_bytes: response.size_bytes,
+            duration_ms: response.duration_ms,
+        }
+    }
+}
+
+/// Rebuild a displayable response from stored data
+impl From<Response> for HttpResponse {
+    fn from(response: Response) -> Self {
+        Self {
+            response_type: ResponseType::from_name(&response.response_type),
+            status: response.status,
+            status_text: response.status_text,
+            headers: response.headers,
+            cookies: Vec::new(), // Cookies are not stored
+            body: response.body,
+            raw_bytes: None,
+            duration_ms: response.duration_ms,
+            size_bytes: response.size_bytes,
+            content_type: response.content_type,
+            lossy_utf8: false,
+        }
+    }
+}
+
+/// The `charset` parameter of a Content-Type, without quotes
+fn charset_label(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// Decode a text body in the Content-Type's charset. Without a charset, or with
+/// one that isn't recognized, it's read as UTF-8. The flag is set when bytes
+/// that aren't valid in that charset were replaced.
+pub fn decode_text(content_type: &str, bytes: &[u8]) -> (String, bool) {
+    if let Some(encoding) =
+        charset_label(content_type).and_then(|l| encoding_rs::Encoding::for_label(l.as_bytes()))
+    {
+        let (text, _, had_errors) = encoding.decode(bytes);
+        return (text.into_owned(), had_errors);
+    }
+    match String::from_utf8_lossy(bytes) {
+        std::borrow::Cow::Borrowed(text) => (text.to_string(), false),
+        std::borrow::Cow::Owned(text) => (text, true),
+    }
 }
 
 /// Detect ResponseType from Content-Type header
@@ -65,6 +161,9 @@ fn detect_response_type(content_type: &str, body: &[u8], status: u16) -> Respons
         || ct_lower.contains("zip")
         || ct_lower.contains("tar")
         || ct_lower.contains("gzip")
+        // Framed gRPC-Web and Connect bodies; grpc-web-text is base64 and stays text
+        || (ct_lower.starts_with("application/grpc") && !ct_lower.contains("-text"))
+        || ct_lower.starts_with("application/connect+")
     {
         return ResponseType::Binary;
     }
@@ -75,8 +174,16 @@ fn detect_response_type(content_type: &str, body: &[u8], status: u16) -> Respons
         return ResponseType::LargeText;
     }
 
+    // Newline-delimited JSON, declared or sent as plain JSON
+    if ct_lower.contains("ndjson") || ct_lower.contains("jsonl") || ct_lower.contains("json-seq") {
+        return ResponseType::Ndjson;
+    }
+
     // JSON
     if ct_lower.contains("application/json") || ct_lower.contains("+json") {
+        if crate::utils::looks_like_ndjson(&String::from_utf8_lossy(body)) {
+            return ResponseType::Ndjson;
+        }
         return ResponseType::Json;
     }
 
@@ -93,6 +200,10 @@ fn detect_response_type(content_type: &str, body: &[u8], status: u16) -> Respons
         return ResponseType::Html;
     }
 
+    if crate::utils::is_form_content_type(&ct_lower) {
+        return ResponseType::FormUrlEncoded;
+    }
+
     // Plain text types
     if ct_lower.starts_with("text/") {
         return ResponseType::PlainText;
@@ -104,6 +215,9 @@ fn detect_response_type(content_type: &str, body: &[u8], status: u16) -> Respons
         let trimmed = String::from_utf8_lossy(body);
         let trimmed = trimmed.trim();
         if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            if crate::utils::looks_like_ndjson(trimmed) {
+                return ResponseType::Ndjson;
+            }
             return ResponseType::Json;
         }
         if trimmed.starts_with('<') {
@@ -139,33 +253,208 @@ pub fn extract_cookies(headers: &[(String, String)]) -> Vec<String> {
         .collect()
 }
 
+/// Read a response body chunk by chunk, stopping early once `cancel` is set
+fn read_body(reader: &mut impl Read, cancel: Option<&AtomicBool>) -> Result<Vec<u8>, MercuryError> {
+    let is_cancelled = || cancel.is_some_and(|flag| flag.load(Ordering::Relaxed));
+    let mut body = Vec::new();
+    let mut chunk = vec![0u8; BODY_CHUNK_SIZE];
+
+    loop {
+        if is_cancelled() {
+            return Err(MercuryError::Cancelled);
+        }
+        match reader.read(&mut chunk) {
+            Ok(0) => return Ok(body),
+            Ok(n) => body.extend_from_slice(&chunk[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                return Err(MercuryError::RequestFailed(format!(
+                    "Failed to read response body: {}",
+                    e
+                )))
+            }
+        }
+    }
+}
+
+/// Parse a `resolve` override against the request URL into the host to pin and the
+/// address to connect to. Accepts an IP (`10.0.0.5`, `[::1]`) or `host:ip`, where the
+/// host must match the URL's. The port always comes from the URL.
+pub fn parse_resolve_override(
+    value: &str,
+    url: &str,
+) -> Result<(String, SocketAddr), MercuryError> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| MercuryError::InvalidUrl(e.to_string()))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| MercuryError::InvalidUrl("URL has no host".to_string()))?
+        .to_string();
+    if host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .is_ok()
+    {
+        return Err(MercuryError::InvalidUrl(
+            "a resolve override needs a host name in the URL, not an IP address".to_string(),
+        ));
+    }
+
+    let value = value.trim();
+    let address = match value.split_once(':') {
+        // `host:ip`; IPv6 addresses have more colons and are handled below
+        Some((name, ip))
+            if !name.is_empty() && name.parse::<IpAddr>().is_err() && !name.starts_with('[') =>
+        {
+            if !name.eq_ignore_ascii_case(&host) {
+                return Err(MercuryError::InvalidUrl(format!(
+                    "resolve override is for '{}' but the URL's host is '{}'",
+                    name, host
+                )));
+            }
+            ip
+        }
+        _ => value,
+    };
+
+    let ip = address
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .map_err(|_| {
+            MercuryError::InvalidUrl(format!("resolve override '{}' is not an IP address", value))
+        })?;
+
+    // Port 0 lets reqwest use the URL's port or the scheme default
+    Ok((host, SocketAddr::new(ip, 0)))
+}
+
+/// Check that a request's URL, after variable substitution, is absolute with a
+/// scheme and host. `template` is the URL as typed, used to name the variable
+/// that should have supplied the base, as in `{{baseUrl}}/users`. Undefined
+/// variables are the caller's to report.
+pub fn validate_resolved_url(template: &str, resolved: &str) -> Result<(), MercuryError> {
+    let resolved = resolved.trim();
+    if resolved.is_empty() {
+        return Err(MercuryError::InvalidUrl("the URL is empty".to_string()));
+    }
+    let leading_variable = template
+        .trim()
+        .strip_prefix("{{")
+        .and_then(|rest| rest.split_once("}}"))
+        .map(|(name, _)| name.trim().to_string());
+    let missing_base = |problem: &str| match &leading_variable {
+        Some(name) => MercuryError::InvalidUrl(format!(
+            "URL resolves to '{}' — set {} in the current environment",
+            resolved, name
+        )),
+        None => MercuryError::InvalidUrl(format!(
+            "'{}' {}, e.g. https://api.example.com",
+            resolved, problem
+        )),
+    };
+
+    match reqwest::Url::parse(resolved) {
+        Ok(url) if !matches!(url.scheme(), "http" | "https") => {
+            Err(missing_base("needs to start with http:// or https://"))
+        }
+        Ok(url) if url.host_str().is_none_or(str::is_empty) => Err(missing_base("has no host")),
+        Ok(_) => Ok(()),
+        Err(_) if !resolved.contains("://") => Err(missing_base("needs a scheme and host")),
+        Err(e) => Err(MercuryError::InvalidUrl(format!("'{}': {}", resolved, e))),
+    }
+}
+
+/// Wait out a request's `delay_ms`, checking `cancel` as it goes
+fn wait_before_send(delay_ms: u64, cancel: Option<&AtomicBool>) -> Result<(), MercuryError> {
+    let until = Instant::now() + Duration::from_millis(delay_ms);
+    while let Some(left) = until.checked_duration_since(Instant::now()) {
+        if cancel.is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+            return Err(MercuryError::Cancelled);
+        }
+        std::thread::sleep(left.min(Duration::from_millis(50)));
+    }
+    Ok(())
+}
+
+/// Execute a request on the calling thread.
+/// Setting `cancel` aborts the request once the server responds or between body chunks;
+/// returning drops the response, which closes the connection. A request's `delay_ms`
+/// is waited out first and isn't counted in its duration.
+///
+/// `timeouts` and `follow_redirects` are the defaults the request's own settings
+/// override; a request's `timeout_secs` replaces the read timeout.
+/// `follow_redirects` must match `shared_client`'s redirect policy; a request
+/// that asks for the other one is sent on a client of its own. That client,
+/// like one for a pinned host, keeps cookies in `cookie_jar`.
 pub fn execute_request(
     request: &JsonRequest,
-    timeout_secs: u64,
+    timeouts: Timeouts,
     follow_redirects: bool,
     shared_client: Option<&reqwest::blocking::Client>,
+    cookie_jar: Option<&Arc<reqwest::cookie::Jar>>,
+    cancel: Option<&AtomicBool>,
 ) -> Result<HttpResponse, MercuryError> {
+    // A malformed patch document is caught before anything is sent
+    if !request.body_hex {
+        let content_type = request
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+            .map(|(_, v)| v.as_str());
+        super::patch::check_patch_body(content_type, &request.body)
+            .map_err(MercuryError::InvalidPatchBody)?;
+    }
+
+    if let Some(delay_ms) = request.delay_ms.filter(|ms| *ms > 0) {
+        wait_before_send(delay_ms, cancel)?;
+    }
     let start = Instant::now();
 
+    // Keychain secrets are read only now, so they never sit in the form or history
+    let resolved = keychain::resolve_request(request)?;
+    let request = resolved.as_ref().unwrap_or(request);
+
+    let timeout_secs = request.timeout_secs.unwrap_or(timeouts.read_secs);
+    let redirects_differ = request
+        .follow_redirects
+        .is_some_and(|follow| follow != follow_redirects);
+    let follow_redirects = request.follow_redirects.unwrap_or(follow_redirects);
+
+    // Pinning a host to an address is a client-level setting
+    let pinned = request
+        .resolve
+        .as_deref()
+        .filter(|r| !r.trim().is_empty())
+        .map(|r| parse_resolve_override(r, &request.url))
+        .transpose()?;
+
     // Use shared client if provided, otherwise create an ephemeral one
     let owned_client;
-    let client = if let Some(c) = shared_client {
-        c
-    } else {
-        let redirect_policy = if follow_redirects {
-            reqwest::redirect::Policy::default() // Follow up to 10 redirects
-        } else {
-            reqwest::redirect::Policy::none()
-        };
-
-        owned_client = reqwest::blocking::Client::builder()
-            .timeout(std::time::Duration::from_secs(timeout_secs))
-            .redirect(redirect_policy)
-            .build()
-            .map_err(|e| {
+    let client = match shared_client {
+        Some(c) if pinned.is_none() && !redirects_differ => c,
+        _ => {
+            let redirect_policy = if follow_redirects {
+                reqwest::redirect::Policy::default() // Follow up to 10 redirects
+            } else {
+                reqwest::redirect::Policy::none()
+            };
+
+            let mut builder = reqwest::blocking::Client::builder()
+                .connect_timeout(std::time::Duration::from_secs(timeouts.connect_secs))
+                .timeout(std::time::Duration::from_secs(timeout_secs))
+                .redirect(redirect_policy);
+            if let Some((host, address)) = &pinned {
+                builder = builder.resolve(host, *address);
+            }
+            if let Some(jar) = cookie_jar {
+                builder = builder.cookie_provider(jar.clone());
+            }
+            owned_client = builder.build().map_err(|e| {
                 MercuryError::RequestFailed(format!("Failed to create HTTP client: {}", e))
             })?;
-        &owned_client
+            &owned_client
+        }
     };
 
     let mut req_builder = match request.method {
@@ -180,17 +469,29 @@ pub fn execute_request(
         HttpMethod::TRACE => client.request(reqwest::Method::TRACE, &request.url),
     };
 
+    // Per-request timeout so a shared client still honors the caller's setting
+    req_builder = req_builder.timeout(std::time::Duration::from_secs(timeout_secs));
+
     for (key, value) in &request.headers {
         req_builder = req_builder.header(key, value);
     }
 
-    if !request.body.is_empty() {
+    if request.body_hex {
+        let bytes = crate::utils::parse_hex(&request.body).map_err(MercuryError::InvalidHexBody)?;
+        if !bytes.is_empty() {
+            req_builder = req_builder.body(bytes);
+        }
+    } else if !request.body.is_empty() {
         req_builder = req_builder.body(request.body.clone());
     }
 
-    let response = req_builder
+    let mut response = req_builder
         .send()
-        .map_err(|e| format_request_error(e, timeout_secs))?;
+        .map_err(|e| format_request_error(e, timeouts.connect_secs, timeout_secs))?;
+
+    if cancel.is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+        return Err(MercuryError::Cancelled);
+    }
 
     let status = response.status().as_u16();
     let status_text = response.status().to_string();
@@ -210,6 +511,23 @@ pub fn execute_request(
 
     let content_type = get_content_type(&headers);
 
+    // A HEAD response has no body; its Content-Length describes what GET would send
+    if request.method == HttpMethod::HEAD {
+        return Ok(HttpResponse {
+            status,
+            status_text,
+            headers,
+            cookies,
+            body: String::new(),
+            raw_bytes: None,
+            duration_ms: start.elapsed().as_millis(),
+            size_bytes: 0,
+            content_type,
+            response_type: ResponseType::Empty,
+            lossy_utf8: false,
+        });
+    }
+
     // Check Content-Length before downloading
     if let Some(content_length) = response.content_length() {
         if content_length as usize > MAX_RESPONSE_SIZE {
@@ -225,36 +543,41 @@ pub fn execute_request(
                 size_bytes: content_length as usize,
                 content_type,
                 response_type: ResponseType::TooLarge,
+                lossy_utf8: false,
             });
         }
     }
 
-    let raw_bytes = response
-        .bytes()
-        .map_err(|e| MercuryError::RequestFailed(format!("Failed to read response body: {}", e)))?;
+    let raw_bytes = read_body(&mut response, cancel)?;
 
     let size_bytes = raw_bytes.len();
 
     // Detect response type
     let response_type = detect_response_type(&content_type, &raw_bytes, status);
 
-    // Convert to string (lossy for encoding errors)
-    let body = match &response_type {
-        ResponseType::Image | ResponseType::Binary => {
-            format!("[Binary data: {} bytes]", size_bytes)
-        }
-        ResponseType::TooLarge => {
-            format!("[Response too large: {} bytes]", size_bytes)
-        }
-        _ => String::from_utf8_lossy(&raw_bytes).into_owned(),
+    // Convert to string in the declared charset
+    let (body, lossy_utf8) = match &response_type {
+        ResponseType::Image | ResponseType::Binary => (
+            format!("[Binary data: {}]", crate::utils::format_bytes(size_bytes)),
+            false,
+        ),
+        ResponseType::TooLarge => (
+            format!(
+                "[Response too large: {}]",
+                crate::utils::format_bytes(size_bytes)
+            ),
+            false,
+        ),
+        _ => decode_text(&content_type, &raw_bytes),
     };
 
     let duration_ms = start.elapsed().as_millis();
 
-    // Store raw bytes only for image type
-    // Store raw bytes only for binary/image types to save memory
+    // Store raw bytes only for binary/image types, and text that isn't a
+    // byte-for-byte copy, to save memory
     let stored_bytes = match response_type {
         ResponseType::Image | ResponseType::Binary => Some(raw_bytes.to_vec()),
+        _ if body.as_bytes() != raw_bytes.as_slice() => Some(raw_bytes.to_vec()),
         _ => None,
     };
 
@@ -269,14 +592,47 @@ pub fn execute_request(
         size_bytes,
         content_type,
         response_type,
+        lossy_utf8,
     })
 }
 
+/// Error fragments (from the resolver or OS) that mean the host can't be reached at all
+const UNREACHABLE_MARKERS: &[&str] = &[
+    "dns error",
+    "failed to lookup address",
+    "name or service not known",
+    "nodename nor servname",
+    "no such host",
+    "temporary failure in name resolution",
+    "network is unreachable",
+    "no route to host",
+];
+
+/// Whether an error chain describes an offline machine or an unresolvable host
+fn is_unreachable_error(chain: &str) -> bool {
+    let chain = chain.to_lowercase();
+    UNREACHABLE_MARKERS
+        .iter()
+        .any(|marker| chain.contains(marker))
+}
+
 /// Format request errors with user-friendly messages
-fn format_request_error(e: reqwest::Error, timeout_secs: u64) -> MercuryError {
+fn format_request_error(e: reqwest::Error, connect_secs: u64, timeout_secs: u64) -> MercuryError {
     let err_str = e.to_string().to_lowercase();
 
-    if e.is_timeout() {
+    // reqwest's own message omits the cause; the resolver error is further down the chain
+    let mut chain = e.to_string();
+    let mut source = std::error::Error::source(&e);
+    while let Some(cause) = source {
+        chain.push_str(": ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+
+    // A connect timeout is both; the host never answered, so say that instead
+    if e.is_connect() && e.is_timeout() {
+        MercuryError::ConnectTimeout(connect_secs * 1000)
+    } else if e.is_timeout() {
         MercuryError::Timeout(timeout_secs * 1000)
     } else if e.is_connect() {
         // Check for TLS/SSL errors in connection failures
@@ -286,6 +642,8 @@ fn format_request_error(e: reqwest::Error, timeout_secs: u64) -> MercuryError {
             || err_str.contains("handshake")
         {
             MercuryError::TlsError(e.to_string())
+        } else if is_unreachable_error(&chain) {
+            MercuryError::HostUnreachable(chain)
         } else {
             MercuryError::ConnectionFailed(e.to_string())
         }
@@ -296,18 +654,114 @@ fn format_request_error(e: reqwest::Error, timeout_secs: u64) -> MercuryError {
     }
 }
 
+/// Canonical layout: two spaces, keys sorted, so equal documents format the same
 pub fn format_json(body: &str) -> String {
-    match serde_json::from_str::<Value>(body) {
-        Ok(json) => serde_json::to_string_pretty(&json).unwrap_or_else(|_| body.to_string()),
+    format_json_with(
+        body,
+        &JsonFormat {
+            indent: JsonIndent::TwoSpaces,
+            sort_keys: true,
+        },
+    )
+}
+
+/// Pretty-print `body` the way `format` asks; anything that isn't JSON comes back unchanged
+pub fn format_json_with(body: &str, format: &JsonFormat) -> String {
+    let Ok(json) = serde_json::from_str::<Value>(body) else {
+        return body.to_string();
+    };
+    let indent = format.indent.as_str();
+    if !format.sort_keys {
+        return reindent_json(body, indent);
+    }
+
+    // `Value` holds objects in a `BTreeMap`, so keys come out sorted at every depth
+    let mut out = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(
+        &mut out,
+        PrettyFormatter::with_indent(indent.as_bytes()),
+    );
+    match json.serialize(&mut serializer) {
+        Ok(()) => String::from_utf8(out).unwrap_or_else(|_| body.to_string()),
         Err(_) => body.to_string(),
     }
 }
 
+/// Lay out the start of a JSON document, such as a large body cut short for a
+/// preview, one value per line. Keys stay in the order they were written.
+pub fn format_json_prefix(json: &str) -> String {
+    reindent_json(json, JsonIndent::TwoSpaces.as_str())
+}
+
+/// Lay out valid JSON one value per line, keeping keys and numbers exactly as written
+fn reindent_json(json: &str, indent: &str) -> String {
+    fn newline(out: &mut String, indent: &str, depth: usize) {
+        out.push('\n');
+        for _ in 0..depth {
+            out.push_str(indent);
+        }
+    }
+
+    let mut out = String::with_capacity(json.len() * 2);
+    let mut depth = 0;
+    let mut chars = json.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                let close = if c == '{' { '}' } else { ']' };
+                if let Some(close) = chars.next_if_eq(&close) {
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut out, indent, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, indent, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, indent, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 /// Format XML with basic indentation
 pub fn format_xml(body: &str) -> String {
+    indent_xml(body, false)
+}
+
+/// Format an XML request body: like `format_xml`, but an element holding only
+/// text stays on one line, the way such bodies are usually written by hand
+pub fn format_xml_body(body: &str) -> String {
+    indent_xml(body, true)
+}
+
+fn indent_xml(body: &str, inline_text: bool) -> String {
     let mut result = String::new();
     let mut indent = 0usize;
     let mut _in_tag = false;
+    let mut after_text = false;
     let mut tag_content = String::new();
 
     for ch in body.chars() {
@@ -315,7 +769,8 @@ pub fn format_xml(body: &str) -> String {
             '<' => {
                 // Flush any text content
                 let trimmed = tag_content.trim();
-                if !trimmed.is_empty() {
+                after_text = !trimmed.is_empty();
+                if after_text {
                     result.push_str(trimmed);
                 }
                 tag_content.clear();
@@ -335,12 +790,15 @@ pub fn format_xml(body: &str) -> String {
                     indent = indent.saturating_sub(1);
                 }
 
-                // Add newline and indent
-                if !result.is_empty() && !result.ends_with('\n') {
-                    result.push('\n');
+                // Add newline and indent, unless this closes the text just written
+                if !(inline_text && is_closing && after_text) {
+                    if !result.is_empty() && !result.ends_with('\n') {
+                        result.push('\n');
+                    }
+                    result.push_str(&"  ".repeat(indent));
                 }
-                result.push_str(&"  ".repeat(indent));
                 result.push_str(tag);
+                after_text = false;
 
                 if !is_closing && !is_self_closing && !is_declaration {
                     indent += 1;
@@ -363,10 +821,179 @@ pub fn format_xml(body: &str) -> String {
     result
 }
 
+/// Check that XML is well-formed enough to format: every tag closed, in order.
+/// Comments, CDATA, declarations, and processing instructions are skipped.
+pub fn check_xml(body: &str) -> Result<(), String> {
+    let mut rest = body.trim();
+    if !rest.starts_with('<') {
+        return Err("Expected a tag at the start".to_string());
+    }
+
+    let mut open: Vec<&str> = Vec::new();
+    while let Some(start) = rest.find('<') {
+        let after = &rest[start..];
+        let end = if after.starts_with("<!--") {
+            "-->"
+        } else if after.starts_with("<![CDATA[") {
+            "]]>"
+        } else if after.starts_with("<?") {
+            "?>"
+        } else {
+            ">"
+        };
+        let Some(len) = after.find(end).map(|at| at + end.len()) else {
+            let shown: String = after.chars().take(20).collect();
+            return Err(format!("Unterminated {}", shown));
+        };
+        let tag = &after[..len];
+        rest = &after[len..];
+        if end != ">" || tag.starts_with("<!") {
+            continue;
+        }
+
+        let inner = tag[1..tag.len() - 1].trim();
+        if let Some(name) = inner.strip_prefix('/') {
+            let name = name.trim();
+            match open.pop() {
+                Some(expected) if expected == name => {}
+                Some(expected) => {
+                    return Err(format!("Expected </{}>, found </{}>", expected, name))
+                }
+                None => return Err(format!("</{}> closes nothing", name)),
+            }
+        } else if !inner.ends_with('/') {
+            match inner.split(char::is_whitespace).next() {
+                Some(name) if !name.is_empty() => open.push(name),
+                _ => return Err("Empty tag <>".to_string()),
+            }
+        }
+    }
+
+    match open.last() {
+        Some(name) => Err(format!("<{}> is never closed", name)),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_resolve_override() {
+        let url = "https://api.example.com:8443/health";
+        let (host, address) = parse_resolve_override("10.0.0.5", url).unwrap();
+        assert_eq!(host, "api.example.com");
+        assert_eq!(address, "10.0.0.5:0".parse().unwrap());
+
+        let (_, address) = parse_resolve_override("api.example.com:[::1]", url).unwrap();
+        assert_eq!(address.ip(), "::1".parse::<IpAddr>().unwrap());
+        assert!(parse_resolve_override("::1", url).is_ok());
+
+        assert!(parse_resolve_override("other.example.com:10.0.0.5", url).is_err());
+        assert!(parse_resolve_override("not-an-ip", url).is_err());
+        assert!(parse_resolve_override("10.0.0.5", "http://127.0.0.1/").is_err());
+    }
+
+    #[test]
+    fn test_format_json_with() {
+        let body = r#"{"b": [1, {}], "a": {"z": "x,\"}", "y": []}, "n": 1.50}"#;
+        let kept = format_json_with(body, &JsonFormat::default());
+        assert_eq!(
+            kept,
+            "{\n  \"b\": [\n    1,\n    {}\n  ],\n  \"a\": {\n    \"z\": \"x,\\\"}\",\n    \"y\": []\n  },\n  \"n\": 1.50\n}"
+        );
+
+        let sorted = format_json_with(
+            body,
+            &JsonFormat {
+                indent: JsonIndent::Tab,
+                sort_keys: true,
+            },
+        );
+        assert_eq!(
+            sorted,
+            "{\n\t\"a\": {\n\t\t\"y\": [],\n\t\t\"z\": \"x,\\\"}\"\n\t},\n\t\"b\": [\n\t\t1,\n\t\t{}\n\t],\n\t\"n\": 1.5\n}"
+        );
+        assert_eq!(format_json(body), format_json(&sorted));
+        assert_eq!(format_json_with("{oops", &JsonFormat::default()), "{oops");
+    }
+
+    #[test]
+    fn test_response_roundtrip_through_storage() {
+        let original = HttpResponse {
+            status: 201,
+            status_text: "Created".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            cookies: vec!["session=abc".to_string()],
+            body: r#"{"id": 1}"#.to_string(),
+            raw_bytes: None,
+            duration_ms: 42,
+            size_bytes: 9,
+            content_type: "application/json".to_string(),
+            response_type: ResponseType::Json,
+            lossy_utf8: false,
+        };
+
+        let stored = Response::from(&original);
+        assert_eq!(stored.response_type, "Json");
+
+        let restored = HttpResponse::from(stored);
+        assert_eq!(restored.status, 201);
+        assert_eq!(restored.body, original.body);
+        assert_eq!(restored.response_type, ResponseType::Json);
+        assert!(restored.cookies.is_empty());
+    }
+
+    #[test]
+    fn test_read_body_in_chunks() {
+        let data = vec![b'x'; BODY_CHUNK_SIZE * 2 + 10];
+        let body = read_body(&mut std::io::Cursor::new(data.clone()), None).unwrap();
+        assert_eq!(body, data);
+    }
+
+    #[test]
+    fn test_read_body_stops_when_cancelled() {
+        let cancel = AtomicBool::new(true);
+        let result = read_body(&mut std::io::Cursor::new(vec![b'x'; 10]), Some(&cancel));
+        assert!(matches!(result, Err(MercuryError::Cancelled)));
+    }
+
+    #[test]
+    fn test_decode_text_uses_charset() {
+        let latin1 = b"caf\xe9 cr\xe8me";
+        assert_eq!(
+            decode_text("text/html; charset=ISO-8859-1", latin1),
+            ("café crème".to_string(), false)
+        );
+        assert_eq!(
+            decode_text("text/plain;Charset=\"latin1\"", latin1),
+            ("café crème".to_string(), false)
+        );
+
+        // No charset, or one that isn't known: UTF-8, flagged when bytes were replaced
+        assert_eq!(
+            decode_text("text/plain", "café".as_bytes()),
+            ("café".to_string(), false)
+        );
+        assert_eq!(
+            decode_text("text/plain; charset=x-unknown", latin1),
+            ("caf\u{fffd} cr\u{fffd}me".to_string(), true)
+        );
+
+        // A declared charset the bytes don't match is flagged too
+        assert_eq!(
+            decode_text("text/plain; charset=utf-8", latin1),
+            ("caf\u{fffd} cr\u{fffd}me".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn test_response_type_from_unknown_name() {
+        assert_eq!(ResponseType::from_name("Empty"), ResponseType::Empty);
+        assert_eq!(ResponseType::from_name("Bogus"), ResponseType::PlainText);
+    }
+
     #[test]
     fn test_detect_json() {
         let body = b"{\"key\": \"value\"}";
@@ -400,6 +1027,18 @@ mod tests {
         let body = b"\x00\x01\x02\x03";
         let result = detect_response_type("application/octet-stream", body, 200);
         assert_eq!(result, ResponseType::Binary);
+
+        // Framed gRPC bodies, even when the frames hold JSON
+        for content_type in ["application/grpc-web+proto", "application/connect+json"] {
+            assert_eq!(
+                detect_response_type(content_type, body, 200),
+                ResponseType::Binary
+            );
+        }
+        assert_ne!(
+            detect_response_type("application/grpc-web-text", b"AAAA", 200),
+            ResponseType::Binary
+        );
     }
 
     #[test]
@@ -410,6 +1049,35 @@ mod tests {
         assert!(formatted.contains("  ")); // Has indentation
     }
 
+    #[test]
+    fn test_format_xml_body_keeps_text_inline() {
+        let xml = "<root><item>test</item><empty/></root>";
+        assert_eq!(
+            format_xml_body(xml),
+            "<root>\n  <item>test</item>\n  <empty/>\n</root>"
+        );
+        assert_eq!(
+            format_xml(xml),
+            "<root>\n  <item>test\n  </item>\n  <empty/>\n</root>"
+        );
+    }
+
+    #[test]
+    fn test_check_xml() {
+        assert!(check_xml(
+            "<?xml version=\"1.0\"?>\n<a x=\"1\"><!-- <b> --><b/><c>{{id}}</c><![CDATA[<d>]]></a>"
+        )
+        .is_ok());
+        assert_eq!(
+            check_xml("<a><b></a>").unwrap_err(),
+            "Expected </b>, found </a>"
+        );
+        assert_eq!(check_xml("<a><b/>").unwrap_err(), "<a> is never closed");
+        assert_eq!(check_xml("<a></a></b>").unwrap_err(), "</b> closes nothing");
+        assert!(check_xml("<a><!-- open").is_err());
+        assert!(check_xml("text").is_err());
+    }
+
     #[test]
     fn test_sniff_json_without_content_type() {
         let body = b"{\"key\": \"value\"}";
@@ -462,6 +1130,55 @@ mod tests {
         assert_eq!(result, ResponseType::PlainText);
     }
 
+    #[test]
+    fn test_validate_resolved_url() {
+        assert!(
+            validate_resolved_url("{{baseUrl}}/users", "https://api.example.com/users").is_ok()
+        );
+        assert!(validate_resolved_url("http://localhost:3000", "http://localhost:3000").is_ok());
+
+        // An empty base leaves a relative path
+        let err = validate_resolved_url("{{baseUrl}}/users", "/users").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid URL: URL resolves to '/users' — set baseUrl in the current environment"
+        );
+
+        let err =
+            validate_resolved_url("api.example.com/users", "api.example.com/users").unwrap_err();
+        assert!(err.to_string().contains("needs a scheme and host"));
+        // Parses, but `localhost` is taken as the scheme
+        let err = validate_resolved_url("localhost:3000", "localhost:3000").unwrap_err();
+        assert!(err.to_string().contains("http:// or https://"));
+        assert!(validate_resolved_url("", "  ").is_err());
+    }
+
+    #[test]
+    fn test_detect_ndjson() {
+        let body = b"{\"id\": 1}\n{\"id\": 2}\n";
+        assert_eq!(
+            detect_response_type("application/x-ndjson", body, 200),
+            ResponseType::Ndjson
+        );
+        // Sniffed when sent as plain JSON
+        assert_eq!(
+            detect_response_type("application/json", body, 200),
+            ResponseType::Ndjson
+        );
+        // A pretty-printed document is still JSON
+        assert_eq!(
+            detect_response_type("application/json", b"{\n  \"id\": 1\n}", 200),
+            ResponseType::Json
+        );
+    }
+
+    #[test]
+    fn test_detect_form_urlencoded() {
+        let body = b"access_token=abc&token_type=bearer";
+        let result = detect_response_type("application/x-www-form-urlencoded", body, 200);
+        assert_eq!(result, ResponseType::FormUrlEncoded);
+    }
+
     #[test]
     fn test_empty_204_no_content() {
         let body = b"";
@@ -546,4 +1263,28 @@ mod tests {
         let cookies = extract_cookies(&headers);
         assert_eq!(cookies.len(), 3);
     }
+
+    #[test]
+    fn test_is_unreachable_error() {
+        assert!(is_unreachable_error(
+            "error sending request: client error (Connect): dns error: failed to lookup address information: Name or service not known"
+        ));
+        assert!(is_unreachable_error(
+            "connect error: Network is unreachable (os error 101)"
+        ));
+        assert!(!is_unreachable_error(
+            "error sending request: tcp connect error: Connection refused (os error 111)"
+        ));
+    }
+
+    #[test]
+    fn test_delay_stops_when_cancelled() {
+        let start = Instant::now();
+        assert!(matches!(
+            wait_before_send(10_000, Some(&AtomicBool::new(true))),
+            Err(MercuryError::Cancelled)
+        ));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(wait_before_send(20, None).is_ok());
+    }
 }
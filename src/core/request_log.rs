@@ -0,0 +1,137 @@
+//! Request Log Module
+//!
+//! An opt-in, append-only log of every request sent from the editor: one JSON
+//! object per line, for grep and external tools rather than browsing (that's
+//! what history is for). Lines are written on a background thread so a slow
+//! disk never stalls the UI, and the file is rotated to `<name>.1` once it
+//! reaches `REQUEST_LOG_MAX_BYTES`.
+
+use super::constants::REQUEST_LOG_MAX_BYTES;
+use super::error::MercuryError;
+use super::types::HttpMethod;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+
+/// One sent request and how it ended
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LogEntry {
+    /// UTC, ISO 8601
+    pub timestamp: String,
+    pub method: HttpMethod,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub duration_ms: u128,
+    pub size_bytes: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The rotated copy of `path`: `requests.log` → `requests.log.1`
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".1");
+    PathBuf::from(name)
+}
+
+/// Append `entry` to the log at `path`, first moving the log aside if the line
+/// would take it past `max_bytes`. Only one old log is kept.
+pub fn append(path: &Path, entry: &LogEntry, max_bytes: u64) -> Result<(), MercuryError> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let write_error = |e: std::io::Error| MercuryError::FileWrite {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let size = fs::metadata(path).map_or(0, |m| m.len());
+    if size > 0 && size + line.len() as u64 > max_bytes {
+        fs::rename(path, rotated_path(path)).map_err(write_error)?;
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(write_error)
+}
+
+/// Writes log entries on its own thread, in the order they were sent
+pub struct RequestLogger {
+    tx: Sender<(PathBuf, LogEntry)>,
+}
+
+impl RequestLogger {
+    pub fn new() -> Self {
+        let (tx, rx) = channel::<(PathBuf, LogEntry)>();
+        std::thread::spawn(move || {
+            for (path, entry) in rx {
+                if let Err(e) = append(&path, &entry, REQUEST_LOG_MAX_BYTES) {
+                    eprintln!("Failed to write request log: {}", e);
+                }
+            }
+        });
+        Self { tx }
+    }
+
+    /// Queue `entry` for the log at `path`; returns immediately
+    pub fn log(&self, path: PathBuf, entry: LogEntry) {
+        let _ = self.tx.send((path, entry));
+    }
+}
+
+impl Default for RequestLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(status: Option<u16>, error: Option<&str>) -> LogEntry {
+        LogEntry {
+            timestamp: "2024-03-01T12:30:05.250Z".to_string(),
+            method: HttpMethod::GET,
+            url: "https://api.example.com/users".to_string(),
+            status,
+            duration_ms: 42,
+            size_bytes: 120,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_appends_json_lines_and_rotates() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("logs").join("requests.log");
+
+        append(&path, &entry(Some(200), None), 1024).unwrap();
+        append(&path, &entry(None, Some("Connection refused")), 1024).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["status"], 200);
+        assert!(lines[0].get("error").is_none());
+        assert_eq!(lines[1]["error"], "Connection refused");
+        assert!(lines[1].get("status").is_none());
+
+        // The next line doesn't fit, so the full log moves aside
+        let cap = content.len() as u64 + 10;
+        append(&path, &entry(Some(201), None), cap).unwrap();
+        assert_eq!(fs::read_to_string(rotated_path(&path)).unwrap(), content);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+    }
+}
@@ -0,0 +1,226 @@
+//! Folder Runner Module
+//!
+//! Runs a batch of requests through a bounded worker pool, with an optional
+//! per-host rate limit so bulk runs stay safe against real servers.
+
+use super::error::MercuryError;
+use super::request::{execute_request, HttpResponse};
+use super::types::{HttpMethod, JsonRequest, Timeouts};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Progress of one request in a run
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunStatus {
+    Pending,
+    Running,
+    Done { status: u16, duration_ms: u128 },
+    Failed(String),
+}
+
+impl RunStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, RunStatus::Done { .. } | RunStatus::Failed(_))
+    }
+
+    /// The finished status for a request's outcome
+    pub fn of(result: &Result<HttpResponse, MercuryError>) -> Self {
+        match result {
+            Ok(response) => RunStatus::Done {
+                status: response.status,
+                duration_ms: response.duration_ms,
+            },
+            Err(e) => RunStatus::Failed(e.to_string()),
+        }
+    }
+}
+
+/// One request of a folder run, as listed in the progress view
+#[derive(Debug, Clone)]
+pub struct RunEntry {
+    pub name: String,
+    pub method: HttpMethod,
+    pub status: RunStatus,
+    /// Kept so the run's responses can be saved together
+    pub response: Option<HttpResponse>,
+}
+
+/// A folder run in progress (or finished, until dismissed)
+pub struct FolderRun {
+    pub id: u64,
+    pub folder_name: String,
+    pub entries: Vec<RunEntry>,
+    pub cancel: Arc<AtomicBool>,
+}
+
+impl FolderRun {
+    pub fn finished_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.status.is_finished())
+            .count()
+    }
+
+    /// Done when every request finished, or when stopped and nothing is still in flight
+    pub fn is_done(&self) -> bool {
+        let running = self.entries.iter().any(|e| e.status == RunStatus::Running);
+        self.finished_count() == self.entries.len()
+            || (self.cancel.load(Ordering::Relaxed) && !running)
+    }
+}
+
+/// Limits how often requests go to the same host
+pub struct HostRateLimiter {
+    interval: Option<Duration>,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    /// `per_second` of 0 disables the limit
+    pub fn new(per_second: u32) -> Self {
+        Self {
+            interval: (per_second > 0).then(|| Duration::from_secs(1) / per_second),
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reserve the next send slot for `host`, at or after `now`
+    fn reserve(&self, host: &str, now: Instant) -> Instant {
+        let Some(interval) = self.interval else {
+            return now;
+        };
+        let mut slots = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = slots.get(host).map_or(now, |next| (*next).max(now));
+        slots.insert(host.to_string(), slot + interval);
+        slot
+    }
+
+    /// Block until a request to `url` may be sent
+    pub fn wait(&self, url: &str) {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let slot = self.reserve(&host, Instant::now());
+        let now = Instant::now();
+        if slot > now {
+            std::thread::sleep(slot - now);
+        }
+    }
+}
+
+/// Run `requests` with at most `concurrency` in flight, calling `started` as each
+/// is sent and `finished` with its response or error. Blocks until all workers
+/// finish; setting `cancel` stops workers from picking up new requests and aborts
+/// in-flight bodies. `follow_redirects` is `client`'s redirect policy (see
+/// `execute_request`).
+#[allow(clippy::too_many_arguments)]
+pub fn run_requests(
+    requests: &[JsonRequest],
+    concurrency: usize,
+    rate_limit: u32,
+    timeouts: Timeouts,
+    follow_redirects: bool,
+    client: &reqwest::blocking::Client,
+    cookie_jar: &Arc<reqwest::cookie::Jar>,
+    cancel: &AtomicBool,
+    started: impl Fn(usize) + Sync,
+    finished: impl Fn(usize, Result<HttpResponse, MercuryError>) + Sync,
+) {
+    let next = AtomicUsize::new(0);
+    let limiter = HostRateLimiter::new(rate_limit);
+
+    std::thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, requests.len().max(1)) {
+            scope.spawn(|| loop {
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(request) = requests.get(index) else {
+                    break;
+                };
+
+                limiter.wait(&request.url);
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                started(index);
+                finished(
+                    index,
+                    execute_request(
+                        request,
+                        timeouts,
+                        follow_redirects,
+                        Some(client),
+                        Some(cookie_jar),
+                        Some(cancel),
+                    ),
+                );
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_spaces_out_same_host() {
+        let limiter = HostRateLimiter::new(4); // 250ms apart
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve("api.example.com", now), now);
+        assert_eq!(
+            limiter.reserve("api.example.com", now),
+            now + Duration::from_millis(250)
+        );
+        // Other hosts have their own schedule
+        assert_eq!(limiter.reserve("other.example.com", now), now);
+    }
+
+    #[test]
+    fn test_rate_limiter_disabled() {
+        let limiter = HostRateLimiter::new(0);
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve("api.example.com", now), now);
+        assert_eq!(limiter.reserve("api.example.com", now), now);
+    }
+
+    #[test]
+    fn test_folder_run_done_after_stop() {
+        let mut run = FolderRun {
+            id: 1,
+            folder_name: "users".to_string(),
+            entries: vec![
+                RunEntry {
+                    name: "list".to_string(),
+                    method: HttpMethod::GET,
+                    status: RunStatus::Failed("boom".to_string()),
+                    response: None,
+                },
+                RunEntry {
+                    name: "create".to_string(),
+                    method: HttpMethod::POST,
+                    status: RunStatus::Pending,
+                    response: None,
+                },
+            ],
+            cancel: Arc::new(AtomicBool::new(false)),
+        };
+
+        assert_eq!(run.finished_count(), 1);
+        assert!(!run.is_done());
+
+        run.cancel.store(true, Ordering::Relaxed);
+        assert!(run.is_done());
+
+        run.entries[1].status = RunStatus::Running;
+        assert!(!run.is_done());
+    }
+}
@@ -0,0 +1,202 @@
+//! Content Search Module
+//!
+//! Full-text search over a workspace's requests: their URLs, headers, and
+//! bodies, where the tree search only looks at names. The index keeps each
+//! file's searchable lines with its modification time, so refreshing it after
+//! the workspace changes only re-reads the files that changed.
+
+use super::constants::SEARCH_SNIPPET_CHARS;
+use super::types::HttpMethod;
+use crate::parser::parse_request_file;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// One request file's searchable lines
+#[derive(Debug)]
+struct IndexedRequest {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    method: Option<HttpMethod>,
+    /// URL, then `Name: value` headers, then body lines
+    lines: Vec<String>,
+    /// `lines` lowercased, for case-insensitive matching
+    lowercase: Vec<String>,
+}
+
+/// A request with at least one line containing the query
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentHit {
+    pub path: PathBuf,
+    pub method: Option<HttpMethod>,
+    /// The first matching line, cut down around the match
+    pub snippet: String,
+    /// How many of the request's lines match
+    pub matches: usize,
+}
+
+/// Searchable contents of every request in a workspace
+#[derive(Debug, Default)]
+pub struct ContentIndex {
+    entries: Vec<IndexedRequest>,
+}
+
+impl ContentIndex {
+    /// Bring the index in line with `paths`: new and changed files are read,
+    /// removed ones dropped, and the rest kept as they were
+    pub fn refresh<'a>(&mut self, paths: impl IntoIterator<Item = &'a Path>) {
+        let mut previous: HashMap<PathBuf, IndexedRequest> = self
+            .entries
+            .drain(..)
+            .map(|entry| (entry.path.clone(), entry))
+            .collect();
+        self.entries = paths
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+                match previous.remove(path) {
+                    Some(entry) if modified.is_some() && entry.modified == modified => entry,
+                    _ => index_file(path, modified),
+                }
+            })
+            .collect();
+    }
+
+    /// Requests with a line containing `query` in any case, in tree order, at most `limit`
+    pub fn search(&self, query: &str, limit: usize) -> Vec<ContentHit> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let mut matching = entry
+                    .lowercase
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, line)| line.contains(&query));
+                let (first, _) = matching.next()?;
+                Some(ContentHit {
+                    path: entry.path.clone(),
+                    method: entry.method.clone(),
+                    snippet: snippet(&entry.lines[first], &query, SEARCH_SNIPPET_CHARS),
+                    matches: 1 + matching.count(),
+                })
+            })
+            .take(limit)
+            .collect()
+    }
+}
+
+fn index_file(path: &Path, modified: Option<SystemTime>) -> IndexedRequest {
+    let request = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| parse_request_file(&content).ok());
+    let (method, lines) = match request {
+        Some(request) => {
+            let mut headers: Vec<String> = request
+                .headers
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect();
+            headers.sort();
+
+            let mut lines = vec![request.url];
+            lines.extend(headers);
+            lines.extend(
+                request
+                    .body
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string),
+            );
+            (Some(request.method), lines)
+        }
+        None => (None, Vec::new()),
+    };
+    IndexedRequest {
+        path: path.to_path_buf(),
+        modified,
+        method,
+        lowercase: lines.iter().map(|line| line.to_lowercase()).collect(),
+        lines,
+    }
+}
+
+/// `line` trimmed to about `max` characters, keeping the match for `query`
+/// (already lowercase) in view
+fn snippet(line: &str, query: &str, max: usize) -> String {
+    let line = line.trim();
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= max {
+        return line.to_string();
+    }
+    let lowercase = line.to_lowercase();
+    let at = lowercase
+        .find(query)
+        .map_or(0, |byte| lowercase[..byte].chars().count());
+    let start = at.saturating_sub(max / 4).min(chars.len() - max);
+    let end = start + max;
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    out.extend(&chars[start..end]);
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_search_urls_headers_and_bodies() {
+        let dir = TempDir::new().unwrap();
+        let orders = dir.path().join("orders.json");
+        let users = dir.path().join("users.json");
+        fs::write(
+            &orders,
+            r#"{"method": "POST", "url": "{{base}}/v2/orders", "headers": {"X-Feature-Flag": "checkout"}, "body": "{\n  \"sku\": \"A-1\"\n}"}"#,
+        )
+        .unwrap();
+        fs::write(&users, r#"{"method": "GET", "url": "{{base}}/v2/users"}"#).unwrap();
+
+        let mut index = ContentIndex::default();
+        index.refresh([orders.as_path(), users.as_path()]);
+        let hits = index.search("/V2/", 10);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].method, Some(HttpMethod::POST));
+
+        let hits = index.search("x-feature-flag", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, orders);
+        assert_eq!(hits[0].snippet, "X-Feature-Flag: checkout");
+
+        let hits = index.search("\"sku\"", 10);
+        assert_eq!(hits[0].snippet, "\"sku\": \"A-1\"");
+        assert!(index.search("  ", 10).is_empty());
+        assert_eq!(index.search("v2", 1).len(), 1);
+
+        // Removed files drop out of the index
+        index.refresh([users.as_path()]);
+        assert!(index.search("orders", 10).is_empty());
+    }
+
+    #[test]
+    fn test_snippet_keeps_match_in_view() {
+        let line = format!("{}needle{}", "a".repeat(100), "b".repeat(100));
+        let cut = snippet(&line, "needle", 40);
+        assert!(cut.starts_with('…') && cut.ends_with('…'));
+        assert!(cut.contains("needle"));
+        assert_eq!(cut.chars().count(), 42);
+        assert_eq!(snippet("  short  ", "short", 40), "short");
+    }
+}
@@ -0,0 +1,189 @@
+//! Secrets Module
+//!
+//! Encrypts and decrypts the per-workspace `.env.enc` file, so secrets can be
+//! committed alongside the environments. The contents are `KEY=VALUE` lines sealed
+//! with AES-256-GCM under a key derived from a passphrase with PBKDF2-HMAC-SHA256.
+//! The passphrase itself is never written anywhere.
+//!
+//! The file is plain text, one field per line:
+//!
+//! ```text
+//! mercury-secrets v1
+//! kdf pbkdf2-sha256 600000
+//! salt <base64>
+//! nonce <base64>
+//! data <base64>
+//! ```
+
+use super::error::MercuryError;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::num::NonZeroU32;
+
+const MAGIC: &str = "mercury-secrets v1";
+const KDF: &str = "pbkdf2-sha256";
+/// OWASP's current recommendation for PBKDF2-HMAC-SHA256
+const ITERATIONS: u32 = 600_000;
+/// Most iterations a file may ask for, so a corrupt or tampered header can't
+/// make unlocking run for hours
+const MAX_ITERATIONS: u32 = ITERATIONS * 10;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+fn secrets_error(message: &str) -> MercuryError {
+    MercuryError::SecretsError(message.to_string())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: NonZeroU32) -> LessSafeKey {
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &key).expect("key is 32 bytes"))
+}
+
+/// The header lines, which are authenticated along with the data so the
+/// iteration count can't be lowered without breaking decryption
+fn header(iterations: u32) -> String {
+    format!("{}\nkdf {} {}", MAGIC, KDF, iterations)
+}
+
+/// Encrypt `plaintext` with `passphrase`, returning the file contents. Every call
+/// uses a fresh salt and nonce.
+pub fn encrypt(plaintext: &str, passphrase: &str) -> Result<String, MercuryError> {
+    encrypt_with(plaintext, passphrase, ITERATIONS)
+}
+
+fn encrypt_with(
+    plaintext: &str,
+    passphrase: &str,
+    iterations: u32,
+) -> Result<String, MercuryError> {
+    let iterations_nz =
+        NonZeroU32::new(iterations).ok_or_else(|| secrets_error("Unsupported key derivation"))?;
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| secrets_error("Could not generate random bytes"))?;
+
+    let header = header(iterations);
+    let key = derive_key(passphrase, &salt, iterations_nz);
+    let mut data = plaintext.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(header.as_bytes()),
+        &mut data,
+    )
+    .map_err(|_| secrets_error("Encryption failed"))?;
+
+    Ok(format!(
+        "{}\nsalt {}\nnonce {}\ndata {}\n",
+        header,
+        STANDARD.encode(salt),
+        STANDARD.encode(nonce),
+        STANDARD.encode(data)
+    ))
+}
+
+/// Decrypt the contents of a `.env.enc` file. A wrong passphrase and a modified
+/// file look the same, and both are errors.
+pub fn decrypt(content: &str, passphrase: &str) -> Result<String, MercuryError> {
+    let mut lines = content.lines().map(str::trim).filter(|l| !l.is_empty());
+    if lines.next() != Some(MAGIC) {
+        return Err(secrets_error("Not a Mercury secrets file"));
+    }
+
+    let mut iterations = None;
+    let mut salt = None;
+    let mut nonce = None;
+    let mut data = None;
+    for line in lines {
+        let (field, value) = line.split_once(' ').unwrap_or((line, ""));
+        let decode = || STANDARD.decode(value.trim()).ok();
+        match field {
+            "kdf" => {
+                iterations = value
+                    .strip_prefix(KDF)
+                    .and_then(|n| n.trim().parse::<u32>().ok())
+            }
+            "salt" => salt = decode(),
+            "nonce" => nonce = decode().and_then(|n| <[u8; NONCE_LEN]>::try_from(n).ok()),
+            "data" => data = decode(),
+            _ => {}
+        }
+    }
+
+    let iterations = iterations.ok_or_else(|| secrets_error("Unsupported key derivation"))?;
+    if iterations > MAX_ITERATIONS {
+        return Err(secrets_error(
+            "The secrets file asks for more key derivation rounds than Mercury allows",
+        ));
+    }
+    let (Some(salt), Some(nonce), Some(mut data)) = (salt, nonce, data) else {
+        return Err(secrets_error("The secrets file is incomplete"));
+    };
+    let iterations_nz =
+        NonZeroU32::new(iterations).ok_or_else(|| secrets_error("Unsupported key derivation"))?;
+
+    let header = header(iterations);
+    let key = derive_key(passphrase, &salt, iterations_nz);
+    let plaintext = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(header.as_bytes()),
+            &mut data,
+        )
+        .map_err(|_| secrets_error("Wrong passphrase, or the file was modified"))?;
+
+    String::from_utf8(plaintext.to_vec()).map_err(|_| secrets_error("Secrets are not valid text"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Few iterations keep the tests fast; the format is the same
+    const TEST_ITERATIONS: u32 = 1_000;
+
+    #[test]
+    fn test_roundtrip() {
+        let plaintext = "API_TOKEN=abc123\nDB_PASSWORD='p#ss'\n";
+        let sealed = encrypt_with(plaintext, "correct horse", TEST_ITERATIONS).unwrap();
+
+        assert!(sealed.starts_with("mercury-secrets v1\nkdf pbkdf2-sha256 1000\n"));
+        assert!(!sealed.contains("abc123"));
+        assert_eq!(decrypt(&sealed, "correct horse").unwrap(), plaintext);
+
+        // Fresh salt and nonce each time
+        let again = encrypt_with(plaintext, "correct horse", TEST_ITERATIONS).unwrap();
+        assert_ne!(sealed, again);
+    }
+
+    #[test]
+    fn test_rejects_wrong_passphrase_and_tampering() {
+        let sealed = encrypt_with("TOKEN=x\n", "right", TEST_ITERATIONS).unwrap();
+
+        let err = decrypt(&sealed, "wrong").unwrap_err();
+        assert!(err.to_string().contains("Wrong passphrase"));
+
+        // Lowering the iteration count changes the authenticated header
+        let weakened = sealed.replace("pbkdf2-sha256 1000", "pbkdf2-sha256 1");
+        assert!(decrypt(&weakened, "right").is_err());
+
+        // An absurd iteration count is refused before any work is done
+        let huge = sealed.replace("pbkdf2-sha256 1000", "pbkdf2-sha256 4294967295");
+        let err = decrypt(&huge, "right").unwrap_err();
+        assert!(err.to_string().contains("more key derivation rounds"));
+
+        assert!(decrypt("TOKEN=x\n", "right").is_err());
+        assert!(decrypt("mercury-secrets v1\nkdf pbkdf2-sha256 1000\n", "right").is_err());
+    }
+}
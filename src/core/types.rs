@@ -10,8 +10,9 @@
 //! - `RecentRequest`: A saved recent request with timestamp
 //! - `TimelineEntry`: A history entry combining request + response
 
+use crate::core::keybindings::Keymap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 /// HTTP request method
@@ -73,6 +74,98 @@ pub struct JsonRequest {
     pub headers: HashMap<String, String>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub body: String,
+    /// The body is hex digits (`48 65 6c 6c 6f`), sent as the bytes they spell
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub body_hex: bool,
+    /// Organizational labels shown in the tree; never sent over the wire
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// When set, the request is sent as a gRPC-Web call instead of plain HTTP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc: Option<GrpcCall>,
+    /// IP address to connect to instead of looking up the URL's host; the Host
+    /// header and TLS SNI still use the URL's host name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolve: Option<String>,
+    /// Seconds to wait for this request; unset uses the timeout from Settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Whether to follow redirects for this request; unset uses Settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follow_redirects: Option<bool>,
+    /// Milliseconds to wait before sending, for reproducing timing-dependent bugs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_ms: Option<u64>,
+    /// Content-Type the response should have (`application/json`, `image/*`, or
+    /// just `json`); anything else is flagged in the response panel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_content_type: Option<String>,
+    /// Response value to keep as a session variable when the request succeeds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub save: Option<SaveRule>,
+    /// Changes applied when an environment is selected, keyed by its name
+    /// (`staging` for `.env.staging`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub overrides: BTreeMap<String, RequestOverride>,
+    /// What the operation does and takes, kept from an OpenAPI import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<RequestDocs>,
+}
+
+/// An operation's documentation, shown in the API Docs view
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct RequestDocs {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<ParamDoc>,
+}
+
+/// One documented parameter of an operation
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct ParamDoc {
+    pub name: String,
+    /// `path`, `query`, `header`, or `body`
+    #[serde(rename = "in")]
+    pub location: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
+    /// Schema type, like `integer` or `array`
+    #[serde(default, rename = "type", skip_serializing_if = "String::is_empty")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+/// Copies a value from a 2xx JSON response into a session variable, so the next
+/// request can use it as `{{variable}}`
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct SaveRule {
+    /// Name without braces
+    pub variable: String,
+    /// JSONPath into the response body, like `$.data.token`
+    pub path: String,
+}
+
+/// Headers and body that replace a request's own in one environment
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct RequestOverride {
+    /// Added, or replacing the request's header of the same name in any case
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// gRPC method a request targets; its body is the JSON form of the input message
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct GrpcCall {
+    /// Path to the `.proto` file, relative to the request file unless absolute
+    pub proto: String,
+    pub service: String,
+    pub method: String,
 }
 
 impl Default for JsonRequest {
@@ -82,6 +175,17 @@ impl Default for JsonRequest {
             url: String::new(),
             headers: HashMap::new(),
             body: String::new(),
+            body_hex: false,
+            tags: Vec::new(),
+            grpc: None,
+            resolve: None,
+            timeout_secs: None,
+            follow_redirects: None,
+            delay_ms: None,
+            expect_content_type: None,
+            save: None,
+            overrides: BTreeMap::new(),
+            docs: None,
         }
     }
 }
@@ -114,6 +218,24 @@ pub struct Response {
     pub duration_ms: u128,
 }
 
+impl Response {
+    /// History record for a request that never got a response
+    pub fn failed(message: String, duration_ms: u128) -> Self {
+        Self {
+            status: 0,
+            status_text: "Failed".to_string(),
+            body: message,
+            duration_ms,
+            ..Default::default()
+        }
+    }
+
+    /// Status 0 marks a network failure rather than an HTTP status
+    pub fn is_failure(&self) -> bool {
+        self.status == 0
+    }
+}
+
 /// Recent request entry (unsaved requests)
 ///
 /// Replaces the old `TempRequest` type.
@@ -134,9 +256,242 @@ pub struct AppState {
     pub auth_text: String,
     pub selected_tab: usize,
     pub selected_env: usize,
+    #[serde(default)]
+    pub settings: Settings,
+    /// Previously opened workspace folders, most recent first
+    #[serde(default)]
+    pub recent_workspaces: Vec<String>,
+    /// Request file that was open, reopened on the next start
+    #[serde(default)]
+    pub current_file: Option<String>,
+    #[serde(default)]
+    pub collapsed_panel: Option<CollapsedPanel>,
+    /// Workspaces open in extra windows, reopened alongside this one
+    #[serde(default)]
+    pub open_windows: Vec<String>,
+    #[serde(default)]
+    pub response_view: ResponseView,
+    #[serde(default)]
+    pub response_tab: ResponseTab,
+    /// The response panel was showing history instead of the response
+    #[serde(default)]
+    pub show_timeline: bool,
+    /// Show `\uXXXX` escapes in JSON responses as characters
+    #[serde(default)]
+    pub unescape_unicode: bool,
 }
 
-/// Collection tree item - folder or request file
+/// Views of the response panel, under the status row
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseTab {
+    #[default]
+    Body,
+    Headers,
+    Cookies,
+    Timing,
+    Security,
+    /// Comparison with the request's saved baseline; saved requests only
+    Baseline,
+}
+
+/// How the response body is shown
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseView {
+    /// Formatted and highlighted
+    #[default]
+    Pretty,
+    /// The body exactly as received
+    Raw,
+    /// Rendered: images drawn, HTML and Markdown laid out as text
+    Preview,
+}
+
+impl ResponseView {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResponseView::Pretty => "Pretty",
+            ResponseView::Raw => "Raw",
+            ResponseView::Preview => "Preview",
+        }
+    }
+}
+
+/// Request editor or response panel shrunk to a strip so the other gets the room
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CollapsedPanel {
+    Request,
+    Response,
+}
+
+/// User preferences edited in the Settings modal
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    // General
+    /// Write unsaved edits to the open request file every few seconds
+    pub auto_save: bool,
+    /// Note under the body when its format and Content-Type disagree
+    pub content_type_hints: bool,
+    pub request_defaults: RequestDefaults,
+    pub json_format: JsonFormat,
+    /// View each new response opens in; `None` keeps the one last picked
+    pub response_view: Option<ResponseView>,
+
+    // Network
+    /// Seconds to wait for a response once connected
+    pub timeout_secs: u64,
+    /// Seconds to wait for a connection to the host
+    pub connect_timeout_secs: u64,
+    pub follow_redirects: bool,
+    /// `User-Agent` for requests that don't set one; empty sends none
+    pub user_agent: String,
+    /// Requests a folder run keeps in flight at once
+    pub run_concurrency: usize,
+    /// Folder run requests per second to any one host (0 = unlimited)
+    pub run_rate_limit: u32,
+    pub request_log: RequestLogSettings,
+
+    // Security
+    /// Keep decoded credentials hidden until explicitly revealed
+    pub mask_secrets: bool,
+
+    // Appearance
+    pub ui_scale: f32,
+    /// Last response status next to each request in the sidebar
+    pub tree_status_badges: bool,
+
+    // Shortcuts
+    pub keybindings: Keymap,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            auto_save: true,
+            content_type_hints: true,
+            request_defaults: RequestDefaults::default(),
+            json_format: JsonFormat::default(),
+            response_view: None,
+            timeout_secs: crate::core::constants::DEFAULT_TIMEOUT_SECS,
+            connect_timeout_secs: crate::core::constants::DEFAULT_CONNECT_TIMEOUT_SECS,
+            follow_redirects: true,
+            user_agent: crate::core::constants::DEFAULT_USER_AGENT.to_string(),
+            run_concurrency: crate::core::constants::DEFAULT_RUN_CONCURRENCY,
+            run_rate_limit: crate::core::constants::DEFAULT_RUN_RATE_LIMIT,
+            request_log: RequestLogSettings::default(),
+            mask_secrets: true,
+            ui_scale: 1.0,
+            tree_status_badges: false,
+            keybindings: Keymap::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn timeouts(&self) -> Timeouts {
+        Timeouts {
+            connect_secs: self.connect_timeout_secs,
+            read_secs: self.timeout_secs,
+        }
+    }
+}
+
+/// How long a request may take to connect, and then to get its response
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timeouts {
+    pub connect_secs: u64,
+    /// A request's own `timeout_secs` overrides this
+    pub read_secs: u64,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Settings::default().timeouts()
+    }
+}
+
+/// How the Format button and the response view pretty-print JSON
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct JsonFormat {
+    pub indent: JsonIndent,
+    /// Order object keys alphabetically at every depth; otherwise keep them as sent
+    pub sort_keys: bool,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonIndent {
+    #[default]
+    TwoSpaces,
+    FourSpaces,
+    Tab,
+}
+
+impl JsonIndent {
+    pub const ALL: [JsonIndent; 3] = [
+        JsonIndent::TwoSpaces,
+        JsonIndent::FourSpaces,
+        JsonIndent::Tab,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            JsonIndent::TwoSpaces => "2 spaces",
+            JsonIndent::FourSpaces => "4 spaces",
+            JsonIndent::Tab => "Tab",
+        }
+    }
+
+    /// One level of indentation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JsonIndent::TwoSpaces => "  ",
+            JsonIndent::FourSpaces => "    ",
+            JsonIndent::Tab => "\t",
+        }
+    }
+}
+
+/// Append every sent request to a log file, for grep and external tools
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct RequestLogSettings {
+    pub enabled: bool,
+    /// Log file; empty uses `~/.mercury/requests.log`
+    pub path: String,
+}
+
+/// Starting point for new requests.
+/// Set per user in `Settings`; a workspace can ship its own in `.mercury.json`.
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct RequestDefaults {
+    #[serde(default)]
+    pub method: HttpMethod,
+    /// Headers in the editor's `Key: Value` line format
+    #[serde(default)]
+    pub headers: String,
+}
+
+/// Workspace-level settings read from `.mercury.json` in the workspace root
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct WorkspaceConfig {
+    #[serde(default)]
+    pub request_defaults: Option<RequestDefaults>,
+}
+
+/// A folder's `.folder.json`, applied to every request below it
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FolderDefaults {
+    pub headers: BTreeMap<String, String>,
+    pub variables: BTreeMap<String, String>,
+}
+
+/// Collection tree item - folder, request file, or `.http` file
 #[derive(Clone, Debug)]
 pub enum CollectionItem {
     Folder {
@@ -149,7 +504,26 @@ pub enum CollectionItem {
         name: String,
         path: PathBuf,
         method: Option<HttpMethod>,
+        /// Saved URL, unsubstituted; empty if the file couldn't be parsed
+        url: String,
+        tags: Vec<String>,
     },
+    /// A `.http` file; each `###` block is a request in it
+    HttpFile {
+        name: String,
+        path: PathBuf,
+        expanded: bool,
+        requests: Vec<HttpFileEntry>,
+    },
+}
+
+/// One request of a `.http` file in the tree, in file order
+#[derive(Clone, Debug)]
+pub struct HttpFileEntry {
+    /// `# @name` label or `###` title; the request line if there's neither
+    pub name: String,
+    pub method: HttpMethod,
+    pub url: String,
 }
 
 /// Timeline entry for request history (full data - stored on disk)
@@ -176,6 +550,12 @@ pub struct TimelineSummary {
     pub duration_ms: u128,
 }
 
+impl TimelineSummary {
+    pub fn is_failure(&self) -> bool {
+        self.status == 0
+    }
+}
+
 impl From<&TimelineEntry> for TimelineSummary {
     fn from(entry: &TimelineEntry) -> Self {
         Self {
@@ -0,0 +1,59 @@
+//! Windows Module
+//!
+//! Extra windows run as separate Mercury processes, so each has its own
+//! workspace, file watcher, and HTTP client. The window that opens them holds
+//! their stdin: when it quits, the pipe closes and they close too, which keeps
+//! "reopen the windows that were open" down to the one window that saves state.
+
+use super::constants::WINDOW_ARG;
+use super::error::MercuryError;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+/// The workspace from `mercury --window <folder>`; `None` for a normal launch
+pub fn window_workspace(mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    args.find(|arg| arg == WINDOW_ARG)?;
+    args.next().map(PathBuf::from)
+}
+
+/// Start another Mercury window on `workspace`
+pub fn open_window(workspace: &Path) -> Result<Child, MercuryError> {
+    let exe = std::env::current_exe().map_err(|e| MercuryError::WindowFailed(e.to_string()))?;
+    Command::new(exe)
+        .arg(WINDOW_ARG)
+        .arg(workspace)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| MercuryError::WindowFailed(e.to_string()))
+}
+
+/// Call `on_close` once the window that opened this one has gone
+pub fn watch_opener(on_close: impl FnOnce() + Send + 'static) {
+    std::thread::spawn(move || {
+        // Nothing is ever written; the read returns when the pipe closes
+        let _ = std::io::stdin().read_to_end(&mut Vec::new());
+        on_close();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_workspace() {
+        let args = |list: &[&str]| {
+            list.iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+        assert_eq!(
+            window_workspace(args(&["mercury", "--window", "/work/api"])),
+            Some(PathBuf::from("/work/api"))
+        );
+        assert_eq!(window_workspace(args(&["mercury"])), None);
+        assert_eq!(window_workspace(args(&["mercury", "--window"])), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! Import Detection Module
+//!
+//! Works out what a file dropped on the window is, so it can go to the right
+//! importer: by extension for `.http` files, and by looking inside JSON and YAML
+//! files, since Postman, Insomnia, OpenAPI, and Mercury's own requests all use
+//! `.json`.
+
+use crate::parser::parse_request_file;
+use std::fs;
+use std::path::Path;
+
+/// Which importer a file belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Postman,
+    Insomnia,
+    OpenApi,
+    /// A `.http`/`.rest` file, converted by "Add to Workspace"
+    HttpFile,
+    /// Mercury request files, or a folder of them, copied by "Add to Workspace"
+    Request,
+}
+
+impl ImportKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImportKind::Postman => "Postman collection",
+            ImportKind::Insomnia => "Insomnia export",
+            ImportKind::OpenApi => "OpenAPI spec",
+            ImportKind::HttpFile => ".http file",
+            ImportKind::Request => "request",
+        }
+    }
+}
+
+/// What kind of import `content` is, from the markers each format carries
+pub fn detect_content(content: &str) -> Option<ImportKind> {
+    let doc: serde_json::Value = serde_json::from_str(content)
+        .ok()
+        .or_else(|| serde_yaml::from_str(content).ok())?;
+    let root = doc.as_object()?;
+
+    if root.contains_key("openapi") || root.contains_key("swagger") {
+        return Some(ImportKind::OpenApi);
+    }
+    if root.get("_type").and_then(|t| t.as_str()) == Some("export")
+        || root.contains_key("__export_format")
+    {
+        return Some(ImportKind::Insomnia);
+    }
+    let info = root.get("info").and_then(|i| i.as_object());
+    if info.is_some_and(|info| {
+        info.contains_key("_postman_id")
+            || info
+                .get("schema")
+                .and_then(|s| s.as_str())
+                .is_some_and(|s| s.contains("getpostman.com"))
+    }) {
+        return Some(ImportKind::Postman);
+    }
+    parse_request_file(content)
+        .is_ok()
+        .then_some(ImportKind::Request)
+}
+
+/// What kind of import the file or folder at `path` is; `None` if it's none of them
+pub fn detect_import(path: &Path) -> Option<ImportKind> {
+    if path.is_dir() {
+        return Some(ImportKind::Request);
+    }
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    match extension.as_str() {
+        "http" | "rest" => Some(ImportKind::HttpFile),
+        "json" | "yaml" | "yml" => detect_content(&fs::read_to_string(path).ok()?),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_content() {
+        let postman = r#"{"info": {"name": "API", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"}, "item": []}"#;
+        assert_eq!(detect_content(postman), Some(ImportKind::Postman));
+
+        let insomnia = r#"{"_type": "export", "__export_format": 4, "resources": []}"#;
+        assert_eq!(detect_content(insomnia), Some(ImportKind::Insomnia));
+
+        let openapi = "openapi: 3.0.0\ninfo:\n  title: Pets\n  version: '1'\npaths: {}\n";
+        assert_eq!(detect_content(openapi), Some(ImportKind::OpenApi));
+        assert_eq!(
+            detect_content(r#"{"swagger": "2.0", "paths": {}}"#),
+            Some(ImportKind::OpenApi)
+        );
+
+        let request = r#"{"method": "GET", "url": "https://example.com"}"#;
+        assert_eq!(detect_content(request), Some(ImportKind::Request));
+
+        assert_eq!(detect_content(r#"{"name": "package"}"#), None);
+        assert_eq!(detect_content("not: [valid"), None);
+    }
+
+    #[test]
+    fn test_detect_import_by_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let http = dir.path().join("api.http");
+        fs::write(&http, "GET https://example.com\n").unwrap();
+        assert_eq!(detect_import(&http), Some(ImportKind::HttpFile));
+        assert_eq!(detect_import(dir.path()), Some(ImportKind::Request));
+
+        let text = dir.path().join("notes.txt");
+        fs::write(&text, "{}").unwrap();
+        assert_eq!(detect_import(&text), None);
+    }
+}
@@ -0,0 +1,217 @@
+//! Workspace Files Module
+//!
+//! Adds request files from elsewhere on disk to the workspace. `.json` requests are
+//! copied as they are, `.http`/`.rest` files are converted, and folders are copied
+//! recursively. Files that don't parse are skipped and reported rather than copied.
+
+use super::http_file::import_http_file_into;
+use crate::core::error::MercuryError;
+use crate::parser::parse_request_file;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// What an add did: files written, and files left out with the reason why
+#[derive(Debug, Default)]
+pub struct AddReport {
+    pub added: usize,
+    pub skipped: Vec<String>,
+}
+
+fn file_kind(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()?.to_lowercase().as_str() {
+        "json" => Some("json"),
+        "http" | "rest" => Some("http"),
+        _ => None,
+    }
+}
+
+/// Default name for `source` in the workspace: the folder name, or the file name
+/// without its extension
+pub fn entry_name(source: &Path) -> String {
+    let name = if source.is_dir() {
+        source.file_name()
+    } else {
+        source.file_stem()
+    };
+    name.unwrap_or_default().to_string_lossy().to_string()
+}
+
+/// Where `source` would land in `dest` under `name`. Requests become `name.json`;
+/// folders and `.http` files become a folder.
+pub fn entry_path(source: &Path, dest: &Path, name: &str) -> PathBuf {
+    if !source.is_dir() && file_kind(source) == Some("json") {
+        dest.join(format!("{}.json", name))
+    } else {
+        dest.join(name)
+    }
+}
+
+/// Add `source` to `dest` under `name`, which the caller has checked is free.
+/// Broken files are recorded in `report` and skipped; only I/O failures are errors.
+pub fn add_to_workspace(
+    source: &Path,
+    dest: &Path,
+    name: &str,
+    report: &mut AddReport,
+) -> Result<(), MercuryError> {
+    let target = entry_path(source, dest, name);
+    if source.is_dir() {
+        // Copying into itself would walk the folders it's creating
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if canonical(dest).starts_with(canonical(source)) {
+            return Err(MercuryError::AddIntoItself(entry_name(source)));
+        }
+        copy_folder(source, &target, report)
+    } else {
+        add_file(source, &target, report)
+    }
+}
+
+fn add_file(source: &Path, target: &Path, report: &mut AddReport) -> Result<(), MercuryError> {
+    let file_name = source.file_name().unwrap_or_default().to_string_lossy();
+    match file_kind(source) {
+        Some("json") => {
+            let content = fs::read_to_string(source).map_err(|e| MercuryError::FileRead {
+                path: source.display().to_string(),
+                reason: e.to_string(),
+            })?;
+            if let Err(e) = parse_request_file(&content) {
+                report.skipped.push(format!("{}: {}", file_name, e));
+                return Ok(());
+            }
+            if let Some(parent) = target.parent() {
+                create_dir(parent)?;
+            }
+            fs::write(target, content).map_err(|e| MercuryError::FileWrite {
+                path: target.display().to_string(),
+                reason: e.to_string(),
+            })?;
+            report.added += 1;
+        }
+        Some(_) => match import_http_file_into(source, target) {
+            Ok(count) => report.added += count,
+            Err(e @ MercuryError::HttpParseError(_)) => {
+                report.skipped.push(format!("{}: {}", file_name, e));
+            }
+            Err(e) => return Err(e),
+        },
+        None => report
+            .skipped
+            .push(format!("{}: not a request or .http file", file_name)),
+    }
+    Ok(())
+}
+
+/// Copy a folder's requests and `.http` files, keeping its layout. Hidden entries
+/// and other file types are left out, as the sidebar would ignore them anyway.
+fn copy_folder(source: &Path, target: &Path, report: &mut AddReport) -> Result<(), MercuryError> {
+    create_dir(target)?;
+
+    let entries = WalkDir::new(source)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok());
+
+    for entry in entries {
+        let path = entry.path();
+        let relative = path.strip_prefix(source).unwrap_or(path);
+        if entry.file_type().is_dir() {
+            create_dir(&target.join(relative))?;
+        } else if file_kind(path).is_some() {
+            let parent = target.join(relative.parent().unwrap_or(Path::new("")));
+            add_file(path, &entry_path(path, &parent, &entry_name(path)), report)?;
+        }
+    }
+    Ok(())
+}
+
+fn create_dir(path: &Path) -> Result<(), MercuryError> {
+    fs::create_dir_all(path).map_err(|e| MercuryError::FileWrite {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_add_file_validates() {
+        let source = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+
+        let good = source.path().join("health.json");
+        fs::write(&good, r#"{"method": "GET", "url": "https://example.com"}"#).unwrap();
+        let broken = source.path().join("broken.json");
+        fs::write(&broken, "{ not json").unwrap();
+
+        let mut report = AddReport::default();
+        add_to_workspace(&good, workspace.path(), "status", &mut report).unwrap();
+        add_to_workspace(&broken, workspace.path(), "broken", &mut report).unwrap();
+
+        assert_eq!(report.added, 1);
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].starts_with("broken.json"));
+        assert!(workspace.path().join("status.json").exists());
+        assert!(!workspace.path().join("broken.json").exists());
+    }
+
+    #[test]
+    fn test_add_folder() {
+        let source = TempDir::new().unwrap();
+        let api = source.path().join("api");
+        fs::create_dir_all(api.join("users")).unwrap();
+        fs::create_dir_all(api.join(".examples")).unwrap();
+        fs::write(
+            api.join("users/list.json"),
+            r#"{"method": "GET", "url": "https://example.com/users"}"#,
+        )
+        .unwrap();
+        fs::write(
+            api.join("auth.http"),
+            "### Login\nPOST https://example.com/login\n\n### Logout\nPOST https://example.com/logout\n",
+        )
+        .unwrap();
+        fs::write(api.join("empty.http"), "# nothing here\n").unwrap();
+        fs::write(api.join("notes.txt"), "ignored").unwrap();
+
+        let workspace = TempDir::new().unwrap();
+        assert_eq!(entry_name(&api), "api");
+        assert_eq!(
+            entry_path(&api, workspace.path(), "api"),
+            workspace.path().join("api")
+        );
+
+        let mut report = AddReport::default();
+        add_to_workspace(&api, workspace.path(), "api", &mut report).unwrap();
+
+        let copied = workspace.path().join("api");
+        assert_eq!(report.added, 3);
+        assert!(copied.join("users/list.json").exists());
+        assert!(copied.join("auth/login.json").exists());
+        assert!(!copied.join(".examples").exists());
+        assert!(!copied.join("notes.txt").exists());
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].starts_with("empty.http"));
+    }
+
+    #[test]
+    fn test_add_folder_into_itself() {
+        let source = TempDir::new().unwrap();
+        let api = source.path().join("api");
+        fs::create_dir_all(api.join("users")).unwrap();
+
+        let mut report = AddReport::default();
+        for dest in [api.clone(), api.join("users")] {
+            let err = add_to_workspace(&api, &dest, "api", &mut report).unwrap_err();
+            assert!(matches!(err, MercuryError::AddIntoItself(_)));
+        }
+        assert!(!api.join("api").exists());
+        assert!(!api.join("users/api").exists());
+    }
+}
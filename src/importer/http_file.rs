@@ -0,0 +1,112 @@
+//! HTTP File Importer Module
+//!
+//! Converts REST Client style `.http` files (requests separated by `###`)
+//! to Mercury JSON format, one request file per block. A `.http` file kept in
+//! the workspace is edited in place instead; see `parser::http_file`.
+
+use super::postman::sanitize_filename;
+use crate::core::error::MercuryError;
+use crate::parser::http_file::parse_http_file;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Import a `.http` file into a folder named after it inside `output_dir`.
+/// Returns (request_count, env_count) like the other importers.
+pub fn import_http_file(
+    http_path: &Path,
+    output_dir: &Path,
+) -> Result<(usize, usize), MercuryError> {
+    let stem = http_path.file_stem().unwrap_or_default().to_string_lossy();
+    let count = import_http_file_into(http_path, &output_dir.join(sanitize_filename(&stem)))?;
+    Ok((count, 0))
+}
+
+/// Write one request file per block of `http_path` into `folder_path`, creating it.
+/// Returns the number of requests written.
+pub fn import_http_file_into(http_path: &Path, folder_path: &Path) -> Result<usize, MercuryError> {
+    let content = fs::read_to_string(http_path).map_err(|e| MercuryError::FileRead {
+        path: http_path.display().to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let requests = parse_http_file(&content);
+    if requests.is_empty() {
+        return Err(MercuryError::HttpParseError(format!(
+            "No requests found in {}",
+            http_path.display()
+        )));
+    }
+
+    fs::create_dir_all(folder_path).map_err(|e| MercuryError::FileWrite {
+        path: folder_path.display().to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let mut used_names = HashSet::new();
+    for (index, entry) in requests.iter().enumerate() {
+        let base = entry
+            .name
+            .as_deref()
+            .map(sanitize_filename)
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| format!("request-{}", index + 1));
+
+        // Unnamed or repeated labels still get distinct files
+        let mut file_name = base.clone();
+        let mut counter = 2;
+        while !used_names.insert(file_name.clone()) {
+            file_name = format!("{}-{}", base, counter);
+            counter += 1;
+        }
+
+        let file_path = folder_path.join(format!("{}.json", file_name));
+        let json_content = serde_json::to_string_pretty(&entry.request)
+            .map_err(|e| MercuryError::HttpParseError(e.to_string()))?;
+
+        fs::write(&file_path, json_content).map_err(|e| MercuryError::FileWrite {
+            path: file_path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+    }
+
+    Ok(requests.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = r#"### List users
+GET {{host}}/users
+
+###
+# @name create-user
+POST {{host}}/users
+"#;
+
+    #[test]
+    fn test_import_http_file() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("Users API.http");
+        fs::write(&file_path, SAMPLE).unwrap();
+
+        let (req_count, env_count) = import_http_file(&file_path, dir.path()).unwrap();
+        assert_eq!((req_count, env_count), (2, 0));
+
+        let folder = dir.path().join("users-api");
+        assert!(folder.join("list-users.json").exists());
+        assert!(folder.join("create-user.json").exists());
+    }
+
+    #[test]
+    fn test_import_empty_http_file() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("empty.http");
+        fs::write(&file_path, "# just a comment\n").unwrap();
+
+        let result = import_http_file(&file_path, dir.path());
+        assert!(matches!(result, Err(MercuryError::HttpParseError(_))));
+    }
+}
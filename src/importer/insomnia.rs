@@ -172,6 +172,7 @@ pub fn import_insomnia_collection(
                 url: request.url.clone(),
                 headers,
                 body,
+                ..Default::default()
             };
 
             // Serialize and write
@@ -1,10 +1,17 @@
 //! Importer Module
 //!
-//! Import collections from other API clients: Insomnia, Postman.
+//! Import collections from other API clients: Insomnia, Postman, OpenAPI specs,
+//! `.http` files, and add loose request files and folders to the workspace.
 
+pub mod detect;
+pub mod files;
+pub mod http_file;
 pub mod insomnia;
+pub mod openapi;
 pub mod postman;
 
 // Re-export import functions
+pub use http_file::import_http_file;
 pub use insomnia::import_insomnia_collection;
+pub use openapi::import_openapi_file;
 pub use postman::import_postman_collection;
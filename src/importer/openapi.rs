@@ -0,0 +1,561 @@
+//! OpenAPI Importer Module
+//!
+//! Converts OpenAPI 3 and Swagger 2 specs (JSON/YAML) to Mercury JSON format.
+//! Each operation becomes a request in a folder named after its first tag, with
+//! the server address kept in a `baseUrl` environment variable.
+
+use super::postman::sanitize_filename;
+use crate::core::error::MercuryError;
+use crate::core::request::{HttpResponse, ResponseType};
+use crate::core::types::{HttpMethod, JsonRequest, ParamDoc, RequestDocs};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Operation keys under a path item, in the order they are imported
+const METHODS: &[&str] = &[
+    "get", "post", "put", "patch", "delete", "head", "options", "trace",
+];
+
+fn import_error(message: impl Into<String>) -> MercuryError {
+    MercuryError::OpenApiImportError(message.into())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpecFormat {
+    OpenApi3,
+    Swagger2,
+}
+
+fn detect_format(spec: &Value) -> Option<SpecFormat> {
+    if spec
+        .get("openapi")
+        .and_then(Value::as_str)
+        .is_some_and(|v| v.starts_with('3'))
+    {
+        Some(SpecFormat::OpenApi3)
+    } else if spec.get("swagger").and_then(Value::as_str) == Some("2.0") {
+        Some(SpecFormat::Swagger2)
+    } else {
+        None
+    }
+}
+
+/// Origin of `url` (`https://host:port`), if it has one
+fn origin(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    let end = rest
+        .find('/')
+        .map_or(url.len(), |i| url.len() - rest.len() + i);
+    Some(&url[..end])
+}
+
+/// Resolve a server URL written relative to where the spec came from
+fn resolve_against(base: &str, source_url: Option<&str>) -> String {
+    if base.contains("://") {
+        return base.to_string();
+    }
+    let Some(source) = source_url else {
+        return base.to_string();
+    };
+    if let Some(rest) = base.strip_prefix("//") {
+        let scheme = source.split_once("://").map_or("https", |(s, _)| s);
+        return format!("{}://{}", scheme, rest);
+    }
+    match origin(source) {
+        Some(origin) if base.starts_with('/') => format!("{}{}", origin, base),
+        Some(_) => {
+            let dir = &source[..source.rfind('/').unwrap_or(source.len())];
+            format!("{}/{}", dir, base)
+        }
+        None => base.to_string(),
+    }
+}
+
+/// The address requests are sent to: the first server (OpenAPI 3) or
+/// scheme + host + basePath (Swagger 2)
+fn base_url(spec: &Value, format: SpecFormat, source_url: Option<&str>) -> String {
+    let base = match format {
+        SpecFormat::OpenApi3 => {
+            let server = spec.get("servers").and_then(|s| s.get(0));
+            let mut url = server
+                .and_then(|s| s.get("url"))
+                .and_then(Value::as_str)
+                .unwrap_or("/")
+                .to_string();
+            if let Some(vars) = server
+                .and_then(|s| s.get("variables"))
+                .and_then(Value::as_object)
+            {
+                for (name, var) in vars {
+                    if let Some(default) = var.get("default").and_then(Value::as_str) {
+                        url = url.replace(&format!("{{{}}}", name), default);
+                    }
+                }
+            }
+            url
+        }
+        SpecFormat::Swagger2 => {
+            let base_path = spec.get("basePath").and_then(Value::as_str).unwrap_or("");
+            match spec.get("host").and_then(Value::as_str) {
+                Some(host) => {
+                    let scheme = spec
+                        .get("schemes")
+                        .and_then(|s| s.get(0))
+                        .and_then(Value::as_str)
+                        .unwrap_or("https");
+                    format!("{}://{}{}", scheme, host, base_path)
+                }
+                None => base_path.to_string(),
+            }
+        }
+    };
+    resolve_against(&base, source_url)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Follow a local `#/...` reference, once
+fn resolve_ref<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    value
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix('#'))
+        .and_then(|pointer| spec.pointer(pointer))
+        .unwrap_or(value)
+}
+
+/// `{id}` path parameters become `{{id}}` variables
+fn template_path(path: &str) -> String {
+    path.replace('{', "{{").replace('}', "}}")
+}
+
+/// Example body for a JSON schema or media type, when the spec gives one
+fn example_body(media: &Value) -> String {
+    let example = media
+        .get("example")
+        .or_else(|| media.get("schema").and_then(|s| s.get("example")))
+        .or_else(|| {
+            media
+                .get("examples")
+                .and_then(Value::as_object)
+                .and_then(|e| e.values().next())
+                .and_then(|e| e.get("value"))
+        });
+    match example {
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => serde_json::to_string_pretty(value).unwrap_or_default(),
+        None => "{}".to_string(),
+    }
+}
+
+fn text(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// A parameter's documentation; OpenAPI 3 keeps the type under `schema`
+fn param_doc(param: &Value, location: &str) -> ParamDoc {
+    let kind = param
+        .get("schema")
+        .filter(|schema| schema.get("type").is_some())
+        .unwrap_or(param);
+    ParamDoc {
+        name: text(param, "name"),
+        location: location.to_string(),
+        required: location == "path"
+            || param.get("required").and_then(Value::as_bool) == Some(true),
+        kind: text(kind, "type"),
+        description: text(param, "description"),
+    }
+}
+
+/// Build one request from an operation, with the parameters shared by its path
+fn build_request(
+    spec: &Value,
+    format: SpecFormat,
+    method: HttpMethod,
+    path: &str,
+    shared_params: &[Value],
+    operation: &Value,
+) -> JsonRequest {
+    let mut params: Vec<&Value> = shared_params.iter().map(|p| resolve_ref(spec, p)).collect();
+    if let Some(own) = operation.get("parameters").and_then(Value::as_array) {
+        params.extend(own.iter().map(|p| resolve_ref(spec, p)));
+    }
+
+    let mut headers = HashMap::new();
+    let mut query = Vec::new();
+    let mut body = String::new();
+    let mut param_docs = Vec::new();
+    for param in params {
+        let name = param.get("name").and_then(Value::as_str).unwrap_or("");
+        let required = param.get("required").and_then(Value::as_bool) == Some(true);
+        if let Some(location @ ("path" | "query" | "header" | "body")) =
+            param.get("in").and_then(Value::as_str)
+        {
+            param_docs.push(param_doc(param, location));
+        }
+        match param.get("in").and_then(Value::as_str) {
+            Some("query") if required => query.push(format!("{}={{{{{}}}}}", name, name)),
+            Some("header") if required => {
+                headers.insert(name.to_string(), format!("{{{{{}}}}}", name));
+            }
+            Some("body") => body = example_body(param),
+            _ => {}
+        }
+    }
+
+    match format {
+        SpecFormat::OpenApi3 => {
+            let request_body = operation.get("requestBody").map(|b| resolve_ref(spec, b));
+            let content = request_body
+                .and_then(|b| b.get("content"))
+                .and_then(Value::as_object);
+            if let Some(content) = content {
+                let json = content
+                    .iter()
+                    .find(|(media_type, _)| media_type.contains("json"));
+                if let Some((media_type, media)) = json.or_else(|| content.iter().next()) {
+                    headers.insert("Content-Type".to_string(), media_type.clone());
+                    if media_type.contains("json") {
+                        body = example_body(media);
+                    }
+                    let schema = media.get("schema").map(|s| resolve_ref(spec, s));
+                    param_docs.push(ParamDoc {
+                        name: "body".to_string(),
+                        location: "body".to_string(),
+                        required: request_body
+                            .and_then(|b| b.get("required"))
+                            .and_then(Value::as_bool)
+                            == Some(true),
+                        kind: schema.map(|s| text(s, "type")).unwrap_or_default(),
+                        description: request_body
+                            .map(|b| text(b, "description"))
+                            .unwrap_or_default(),
+                    });
+                }
+            }
+        }
+        SpecFormat::Swagger2 if !body.is_empty() => {
+            let consumes = operation
+                .get("consumes")
+                .or_else(|| spec.get("consumes"))
+                .and_then(|c| c.get(0))
+                .and_then(Value::as_str)
+                .unwrap_or("application/json");
+            headers.insert("Content-Type".to_string(), consumes.to_string());
+        }
+        SpecFormat::Swagger2 => {}
+    }
+
+    let mut url = format!("{{{{baseUrl}}}}{}", template_path(path));
+    if !query.is_empty() {
+        url.push('?');
+        url.push_str(&query.join("&"));
+    }
+
+    let docs = RequestDocs {
+        summary: text(operation, "summary"),
+        description: text(operation, "description"),
+        params: param_docs,
+    };
+
+    JsonRequest {
+        method,
+        url,
+        headers,
+        body,
+        docs: Some(docs).filter(|d| *d != RequestDocs::default()),
+        ..Default::default()
+    }
+}
+
+/// Parse a spec document, JSON first and then YAML
+fn parse_spec(content: &str) -> Result<Value, MercuryError> {
+    match serde_json::from_str(content) {
+        Ok(json) => Ok(json),
+        Err(json_err) => serde_yaml::from_str(content).map_err(|yaml_err| {
+            import_error(format!(
+                "Failed to parse as JSON ({}) or YAML ({})",
+                json_err, yaml_err
+            ))
+        }),
+    }
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), MercuryError> {
+    fs::write(path, content).map_err(|e| MercuryError::FileWrite {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// Imports an OpenAPI 3 or Swagger 2 spec into Mercury's .json file format.
+///
+/// `source_url` is where the spec was downloaded from, if anywhere; server URLs
+/// written relative to the spec are resolved against it.
+///
+/// # Returns
+/// A tuple of (request_count, environment_count) on success
+///
+/// # Behavior
+/// - Creates a folder per tag (`default` for untagged operations)
+/// - Names each request after its summary, operation ID, or method and path
+/// - Turns path parameters and required query/header parameters into variables
+/// - Uses a JSON example body where the spec has one, `{}` otherwise
+/// - Keeps each operation's summary, description, and parameters as `docs`
+/// - Writes the server address as `baseUrl` in a `.env.<title>` file
+pub fn import_openapi_spec(
+    content: &str,
+    output_dir: &Path,
+    source_url: Option<&str>,
+) -> Result<(usize, usize), MercuryError> {
+    let spec = parse_spec(content)?;
+    let format = detect_format(&spec)
+        .ok_or_else(|| import_error("Not an OpenAPI 3 or Swagger 2 document"))?;
+    let paths = spec
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or_else(|| import_error("The spec has no paths"))?;
+
+    let title = spec
+        .get("info")
+        .and_then(|i| i.get("title"))
+        .and_then(Value::as_str)
+        .unwrap_or("openapi");
+    let env_path = output_dir.join(format!(".env.{}", sanitize_filename(title)));
+    write_file(
+        &env_path,
+        &format!("baseUrl={}\n", base_url(&spec, format, source_url)),
+    )?;
+
+    let mut used_names: HashSet<String> = HashSet::new();
+    let mut request_count = 0;
+    for (path, item) in paths {
+        let item = resolve_ref(&spec, item);
+        let shared_params = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        for method_key in METHODS {
+            let Some(operation) = item.get(*method_key) else {
+                continue;
+            };
+            let Some(method) = HttpMethod::from_str(method_key) else {
+                continue;
+            };
+
+            let folder_name = operation
+                .get("tags")
+                .and_then(|t| t.get(0))
+                .and_then(Value::as_str)
+                .map(sanitize_filename)
+                .unwrap_or_else(|| "default".to_string());
+            let folder_path = output_dir.join(&folder_name);
+            fs::create_dir_all(&folder_path).map_err(|e| MercuryError::FileWrite {
+                path: folder_path.display().to_string(),
+                reason: e.to_string(),
+            })?;
+
+            let name = operation
+                .get("summary")
+                .or_else(|| operation.get("operationId"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{} {}", method_key, path));
+            let base_name = sanitize_filename(&name);
+            let mut file_name = base_name.clone();
+            let mut n = 2;
+            while !used_names.insert(format!("{}/{}", folder_name, file_name)) {
+                file_name = format!("{}-{}", base_name, n);
+                n += 1;
+            }
+
+            let request = build_request(&spec, format, method, path, &shared_params, operation);
+            let json_content =
+                serde_json::to_string_pretty(&request).map_err(|e| import_error(e.to_string()))?;
+            write_file(
+                &folder_path.join(format!("{}.json", file_name)),
+                &json_content,
+            )?;
+            request_count += 1;
+        }
+    }
+
+    Ok((request_count, 1))
+}
+
+/// Imports a spec file from disk. See [`import_openapi_spec`].
+pub fn import_openapi_file(
+    spec_path: &Path,
+    output_dir: &Path,
+) -> Result<(usize, usize), MercuryError> {
+    let content = fs::read_to_string(spec_path).map_err(|e| MercuryError::FileRead {
+        path: spec_path.display().to_string(),
+        reason: e.to_string(),
+    })?;
+    import_openapi_spec(&content, output_dir, None)
+}
+
+/// The spec text from a download, or why it can't be used. Some servers send
+/// specs as `application/octet-stream`, so binary bodies are read as text too.
+pub fn spec_from_response(response: &HttpResponse) -> Result<String, MercuryError> {
+    if !(200..300).contains(&response.status) {
+        return Err(import_error(format!(
+            "The server answered {} {}",
+            response.status, response.status_text
+        )));
+    }
+    match (&response.response_type, &response.raw_bytes) {
+        (ResponseType::TooLarge, _) => Err(import_error("The spec is too large to import")),
+        (ResponseType::Empty, _) => Err(import_error("The server sent an empty response")),
+        (ResponseType::Binary | ResponseType::Image, Some(bytes)) => {
+            Ok(String::from_utf8_lossy(bytes).into_owned())
+        }
+        _ => Ok(response.body.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn read_request(path: &Path) -> JsonRequest {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_import_openapi3() {
+        let dir = TempDir::new().unwrap();
+        let spec = r#"{
+            "openapi": "3.0.1",
+            "info": {"title": "Pet Store", "version": "1"},
+            "servers": [{"url": "/api/{version}", "variables": {"version": {"default": "v2"}}}],
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [{"name": "petId", "in": "path", "required": true, "schema": {"type": "integer"}}],
+                    "get": {"tags": ["pets"], "summary": "Get pet", "description": "One pet by ID"},
+                    "put": {
+                        "tags": ["pets"],
+                        "operationId": "updatePet",
+                        "parameters": [{"name": "dryRun", "in": "query", "required": true}],
+                        "requestBody": {
+                            "content": {"application/json": {"example": {"name": "Rex"}}}
+                        }
+                    }
+                },
+                "/health": {"get": {}}
+            }
+        }"#;
+
+        let (requests, envs) = import_openapi_spec(
+            spec,
+            dir.path(),
+            Some("https://petstore.example.com/docs/openapi.json"),
+        )
+        .unwrap();
+
+        assert_eq!((requests, envs), (3, 1));
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".env.pet-store")).unwrap(),
+            "baseUrl=https://petstore.example.com/api/v2\n"
+        );
+
+        let get = read_request(&dir.path().join("pets/get-pet.json"));
+        assert_eq!(get.method, HttpMethod::GET);
+        assert_eq!(get.url, "{{baseUrl}}/pets/{{petId}}");
+        let docs = get.docs.unwrap();
+        assert_eq!(
+            (docs.summary.as_str(), docs.description.as_str()),
+            ("Get pet", "One pet by ID")
+        );
+        assert_eq!(
+            docs.params,
+            vec![ParamDoc {
+                name: "petId".to_string(),
+                location: "path".to_string(),
+                required: true,
+                kind: "integer".to_string(),
+                description: String::new(),
+            }]
+        );
+
+        let put = read_request(&dir.path().join("pets/updatepet.json"));
+        assert_eq!(put.url, "{{baseUrl}}/pets/{{petId}}?dryRun={{dryRun}}");
+        assert_eq!(put.headers["Content-Type"], "application/json");
+        assert!(put.body.contains("\"name\": \"Rex\""));
+        let params = put.docs.unwrap().params;
+        let locations: Vec<&str> = params.iter().map(|p| p.location.as_str()).collect();
+        assert_eq!(locations, ["path", "query", "body"]);
+
+        let health = read_request(&dir.path().join("default/get-health.json"));
+        assert_eq!(health.docs, None);
+    }
+
+    #[test]
+    fn test_import_swagger2_yaml() {
+        let dir = TempDir::new().unwrap();
+        let spec = r#"
+swagger: "2.0"
+info:
+  title: Users
+host: api.example.com
+basePath: /v1
+schemes: [http]
+paths:
+  /users:
+    post:
+      summary: Create user
+      parameters:
+        - name: user
+          in: body
+          schema:
+            example: {name: ada}
+"#;
+
+        assert_eq!(import_openapi_spec(spec, dir.path(), None).unwrap(), (1, 1));
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".env.users")).unwrap(),
+            "baseUrl=http://api.example.com/v1\n"
+        );
+        let post = read_request(&dir.path().join("default/create-user.json"));
+        assert_eq!(post.method, HttpMethod::POST);
+        assert_eq!(post.headers["Content-Type"], "application/json");
+        assert!(post.body.contains("ada"));
+    }
+
+    #[test]
+    fn test_rejects_other_documents() {
+        let dir = TempDir::new().unwrap();
+        let err = import_openapi_spec(r#"{"info": {"name": "x"}, "item": []}"#, dir.path(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("Not an OpenAPI 3 or Swagger 2"));
+
+        let denied = HttpResponse {
+            status: 401,
+            status_text: "Unauthorized".to_string(),
+            headers: Vec::new(),
+            cookies: Vec::new(),
+            body: String::new(),
+            raw_bytes: None,
+            duration_ms: 0,
+            size_bytes: 0,
+            content_type: String::new(),
+            response_type: ResponseType::Empty,
+            lossy_utf8: false,
+        };
+        assert!(spec_from_response(&denied)
+            .unwrap_err()
+            .to_string()
+            .contains("401 Unauthorized"));
+    }
+}
@@ -1,6 +1,11 @@
 //! Postman Importer Module
 //!
 //! Converts Postman collection exports to Mercury JSON format.
+//!
+//! Postman's dynamic variables (`{{$guid}}`, `{{$timestamp}}`, ...) are renamed to
+//! Mercury's built-in equivalents. Anything Mercury can't evaluate, like nested
+//! variables or `{{#each}}` blocks, is left as written and the request is tagged
+//! `needs-review` so it's easy to find after the import.
 
 use crate::core::error::MercuryError;
 use serde::Deserialize;
@@ -11,7 +16,7 @@ use std::path::Path;
 /// Sanitizes a name for use as a filename or directory name.
 /// Converts to lowercase, replaces spaces with dashes, and removes
 /// characters that are invalid on Windows, macOS, or Linux filesystems.
-fn sanitize_filename(name: &str) -> String {
+pub(crate) fn sanitize_filename(name: &str) -> String {
     // Invalid chars: / \ : * ? " < > | and space
     let lower = name.to_lowercase();
     let mut result = String::with_capacity(lower.len());
@@ -172,6 +177,63 @@ struct PostmanVariable {
     value: Value,
 }
 
+/// Tag added to requests that still contain Postman syntax Mercury can't evaluate
+pub const NEEDS_REVIEW_TAG: &str = "needs-review";
+
+/// Postman dynamic variables and their Mercury names
+const DYNAMIC_VARIABLE_MAP: &[(&str, &str)] = &[
+    ("$guid", "$uuid"),
+    ("$randomUUID", "$uuid"),
+    ("$timestamp", "$timestamp"),
+    ("$isoTimestamp", "$isoTimestamp"),
+    ("$randomInt", "$randomInt"),
+];
+
+/// What an import produced
+#[derive(Debug, Default, PartialEq)]
+pub struct PostmanImport {
+    pub requests: usize,
+    pub environments: usize,
+    /// Requests tagged `needs-review`
+    pub needs_review: usize,
+}
+
+/// Renames Postman dynamic variables to Mercury's. Constructs Mercury can't
+/// evaluate are kept as written and added to `unsupported`.
+fn normalize_variables(text: &str, unsupported: &mut Vec<String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let inner_start = start + 2;
+        let Some(len) = rest[inner_start..].find("}}") else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let inner_end = inner_start + len;
+        let inner = &rest[inner_start..inner_end];
+        let original = &rest[start..inner_end + 2];
+        let name = inner.trim();
+
+        let mapped = DYNAMIC_VARIABLE_MAP
+            .iter()
+            .find(|(postman, _)| *postman == name)
+            .map(|(_, mercury)| *mercury);
+        if let Some(mercury) = mapped {
+            result.push_str(&format!("{{{{{}}}}}", mercury));
+        } else {
+            let is_unsupported =
+                inner.contains("{{") || name.starts_with(['$', '#', '/']) || name.is_empty();
+            if is_unsupported {
+                unsupported.push(original.to_string());
+            }
+            result.push_str(original);
+        }
+        rest = &rest[inner_end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
 /// Reconstructs a URL string from Postman's URL format.
 ///
 /// Postman URLs can be either:
@@ -239,24 +301,31 @@ fn reconstruct_url(url: &PostmanUrl) -> String {
 /// * `parent_dir` - The parent directory where this item should be created
 /// * `depth` - Current nesting depth (used for tracking recursion level)
 ///
-/// # Returns
-/// The number of requests processed (0 for empty folders, 1 for requests, sum of children for folders)
+/// * `report` - Counts of imported and flagged requests, updated in place
 ///
 /// # Behavior
 /// - If item contains a request: creates a .json file
 /// - If item contains sub-items: creates a folder and recursively processes children
-/// - If item is empty: returns 0
-fn process_item(item: &PostmanItem, parent_dir: &Path) -> Result<usize, MercuryError> {
+/// - If item is empty: nothing is written
+fn process_item(
+    item: &PostmanItem,
+    parent_dir: &Path,
+    report: &mut PostmanImport,
+) -> Result<(), MercuryError> {
     if let Some(request) = &item.request {
         // This is a request - create JSON file
         let file_name = format!("{}.json", sanitize_filename(&item.name));
         let file_path = parent_dir.join(&file_name);
+        let mut unsupported = Vec::new();
 
         // Build headers HashMap
         let mut headers = std::collections::HashMap::new();
         for header in &request.header {
             if !header.disabled {
-                headers.insert(header.key.clone(), header.value.clone());
+                headers.insert(
+                    normalize_variables(&header.key, &mut unsupported),
+                    normalize_variables(&header.value, &mut unsupported),
+                );
             }
         }
 
@@ -267,12 +336,22 @@ fn process_item(item: &PostmanItem, parent_dir: &Path) -> Result<usize, MercuryE
             String::new()
         };
 
+        let mut tags = Vec::new();
+        let url = normalize_variables(&reconstruct_url(&request.url), &mut unsupported);
+        let body = normalize_variables(&body, &mut unsupported);
+        if !unsupported.is_empty() {
+            tags.push(NEEDS_REVIEW_TAG.to_string());
+            report.needs_review += 1;
+        }
+
         // Create JsonRequest
         let json_request = crate::core::types::JsonRequest {
             method: crate::core::types::HttpMethod::from_str(&request.method).unwrap_or_default(),
-            url: reconstruct_url(&request.url),
+            url,
             headers,
             body,
+            tags,
+            ..Default::default()
         };
 
         // Serialize and write
@@ -283,7 +362,8 @@ fn process_item(item: &PostmanItem, parent_dir: &Path) -> Result<usize, MercuryE
             path: file_path.display().to_string(),
             reason: e.to_string(),
         })?;
-        Ok(1)
+        report.requests += 1;
+        Ok(())
     } else if !item.item.is_empty() {
         // This is a folder - create directory and recurse
         let folder_name = sanitize_filename(&item.name);
@@ -293,14 +373,13 @@ fn process_item(item: &PostmanItem, parent_dir: &Path) -> Result<usize, MercuryE
             reason: e.to_string(),
         })?;
 
-        let mut count = 0;
         for child in &item.item {
-            count += process_item(child, &folder_path)?;
+            process_item(child, &folder_path, report)?;
         }
-        Ok(count)
+        Ok(())
     } else {
         // Empty item
-        Ok(0)
+        Ok(())
     }
 }
 
@@ -311,7 +390,8 @@ fn process_item(item: &PostmanItem, parent_dir: &Path) -> Result<usize, MercuryE
 /// * `output_dir` - Directory where imported files will be created
 ///
 /// # Returns
-/// A tuple of (request_count, environment_count) on success, or an error message on failure
+/// The number of requests and environments created, and how many requests were
+/// tagged `needs-review`, or an error message on failure
 ///
 /// # Behavior
 /// - Parses the Postman collection JSON file
@@ -319,6 +399,7 @@ fn process_item(item: &PostmanItem, parent_dir: &Path) -> Result<usize, MercuryE
 /// - Extracts collection variables to a .env file (if any exist)
 /// - Handles nested folders with unlimited depth
 /// - Reconstructs URLs from Postman's object format
+/// - Renames Postman dynamic variables to Mercury's built-in ones
 ///
 /// # Errors
 /// Returns an error if:
@@ -328,7 +409,7 @@ fn process_item(item: &PostmanItem, parent_dir: &Path) -> Result<usize, MercuryE
 pub fn import_postman_collection(
     json_path: &Path,
     output_dir: &Path,
-) -> Result<(usize, usize), MercuryError> {
+) -> Result<PostmanImport, MercuryError> {
     let content = fs::read_to_string(json_path).map_err(|e| MercuryError::FileRead {
         path: json_path.display().to_string(),
         reason: e.to_string(),
@@ -338,7 +419,7 @@ pub fn import_postman_collection(
         .map_err(|e| MercuryError::PostmanImportError(e.to_string()))?;
 
     // Extract collection variables to .env file
-    let mut env_count = 0;
+    let mut report = PostmanImport::default();
     if !collection.variable.is_empty() {
         let collection_name = sanitize_filename(&collection.info.name);
         let env_path = output_dir.join(format!(".env.{}", collection_name));
@@ -362,16 +443,15 @@ pub fn import_postman_collection(
             path: env_path.display().to_string(),
             reason: e.to_string(),
         })?;
-        env_count = 1;
+        report.environments = 1;
     }
 
     // Process all items (requests and folders)
-    let mut request_count = 0;
     for item in &collection.item {
-        request_count += process_item(item, output_dir)?;
+        process_item(item, output_dir, &mut report)?;
     }
 
-    Ok((request_count, env_count))
+    Ok(report)
 }
 
 #[cfg(test)]
@@ -418,7 +498,11 @@ mod tests {
 
         let result = import_postman_collection(&file_path, &output_dir);
         assert!(result.is_ok());
-        let (req_count, env_count) = result.unwrap();
+        let PostmanImport {
+            requests: req_count,
+            environments: env_count,
+            ..
+        } = result.unwrap();
         assert_eq!(req_count, 1);
         assert_eq!(env_count, 0);
         // Check that file was created
@@ -460,7 +544,7 @@ mod tests {
 
         let result = import_postman_collection(&file_path, &output_dir);
         assert!(result.is_ok());
-        let (req_count, _) = result.unwrap();
+        let req_count = result.unwrap().requests;
         assert_eq!(req_count, 1);
 
         // Check that folder and file were created
@@ -496,7 +580,11 @@ mod tests {
 
         let result = import_postman_collection(&file_path, &output_dir);
         assert!(result.is_ok());
-        let (req_count, env_count) = result.unwrap();
+        let PostmanImport {
+            requests: req_count,
+            environments: env_count,
+            ..
+        } = result.unwrap();
         assert_eq!(req_count, 0);
         assert_eq!(env_count, 1);
 
@@ -549,7 +637,7 @@ mod tests {
 
         let result = import_postman_collection(&file_path, &output_dir);
         assert!(result.is_ok());
-        let (req_count, _) = result.unwrap();
+        let req_count = result.unwrap().requests;
         assert_eq!(req_count, 1);
 
         // Check nested folder structure
@@ -681,7 +769,11 @@ mod tests {
 
         let result = import_postman_collection(&file_path, &output_dir);
         assert!(result.is_ok());
-        let (req_count, env_count) = result.unwrap();
+        let PostmanImport {
+            requests: req_count,
+            environments: env_count,
+            ..
+        } = result.unwrap();
         assert_eq!(req_count, 3); // Login, List, Health
         assert_eq!(env_count, 1);
 
@@ -880,4 +972,73 @@ mod tests {
         assert!(content.contains("\"Active\": \"yes\""));
         assert!(!content.contains("\"Inactive\""));
     }
+
+    #[test]
+    fn test_normalizes_dynamic_variables() {
+        let dir = TempDir::new().unwrap();
+        let output_dir = dir.path().join("output");
+        fs::create_dir(&output_dir).unwrap();
+
+        let collection = r#"{
+            "info": { "name": "Dynamic" },
+            "item": [
+                {
+                    "name": "Create",
+                    "request": {
+                        "method": "POST",
+                        "header": [{ "key": "X-Trace", "value": "{{$randomUUID}}" }],
+                        "url": "{{baseUrl}}/users/{{$guid}}?ts={{ $timestamp }}",
+                        "body": { "mode": "raw", "raw": "{\"id\": \"{{$guid}}\"}" }
+                    }
+                },
+                {
+                    "name": "Nested",
+                    "request": {
+                        "method": "GET",
+                        "url": "{{host_{{env}}}}/items",
+                        "body": { "mode": "raw", "raw": "{{#each items}}{{$randomColor}}" }
+                    }
+                }
+            ]
+        }"#;
+        let file_path = create_temp_file(dir.path(), "dynamic.json", collection);
+
+        let report = import_postman_collection(&file_path, &output_dir).unwrap();
+        assert_eq!(
+            report,
+            PostmanImport {
+                requests: 2,
+                environments: 0,
+                needs_review: 1,
+            }
+        );
+
+        let read = |name: &str| {
+            crate::parser::parse_request_file(&fs::read_to_string(output_dir.join(name)).unwrap())
+                .unwrap()
+        };
+        let create = read("create.json");
+        assert_eq!(create.url, "{{baseUrl}}/users/{{$uuid}}?ts={{$timestamp}}");
+        assert_eq!(create.headers.get("X-Trace").unwrap(), "{{$uuid}}");
+        assert_eq!(create.body, r#"{"id": "{{$uuid}}"}"#);
+        assert!(create.tags.is_empty());
+
+        // Unsupported constructs are kept as written, not half-converted
+        let nested = read("nested.json");
+        assert_eq!(nested.url, "{{host_{{env}}}}/items");
+        assert_eq!(nested.body, "{{#each items}}{{$randomColor}}");
+        assert_eq!(nested.tags, vec![NEEDS_REVIEW_TAG]);
+    }
+
+    #[test]
+    fn test_normalize_variables_collects_unsupported() {
+        let mut unsupported = Vec::new();
+        let text = normalize_variables(
+            "{{a}} {{$guid}} {{/each}} {{b_{{c}}}} {{open",
+            &mut unsupported,
+        );
+
+        assert_eq!(text, "{{a}} {{$uuid}} {{/each}} {{b_{{c}}}} {{open");
+        assert_eq!(unsupported, vec!["{{/each}}", "{{b_{{c}}"]);
+    }
 }
@@ -17,6 +17,8 @@ use eframe::egui;
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 fn main() -> Result<(), eframe::Error> {
+    let window_workspace = core::windows::window_workspace(std::env::args());
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1200.0, 800.0])
@@ -148,7 +150,7 @@ fn main() -> Result<(), eframe::Error> {
             cc.egui_ctx
                 .set_zoom_factor(theme::Layout::DEFAULT_ZOOM_FACTOR);
 
-            Ok(Box::new(ui::app::MercuryApp::new(cc)))
+            Ok(Box::new(ui::app::MercuryApp::new(cc, window_workspace)))
         }),
     )
 }
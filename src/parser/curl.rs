@@ -69,57 +69,45 @@ pub fn parse_curl(curl_cmd: &str) -> Result<CurlRequest, MercuryError> {
         let token = &tokens[i];
 
         match token.as_str() {
-            "-X" | "--request" => {
-                if i + 1 < tokens.len() {
-                    method = HttpMethod::from_str(&tokens[i + 1]).unwrap_or(HttpMethod::GET);
-                    i += 1;
+            "-X" | "--request" if i + 1 < tokens.len() => {
+                method = HttpMethod::from_str(&tokens[i + 1]).unwrap_or(HttpMethod::GET);
+                i += 1;
+            }
+            "-H" | "--header" if i + 1 < tokens.len() => {
+                let header = &tokens[i + 1];
+                if let Some(pos) = header.find(':') {
+                    let name = header[..pos].trim().to_string();
+                    let value = header[pos + 1..].trim().to_string();
+                    headers.push((name, value));
                 }
+                i += 1;
             }
-            "-H" | "--header" => {
-                if i + 1 < tokens.len() {
-                    let header = &tokens[i + 1];
-                    if let Some(pos) = header.find(':') {
-                        let name = header[..pos].trim().to_string();
-                        let value = header[pos + 1..].trim().to_string();
-                        headers.push((name, value));
-                    }
-                    i += 1;
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--json" if i + 1 < tokens.len() => {
+                body = Some(tokens[i + 1].clone());
+                if method == HttpMethod::GET {
+                    method = HttpMethod::POST;
                 }
-            }
-            "-d" | "--data" | "--data-raw" | "--data-binary" | "--json" => {
-                if i + 1 < tokens.len() {
-                    body = Some(tokens[i + 1].clone());
-                    if method == HttpMethod::GET {
-                        method = HttpMethod::POST;
-                    }
-                    // --json also adds Content-Type header
-                    if token == "--json" {
-                        headers.push(("Content-Type".to_string(), "application/json".to_string()));
-                    }
-                    i += 1;
+                // --json also adds Content-Type header
+                if token == "--json" {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
                 }
+                i += 1;
             }
-            "-u" | "--user" => {
+            "-u" | "--user" if i + 1 < tokens.len() => {
                 // Basic auth: -u user:password
-                if i + 1 < tokens.len() {
-                    let credentials = &tokens[i + 1];
-                    use base64::Engine;
-                    let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
-                    headers.push(("Authorization".to_string(), format!("Basic {}", encoded)));
-                    i += 1;
-                }
+                let credentials = &tokens[i + 1];
+                use base64::Engine;
+                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                headers.push(("Authorization".to_string(), format!("Basic {}", encoded)));
+                i += 1;
             }
-            "-A" | "--user-agent" => {
-                if i + 1 < tokens.len() {
-                    headers.push(("User-Agent".to_string(), tokens[i + 1].clone()));
-                    i += 1;
-                }
+            "-A" | "--user-agent" if i + 1 < tokens.len() => {
+                headers.push(("User-Agent".to_string(), tokens[i + 1].clone()));
+                i += 1;
             }
-            "-b" | "--cookie" => {
-                if i + 1 < tokens.len() {
-                    headers.push(("Cookie".to_string(), tokens[i + 1].clone()));
-                    i += 1;
-                }
+            "-b" | "--cookie" if i + 1 < tokens.len() => {
+                headers.push(("Cookie".to_string(), tokens[i + 1].clone()));
+                i += 1;
             }
             "-I" | "--head" => {
                 method = HttpMethod::HEAD;
@@ -139,11 +127,9 @@ pub fn parse_curl(curl_cmd: &str) -> Result<CurlRequest, MercuryError> {
                 // Ignore these flags that take one argument
                 i += 1; // Skip the argument
             }
-            arg if !arg.starts_with('-') => {
+            arg if !arg.starts_with('-') && url.is_empty() => {
                 // Assume it's the URL
-                if url.is_empty() {
-                    url = arg.to_string();
-                }
+                url = arg.to_string();
             }
             _ => {
                 // Unknown flag, skip
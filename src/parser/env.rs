@@ -1,14 +1,96 @@
 //! Environment Parser Module
 //!
 //! Parses `.env` files and substitutes `{{variables}}` in request content.
+//! Built-in `{{$name}}` variables get a fresh value every time they're substituted.
 
-use std::collections::HashMap;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Built-in dynamic variables and what each one produces
+pub const DYNAMIC_VARIABLES: &[(&str, &str)] = &[
+    ("$uuid", "Random UUID (v4)"),
+    ("$timestamp", "Current Unix time in seconds"),
+    ("$isoTimestamp", "Current UTC time, ISO 8601"),
+    ("$randomInt", "Random integer from 0 to 1000"),
+];
+
+pub fn is_dynamic_variable(name: &str) -> bool {
+    DYNAMIC_VARIABLES.iter().any(|(n, _)| *n == name.trim())
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    // The system RNG only fails if the OS has none; zeros still make a valid value
+    let _ = SystemRandom::new().fill(&mut bytes);
+    bytes
+}
+
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// `2024-03-01T12:30:05.250Z` for milliseconds since the Unix epoch
+pub fn iso_timestamp(millis: u128) -> String {
+    let secs = (millis / 1000) as i64;
+    let (days, day_secs) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    // Days to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        day_secs / 3600,
+        day_secs % 3600 / 60,
+        day_secs % 60,
+        millis % 1000
+    )
+}
+
+/// A fresh value for a built-in variable, or `None` if `name` isn't one
+pub fn dynamic_value(name: &str) -> Option<String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    match name.trim() {
+        "$uuid" => Some(uuid_v4(random_bytes())),
+        "$timestamp" => Some(now.as_secs().to_string()),
+        "$isoTimestamp" => Some(iso_timestamp(now.as_millis())),
+        "$randomInt" => Some((u32::from_le_bytes(random_bytes()) % 1001).to_string()),
+        _ => None,
+    }
+}
 
 pub fn parse_env_file(path: &Path) -> Result<HashMap<String, String>, std::io::Error> {
     let content = fs::read_to_string(path)?;
-    let mut vars = HashMap::new();
+    Ok(parse_env_str(&content).into_iter().collect())
+}
+
+/// Parse `KEY=VALUE` lines from a string, preserving their order.
+/// Tolerates shell-style `export KEY=value` lines so snippets can be pasted as-is.
+pub fn parse_env_str(content: &str) -> Vec<(String, String)> {
+    let mut vars = Vec::new();
 
     for line in content.lines() {
         let line = line.trim();
@@ -20,7 +102,11 @@ pub fn parse_env_file(path: &Path) -> Result<HashMap<String, String>, std::io::E
 
         // Parse KEY=VALUE
         if let Some((key, value)) = line.split_once('=') {
-            let key = key.trim().to_string();
+            let key = key.trim();
+            let key = key.strip_prefix("export ").unwrap_or(key).trim();
+            if key.is_empty() {
+                continue;
+            }
 
             // Proper quote handling: only strip if both ends have matching quotes
             let value = value.trim();
@@ -33,11 +119,164 @@ pub fn parse_env_file(path: &Path) -> Result<HashMap<String, String>, std::io::E
                 value.to_string()
             };
 
-            vars.insert(key, value);
+            vars.push((key.to_string(), value));
+        }
+    }
+
+    vars
+}
+
+/// Merge `vars` into the `.env` file at `path`.
+/// Existing keys are rewritten in place, new keys are appended, comments are kept.
+pub fn serialize_env_file(path: &Path, vars: &[(String, String)]) -> Result<(), std::io::Error> {
+    let existing = if path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+    fs::write(path, merge_env_content(&existing, vars))
+}
+
+/// Render `vars` as `.env` lines, quoting values where needed
+pub fn format_env(vars: &[(String, String)]) -> String {
+    merge_env_content("", vars)
+}
+
+fn merge_env_content(existing: &str, vars: &[(String, String)]) -> String {
+    // Last assignment wins, matching how parse_env_file resolves duplicates
+    let updates: HashMap<&str, &str> = vars.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    let mut written = HashSet::new();
+    let mut lines = Vec::new();
+
+    for line in existing.lines() {
+        let key = parse_env_str(line).into_iter().next().map(|(k, _)| k);
+        match key.as_deref().and_then(|k| updates.get_key_value(k)) {
+            Some((key, value)) => {
+                // Drop repeated assignments of a key we already rewrote
+                if written.insert(*key) {
+                    lines.push(format_env_line(key, value));
+                }
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+
+    for (key, _) in vars {
+        if written.insert(key.as_str()) {
+            lines.push(format_env_line(key, updates[key.as_str()]));
         }
     }
 
-    Ok(vars)
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+fn format_env_line(key: &str, value: &str) -> String {
+    // Quote values that would otherwise lose whitespace or look like comments
+    let needs_quotes = value != value.trim() || value.contains('#') || value.contains('"');
+    if needs_quotes && !value.contains('\'') {
+        format!("{}='{}'", key, value)
+    } else if needs_quotes {
+        format!("{}=\"{}\"", key, value)
+    } else {
+        format!("{}={}", key, value)
+    }
+}
+
+/// A variable `.env.schema` expects every environment to set
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaVar {
+    pub name: String,
+    /// The `#` comment lines directly above it
+    pub description: String,
+    /// Written when the variable is added to an environment; may be empty
+    pub default: String,
+}
+
+/// Parse `.env.schema`: `KEY=default` lines as in a `.env` file, or a bare `KEY`
+/// for no default, each described by the comment lines directly above it
+pub fn parse_env_schema(content: &str) -> Vec<SchemaVar> {
+    let mut vars = Vec::new();
+    let mut comment: Vec<&str> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(text) = line.strip_prefix('#') {
+            comment.push(text.trim());
+            continue;
+        }
+
+        let line = if line.contains('=') {
+            line.to_string()
+        } else {
+            format!("{}=", line)
+        };
+        if let Some((name, default)) = parse_env_str(&line).into_iter().next() {
+            vars.push(SchemaVar {
+                name,
+                description: comment.join(" "),
+                default,
+            });
+        }
+        comment.clear();
+    }
+
+    vars
+}
+
+/// The schema's variables that `variables` doesn't define, in schema order
+pub fn missing_variables(
+    schema: &[SchemaVar],
+    variables: &HashMap<String, String>,
+) -> Vec<SchemaVar> {
+    schema
+        .iter()
+        .filter(|var| !variables.contains_key(&var.name))
+        .cloned()
+        .collect()
+}
+
+/// Where a variable of a layered environment gets its value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VarSource {
+    /// Only in the base `.env`
+    Inherited,
+    /// Only in the selected environment
+    Own,
+    /// In both; the selected environment's value wins
+    Overridden,
+    /// Decrypted from `.env.enc`, which wins over both
+    Secret,
+    /// From a `.folder.json` above the open request; any environment value wins
+    Folder,
+    /// Set for this session from the environment menu; wins over everything
+    Session,
+}
+
+/// Layer the selected environment over the base `.env`: base keys load first,
+/// then `env` replaces any it redefines. Returns the merged variables and where
+/// each one came from.
+pub fn merge_env_layers(
+    base: HashMap<String, String>,
+    env: HashMap<String, String>,
+) -> (HashMap<String, String>, HashMap<String, VarSource>) {
+    let mut sources: HashMap<String, VarSource> = base
+        .keys()
+        .map(|k| (k.clone(), VarSource::Inherited))
+        .collect();
+    for key in env.keys() {
+        let source = if sources.contains_key(key) {
+            VarSource::Overridden
+        } else {
+            VarSource::Own
+        };
+        sources.insert(key.clone(), source);
+    }
+
+    let mut merged = base;
+    merged.extend(env);
+    (merged, sources)
 }
 
 pub fn substitute_variables(text: &str, variables: &HashMap<String, String>) -> String {
@@ -48,6 +287,34 @@ pub fn substitute_variables(text: &str, variables: &HashMap<String, String>) ->
         result = result.replace(&pattern, value);
     }
 
+    if result.contains("{{$") {
+        result = substitute_dynamic(&result);
+    }
+    result
+}
+
+/// Replace each built-in `{{$name}}` with its own fresh value; others are left alone
+fn substitute_dynamic(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{$") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                match dynamic_value(&after[..end]) {
+                    Some(value) => result.push_str(&value),
+                    None => result.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
     result
 }
 
@@ -55,6 +322,27 @@ pub fn substitute_variables(text: &str, variables: &HashMap<String, String>) ->
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_merge_env_layers() {
+        let base: HashMap<String, String> = [("HOST", "localhost"), ("TIMEOUT", "30")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let env: HashMap<String, String> = [("HOST", "staging.example.com"), ("TOKEN", "abc")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let (merged, sources) = merge_env_layers(base, env);
+
+        assert_eq!(merged["HOST"], "staging.example.com");
+        assert_eq!(merged["TIMEOUT"], "30");
+        assert_eq!(merged["TOKEN"], "abc");
+        assert_eq!(sources["HOST"], VarSource::Overridden);
+        assert_eq!(sources["TIMEOUT"], VarSource::Inherited);
+        assert_eq!(sources["TOKEN"], VarSource::Own);
+    }
+
     #[test]
     fn test_substitute_variables() {
         let mut vars = HashMap::new();
@@ -66,4 +354,102 @@ mod tests {
 
         assert_eq!(output, "https://api.example.com/users?token=abc123");
     }
+
+    #[test]
+    fn test_substitute_dynamic_variables() {
+        let vars = HashMap::from([("id".to_string(), "7".to_string())]);
+        let output = substitute_variables("{{id}}/{{$uuid}}/{{$uuid}}/{{$nope}}", &vars);
+        let parts: Vec<&str> = output.split('/').collect();
+
+        assert_eq!(parts[0], "7");
+        assert_eq!(parts[1].len(), 36);
+        assert_eq!(&parts[1][14..15], "4");
+        // Every occurrence gets its own value
+        assert_ne!(parts[1], parts[2]);
+        assert_eq!(parts[3], "{{$nope}}");
+
+        let n: u32 = dynamic_value("$randomInt").unwrap().parse().unwrap();
+        assert!(n <= 1000);
+        assert!(is_dynamic_variable(" $timestamp "));
+    }
+
+    #[test]
+    fn test_iso_timestamp() {
+        assert_eq!(iso_timestamp(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(iso_timestamp(1_709_296_205_250), "2024-03-01T12:30:05.250Z");
+        assert_eq!(iso_timestamp(951_782_400_000), "2000-02-29T00:00:00.000Z");
+    }
+
+    #[test]
+    fn test_parse_env_str_handles_export_and_quotes() {
+        let input =
+            "# shared\nexport API_KEY=\"abc 123\"\nHOST='api.example.com'\n=orphan\nPORT=8080";
+        let vars = parse_env_str(input);
+
+        assert_eq!(
+            vars,
+            vec![
+                ("API_KEY".to_string(), "abc 123".to_string()),
+                ("HOST".to_string(), "api.example.com".to_string()),
+                ("PORT".to_string(), "8080".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_env_schema() {
+        let schema = parse_env_schema(
+            "# Gateway in front of the API\n# (with scheme)\nBASE_URL=http://localhost:8080\n\nAPI_KEY\n\n# Unused\n\nexport TENANT=\n",
+        );
+        assert_eq!(
+            schema,
+            vec![
+                SchemaVar {
+                    name: "BASE_URL".to_string(),
+                    description: "Gateway in front of the API (with scheme)".to_string(),
+                    default: "http://localhost:8080".to_string(),
+                },
+                SchemaVar {
+                    name: "API_KEY".to_string(),
+                    description: String::new(),
+                    default: String::new(),
+                },
+                SchemaVar {
+                    name: "TENANT".to_string(),
+                    description: String::new(),
+                    default: String::new(),
+                },
+            ]
+        );
+
+        // Blank values count as set
+        let vars: HashMap<String, String> = [("API_KEY", "")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let missing: Vec<String> = missing_variables(&schema, &vars)
+            .into_iter()
+            .map(|var| var.name)
+            .collect();
+        assert_eq!(missing, ["BASE_URL", "TENANT"]);
+    }
+
+    #[test]
+    fn test_merge_env_content_overwrites_and_appends() {
+        let existing = "# Environment variables\nHOST=old.example.com\nTOKEN=abc\n";
+        let vars = vec![
+            ("HOST".to_string(), "new.example.com".to_string()),
+            ("GREETING".to_string(), "hello world ".to_string()),
+        ];
+
+        let merged = merge_env_content(existing, &vars);
+
+        assert_eq!(
+            merged,
+            "# Environment variables\nHOST=new.example.com\nTOKEN=abc\nGREETING='hello world '\n"
+        );
+        let reparsed: HashMap<_, _> = parse_env_str(&merged).into_iter().collect();
+        assert_eq!(reparsed["GREETING"], "hello world ");
+        assert_eq!(reparsed["TOKEN"], "abc");
+    }
 }
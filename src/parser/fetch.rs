@@ -0,0 +1,154 @@
+//! Fetch Parser Module
+//!
+//! Parses the `fetch(url, options)` calls browsers produce with DevTools'
+//! "Copy as fetch". Chrome and Firefox both write the options as a JSON object,
+//! so everything after the URL is read as JSON rather than as JavaScript.
+
+use crate::core::error::MercuryError;
+use crate::core::types::HttpMethod;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub struct FetchRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+fn error(message: &str) -> MercuryError {
+    MercuryError::FetchParseError(message.to_string())
+}
+
+/// Strip `await` and the trailing `;`, leaving `fetch(...)`
+fn fetch_call(input: &str) -> &str {
+    let call = input.trim().trim_end_matches(';').trim_end();
+    call.strip_prefix("await ")
+        .map(str::trim_start)
+        .unwrap_or(call)
+}
+
+/// Whether `input` is a pasted fetch call rather than a URL
+pub fn is_fetch_call(input: &str) -> bool {
+    fetch_call(input).starts_with("fetch(")
+}
+
+/// Parse a `fetch(url, options)` call into a structured request
+pub fn parse_fetch(input: &str) -> Result<FetchRequest, MercuryError> {
+    let args = fetch_call(input)
+        .strip_prefix("fetch(")
+        .and_then(|args| args.strip_suffix(')'))
+        .ok_or_else(|| error("Expected fetch(url, options)"))?;
+
+    // The URL is the first argument, a quoted string
+    let mut values = serde_json::Deserializer::from_str(args).into_iter::<String>();
+    let url = match values.next() {
+        Some(Ok(url)) if !url.is_empty() => url,
+        _ => return Err(error("No URL found in fetch call")),
+    };
+    let rest = args[values.byte_offset()..].trim();
+
+    let options = match rest.strip_prefix(',').map(str::trim) {
+        None if rest.is_empty() => Value::Null,
+        Some("") => Value::Null,
+        Some(options) => serde_json::from_str(options)
+            .map_err(|e| MercuryError::FetchParseError(format!("Invalid options: {}", e)))?,
+        None => return Err(error("Expected a comma after the URL")),
+    };
+
+    let method = options
+        .get("method")
+        .and_then(|m| m.as_str())
+        .and_then(HttpMethod::from_str)
+        .unwrap_or(HttpMethod::GET);
+
+    let headers = options
+        .get("headers")
+        .and_then(|h| h.as_object())
+        .map(|headers| {
+            headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (name.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // `"body": null` is what Chrome writes for requests without one
+    let body = match options.get("body") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(other) => Some(other.to_string()),
+    };
+
+    Ok(FetchRequest {
+        method,
+        url,
+        headers,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chrome_post() {
+        let fetch = r#"fetch("https://api.example.com/users", {
+  "headers": {
+    "accept": "application/json",
+    "content-type": "application/json",
+    "authorization": "Bearer token123"
+  },
+  "referrer": "https://example.com/",
+  "referrerPolicy": "strict-origin-when-cross-origin",
+  "body": "{\"name\":\"John\"}",
+  "method": "POST",
+  "mode": "cors",
+  "credentials": "include"
+});"#;
+        let req = parse_fetch(fetch).unwrap();
+        assert_eq!(req.method, HttpMethod::POST);
+        assert_eq!(req.url, "https://api.example.com/users");
+        assert_eq!(req.headers.len(), 3);
+        assert!(req
+            .headers
+            .contains(&("authorization".to_string(), "Bearer token123".to_string())));
+        assert_eq!(req.body, Some(r#"{"name":"John"}"#.to_string()));
+    }
+
+    #[test]
+    fn test_null_body_and_defaults() {
+        let fetch = r#"fetch("https://api.example.com/users?page=2", {
+  "headers": {"accept": "*/*"},
+  "body": null,
+  "method": "GET"
+});"#;
+        let req = parse_fetch(fetch).unwrap();
+        assert_eq!(req.method, HttpMethod::GET);
+        assert_eq!(req.url, "https://api.example.com/users?page=2");
+        assert_eq!(req.body, None);
+
+        // Firefox prefixes `await`; a bare call has no options at all
+        let req =
+            parse_fetch(r#"await fetch("https://example.com/", {"method": "DELETE"});"#).unwrap();
+        assert_eq!(req.method, HttpMethod::DELETE);
+        let req = parse_fetch(r#"fetch("https://example.com/")"#).unwrap();
+        assert_eq!(req.method, HttpMethod::GET);
+        assert!(req.headers.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_fetch() {
+        assert!(is_fetch_call("await fetch(\"https://example.com\");"));
+        assert!(!is_fetch_call("https://example.com/fetch("));
+        assert!(parse_fetch("fetch()").is_err());
+        assert!(parse_fetch(r#"fetch("https://example.com", {method: "POST"})"#).is_err());
+    }
+}
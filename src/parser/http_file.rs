@@ -0,0 +1,448 @@
+//! HTTP File Parser Module
+//!
+//! Parses and writes REST Client style `.http` files, where several requests
+//! share one file separated by `###` lines. File-level `@variable = value`
+//! lines are kept on save; other comments are not.
+
+use crate::core::error::MercuryError;
+use crate::core::types::{HttpMethod, JsonRequest, SaveRule};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// A single request block from a `.http` file
+#[derive(Debug, Clone)]
+pub struct HttpFileRequest {
+    /// Label from `# @name`, or the text after `###`
+    pub name: Option<String>,
+    pub request: JsonRequest,
+}
+
+/// A `# @...` directive read from the comments above a request line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Directive {
+    pub name: &'static str,
+    pub syntax: &'static str,
+    pub description: &'static str,
+    pub example: &'static str,
+}
+
+/// Every directive the parser acts on; the Help window lists these too
+pub const DIRECTIVES: &[Directive] = &[
+    Directive {
+        name: "name",
+        syntax: "# @name <label>",
+        description: "Names the request in the sidebar and the file it's imported to",
+        example: "# @name create-user",
+    },
+    Directive {
+        name: "resolve",
+        syntax: "# @resolve <host>:<address>",
+        description: "Sends the request to a fixed address, like curl --resolve",
+        example: "# @resolve api.example.com:10.0.0.5",
+    },
+    Directive {
+        name: "expect-content-type",
+        syntax: "# @expect-content-type <type>",
+        description: "Flags a response with any other Content-Type",
+        example: "# @expect-content-type application/json",
+    },
+    Directive {
+        name: "delay",
+        syntax: "# @delay <duration>",
+        description: "Waits before sending, in ms or s, to reproduce timing bugs",
+        example: "# @delay 2s",
+    },
+    Directive {
+        name: "save",
+        syntax: "# @save <variable>=<path>",
+        description: "Keeps a value from a successful JSON response as a session variable",
+        example: "# @save TOKEN=$.access_token",
+    },
+];
+
+/// `TOKEN=$.access_token` (braces around the name allowed) as a save rule
+fn parse_save(value: &str) -> Option<SaveRule> {
+    let (variable, path) = value.split_once('=')?;
+    let variable = variable
+        .trim()
+        .trim_start_matches("{{")
+        .trim_end_matches("}}");
+    let (variable, path) = (variable.trim(), path.trim());
+    (!variable.is_empty() && !path.is_empty()).then(|| SaveRule {
+        variable: variable.to_string(),
+        path: path.to_string(),
+    })
+}
+
+/// Milliseconds in a `@delay` value: `500ms`, `2s`, or `1.5s`
+fn parse_delay(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (number, scale) = match value.strip_suffix("ms") {
+        Some(ms) => (ms, 1.0),
+        None => (value.strip_suffix('s').unwrap_or(value), 1000.0),
+    };
+    let amount: f64 = number.trim().parse().ok()?;
+    (amount >= 0.0).then(|| (amount * scale).round() as u64)
+}
+
+/// The directive a comment starts with, and the text after it
+fn parse_directive(comment: &str) -> Option<(&'static Directive, &str)> {
+    let rest = comment.strip_prefix('@')?;
+    let (word, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let directive = DIRECTIVES.iter().find(|d| d.name == word)?;
+    Some((directive, value.trim()))
+}
+
+/// The lines between `###` separators, each with the title after its `###`
+fn split_blocks(content: &str) -> Vec<(Option<String>, Vec<&str>)> {
+    let mut blocks: Vec<(Option<String>, Vec<&str>)> = vec![(None, Vec::new())];
+
+    for line in content.lines() {
+        if let Some(title) = line.trim_start().strip_prefix("###") {
+            let title = title.trim();
+            blocks.push(((!title.is_empty()).then(|| title.to_string()), Vec::new()));
+        } else if let Some((_, lines)) = blocks.last_mut() {
+            lines.push(line);
+        }
+    }
+    blocks
+}
+
+/// Split a `.http` file on `###` lines and parse every request block.
+/// Blocks without a request line (e.g. only comments) are skipped.
+pub fn parse_http_file(content: &str) -> Vec<HttpFileRequest> {
+    split_blocks(content)
+        .into_iter()
+        .filter_map(|(title, lines)| parse_block(&lines, title))
+        .collect()
+}
+
+/// The file's `@variable = value` lines, from above each block's request line
+fn file_variables(content: &str) -> Vec<String> {
+    split_blocks(content)
+        .iter()
+        .flat_map(|(_, lines)| {
+            lines
+                .iter()
+                .map(|line| line.trim())
+                .take_while(|line| {
+                    line.is_empty()
+                        || line.starts_with('@')
+                        || line.starts_with('#')
+                        || line.starts_with("//")
+                })
+                .filter(|line| line.starts_with('@'))
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// Render `requests` as a `.http` file: `variables` first, then each request
+/// after a `###` line, with its label as `# @name` and its settings as directives
+pub fn serialize_http_file(variables: &[String], requests: &[HttpFileRequest]) -> String {
+    let mut out = String::new();
+    for variable in variables {
+        out.push_str(variable);
+        out.push('\n');
+    }
+
+    for (index, entry) in requests.iter().enumerate() {
+        if index > 0 || !variables.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("###\n");
+        let request = &entry.request;
+        let directives = [
+            ("name", entry.name.clone()),
+            ("resolve", request.resolve.clone()),
+            ("expect-content-type", request.expect_content_type.clone()),
+            ("delay", request.delay_ms.map(|ms| format!("{}ms", ms))),
+            (
+                "save",
+                request
+                    .save
+                    .as_ref()
+                    .map(|rule| format!("{}={}", rule.variable, rule.path)),
+            ),
+        ];
+        for (name, value) in directives {
+            if let Some(value) = value {
+                out.push_str(&format!("# @{} {}\n", name, value));
+            }
+        }
+
+        out.push_str(&format!("{} {}\n", request.method.as_str(), request.url));
+        let mut headers: Vec<_> = request.headers.iter().collect();
+        headers.sort();
+        for (key, value) in headers {
+            out.push_str(&format!("{}: {}\n", key, value));
+        }
+        if !request.body.trim().is_empty() {
+            out.push('\n');
+            out.push_str(request.body.trim());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Replace the request at `index` (counting only blocks with a request line) in
+/// the `.http` file at `path`, writing every request back
+pub fn save_http_request(
+    path: &Path,
+    index: usize,
+    request: JsonRequest,
+) -> Result<(), MercuryError> {
+    let content = fs::read_to_string(path).map_err(|e| MercuryError::FileRead {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    })?;
+    let mut requests = parse_http_file(&content);
+    let entry = requests.get_mut(index).ok_or_else(|| {
+        MercuryError::HttpParseError(format!(
+            "{} no longer has request {}",
+            path.display(),
+            index + 1
+        ))
+    })?;
+    entry.request = request;
+
+    fs::write(
+        path,
+        serialize_http_file(&file_variables(&content), &requests),
+    )
+    .map_err(|e| MercuryError::FileWrite {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
+fn parse_block(lines: &[&str], title: Option<String>) -> Option<HttpFileRequest> {
+    let mut name = title;
+    let mut resolve = None;
+    let mut expect_content_type = None;
+    let mut delay_ms = None;
+    let mut save = None;
+    let mut rest = lines.iter();
+
+    // Leading comments and directives, up to the request line
+    let request_line = loop {
+        let line = rest.next()?.trim();
+        if line.is_empty() || line.starts_with('@') {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#').or_else(|| line.strip_prefix("//")) {
+            if let Some((directive, value)) = parse_directive(comment.trim()) {
+                match directive.name {
+                    "name" => name = Some(value.to_string()),
+                    "resolve" => resolve = Some(value.to_string()).filter(|v| !v.is_empty()),
+                    "expect-content-type" => {
+                        expect_content_type = Some(value.to_string()).filter(|v| !v.is_empty())
+                    }
+                    "delay" => delay_ms = parse_delay(value).filter(|ms| *ms > 0),
+                    "save" => save = parse_save(value),
+                    _ => {}
+                }
+            }
+            continue;
+        }
+        break line;
+    };
+
+    // `METHOD URL [HTTP/1.1]`, or just a URL for GET
+    let mut parts = request_line.split_whitespace();
+    let first = parts.next()?;
+    let (method, url) = match HttpMethod::from_str(first) {
+        Some(method) => (method, parts.next()?.to_string()),
+        None => (HttpMethod::GET, first.to_string()),
+    };
+
+    let mut headers = HashMap::new();
+    for line in rest.by_ref() {
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    let body = rest
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+
+    Some(HttpFileRequest {
+        name: name.filter(|n| !n.is_empty()),
+        request: JsonRequest {
+            method,
+            url,
+            headers,
+            body,
+            resolve,
+            expect_content_type,
+            delay_ms,
+            save,
+            ..Default::default()
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = r#"@host = https://api.example.com
+
+### List users
+GET {{host}}/users HTTP/1.1
+Accept: application/json
+
+###
+# @name create-user
+POST {{host}}/users
+Content-Type: application/json
+
+{
+  "name": "Ada"
+}
+
+### Comment-only block
+# nothing to send here
+"#;
+
+    #[test]
+    fn test_parse_http_file_splits_requests() {
+        let requests = parse_http_file(SAMPLE);
+        assert_eq!(requests.len(), 2);
+
+        assert_eq!(requests[0].name.as_deref(), Some("List users"));
+        assert_eq!(requests[0].request.method, HttpMethod::GET);
+        assert_eq!(requests[0].request.url, "{{host}}/users");
+        assert_eq!(
+            requests[0].request.headers.get("Accept"),
+            Some(&"application/json".to_string())
+        );
+        assert!(requests[0].request.body.is_empty());
+
+        assert_eq!(requests[1].name.as_deref(), Some("create-user"));
+        assert_eq!(requests[1].request.method, HttpMethod::POST);
+        assert_eq!(requests[1].request.body, "{\n  \"name\": \"Ada\"\n}");
+    }
+
+    #[test]
+    fn test_parse_bare_url_defaults_to_get() {
+        let requests = parse_http_file("https://example.com/health\n");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].request.method, HttpMethod::GET);
+        assert_eq!(requests[0].request.url, "https://example.com/health");
+        assert!(requests[0].name.is_none());
+    }
+
+    #[test]
+    fn test_parse_resolve_directive() {
+        let requests = parse_http_file(
+            "# @resolve api.example.com:10.0.0.5\n\
+             # @expect-content-type application/json\n\
+             # @delay 1.5s\n\
+             # @save {{TOKEN}} = $.data.token\n\
+             GET https://api.example.com/\n",
+        );
+        assert_eq!(
+            requests[0].request.save,
+            Some(SaveRule {
+                variable: "TOKEN".to_string(),
+                path: "$.data.token".to_string(),
+            })
+        );
+        assert_eq!(requests[0].request.delay_ms, Some(1500));
+        assert_eq!(
+            requests[0].request.resolve.as_deref(),
+            Some("api.example.com:10.0.0.5")
+        );
+        assert_eq!(
+            requests[0].request.expect_content_type.as_deref(),
+            Some("application/json")
+        );
+    }
+
+    #[test]
+    fn test_directive_examples_parse() {
+        for directive in DIRECTIVES {
+            let (parsed, value) = parse_directive(directive.example.trim_start_matches("# "))
+                .unwrap_or_else(|| panic!("{} example doesn't parse", directive.name));
+            assert_eq!(parsed, directive);
+            assert!(!value.is_empty());
+        }
+        assert!(parse_directive("@unknown value").is_none());
+    }
+
+    #[test]
+    fn test_parse_delay() {
+        assert_eq!(parse_delay("2s"), Some(2000));
+        assert_eq!(parse_delay("250ms"), Some(250));
+        assert_eq!(parse_delay("0.5 s"), Some(500));
+        assert_eq!(parse_delay("soon"), None);
+        assert_eq!(parse_delay("-1s"), None);
+    }
+
+    #[test]
+    fn test_serialize_http_file_round_trips() {
+        let requests = parse_http_file(SAMPLE);
+        let written = serialize_http_file(&file_variables(SAMPLE), &requests);
+
+        assert!(written.starts_with("@host = https://api.example.com\n\n###\n"));
+        assert!(written.contains("###\n# @name List users\nGET {{host}}/users\n"));
+        assert!(written.contains("# @name create-user\nPOST {{host}}/users\n"));
+
+        let reparsed = parse_http_file(&written);
+        assert_eq!(reparsed.len(), 2);
+        for (before, after) in requests.iter().zip(&reparsed) {
+            assert_eq!(before.name, after.name);
+            assert_eq!(
+                serde_json::to_value(&before.request).unwrap(),
+                serde_json::to_value(&after.request).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialize_keeps_directives() {
+        let content = "# @name login\n\
+                       # @delay 1.5s\n\
+                       # @save TOKEN=$.token\n\
+                       POST https://example.com/login\n";
+        let written = serialize_http_file(&[], &parse_http_file(content));
+        assert_eq!(
+            written,
+            "###\n# @name login\n# @delay 1500ms\n# @save TOKEN=$.token\nPOST https://example.com/login\n"
+        );
+    }
+
+    #[test]
+    fn test_save_http_request_replaces_one_block() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("users.http");
+        fs::write(&path, SAMPLE).unwrap();
+
+        let mut request = parse_http_file(SAMPLE)[1].request.clone();
+        request.body = "{\"name\": \"Grace\"}".to_string();
+        save_http_request(&path, 1, request).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        let requests = parse_http_file(&saved);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].request.url, "{{host}}/users");
+        assert_eq!(requests[1].name.as_deref(), Some("create-user"));
+        assert_eq!(requests[1].request.body, "{\"name\": \"Grace\"}");
+        assert!(saved.starts_with("@host = https://api.example.com\n"));
+
+        let missing = save_http_request(&path, 5, JsonRequest::default());
+        assert!(matches!(missing, Err(MercuryError::HttpParseError(_))));
+    }
+}
@@ -1,14 +1,24 @@
 //! Parser Module
 //!
-//! Parsers for different file formats: JSON request files, cURL commands, .env files.
+//! Parsers for different file formats: JSON request files, `.http` files, cURL commands, fetch calls, .env files, .proto files.
 
 pub mod curl;
 pub mod env;
+pub mod fetch;
+pub mod http_file;
+pub mod proto;
 pub mod request_file;
 
 // Re-export commonly used items
 pub use curl::parse_curl;
-pub use env::{parse_env_file, substitute_variables};
+pub use env::{
+    format_env, is_dynamic_variable, merge_env_layers, missing_variables, parse_env_file,
+    parse_env_schema, parse_env_str, serialize_env_file, substitute_variables, SchemaVar,
+    VarSource, DYNAMIC_VARIABLES,
+};
+pub use fetch::{is_fetch_call, parse_fetch};
+pub use http_file::{parse_http_file, save_http_request};
+pub use proto::parse_proto;
 pub use request_file::{parse_request_file, serialize_request_file};
 
 // Re-export HttpMethod from types for backward compatibility with existing imports
@@ -0,0 +1,656 @@
+//! Proto File Parser Module
+//!
+//! Minimal `.proto` parser for gRPC calls: messages, enums, maps, oneofs and services.
+//! Options, imports and reserved ranges are skipped; imported types are not resolved.
+
+use crate::core::error::MercuryError;
+use std::collections::HashMap;
+
+/// Protobuf scalar value types
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scalar {
+    Double,
+    Float,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Sint32,
+    Sint64,
+    Fixed32,
+    Fixed64,
+    Sfixed32,
+    Sfixed64,
+    Bool,
+    String,
+    Bytes,
+}
+
+impl Scalar {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "double" => Scalar::Double,
+            "float" => Scalar::Float,
+            "int32" => Scalar::Int32,
+            "int64" => Scalar::Int64,
+            "uint32" => Scalar::Uint32,
+            "uint64" => Scalar::Uint64,
+            "sint32" => Scalar::Sint32,
+            "sint64" => Scalar::Sint64,
+            "fixed32" => Scalar::Fixed32,
+            "fixed64" => Scalar::Fixed64,
+            "sfixed32" => Scalar::Sfixed32,
+            "sfixed64" => Scalar::Sfixed64,
+            "bool" => Scalar::Bool,
+            "string" => Scalar::String,
+            "bytes" => Scalar::Bytes,
+            _ => return None,
+        })
+    }
+}
+
+/// Resolved type of a field; message and enum names are fully qualified
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    Scalar(Scalar),
+    Message(String),
+    Enum(String),
+    /// `map<K, V>`, sent on the wire as repeated `{key = 1, value = 2}` entries
+    Map(Box<FieldType>, Box<FieldType>),
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldDef {
+    pub name: String,
+    pub number: u32,
+    pub field_type: FieldType,
+    pub repeated: bool,
+}
+
+impl FieldDef {
+    /// lowerCamelCase name used by the proto3 JSON mapping
+    pub fn json_name(&self) -> String {
+        let mut out = String::new();
+        let mut upper_next = false;
+        for ch in self.name.chars() {
+            if ch == '_' {
+                upper_next = true;
+            } else if upper_next {
+                out.extend(ch.to_uppercase());
+                upper_next = false;
+            } else {
+                out.push(ch);
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MessageDef {
+    pub fields: Vec<FieldDef>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EnumDef {
+    pub values: Vec<(String, i32)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MethodDef {
+    pub name: String,
+    pub input: String,
+    pub output: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServiceDef {
+    /// Fully qualified name, e.g. `helloworld.Greeter`
+    pub name: String,
+    pub methods: Vec<MethodDef>,
+}
+
+/// A parsed `.proto` file
+#[derive(Debug, Clone, Default)]
+pub struct ProtoFile {
+    pub messages: HashMap<String, MessageDef>,
+    pub enums: HashMap<String, EnumDef>,
+    pub services: Vec<ServiceDef>,
+}
+
+impl ProtoFile {
+    /// Find a service by full (`pkg.Greeter`) or short (`Greeter`) name
+    pub fn service(&self, name: &str) -> Option<&ServiceDef> {
+        self.services
+            .iter()
+            .find(|s| s.name == name || s.name.rsplit('.').next() == Some(name))
+    }
+}
+
+/// Field as written in the source, before type names are resolved
+struct RawField {
+    name: String,
+    number: u32,
+    type_name: String,
+    /// For maps: (key, value) type names
+    map: Option<(String, String)>,
+    repeated: bool,
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+    package: String,
+    raw_messages: Vec<(String, Vec<RawField>)>,
+    enums: HashMap<String, EnumDef>,
+    raw_services: Vec<(String, Vec<MethodDef>)>,
+}
+
+fn parse_error(message: impl Into<String>) -> MercuryError {
+    MercuryError::ProtoParseError(message.into())
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+/// Split source into identifiers, numbers, string literals and single-char symbols
+fn tokenize(source: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = source.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch.is_whitespace() {
+            i += 1;
+        } else if ch == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if ch == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if ch == '"' || ch == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != ch {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(chars[start..i.min(chars.len())].iter().collect());
+        } else if ch.is_alphanumeric() || ch == '_' || ch == '.' || ch == '-' || ch == '+' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '-' | '+'))
+            {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        } else {
+            tokens.push(ch.to_string());
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Result<String, MercuryError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| parse_error("Unexpected end of file"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), MercuryError> {
+        let token = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(parse_error(format!(
+                "Expected '{}' but found '{}'",
+                expected, token
+            )))
+        }
+    }
+
+    /// Skip to the end of the current statement, stepping over `{...}` aggregates
+    fn skip_statement(&mut self) -> Result<(), MercuryError> {
+        loop {
+            match self.next()?.as_str() {
+                ";" => return Ok(()),
+                "{" => self.skip_block()?,
+                _ => {}
+            }
+        }
+    }
+
+    /// Skip a `{...}` block whose opening brace was already consumed
+    fn skip_block(&mut self) -> Result<(), MercuryError> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()?.as_str() {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip `[deprecated = true, ...]` field options if present
+    fn skip_field_options(&mut self) -> Result<(), MercuryError> {
+        if self.peek() == Some("[") {
+            while self.next()? != "]" {}
+        }
+        Ok(())
+    }
+
+    fn parse_number(&mut self) -> Result<i64, MercuryError> {
+        let token = self.next()?;
+        let parsed = match token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+        {
+            Some(hex) => i64::from_str_radix(hex, 16).ok(),
+            None => token.parse().ok(),
+        };
+        parsed.ok_or_else(|| parse_error(format!("Expected a number but found '{}'", token)))
+    }
+
+    fn parse_file(&mut self) -> Result<(), MercuryError> {
+        while let Some(token) = self.peek() {
+            match token {
+                "package" => {
+                    self.pos += 1;
+                    self.package = self.next()?;
+                    self.expect(";")?;
+                }
+                "syntax" | "edition" | "import" | "option" => self.skip_statement()?,
+                "message" => {
+                    self.pos += 1;
+                    let scope = self.package.clone();
+                    self.parse_message(&scope)?;
+                }
+                "enum" => {
+                    self.pos += 1;
+                    let scope = self.package.clone();
+                    self.parse_enum(&scope)?;
+                }
+                "service" => {
+                    self.pos += 1;
+                    self.parse_service()?;
+                }
+                ";" => self.pos += 1,
+                other => return Err(parse_error(format!("Unexpected '{}'", other))),
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_message(&mut self, scope: &str) -> Result<(), MercuryError> {
+        let full_name = qualify(scope, &self.next()?);
+        self.expect("{")?;
+        let mut fields = Vec::new();
+        self.parse_message_body(&full_name, &mut fields)?;
+        self.raw_messages.push((full_name, fields));
+        Ok(())
+    }
+
+    /// Parse fields up to the closing brace; also used for `oneof` bodies
+    fn parse_message_body(
+        &mut self,
+        full_name: &str,
+        fields: &mut Vec<RawField>,
+    ) -> Result<(), MercuryError> {
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                "}" => return Ok(()),
+                ";" => {}
+                "message" => self.parse_message(full_name)?,
+                "enum" => self.parse_enum(full_name)?,
+                "oneof" => {
+                    self.next()?; // oneof name
+                    self.expect("{")?;
+                    self.parse_message_body(full_name, fields)?;
+                }
+                "option" | "reserved" | "extensions" | "extend" => {
+                    if token == "extend" {
+                        while self.next()? != "{" {}
+                        self.skip_block()?;
+                    } else {
+                        self.skip_statement()?;
+                    }
+                }
+                "map" => {
+                    self.expect("<")?;
+                    let key = self.next()?;
+                    self.expect(",")?;
+                    let value = self.next()?;
+                    self.expect(">")?;
+                    fields.push(self.parse_field_tail(String::new(), Some((key, value)), false)?);
+                }
+                "repeated" => {
+                    let type_name = self.next()?;
+                    fields.push(self.parse_field_tail(type_name, None, true)?);
+                }
+                "optional" | "required" => {
+                    let type_name = self.next()?;
+                    fields.push(self.parse_field_tail(type_name, None, false)?);
+                }
+                _ => fields.push(self.parse_field_tail(token, None, false)?),
+            }
+        }
+    }
+
+    /// `name = number [options];` after the type
+    fn parse_field_tail(
+        &mut self,
+        type_name: String,
+        map: Option<(String, String)>,
+        repeated: bool,
+    ) -> Result<RawField, MercuryError> {
+        let name = self.next()?;
+        self.expect("=")?;
+        let number = self.parse_number()?;
+        self.skip_field_options()?;
+        self.expect(";")?;
+        Ok(RawField {
+            name,
+            number: u32::try_from(number)
+                .map_err(|_| parse_error(format!("Invalid field number {}", number)))?,
+            type_name,
+            map,
+            repeated,
+        })
+    }
+
+    fn parse_enum(&mut self, scope: &str) -> Result<(), MercuryError> {
+        let full_name = qualify(scope, &self.next()?);
+        self.expect("{")?;
+        let mut def = EnumDef::default();
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                "}" => break,
+                ";" => {}
+                "option" | "reserved" => self.skip_statement()?,
+                _ => {
+                    self.expect("=")?;
+                    let number = self.parse_number()?;
+                    self.skip_field_options()?;
+                    self.expect(";")?;
+                    def.values.push((token, number as i32));
+                }
+            }
+        }
+        self.enums.insert(full_name, def);
+        Ok(())
+    }
+
+    fn parse_service(&mut self) -> Result<(), MercuryError> {
+        let name = self.next()?;
+        let full_name = qualify(&self.package, &name);
+        self.expect("{")?;
+        let mut methods = Vec::new();
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                "}" => break,
+                ";" => {}
+                "option" => self.skip_statement()?,
+                "rpc" => {
+                    let name = self.next()?;
+                    let (input, client_streaming) = self.parse_rpc_type()?;
+                    self.expect("returns")?;
+                    let (output, server_streaming) = self.parse_rpc_type()?;
+                    match self.next()?.as_str() {
+                        ";" => {}
+                        "{" => self.skip_block()?,
+                        other => return Err(parse_error(format!("Unexpected '{}'", other))),
+                    }
+                    methods.push(MethodDef {
+                        name,
+                        input,
+                        output,
+                        client_streaming,
+                        server_streaming,
+                    });
+                }
+                other => return Err(parse_error(format!("Unexpected '{}'", other))),
+            }
+        }
+        self.raw_services.push((full_name, methods));
+        Ok(())
+    }
+
+    /// `( [stream] Type )`
+    fn parse_rpc_type(&mut self) -> Result<(String, bool), MercuryError> {
+        self.expect("(")?;
+        let mut token = self.next()?;
+        let streaming = token == "stream";
+        if streaming {
+            token = self.next()?;
+        }
+        self.expect(")")?;
+        Ok((token, streaming))
+    }
+}
+
+/// Resolve a type reference the way protoc does: innermost scope first
+fn resolve_type(
+    scope: &str,
+    name: &str,
+    messages: &HashMap<String, Vec<RawField>>,
+    enums: &HashMap<String, EnumDef>,
+) -> Result<FieldType, MercuryError> {
+    if let Some(scalar) = Scalar::from_name(name) {
+        return Ok(FieldType::Scalar(scalar));
+    }
+
+    let lookup = |candidate: &str| {
+        if messages.contains_key(candidate) {
+            Some(FieldType::Message(candidate.to_string()))
+        } else if enums.contains_key(candidate) {
+            Some(FieldType::Enum(candidate.to_string()))
+        } else {
+            None
+        }
+    };
+
+    if let Some(absolute) = name.strip_prefix('.') {
+        return lookup(absolute).ok_or_else(|| parse_error(format!("Unknown type '{}'", name)));
+    }
+
+    let mut current = scope;
+    loop {
+        if let Some(found) = lookup(&qualify(current, name)) {
+            return Ok(found);
+        }
+        if current.is_empty() {
+            break;
+        }
+        current = current
+            .rsplit_once('.')
+            .map(|(parent, _)| parent)
+            .unwrap_or("");
+    }
+
+    Err(parse_error(format!(
+        "Unknown type '{}' (imported types are not supported)",
+        name
+    )))
+}
+
+/// Parse `.proto` source into resolved message, enum and service definitions
+pub fn parse_proto(source: &str) -> Result<ProtoFile, MercuryError> {
+    let mut parser = Parser {
+        tokens: tokenize(source),
+        pos: 0,
+        package: String::new(),
+        raw_messages: Vec::new(),
+        enums: HashMap::new(),
+        raw_services: Vec::new(),
+    };
+    parser.parse_file()?;
+
+    let raw_messages: HashMap<String, Vec<RawField>> = parser.raw_messages.into_iter().collect();
+    let enums = parser.enums;
+
+    let mut messages = HashMap::new();
+    for (full_name, raw_fields) in &raw_messages {
+        let mut fields = Vec::new();
+        for raw in raw_fields {
+            let field_type = match &raw.map {
+                Some((key, value)) => FieldType::Map(
+                    Box::new(resolve_type(full_name, key, &raw_messages, &enums)?),
+                    Box::new(resolve_type(full_name, value, &raw_messages, &enums)?),
+                ),
+                None => resolve_type(full_name, &raw.type_name, &raw_messages, &enums)?,
+            };
+            fields.push(FieldDef {
+                name: raw.name.clone(),
+                number: raw.number,
+                field_type,
+                repeated: raw.repeated,
+            });
+        }
+        messages.insert(full_name.clone(), MessageDef { fields });
+    }
+
+    let mut services = Vec::new();
+    for (name, methods) in parser.raw_services {
+        let mut resolved = Vec::new();
+        for mut method in methods {
+            for type_name in [&mut method.input, &mut method.output] {
+                match resolve_type(&parser.package, type_name, &raw_messages, &enums)? {
+                    FieldType::Message(full) => *type_name = full,
+                    _ => {
+                        return Err(parse_error(format!(
+                            "RPC type '{}' is not a message",
+                            type_name
+                        )))
+                    }
+                }
+            }
+            resolved.push(method);
+        }
+        services.push(ServiceDef {
+            name,
+            methods: resolved,
+        });
+    }
+
+    Ok(ProtoFile {
+        messages,
+        enums,
+        services,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GREETER: &str = r#"
+syntax = "proto3";
+package helloworld;
+
+option go_package = "example.com/hello";
+
+// The greeting service
+service Greeter {
+  rpc SayHello (HelloRequest) returns (HelloReply) {}
+  rpc StreamHellos (HelloRequest) returns (stream HelloReply);
+}
+
+message HelloRequest {
+  string name = 1;
+  repeated int32 lucky_numbers = 2 [packed = true];
+  map<string, int64> scores = 3;
+  Mood mood = 4;
+  oneof contact {
+    string email = 5;
+    Address address = 6;
+  }
+
+  message Address {
+    string city = 1;
+  }
+}
+
+/* Replies carry one message */
+message HelloReply {
+  string message = 1;
+  reserved 2, 3;
+}
+
+enum Mood {
+  MOOD_UNSPECIFIED = 0;
+  HAPPY = 1;
+}
+"#;
+
+    #[test]
+    fn test_parse_services_and_methods() {
+        let proto = parse_proto(GREETER).unwrap();
+        let service = proto.service("Greeter").unwrap();
+        assert_eq!(service.name, "helloworld.Greeter");
+        assert_eq!(service.methods.len(), 2);
+        assert_eq!(service.methods[0].input, "helloworld.HelloRequest");
+        assert_eq!(service.methods[0].output, "helloworld.HelloReply");
+        assert!(!service.methods[0].server_streaming);
+        assert!(service.methods[1].server_streaming);
+    }
+
+    #[test]
+    fn test_parse_message_fields() {
+        let proto = parse_proto(GREETER).unwrap();
+        let request = &proto.messages["helloworld.HelloRequest"];
+        assert_eq!(request.fields.len(), 6);
+
+        let lucky = &request.fields[1];
+        assert!(lucky.repeated);
+        assert_eq!(lucky.field_type, FieldType::Scalar(Scalar::Int32));
+        assert_eq!(lucky.json_name(), "luckyNumbers");
+
+        assert!(matches!(request.fields[2].field_type, FieldType::Map(_, _)));
+        assert_eq!(
+            request.fields[3].field_type,
+            FieldType::Enum("helloworld.Mood".to_string())
+        );
+        // Nested type resolved from the enclosing message's scope
+        assert_eq!(
+            request.fields[5].field_type,
+            FieldType::Message("helloworld.HelloRequest.Address".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unknown_type_is_an_error() {
+        let result = parse_proto("message A { google.protobuf.Timestamp at = 1; }");
+        assert!(matches!(result, Err(MercuryError::ProtoParseError(_))));
+    }
+}
@@ -20,8 +20,10 @@ pub fn serialize_request_file(request: &JsonRequest) -> Result<String, MercuryEr
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::types::HttpMethod;
-    use std::collections::HashMap;
+    use crate::core::types::{
+        GrpcCall, HttpMethod, ParamDoc, RequestDocs, RequestOverride, SaveRule,
+    };
+    use std::collections::{BTreeMap, HashMap};
 
     #[test]
     fn test_parse_simple_get() {
@@ -70,6 +72,7 @@ mod tests {
             url: "https://api.example.com/users".to_string(),
             headers,
             body: "{\"name\": \"John\"}".to_string(),
+            ..Default::default()
         };
 
         let json = serialize_request_file(&request).unwrap();
@@ -93,7 +96,41 @@ mod tests {
             method: HttpMethod::DELETE,
             url: "https://api.example.com/users/1".to_string(),
             headers,
-            body: String::new(),
+            body: "48 65 6c 6c 6f".to_string(),
+            body_hex: true,
+            tags: vec!["smoke".to_string(), "auth".to_string()],
+            grpc: Some(GrpcCall {
+                proto: "protos/users.proto".to_string(),
+                service: "users.Users".to_string(),
+                method: "Delete".to_string(),
+            }),
+            resolve: Some("10.0.0.5".to_string()),
+            timeout_secs: Some(5),
+            follow_redirects: Some(false),
+            delay_ms: Some(2000),
+            expect_content_type: Some("application/json".to_string()),
+            save: Some(SaveRule {
+                variable: "TOKEN".to_string(),
+                path: "$.data.token".to_string(),
+            }),
+            overrides: BTreeMap::from([(
+                "staging".to_string(),
+                RequestOverride {
+                    headers: HashMap::from([("X-Debug".to_string(), "true".to_string())]),
+                    body: None,
+                },
+            )]),
+            docs: Some(RequestDocs {
+                summary: "Delete a user".to_string(),
+                description: String::new(),
+                params: vec![ParamDoc {
+                    name: "id".to_string(),
+                    location: "path".to_string(),
+                    required: true,
+                    kind: "integer".to_string(),
+                    description: String::new(),
+                }],
+            }),
         };
 
         let json = serialize_request_file(&original).unwrap();
@@ -103,5 +140,32 @@ mod tests {
         assert_eq!(parsed.url, original.url);
         assert_eq!(parsed.headers, original.headers);
         assert_eq!(parsed.body, original.body);
+        assert!(parsed.body_hex);
+        assert_eq!(parsed.tags, original.tags);
+        assert_eq!(parsed.grpc, original.grpc);
+        assert_eq!(parsed.resolve, original.resolve);
+        assert_eq!(parsed.timeout_secs, original.timeout_secs);
+        assert_eq!(parsed.follow_redirects, original.follow_redirects);
+        assert_eq!(parsed.delay_ms, original.delay_ms);
+        assert_eq!(parsed.expect_content_type, original.expect_content_type);
+        assert_eq!(parsed.save, original.save);
+        assert_eq!(parsed.overrides, original.overrides);
+        assert_eq!(parsed.docs, original.docs);
+        assert!(json.contains(r#""in": "path""#));
+    }
+
+    #[test]
+    fn test_empty_tags_not_serialized() {
+        let request = JsonRequest {
+            url: "https://api.example.com".to_string(),
+            ..Default::default()
+        };
+
+        let json = serialize_request_file(&request).unwrap();
+        assert!(!json.contains("tags"));
+        assert!(!json.contains("grpc"));
+        assert!(!json.contains("overrides"));
+        assert!(!json.contains("body_hex"));
+        assert!(parse_request_file(&json).unwrap().tags.is_empty());
     }
 }
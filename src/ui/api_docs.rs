@@ -0,0 +1,157 @@
+//! API Docs Module
+//!
+//! A Swagger UI-style reference for the workspace: every request that carries
+//! docs, with its summary, description, and parameters. "Try it" opens the
+//! request in the editor, ready to fill in and send.
+
+use super::app::MercuryApp;
+use super::components::{method_badge, show_modal};
+use super::theme::{Colors, FontSize, Spacing};
+use crate::core::collection;
+use crate::core::types::{JsonRequest, RequestDocs};
+use egui::{self, Context, RichText, Ui};
+use std::path::Path;
+
+impl MercuryApp {
+    /// Read the docs of every request in the workspace and show them
+    pub fn open_api_docs(&mut self) {
+        let paths = collection::request_paths(&self.collection_tree);
+        self.api_docs = collection::documented_requests(&paths);
+        self.show_api_docs = true;
+    }
+
+    pub fn render_api_docs_modal(&mut self, ctx: &Context) {
+        if !self.show_api_docs {
+            return;
+        }
+
+        let workspace = self.workspace_path.clone();
+        let docs = &self.api_docs;
+        let mut try_it = None;
+
+        self.show_api_docs = show_modal(ctx, "API Docs", self.show_api_docs, |ui, open| {
+            if docs.is_empty() {
+                ui.label(
+                    RichText::new(
+                        "No requests here have docs. Import an OpenAPI spec to browse its operations.",
+                    )
+                    .color(Colors::TEXT_SECONDARY),
+                );
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(480.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    let mut folder = None;
+                    for (i, (path, request)) in docs.iter().enumerate() {
+                        let Some(request_docs) = &request.docs else {
+                            continue;
+                        };
+
+                        // A heading for each folder, as in the sidebar
+                        let parent = path
+                            .parent()
+                            .zip(workspace.as_deref())
+                            .and_then(|(parent, root)| parent.strip_prefix(root).ok());
+                        if parent != folder {
+                            folder = parent;
+                            folder_heading(ui, parent);
+                        }
+
+                        if operation_docs(ui, i, request, request_docs) {
+                            try_it = Some(path.clone());
+                            *open = false;
+                        }
+                    }
+                });
+
+            ui.add_space(Spacing::MD);
+            if ui.button("Close").clicked() {
+                *open = false;
+            }
+        });
+
+        if let Some(path) = try_it {
+            self.load_file(&path);
+        }
+    }
+}
+
+fn folder_heading(ui: &mut Ui, folder: Option<&Path>) {
+    let Some(name) = folder.filter(|f| !f.as_os_str().is_empty()) else {
+        return;
+    };
+    ui.add_space(Spacing::SM);
+    ui.label(
+        RichText::new(name.display().to_string())
+            .size(FontSize::SM)
+            .strong()
+            .color(Colors::TEXT_MUTED),
+    );
+}
+
+/// One operation: method and path, summary, description, and a parameter table.
+/// Returns whether "Try it" was clicked.
+fn operation_docs(ui: &mut Ui, index: usize, request: &JsonRequest, docs: &RequestDocs) -> bool {
+    let mut try_it = false;
+
+    ui.add_space(Spacing::SM);
+    ui.horizontal(|ui| {
+        method_badge(ui, request.method.as_str());
+        let path = request.url.trim_start_matches("{{baseUrl}}");
+        ui.label(RichText::new(path).monospace().strong());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            try_it = ui
+                .button("Try it")
+                .on_hover_text("Open this request in the editor")
+                .clicked();
+        });
+    });
+
+    if !docs.summary.is_empty() {
+        ui.label(RichText::new(&docs.summary).strong());
+    }
+    if !docs.description.is_empty() {
+        ui.label(
+            RichText::new(&docs.description)
+                .size(FontSize::SM)
+                .color(Colors::TEXT_SECONDARY),
+        );
+    }
+
+    if !docs.params.is_empty() {
+        ui.add_space(Spacing::XS);
+        egui::Grid::new(("api_docs_params", index))
+            .num_columns(4)
+            .spacing([Spacing::MD, Spacing::XS])
+            .show(ui, |ui| {
+                for param in &docs.params {
+                    let (name, hint) = if param.required {
+                        (format!("{} *", param.name), "Required")
+                    } else {
+                        (param.name.clone(), "Optional")
+                    };
+                    ui.label(RichText::new(name).size(FontSize::SM).monospace())
+                        .on_hover_text(hint);
+                    for text in [&param.location, &param.kind] {
+                        ui.label(
+                            RichText::new(text)
+                                .size(FontSize::SM)
+                                .color(Colors::TEXT_MUTED),
+                        );
+                    }
+                    ui.label(
+                        RichText::new(&param.description)
+                            .size(FontSize::SM)
+                            .color(Colors::TEXT_SECONDARY),
+                    );
+                    ui.end_row();
+                }
+            });
+    }
+
+    ui.add_space(Spacing::SM);
+    ui.separator();
+    try_it
+}
@@ -8,22 +8,50 @@
 //! - UI state and rendering dispatch
 //! - Session persistence (state, history, recent requests)
 
+use crate::core::baseline::{self, BaselineDiff};
+use crate::core::codegen::{
+    generate_curl, generate_markdown, mask_literal_secrets, MarkdownOptions,
+};
+use crate::core::collection;
+use crate::core::env_compare::EnvComparison;
+use crate::core::examples;
+use crate::core::folder_defaults::{self, InheritedDefaults};
+use crate::core::grpc;
+use crate::core::keybindings::Action;
+use crate::core::keychain::{self, KeychainRef};
 use crate::core::persistence;
+use crate::core::request_log::{LogEntry, RequestLogger};
+use crate::core::runner::{self, FolderRun, RunEntry, RunStatus};
+use crate::core::search::{ContentHit, ContentIndex};
+use crate::core::secrets;
 use crate::core::types::{
-    AppState, CollectionItem, JsonRequest, RecentRequest, Request, Response, TimelineEntry,
-    TimelineSummary,
+    AppState, CollapsedPanel, CollectionItem, GrpcCall, HttpFileEntry, JsonRequest, RecentRequest,
+    Request, RequestDefaults, RequestDocs, RequestOverride, Response, ResponseView, SaveRule,
+    Settings, TimelineEntry, TimelineSummary, Timeouts,
 };
+use crate::core::windows;
 use crate::core::{execute_request, HttpResponse, MercuryError};
+use crate::importer::detect::{self, ImportKind};
+use crate::importer::files::{self as workspace_files, AddReport};
 use crate::parser::{
-    parse_env_file, parse_request_file, serialize_request_file, substitute_variables, HttpMethod,
+    format_env, merge_env_layers, missing_variables, parse_env_file, parse_env_schema,
+    parse_env_str, parse_http_file, parse_proto, parse_request_file, save_http_request,
+    serialize_env_file, serialize_request_file, substitute_variables, HttpMethod, SchemaVar,
+    VarSource,
+};
+use crate::ui::components::{
+    menu_button, modal_input_field, popup_menu, sending_bar, show_modal, AccessibleButton,
+    DisplayBody, ResponsePreview, Toast, ToastAction, ToastEvent, ToastQueue,
 };
-use crate::ui::components::{menu_button, modal_input_field, popup_menu, show_modal};
 use crate::ui::icons::Icons;
+use crate::ui::selection::TreeSelection;
+use crate::ui::shortcuts::{combo_keys, combo_label, combo_pressed};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use eframe::egui;
 use notify_debouncer_mini::new_debouncer;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
@@ -34,41 +62,158 @@ pub struct MercuryApp {
     pub workspace_path: Option<PathBuf>,
     pub workspace_name: String,
     pub collection_tree: Vec<CollectionItem>,
+    pub settings: Settings,
+    /// Set when the workspace ships a `.mercury.json`; wins over `settings.request_defaults`
+    pub workspace_request_defaults: Option<RequestDefaults>,
 
     pub current_file: Option<PathBuf>,
+    /// Which request of `current_file` is open when it's a `.http` file
+    pub current_block: Option<usize>,
+    /// Opened request files, most recent first
+    pub recent_files: Vec<PathBuf>,
     pub method: HttpMethod,
     pub url: String,
     pub query_params: Vec<crate::utils::QueryParam>,
-    pub params_text: String,  // Text representation for bulk edit
+    pub params_text: String, // Text representation for bulk edit
+    /// Form-urlencoded body fields, disabled ones included; only enabled ones are in the body
+    pub form_fields: Vec<crate::utils::QueryParam>,
+    /// Form-urlencoded body as `key=value` lines, for the fields editor
+    pub form_fields_text: String,
+    pub form_fields_bulk_edit: bool,
+    /// Edit a form-urlencoded body as raw text instead of fields
+    pub form_body_raw: bool,
+    /// JSON Patch builder rows, and the body they were last synced with; a body
+    /// that differs was edited elsewhere and is parsed again
+    pub json_patch_ops: Vec<crate::core::patch::PatchOp>,
+    pub json_patch_body: String,
+    /// `json_patch_body` checked as a JSON Patch, so it isn't checked every frame
+    pub json_patch_check: Result<(), String>,
+    /// `json_patch_body` couldn't be read into rows, so it's edited as text
+    pub json_patch_unparsed: bool,
+    /// Edit a JSON Patch body as raw text instead of rows
+    pub json_patch_raw: bool,
     pub headers_text: String, // Single source of truth - includes Authorization header
     pub body_text: String,
+    /// Bumped on every change to `body_text`, so caches of it can be keyed on it
+    pub body_generation: u64,
+    /// `(generation, len, (lines, chars))` for the counts under the body editor
+    pub body_counts_cache: Option<(u64, usize, (usize, usize))>,
+    /// The body is hex digits, sent as raw bytes
+    pub body_hex: bool,
+    /// JSON or XML found on the clipboard when the empty body editor got focus,
+    /// pretty-printed and offered as the body
+    pub clipboard_body: Option<(String, crate::utils::BodyFormat)>,
+    /// A clipboard read was requested; its `Event::Paste` arrives next frame
+    pub clipboard_probe: bool,
+    pub request_tags: Vec<String>,
+    /// gRPC target of the current request; `None` sends plain HTTP
+    pub grpc_call: Option<GrpcCall>,
+    /// IP to connect to instead of resolving the URL's host (empty = normal DNS)
+    pub resolve_override: String,
+    /// Content-Type the response should have; empty checks nothing
+    pub expect_content_type: String,
+    /// Request's own timeout and redirect handling; `None` uses Settings
+    pub timeout_override: Option<u64>,
+    pub follow_redirects_override: Option<bool>,
+    /// Milliseconds to wait before sending; `None` sends right away
+    pub send_delay_ms: Option<u64>,
+    /// Documentation kept from an OpenAPI import, written back on save
+    pub request_docs: Option<RequestDocs>,
+    /// "On success, save `save_path` to `{{save_variable}}`"; both empty saves nothing
+    pub save_path: String,
+    pub save_variable: String,
+    /// Per-environment overrides from the request file; edited in the file itself
+    pub request_overrides: BTreeMap<String, RequestOverride>,
+    pub current_examples: Vec<String>, // Example names for the open request
     // Auth UI helpers (ephemeral - populated from headers_text)
     pub auth_username: String,
     pub auth_password: String,
     pub auth_token: String,
 
     pub response: Option<HttpResponse>,
-    pub response_view_raw: bool,
-    pub show_response_headers: bool,
-    pub show_response_cookies: bool,
+    pub response_view: ResponseView,
+    /// Show `\uXXXX` escapes in the Pretty JSON view as the characters themselves
+    pub unescape_unicode: bool,
+    pub response_tab: ResponseTab,
+    pub show_response_extract: bool,
+    /// Show the inferred structure of a JSON response instead of its body
+    pub show_response_inspect: bool,
+    /// Show binary responses as a hex dump instead of the placeholder
+    pub show_response_hex: bool,
+    pub hex_offset: usize,
+    pub hex_offset_text: String,
+    pub extract_path: String,
     // Cached formatted response to avoid cloning every frame
-    pub formatted_response_cache: Option<String>,
+    pub formatted_response_cache: Option<DisplayBody>,
+    pub response_shape_cache: Option<crate::utils::JsonShape>,
+    /// The JSON body parsed once for Extract; `Some(None)` if it isn't JSON
+    pub response_json_cache: Option<Option<serde_json::Value>>,
+    /// Error read from a framed gRPC-Web/Connect body; `Some(None)` once checked
+    pub grpc_error_cache: Option<Option<crate::core::grpc::GrpcErrorDetails>>,
+    /// NDJSON records as `(line, pretty)`, see `ndjson_records_view`
+    pub ndjson_cache: Option<Vec<(String, Option<String>)>>,
+    pub response_preview_cache: Option<ResponsePreview>,
+    /// Comparison with the baseline: outer None until computed, inner None when the
+    /// request has no baseline
+    pub baseline_cache: Option<Option<BaselineDiff>>,
+    /// When a 429's `Retry-After` window ends (egui time)
+    pub rate_limit_until: Option<f64>,
+    /// Resend the request once the `Retry-After` window ends
+    pub auto_resend_rate_limited: bool,
 
     pub env_files: Vec<String>,
+    /// Variables each environment file defines, filled in as the environment
+    /// menu shows them; `None` if the file couldn't be read. Cleared on changes.
+    pub env_var_counts: HashMap<String, Option<usize>>,
     pub selected_env: usize,
     pub env_variables: HashMap<String, String>,
+    /// Whether each variable comes from the base `.env`, the selected file, or both
+    pub env_sources: HashMap<String, VarSource>,
+    /// Variables `.env.schema` lists that the selected environment doesn't set
+    pub missing_env_vars: Vec<SchemaVar>,
+    /// Headers and variables the open request gets from `.folder.json` files
+    pub folder_defaults: InheritedDefaults,
+    /// The request `folder_defaults` was loaded for
+    folder_defaults_for: Option<PathBuf>,
+    /// Temporary values layered over the environment; never written to disk
+    pub session_overrides: Vec<(String, String)>,
+    /// Keep `session_overrides` when switching environments
+    pub pin_session_overrides: bool,
+    pub show_session_overrides_dialog: bool,
+    pub session_overrides_text: String,
 
     pub search_query: String,
+    /// Search request URLs, headers, and bodies instead of names
+    pub search_contents: bool,
+    pub content_index: ContentIndex,
+    /// Set when the tree is rebuilt; the index catches up on the next content search
+    pub content_index_stale: bool,
+    /// Results for the query they were found with
+    pub content_hits: Option<(String, Vec<ContentHit>)>,
     pub show_shortcuts: bool,
     pub selected_tab: usize,
     pub focus_mode: bool,
+    pub collapsed_panel: Option<CollapsedPanel>,
     pub headers_bulk_edit: bool, // Toggle between key-value and bulk edit
     pub params_bulk_edit: bool,  // Toggle between key-value and bulk edit for params
 
     pub timeline: Vec<TimelineSummary>,
     pub timeline_search: String,
+    /// Response previews for hovered history rows, keyed by `timestamp.to_bits()`
+    pub history_previews: HashMap<u64, String>,
+    /// Previews being read on a worker, so a row hovered for a while asks once
+    history_previews_loading: HashSet<u64>,
+    history_preview_tx: Sender<(u64, String)>,
+    history_preview_rx: Receiver<(u64, String)>,
+    /// History row under the pointer and when the hover started
+    pub history_hover: Option<(u64, f64)>,
     pub show_timeline: bool,
     pub history_loaded: bool,
+    /// Latest status in history for each `METHOD url`, for the sidebar badges
+    pub last_status: HashMap<String, u16>,
+    /// Runs of the open request, keyed by `status_key`; cleared whenever the
+    /// timeline changes
+    pub run_stats_cache: Option<(String, Option<RunStats>)>,
 
     pub recent_requests: Vec<RecentRequest>,
     pub recent_expanded: bool,
@@ -83,31 +228,169 @@ pub struct MercuryApp {
     pub new_folder_name: String,
     pub show_new_env_dialog: bool,
     pub new_env_name: String,
+    pub show_paste_env_dialog: bool,
+    pub paste_env_text: String,
+    pub show_duplicate_dialog: bool,
+    pub duplicate_name: String,
+    pub show_save_example_dialog: bool,
+    pub example_name: String,
+    pub show_set_variable_dialog: bool,
+    pub variable_name: String,
+    pub variable_value: String,
+    /// Asks before response JSON replaces a request body that isn't empty
+    pub show_replace_body_confirm: bool,
+    pub pending_request_body: String,
+    /// Closing the window was held back because work is still running
+    pub show_quit_confirm: bool,
+    /// The user chose to quit; the next close request goes through
+    pub quit_confirmed: bool,
+    /// Started with `--window` by another window; the saved session and settings
+    /// aren't this one's to write
+    pub extra_window: bool,
+    /// Set once the window that opened this one has quit
+    opener_closed: Arc<AtomicBool>,
+    /// Windows opened from this one, with the workspace each was opened on
+    child_windows: Vec<(PathBuf, std::process::Child)>,
+    pub should_open_new_window: bool,
+    window_tx: Sender<PathBuf>,
+    window_rx: Receiver<PathBuf>,
+    /// The gRPC tab's Browse button; the file dialog runs on its own thread
+    pub should_pick_proto: bool,
+    proto_tx: Sender<PathBuf>,
+    proto_rx: Receiver<PathBuf>,
+    pub show_keychain_dialog: bool,
+    pub keychain_service: String,
+    pub keychain_account: String,
+    pub keychain_secret: String,
+    pub keychain_variable: String,
+    pub show_openapi_url_dialog: bool,
+    pub openapi_url: String,
+    /// Optional `Name: value` header sent when fetching the spec
+    pub openapi_header: String,
+    /// Variables decrypted from `.env.enc`; kept in memory only
+    pub secret_variables: Vec<(String, String)>,
+    /// Passphrase that unlocked `.env.enc`, for re-encrypting on change. Never saved.
+    secrets_passphrase: Option<String>,
+    pub show_unlock_secrets_dialog: bool,
+    pub show_secret_dialog: bool,
+    pub secret_name: String,
+    pub secret_value: String,
+    pub secrets_passphrase_input: String,
+    pub secrets_passphrase_confirm: String,
+    pub secrets_error: Option<String>,
+    /// Key derivation is slow on purpose, so unlocking runs on a worker thread.
+    /// Results carry the secrets file, the passphrase, and the plaintext.
+    pub unlocking_secrets: bool,
+    unlock_tx: Sender<(PathBuf, String, Result<String, MercuryError>)>,
+    unlock_rx: Receiver<(PathBuf, String, Result<String, MercuryError>)>,
+    /// Encrypting is just as slow, so saving a secret runs on a worker too
+    pub saving_secret: bool,
+    secret_tx: Sender<Result<SavedSecret, MercuryError>>,
+    secret_rx: Receiver<Result<SavedSecret, MercuryError>>,
+    pub show_tags_dialog: bool,
+    pub tags_text: String,
     pub show_delete_confirm: bool,
     pub delete_target: Option<PathBuf>,
+    /// Items picked with Cmd/Shift+click for bulk actions
+    pub tree_selection: TreeSelection,
+    pub show_bulk_delete_confirm: bool,
+    pub show_move_dialog: bool,
+    pub move_destination: Option<PathBuf>,
+    pub show_add_dialog: bool,
+    /// Folder that "Add to Workspace" copies into
+    pub add_destination: Option<PathBuf>,
+    /// Picked files and folders still to be added, in order
+    pub add_queue: Vec<PathBuf>,
+    add_report: AddReport,
+    /// The head of `add_queue` collides with an existing entry and needs a new name
+    pub show_add_rename_dialog: bool,
+    pub add_rename_text: String,
 
     pub should_create_new_request: bool,
     pub should_execute_request: bool,
+    pub should_retry_last: bool,
+    /// The most recent request as it went out, for "Retry last"
+    pub last_sent: Option<SentRequest>,
+    /// What produced the response on screen; None for saved examples
+    pub response_request: Option<ResponseRequest>,
     pub should_open_folder_dialog: bool,
     pub should_open_insomnia_import: bool,
     pub should_open_postman_import: bool,
+    pub should_open_openapi_import: bool,
+    pub should_open_http_import: bool,
     pub should_focus_search: bool,
     pub should_focus_url_bar: bool,
+    pub should_open_previous_request: bool,
     pub should_copy_curl: bool,
-
+    pub should_copy_markdown: bool,
+    pub should_copy_response_curl: bool,
+    /// The copy or export waiting on "Inline secret values?"
+    pub secrets_prompt: Option<SecretExport>,
+    pub export_inline_secrets: bool,
+    /// What "Copy as Markdown" includes
+    pub markdown_include_headers: bool,
+    pub markdown_redact_secrets: bool,
+    /// Credentials the request would carry, named in the prompt
+    pub export_secrets: Vec<String>,
+    pub should_open_response_in_browser: bool,
+    pub should_run_folder: bool,
+    pub should_duplicate_selection: bool,
+    pub should_update_baseline: bool,
+    /// Move `pending_request_body` into the request body
+    pub should_use_as_body: bool,
+
+    /// One-off status message; moved into `toasts` each frame
     pub last_action_message: Option<(String, f64, bool)>,
+    pub toasts: ToastQueue,
+    /// Toasts from background threads, e.g. a finished import
+    toast_rx: Receiver<Toast>,
+    toast_tx: Sender<Toast>,
     pub copied_feedback_until: f64,
-    pub request_error: Option<String>,
+    pub request_error: Option<MercuryError>,
 
     pub show_about: bool,
+    pub show_settings: bool,
+    /// Action whose new key binding is being recorded in Settings
+    pub recording_binding: Option<Action>,
+    /// Previously opened workspaces, most recent first
+    pub recent_workspaces: Vec<PathBuf>,
+    pub show_workspace_switcher: bool,
+    pub workspace_switcher_query: String,
 
     pub ongoing_request: Option<(u64, f64)>, // (id, start_time)
+    /// Cancel signal shared with the thread running `ongoing_request`
+    ongoing_cancel: Option<Arc<AtomicBool>>,
     request_id_counter: u64,
-    response_rx: Receiver<(u64, Result<HttpResponse, String>)>,
-    response_tx: Sender<(u64, Result<HttpResponse, String>)>,
+    response_rx: Receiver<(u64, Result<HttpResponse, MercuryError>)>,
+    response_tx: Sender<(u64, Result<HttpResponse, MercuryError>)>,
+    /// Writes `settings.request_log` entries off the UI thread
+    request_logger: RequestLogger,
 
     folder_rx: Receiver<PathBuf>,
     folder_tx: Sender<PathBuf>,
+    add_rx: Receiver<Vec<PathBuf>>,
+    add_tx: Sender<Vec<PathBuf>>,
+
+    // Folder runs
+    pub folder_run: Option<FolderRun>,
+    pub show_folder_run: bool,
+    run_id_counter: u64,
+    run_rx: Receiver<(u64, usize, RunStatus, Option<HttpResponse>)>,
+    run_tx: Sender<(u64, usize, RunStatus, Option<HttpResponse>)>,
+
+    // API docs, read from the workspace's requests when opened
+    pub api_docs: Vec<(PathBuf, JsonRequest)>,
+    pub show_api_docs: bool,
+
+    // Environment comparisons
+    pub env_compare: Option<EnvComparison>,
+    pub show_env_compare: bool,
+    pub show_env_compare_picker: bool,
+    /// Environments ticked in the picker, as indices into `env_files`
+    pub env_compare_selection: HashSet<usize>,
+    pub env_compare_prod_confirmed: bool,
+    compare_rx: Receiver<(u64, usize, RunStatus, Option<Response>)>,
+    compare_tx: Sender<(u64, usize, RunStatus, Option<Response>)>,
 
     // Auto-save tracking
     pub has_unsaved_changes: bool,
@@ -121,56 +404,302 @@ pub struct MercuryApp {
     watcher_shutdown: Option<Sender<()>>,
     watched_path: Option<PathBuf>,
     expanded_folders: HashSet<PathBuf>,
+    /// Symlink loops already reported, so a rescan doesn't warn again
+    warned_symlink_loops: HashSet<PathBuf>,
+    /// Warning from the last tree scan, shown as a toast on the next frame
+    scan_warning: Option<String>,
     file_watcher_error: Option<String>,
 
+    // Temp HTML files opened in the browser, removed on exit
+    pub browser_temp_files: Vec<PathBuf>,
+
     // Shared HTTP client with cookie store for automatic cookie handling
     http_client: Arc<reqwest::blocking::Client>,
+    /// Cookie store outliving client rebuilds (e.g. when redirect settings change)
+    cookie_jar: Arc<reqwest::cookie::Jar>,
 }
 
 pub use crate::utils::AuthMode;
 
+pub use crate::core::types::ResponseTab;
+
+/// A request as it was sent, so it can be replayed after the form has changed
+#[derive(Clone)]
+pub struct SentRequest {
+    /// The form as it was, for history
+    pub form: Request,
+    /// With variables filled in
+    pub request: JsonRequest,
+    /// The `.proto` file and call, for gRPC
+    pub grpc: Option<(PathBuf, GrpcCall)>,
+}
+
+/// A secret written to `.env.enc` on a worker thread
+pub struct SavedSecret {
+    /// The secrets file it went into
+    pub path: PathBuf,
+    pub passphrase: String,
+    pub name: String,
+    /// Every secret in the file, the new one included
+    pub variables: Vec<(String, String)>,
+}
+
+/// A copy or export of the request that can carry its credentials
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretExport {
+    /// Copy as cURL, or save it as a script
+    Curl,
+    /// Copy the request behind the response on screen as cURL
+    ResponseCurl,
+    /// Copy the request and response as Markdown
+    Markdown,
+}
+
+/// The request behind the response on screen, for the response panel's Request section
+#[derive(Clone)]
+pub struct ResponseRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    /// Sorted by name
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub body_hex: bool,
+    /// Variables were filled in; history keeps the form as it was typed
+    pub resolved: bool,
+    /// URL, headers, and body with literal credentials as `{{NAME}}` placeholders
+    pub masked: (String, Vec<(String, String)>, String),
+    /// Names of the placeholders in `masked`
+    pub secrets: Vec<String>,
+    /// The Content-Type the request expected back, if it named one
+    pub expect_content_type: Option<String>,
+}
+
+impl ResponseRequest {
+    fn new(
+        method: HttpMethod,
+        url: String,
+        mut headers: Vec<(String, String)>,
+        body: String,
+        body_hex: bool,
+        resolved: bool,
+    ) -> Self {
+        headers.sort();
+        let mut masked = (url.clone(), headers.clone(), body.clone());
+        let secrets = mask_literal_secrets(&mut masked.0, &mut masked.1, &mut masked.2);
+        Self {
+            method,
+            url,
+            headers,
+            body,
+            body_hex,
+            resolved,
+            masked,
+            secrets,
+            expect_content_type: None,
+        }
+    }
+}
+
+impl From<&JsonRequest> for ResponseRequest {
+    fn from(request: &JsonRequest) -> Self {
+        let sent = Self::new(
+            request.method.clone(),
+            request.url.clone(),
+            request
+                .headers
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            request.body.clone(),
+            request.body_hex,
+            true,
+        );
+        Self {
+            expect_content_type: request.expect_content_type.clone(),
+            ..sent
+        }
+    }
+}
+
+impl From<&Request> for ResponseRequest {
+    fn from(request: &Request) -> Self {
+        Self::new(
+            request.method.clone(),
+            request.url.clone(),
+            crate::utils::parse_header_lines(&request.headers)
+                .into_iter()
+                .collect(),
+            request.body.clone(),
+            false,
+            false,
+        )
+    }
+}
+
+/// Shared client for all requests; per-request timeouts are applied by `execute_request`
+fn build_http_client(
+    cookie_jar: &Arc<reqwest::cookie::Jar>,
+    follow_redirects: bool,
+    timeouts: Timeouts,
+) -> reqwest::blocking::Client {
+    let redirect_policy = if follow_redirects {
+        reqwest::redirect::Policy::default() // Follow up to 10 redirects
+    } else {
+        reqwest::redirect::Policy::none()
+    };
+
+    reqwest::blocking::Client::builder()
+        .cookie_provider(cookie_jar.clone())
+        .redirect(redirect_policy)
+        .connect_timeout(Duration::from_secs(timeouts.connect_secs))
+        .timeout(Duration::from_secs(timeouts.read_secs))
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+/// How often a request was sent, and the last run's time and status
+type RunStats = (usize, f64, u16);
+
+/// Key into `last_status`
+fn status_key(method: &HttpMethod, url: &str) -> String {
+    format!("{} {}", method.as_str(), url)
+}
+
+/// "Imported N requests", with a button to open the folder they went into
+fn import_toast(count: usize, folder: &Path) -> Toast {
+    let requests = if count == 1 { "request" } else { "requests" };
+    Toast::new(format!("Imported {} {}", count, requests), false)
+        .with_action("Open", ToastAction::Open(folder.to_path_buf()))
+}
+
 impl MercuryApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>, window_workspace: Option<PathBuf>) -> Self {
         let (response_tx, response_rx) = channel();
         let (folder_tx, folder_rx) = channel();
+        let (add_tx, add_rx) = channel();
+        let (run_tx, run_rx) = channel();
+        let (compare_tx, compare_rx) = channel();
+        let (toast_tx, toast_rx) = channel();
         let (watcher_tx, watcher_rx) = channel();
-
-        // Load saved state
-        let saved_state = persistence::load_state();
+        let (unlock_tx, unlock_rx) = channel();
+        let (secret_tx, secret_rx) = channel();
+        let (history_preview_tx, history_preview_rx) = channel();
+        let (window_tx, window_rx) = channel();
+        let (proto_tx, proto_rx) = channel();
+
+        // Load saved state; an extra window shares only the settings
+        let saved_state = persistence::load_state().map(|state| match window_workspace {
+            Some(_) => AppState {
+                settings: state.settings,
+                recent_workspaces: state.recent_workspaces,
+                ..Default::default()
+            },
+            None => state,
+        });
+        let reopen_windows = saved_state
+            .as_ref()
+            .map(|state| state.open_windows.clone())
+            .unwrap_or_default();
+        let cookie_jar = Arc::new(reqwest::cookie::Jar::default());
 
         let mut app = Self {
             workspace_path: None,
             workspace_name: String::new(),
             collection_tree: Vec::new(),
+            settings: Settings::default(),
+            workspace_request_defaults: None,
             current_file: None,
+            current_block: None,
+            recent_files: Vec::new(),
             method: HttpMethod::GET,
             url: String::new(),
             query_params: Vec::new(),
             params_text: String::new(),
+            form_fields: Vec::new(),
+            form_fields_text: String::new(),
+            form_fields_bulk_edit: false,
+            form_body_raw: false,
+            json_patch_ops: Vec::new(),
+            json_patch_body: String::new(),
+            json_patch_check: Ok(()),
+            json_patch_unparsed: false,
+            json_patch_raw: false,
             headers_text: String::new(),
             body_text: String::new(),
+            body_generation: 0,
+            body_counts_cache: None,
+            body_hex: false,
+            clipboard_body: None,
+            clipboard_probe: false,
+            request_tags: Vec::new(),
+            grpc_call: None,
+            resolve_override: String::new(),
+            expect_content_type: String::new(),
+            timeout_override: None,
+            follow_redirects_override: None,
+            send_delay_ms: None,
+            request_docs: None,
+            save_path: String::new(),
+            save_variable: String::new(),
+            request_overrides: BTreeMap::new(),
+            current_examples: Vec::new(),
             auth_username: String::new(),
             auth_password: String::new(),
             auth_token: String::new(),
             response: None,
-            response_view_raw: false,
-            show_response_headers: false,
-            show_response_cookies: false,
+            response_view: ResponseView::default(),
+            unescape_unicode: false,
+            response_tab: ResponseTab::default(),
+            show_response_extract: false,
+            show_response_inspect: false,
+            show_response_hex: false,
+            hex_offset: 0,
+            hex_offset_text: String::new(),
+            extract_path: String::new(),
             formatted_response_cache: None,
+            response_shape_cache: None,
+            response_json_cache: None,
+            grpc_error_cache: None,
+            ndjson_cache: None,
+            response_preview_cache: None,
+            baseline_cache: None,
+            rate_limit_until: None,
+            auto_resend_rate_limited: false,
 
             env_files: vec!["None".to_string()],
+            env_var_counts: HashMap::new(),
             selected_env: 0,
             env_variables: HashMap::new(),
+            folder_defaults: InheritedDefaults::default(),
+            folder_defaults_for: None,
+            session_overrides: Vec::new(),
+            pin_session_overrides: false,
+            show_session_overrides_dialog: false,
+            session_overrides_text: String::new(),
+            env_sources: HashMap::new(),
+            missing_env_vars: Vec::new(),
             search_query: String::new(),
+            search_contents: false,
+            content_index: ContentIndex::default(),
+            content_index_stale: true,
+            content_hits: None,
             show_shortcuts: false,
             selected_tab: 0,
             focus_mode: false,
+            collapsed_panel: None,
             headers_bulk_edit: false,
             params_bulk_edit: false,
             timeline: Vec::new(),
             timeline_search: String::new(),
+            history_previews: HashMap::new(),
+            history_previews_loading: HashSet::new(),
+            history_preview_tx,
+            history_preview_rx,
+            history_hover: None,
             show_timeline: false,
             history_loaded: false,
+            last_status: HashMap::new(),
+            run_stats_cache: None,
             recent_requests: persistence::load_recent_requests(),
             recent_expanded: true,
             context_menu_item: None,
@@ -183,26 +712,127 @@ impl MercuryApp {
             new_folder_name: String::new(),
             show_new_env_dialog: false,
             new_env_name: String::new(),
+            show_paste_env_dialog: false,
+            paste_env_text: String::new(),
+            show_duplicate_dialog: false,
+            duplicate_name: String::new(),
+            show_save_example_dialog: false,
+            show_set_variable_dialog: false,
+            variable_name: String::new(),
+            variable_value: String::new(),
+            show_replace_body_confirm: false,
+            pending_request_body: String::new(),
+            show_quit_confirm: false,
+            quit_confirmed: false,
+            extra_window: window_workspace.is_some(),
+            opener_closed: Arc::new(AtomicBool::new(false)),
+            child_windows: Vec::new(),
+            should_open_new_window: false,
+            window_tx,
+            window_rx,
+            should_pick_proto: false,
+            proto_tx,
+            proto_rx,
+            show_keychain_dialog: false,
+            secret_variables: Vec::new(),
+            secrets_passphrase: None,
+            show_unlock_secrets_dialog: false,
+            show_secret_dialog: false,
+            secret_name: String::new(),
+            secret_value: String::new(),
+            secrets_passphrase_input: String::new(),
+            secrets_passphrase_confirm: String::new(),
+            secrets_error: None,
+            unlocking_secrets: false,
+            saving_secret: false,
+            secret_tx,
+            secret_rx,
+            unlock_tx,
+            unlock_rx,
+            keychain_service: String::new(),
+            keychain_account: String::new(),
+            keychain_secret: String::new(),
+            keychain_variable: String::new(),
+            show_openapi_url_dialog: false,
+            openapi_url: String::new(),
+            openapi_header: String::new(),
+            example_name: String::new(),
+            show_tags_dialog: false,
+            tags_text: String::new(),
             show_delete_confirm: false,
             delete_target: None,
+            tree_selection: TreeSelection::default(),
+            show_bulk_delete_confirm: false,
+            show_move_dialog: false,
+            move_destination: None,
+            show_add_dialog: false,
+            add_destination: None,
+            add_queue: Vec::new(),
+            add_report: AddReport::default(),
+            show_add_rename_dialog: false,
+            add_rename_text: String::new(),
             should_create_new_request: false,
             should_execute_request: false,
+            should_retry_last: false,
+            last_sent: None,
+            response_request: None,
             should_open_folder_dialog: false,
             should_open_insomnia_import: false,
             should_open_postman_import: false,
+            should_open_openapi_import: false,
+            should_open_http_import: false,
             should_focus_search: false,
             should_focus_url_bar: false,
+            should_open_previous_request: false,
             should_copy_curl: false,
+            should_copy_markdown: false,
+            should_copy_response_curl: false,
+            secrets_prompt: None,
+            export_inline_secrets: false,
+            markdown_include_headers: true,
+            markdown_redact_secrets: true,
+            export_secrets: Vec::new(),
+            should_open_response_in_browser: false,
+            should_run_folder: false,
+            should_duplicate_selection: false,
+            should_update_baseline: false,
+            should_use_as_body: false,
             last_action_message: None,
+            toasts: ToastQueue::default(),
+            toast_rx,
+            toast_tx,
             copied_feedback_until: 0.0,
             request_error: None,
             show_about: false,
+            show_settings: false,
+            recording_binding: None,
+            recent_workspaces: Vec::new(),
+            show_workspace_switcher: false,
+            workspace_switcher_query: String::new(),
             ongoing_request: None,
+            ongoing_cancel: None,
             request_id_counter: 0,
             response_rx,
             response_tx,
+            request_logger: RequestLogger::new(),
             folder_rx,
             folder_tx,
+            add_rx,
+            add_tx,
+            folder_run: None,
+            show_folder_run: false,
+            run_id_counter: 0,
+            run_rx,
+            run_tx,
+            api_docs: Vec::new(),
+            show_api_docs: false,
+            env_compare: None,
+            show_env_compare: false,
+            show_env_compare_picker: false,
+            env_compare_selection: HashSet::new(),
+            env_compare_prod_confirmed: false,
+            compare_rx,
+            compare_tx,
             has_unsaved_changes: false,
             last_save_time: f64::MAX, // Start high so first auto-save waits for actual save/load
             last_saved_content: None,
@@ -211,15 +841,13 @@ impl MercuryApp {
             watcher_shutdown: None,
             watched_path: None,
             expanded_folders: HashSet::new(),
+            warned_symlink_loops: HashSet::new(),
+            scan_warning: None,
             file_watcher_error: None,
+            browser_temp_files: Vec::new(),
             // Initialize shared HTTP client with cookie store
-            http_client: Arc::new(
-                reqwest::blocking::Client::builder()
-                    .cookie_store(true)
-                    .timeout(std::time::Duration::from_secs(30))
-                    .build()
-                    .expect("Failed to create HTTP client"),
-            ),
+            http_client: Arc::new(build_http_client(&cookie_jar, true, Timeouts::default())),
+            cookie_jar,
         };
 
         // Restore saved state
@@ -262,17 +890,69 @@ impl MercuryApp {
             app.auth_token = token;
 
             app.selected_tab = state.selected_tab;
+            app.collapsed_panel = state.collapsed_panel;
+            app.response_view = state.response_view;
+            app.response_tab = state.response_tab;
+            app.show_timeline = state.show_timeline;
+            app.unescape_unicode = state.unescape_unicode;
+            app.settings = state.settings;
+            app.recent_workspaces = state
+                .recent_workspaces
+                .iter()
+                .map(PathBuf::from)
+                .filter(|p| p.is_dir())
+                .collect();
+            cc.egui_ctx.set_zoom_factor(app.settings.ui_scale);
+            if app.settings.tree_status_badges || app.show_timeline {
+                app.ensure_history_loaded();
+            }
+            if !app.settings.follow_redirects || app.settings.timeouts() != Timeouts::default() {
+                app.http_client = Arc::new(build_http_client(
+                    &app.cookie_jar,
+                    app.settings.follow_redirects,
+                    app.settings.timeouts(),
+                ));
+            }
 
             // Restore workspace if it exists
             if let Some(workspace_str) = state.workspace_path {
                 let workspace_path = PathBuf::from(&workspace_str);
                 if workspace_path.exists() {
-                    app.load_workspace(workspace_path);
+                    app.load_workspace(workspace_path.clone());
                     // Restore selected env after loading workspace
                     if state.selected_env < app.env_files.len() {
                         app.selected_env = state.selected_env;
                         app.load_env();
                     }
+
+                    // Reopen the request that was open; edits were saved on exit.
+                    // If it's gone, the restored fields stay as a scratch request.
+                    if let Some(file) = state.current_file.map(PathBuf::from) {
+                        if file.is_file() && file.starts_with(&workspace_path) {
+                            app.load_file(&file);
+                        }
+                    }
+                }
+            }
+        }
+
+        match window_workspace {
+            Some(workspace) => {
+                if workspace.is_dir() {
+                    app.load_workspace(workspace);
+                }
+                let opener_closed = app.opener_closed.clone();
+                let ctx = cc.egui_ctx.clone();
+                windows::watch_opener(move || {
+                    opener_closed.store(true, Ordering::Relaxed);
+                    ctx.request_repaint();
+                });
+            }
+            None => {
+                for workspace in reopen_windows.iter().map(PathBuf::from) {
+                    if workspace.is_dir() {
+                        app.open_window(workspace);
+                    }
                 }
             }
         }
@@ -280,17 +960,122 @@ impl MercuryApp {
         app
     }
 
+    /// Forget the windows that have closed, collecting their exit status
+    fn reap_child_windows(&mut self) {
+        self.child_windows
+            .retain_mut(|(_, child)| matches!(child.try_wait(), Ok(None)));
+    }
+
+    /// Open `workspace` in a window of its own
+    fn open_window(&mut self, workspace: PathBuf) {
+        match windows::open_window(&workspace) {
+            Ok(child) => self.child_windows.push((workspace, child)),
+            Err(e) => self.toasts.push(Toast::new(e.to_string(), true)),
+        }
+    }
+
+    /// Replace the request body
+    pub fn set_body(&mut self, body: String) {
+        self.body_text = body;
+        self.body_generation += 1;
+    }
+
+    /// Lines and characters in the body, counted again only when it changes
+    pub fn body_counts(&mut self) -> (usize, usize) {
+        let (generation, len) = (self.body_generation, self.body_text.len());
+        match self.body_counts_cache {
+            Some((g, l, counts)) if g == generation && l == len => counts,
+            _ => {
+                let counts = crate::utils::text_counts(&self.body_text);
+                self.body_counts_cache = Some((generation, len, counts));
+                counts
+            }
+        }
+    }
+
     /// Ensure history (timeline summaries) is loaded from disk if it hasn't been yet
     pub fn ensure_history_loaded(&mut self) {
         if !self.history_loaded {
             self.timeline = persistence::load_history_summaries();
+            self.run_stats_cache = None;
+            self.last_status = self
+                .timeline
+                .iter()
+                .map(|s| (status_key(&s.method, &s.url), s.status))
+                .collect();
             self.history_loaded = true;
         }
     }
 
+    /// Add the current request and its outcome to history, in memory and on disk
+    fn record_history(&mut self, response: Response, timestamp: f64) {
+        // What was sent, even if the form has changed since (or this was a retry)
+        let request = match &self.last_sent {
+            Some(sent) => sent.form.clone(),
+            None => Request {
+                method: self.method.clone(),
+                url: self.url.clone(),
+                headers: self.headers_text.clone(),
+                body: self.body_text.clone(),
+            },
+        };
+        let entry = TimelineEntry {
+            timestamp,
+            request,
+            response,
+        };
+        self.log_request(&entry);
+
+        // Add summary to timeline for display
+        self.last_status.insert(
+            status_key(&entry.request.method, &entry.request.url),
+            entry.response.status,
+        );
+        self.timeline.push(TimelineSummary::from(&entry));
+        self.run_stats_cache = None;
+
+        if self.timeline.len() > crate::core::constants::MAX_TIMELINE_ENTRIES {
+            self.timeline.remove(0);
+        }
+
+        // Save full entry to disk
+        persistence::append_history_entry(&entry);
+    }
+
+    /// Queue `entry` for the request log, if Settings turns it on
+    fn log_request(&self, entry: &TimelineEntry) {
+        let settings = &self.settings.request_log;
+        if !settings.enabled {
+            return;
+        }
+        let path = match settings.path.trim() {
+            "" => persistence::get_request_log_path(),
+            path => PathBuf::from(path),
+        };
+        let response = &entry.response;
+        let failed = response.is_failure();
+        self.request_logger.log(
+            path,
+            LogEntry {
+                timestamp: crate::parser::env::iso_timestamp((entry.timestamp * 1000.0) as u128),
+                method: entry.request.method.clone(),
+                // The URL as sent, variables filled in
+                url: self.last_sent.as_ref().map_or_else(
+                    || entry.request.url.clone(),
+                    |sent| sent.request.url.clone(),
+                ),
+                status: (!failed).then_some(response.status),
+                duration_ms: response.duration_ms,
+                size_bytes: response.size_bytes,
+                error: failed.then(|| response.body.clone()),
+            },
+        );
+    }
+
     fn load_workspace(&mut self, path: PathBuf) {
         // Validate workspace exists
         if !path.exists() || !path.is_dir() {
+            self.recent_workspaces.retain(|p| p != &path);
             self.last_action_message = Some((
                 MercuryError::WorkspaceNotFound(path.display().to_string()).to_string(),
                 0.0,
@@ -299,13 +1084,34 @@ impl MercuryApp {
             return;
         }
 
+        if self.workspace_path.as_ref() != Some(&path) {
+            // Secrets belong to the workspace that unlocked them
+            self.secret_variables.clear();
+            self.secrets_passphrase = None;
+            self.session_overrides.clear();
+            // The picker's indices point into the old workspace's environments
+            self.show_env_compare_picker = false;
+            self.recent_files.clear();
+        }
         self.workspace_path = Some(path.clone());
+        self.tree_selection.clear();
+        crate::utils::remember_workspace(
+            &mut self.recent_workspaces,
+            &path,
+            crate::core::constants::MAX_RECENT_WORKSPACES,
+        );
+        self.workspace_request_defaults =
+            persistence::load_workspace_config(&path).request_defaults;
 
         // Scan for .env files
         self.env_files = vec!["None".to_string()];
+        self.env_var_counts.clear();
         for entry in WalkDir::new(&path).max_depth(2).into_iter().flatten() {
             let file_name = entry.file_name().to_string_lossy();
-            if file_name.starts_with(".env") {
+            if file_name.starts_with(".env")
+                && file_name != crate::core::constants::SECRETS_FILE
+                && file_name != crate::core::constants::ENV_SCHEMA_FILE
+            {
                 self.env_files.push(file_name.to_string());
             }
         }
@@ -322,6 +1128,15 @@ impl MercuryApp {
             self.load_env();
         } else {
             self.selected_env = 0;
+            self.missing_env_vars.clear();
+        }
+
+        if self.secrets_passphrase.is_none()
+            && path.join(crate::core::constants::SECRETS_FILE).exists()
+        {
+            self.secrets_passphrase_input.clear();
+            self.secrets_error = None;
+            self.show_unlock_secrets_dialog = true;
         }
 
         // Build collection tree
@@ -331,7 +1146,7 @@ impl MercuryApp {
         self.start_file_watcher();
     }
 
-    fn load_file(&mut self, path: &Path) {
+    pub fn load_file(&mut self, path: &Path) {
         // Save current file before loading new one
         if self.has_unsaved_changes {
             self.save_current_file();
@@ -339,33 +1154,90 @@ impl MercuryApp {
 
         if let Ok(content) = fs::read_to_string(path) {
             if let Ok(request) = parse_request_file(&content) {
-                self.current_file = Some(path.to_path_buf());
-                self.method = request.method;
-                self.url = request.url;
+                self.recent_files.retain(|recent| recent != path);
+                self.recent_files.insert(0, path.to_path_buf());
+                self.recent_files
+                    .truncate(crate::core::constants::MAX_RECENT_FILES);
+                self.show_request(path, None, request);
+            }
+        }
+    }
 
-                // Convert headers map to text
-                self.headers_text = request
-                    .headers
-                    .iter()
-                    .map(|(k, v)| format!("{}: {}", k, v))
-                    .collect::<Vec<_>>()
-                    .join("\n");
+    /// Open the request at `index` of a `.http` file; saving writes the whole file back
+    pub fn load_http_request(&mut self, path: &Path, index: usize) {
+        if self.has_unsaved_changes {
+            self.save_current_file();
+        }
 
-                self.body_text = request.body;
-                self.response = None;
+        if let Ok(content) = fs::read_to_string(path) {
+            if let Some(entry) = parse_http_file(&content).into_iter().nth(index) {
+                self.show_request(path, Some(index), entry.request);
+            }
+        }
+    }
 
-                // Sync query params from URL
-                self.query_params = crate::utils::parse_query_params(&self.url);
+    /// Fill the form from a request read from `path`
+    fn show_request(&mut self, path: &Path, block: Option<usize>, request: JsonRequest) {
+        self.current_file = Some(path.to_path_buf());
+        self.current_block = block;
+        self.method = request.method;
+        self.url = request.url;
 
-                // Track the loaded content for change detection
-                self.last_saved_content = Some(self.get_current_content());
-                self.has_unsaved_changes = false;
-            }
+        // Convert headers map to text
+        self.headers_text = request
+            .headers
+            .iter()
+            .map(|(k, v)| format!("{}: {}", k, v))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        self.set_body(request.body);
+        self.body_hex = request.body_hex;
+        self.request_tags = request.tags;
+        self.grpc_call = request.grpc;
+        self.resolve_override = request.resolve.unwrap_or_default();
+        self.expect_content_type = request.expect_content_type.unwrap_or_default();
+        self.timeout_override = request.timeout_secs;
+        self.follow_redirects_override = request.follow_redirects;
+        self.send_delay_ms = request.delay_ms;
+        self.request_docs = request.docs;
+        (self.save_variable, self.save_path) = request
+            .save
+            .map(|rule| (rule.variable, rule.path))
+            .unwrap_or_default();
+        self.request_overrides = request.overrides;
+        self.current_examples = examples::list_examples(path);
+        self.response = None;
+
+        // Sync query params from URL
+        self.query_params = crate::utils::parse_query_params(&self.url);
+
+        // Track the loaded content for change detection
+        self.last_saved_content = Some(self.get_current_content());
+        self.has_unsaved_changes = false;
+    }
+
+    /// Switch to the request opened before this one, skipping files that are gone;
+    /// pressing it again switches back
+    pub fn open_previous_request(&mut self) {
+        self.recent_files.retain(|path| path.is_file());
+        let previous = self
+            .recent_files
+            .iter()
+            .find(|path| self.current_file.as_ref() != Some(*path))
+            .cloned();
+        if let Some(path) = previous {
+            self.load_file(&path);
         }
     }
 
     /// Get the current request content as a JSON file string
     fn get_current_content(&self) -> String {
+        serialize_request_file(&self.current_request()).unwrap_or_default()
+    }
+
+    /// The request in the form, as it's stored in a request file
+    fn current_request(&self) -> JsonRequest {
         // Parse headers text into HashMap
         let mut headers = std::collections::HashMap::new();
         for line in self.headers_text.lines() {
@@ -378,21 +1250,38 @@ impl MercuryApp {
             }
         }
 
-        let request = JsonRequest {
+        JsonRequest {
             method: self.method.clone(),
             url: self.url.clone(),
             headers,
             body: self.body_text.clone(),
-        };
-
-        serialize_request_file(&request).unwrap_or_default()
+            body_hex: self.body_hex,
+            tags: self.request_tags.clone(),
+            grpc: self.grpc_call.clone(),
+            resolve: Some(self.resolve_override.trim().to_string()).filter(|r| !r.is_empty()),
+            timeout_secs: self.timeout_override,
+            follow_redirects: self.follow_redirects_override,
+            delay_ms: self.send_delay_ms,
+            save: self.save_rule(),
+            expect_content_type: Some(self.expect_content_type.trim().to_string())
+                .filter(|t| !t.is_empty()),
+            overrides: self.request_overrides.clone(),
+            docs: self.request_docs.clone(),
+        }
     }
 
     /// Save current file to disk
     pub fn save_current_file(&mut self) -> bool {
         if let Some(ref path) = self.current_file {
             let content = self.get_current_content();
-            if fs::write(path, &content).is_ok() {
+            let saved = match self
+                .current_block
+                .filter(|_| collection::is_http_file(path))
+            {
+                Some(index) => save_http_request(path, index, self.current_request()).is_ok(),
+                None => fs::write(path, &content).is_ok(),
+            };
+            if saved {
                 self.last_saved_content = Some(content);
                 self.has_unsaved_changes = false;
                 return true;
@@ -409,18 +1298,43 @@ impl MercuryApp {
         }
     }
 
-    /// Clear the request form to empty state (used by new request, delete, etc.)
+    /// Defaults for new requests: the workspace's `.mercury.json` wins over the user's own
+    pub fn effective_request_defaults(&self) -> &RequestDefaults {
+        self.workspace_request_defaults
+            .as_ref()
+            .unwrap_or(&self.settings.request_defaults)
+    }
+
+    /// Reset the request form to the new-request baseline (used by new request, delete, etc.)
     pub fn clear_request_form(&mut self) {
+        let defaults = self.effective_request_defaults().clone();
         self.current_file = None;
-        self.method = HttpMethod::GET;
+        self.current_block = None;
+        self.method = defaults.method;
         self.url = String::new();
         self.query_params.clear();
-        self.headers_text = String::new(); // This also clears auth (single source of truth)
-        self.body_text = String::new();
-        // Clear auth UI input helpers
-        self.auth_username = String::new();
-        self.auth_password = String::new();
-        self.auth_token = String::new();
+        self.form_fields.clear();
+        self.headers_text = defaults.headers; // This also resets auth (single source of truth)
+        self.set_body(String::new());
+        self.body_hex = false;
+        self.request_tags.clear();
+        self.grpc_call = None;
+        self.resolve_override.clear();
+        self.expect_content_type.clear();
+        self.timeout_override = None;
+        self.follow_redirects_override = None;
+        self.send_delay_ms = None;
+        self.request_docs = None;
+        self.save_path.clear();
+        self.save_variable.clear();
+        self.request_overrides.clear();
+        self.current_examples.clear();
+        // Auth UI input helpers follow whatever auth the default headers carry
+        let (_, username, password, token) =
+            crate::utils::get_auth_from_headers(&self.headers_text);
+        self.auth_username = username;
+        self.auth_password = password;
+        self.auth_token = token;
         self.response = None;
         self.has_unsaved_changes = false;
         self.last_saved_content = None;
@@ -435,11 +1349,24 @@ impl MercuryApp {
         body: String,
     ) {
         self.current_file = None;
+        self.current_block = None;
         self.method = method;
         self.url = url;
         self.headers_text = headers.clone(); // Single source of truth - includes Authorization if present
-        self.body_text = body;
+        self.set_body(body);
+        self.body_hex = false;
+        self.grpc_call = None;
+        self.resolve_override.clear();
+        self.expect_content_type.clear();
+        self.timeout_override = None;
+        self.follow_redirects_override = None;
+        self.send_delay_ms = None;
+        self.request_docs = None;
+        self.save_path.clear();
+        self.save_variable.clear();
+        self.request_overrides.clear();
         self.query_params = crate::utils::parse_query_params(&self.url);
+        self.form_fields.clear();
         self.response = None;
 
         // Populate auth UI helpers from headers (for display in Auth tab)
@@ -449,19 +1376,128 @@ impl MercuryApp {
         self.auth_token = token;
     }
 
-    fn load_env(&mut self) {
-        self.env_variables.clear();
+    /// The open request's override for the selected environment, if it has one
+    pub fn active_override(&self) -> Option<&RequestOverride> {
+        if self.selected_env == 0 {
+            return None;
+        }
+        let env_file = self.env_files.get(self.selected_env)?;
+        crate::utils::request_override(&self.request_overrides, env_file)
+    }
 
-        if self.selected_env > 0 && self.selected_env < self.env_files.len() {
-            if let Some(workspace) = &self.workspace_path {
-                let env_file = workspace.join(&self.env_files[self.selected_env]);
-                if let Ok(vars) = parse_env_file(&env_file) {
-                    self.env_variables = vars;
-                }
+    /// Variables of environment `index` on top of the base `.env`, if there is one,
+    /// with secrets and the open request's folder variables; no session overrides
+    fn env_layers(&self, index: usize) -> (HashMap<String, String>, HashMap<String, VarSource>) {
+        let (mut variables, mut sources) = match (self.env_files.get(index), &self.workspace_path) {
+            (Some(env_name), Some(workspace)) if index > 0 => {
+                let env = parse_env_file(&workspace.join(env_name)).unwrap_or_default();
+                let base = if env_name == crate::core::constants::BASE_ENV_FILE {
+                    HashMap::new()
+                } else {
+                    parse_env_file(&workspace.join(crate::core::constants::BASE_ENV_FILE))
+                        .unwrap_or_default()
+                };
+                merge_env_layers(base, env)
+            }
+            _ => (HashMap::new(), HashMap::new()),
+        };
+
+        // Decrypted secrets apply whichever environment is selected
+        for (name, value) in &self.secret_variables {
+            variables.insert(name.clone(), value.clone());
+            sources.insert(name.clone(), VarSource::Secret);
+        }
+
+        // Folder variables only fill in what no environment file sets
+        for variable in &self.folder_defaults.variables {
+            if !variables.contains_key(&variable.name) {
+                variables.insert(variable.name.clone(), variable.value.clone());
+                sources.insert(variable.name.clone(), VarSource::Folder);
+            }
+        }
+        (variables, sources)
+    }
+
+    /// Load the selected environment, then this session's overrides
+    fn load_env(&mut self) {
+        (self.env_variables, self.env_sources) = self.env_layers(self.selected_env);
+        // The file may just have been written
+        self.env_var_counts.clear();
+
+        // Checked before session overrides, which don't last
+        self.missing_env_vars = match &self.workspace_path {
+            Some(workspace) if self.selected_env > 0 => {
+                fs::read_to_string(workspace.join(crate::core::constants::ENV_SCHEMA_FILE))
+                    .map(|schema| {
+                        missing_variables(&parse_env_schema(&schema), &self.env_variables)
+                    })
+                    .unwrap_or_default()
             }
+            _ => Vec::new(),
+        };
+
+        for (name, value) in &self.session_overrides {
+            self.env_variables.insert(name.clone(), value.clone());
+            self.env_sources.insert(name.clone(), VarSource::Session);
+        }
+    }
+
+    /// How many variables the environment file `env` defines, read once and cached
+    fn env_var_count(&mut self, env: &str) -> Option<usize> {
+        if let Some(count) = self.env_var_counts.get(env) {
+            return *count;
+        }
+        let count = self
+            .workspace_path
+            .as_ref()
+            .and_then(|workspace| parse_env_file(&workspace.join(env)).ok())
+            .map(|vars| vars.len());
+        self.env_var_counts.insert(env.to_string(), count);
+        count
+    }
+
+    /// Switch environments; session overrides are dropped unless pinned
+    fn select_env(&mut self, index: usize) {
+        self.selected_env = index;
+        if !self.pin_session_overrides {
+            self.session_overrides.clear();
+        }
+        self.load_env();
+    }
+
+    /// Reload `folder_defaults` when the open request changes, or always with `force`
+    /// (a file in the workspace changed)
+    fn sync_folder_defaults(&mut self, force: bool) {
+        if !force && self.folder_defaults_for == self.current_file {
+            return;
+        }
+        self.folder_defaults_for = self.current_file.clone();
+        let inherited = match (&self.current_file, &self.workspace_path) {
+            (Some(file), Some(workspace)) => folder_defaults::load_inherited(file, workspace),
+            _ => InheritedDefaults::default(),
+        };
+        if inherited != self.folder_defaults {
+            self.folder_defaults = inherited;
+            self.load_env();
         }
     }
 
+    /// Environment variables without the open request's folder variables, for
+    /// requests that live somewhere else
+    fn environment_only_variables(&self) -> HashMap<String, String> {
+        self.env_variables
+            .iter()
+            .filter(|(name, _)| self.env_sources.get(*name) != Some(&VarSource::Folder))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Replace the session overrides with `KEY=value` lines
+    fn apply_session_overrides(&mut self) {
+        self.session_overrides = parse_env_str(&self.session_overrides_text);
+        self.load_env();
+    }
+
     pub fn extract_variables(text: &str) -> Vec<String> {
         let mut vars = Vec::new();
         let mut chars = text.chars().peekable();
@@ -475,7 +1511,14 @@ impl MercuryApp {
                     if c == '}' {
                         if chars.peek() == Some(&'}') {
                             chars.next(); // consume second }
-                            if !var_name.is_empty() {
+
+                            // Keychain references are resolved at send time, not from the env
+                            if !var_name.is_empty()
+                                && !var_name
+                                    .trim()
+                                    .starts_with(crate::core::keychain::TOKEN_PREFIX)
+                                && !crate::parser::is_dynamic_variable(&var_name)
+                            {
                                 vars.push(var_name.trim().to_string());
                             }
                             break;
@@ -497,7 +1540,21 @@ impl MercuryApp {
             self.save_expanded_state(&old_tree);
 
             // Rebuild tree
-            self.collection_tree = self.scan_directory(&workspace, &workspace);
+            let ignore = collection::workspace_ignore(&workspace);
+            let scan = collection::scan_collection(&workspace, &self.expanded_folders, &ignore);
+            self.collection_tree = scan.items;
+            self.content_index_stale = true;
+            if let Some(link) = scan
+                .loops
+                .into_iter()
+                .find(|link| self.warned_symlink_loops.insert(link.clone()))
+            {
+                let link = link.strip_prefix(&workspace).unwrap_or(&link);
+                self.scan_warning = Some(format!(
+                    "Skipped {}: it links back to a folder that contains it",
+                    link.display()
+                ));
+            }
 
             self.workspace_name = workspace
                 .file_name()
@@ -510,19 +1567,23 @@ impl MercuryApp {
     /// Save the expanded state of all folders to the HashSet
     fn save_expanded_state(&mut self, items: &[CollectionItem]) {
         for item in items {
-            if let CollectionItem::Folder {
-                path,
-                expanded,
-                children,
-                ..
-            } = item
-            {
-                if *expanded {
-                    self.expanded_folders.insert(path.clone());
-                } else {
-                    self.expanded_folders.remove(path);
+            let (path, expanded) = match item {
+                CollectionItem::Folder {
+                    path,
+                    expanded,
+                    children,
+                    ..
+                } => {
+                    self.save_expanded_state(children);
+                    (path, expanded)
                 }
-                self.save_expanded_state(children);
+                CollectionItem::HttpFile { path, expanded, .. } => (path, expanded),
+                CollectionItem::Request { .. } => continue,
+            };
+            if *expanded {
+                self.expanded_folders.insert(path.clone());
+            } else {
+                self.expanded_folders.remove(path);
             }
         }
     }
@@ -569,10 +1630,10 @@ impl MercuryApp {
                     }
                 };
 
-                // Watch the workspace directory recursively
+                // Watch the workspace directory itself
                 if let Err(e) = debouncer
                     .watcher()
-                    .watch(&workspace_path, notify::RecursiveMode::Recursive)
+                    .watch(&workspace_path, notify::RecursiveMode::NonRecursive)
                 {
                     let _ = tx.send(Err(MercuryError::FileWatcherError(format!(
                         "Failed to watch directory: {}",
@@ -582,10 +1643,30 @@ impl MercuryApp {
                     return;
                 }
 
+                // Folders are registered one by one so ignored ones (node_modules,
+                // build output) aren't watched at all. The set is brought up to
+                // date whenever the tree or the rules change, which picks up new
+                // folders too.
+                let mut ignore = collection::workspace_ignore(&workspace_path);
+                let mut watched = HashSet::from([workspace_path.clone()]);
+                let mut sync_watches =
+                    |watcher: &mut dyn notify::Watcher, ignore: &ignore::gitignore::Gitignore| {
+                        let wanted: HashSet<PathBuf> =
+                            collection::watched_dirs(&workspace_path, ignore)
+                                .into_iter()
+                                .collect();
+                        for dir in watched.difference(&wanted) {
+                            let _ = watcher.unwatch(dir);
+                        }
+                        for dir in wanted.difference(&watched) {
+                            let _ = watcher.watch(dir, notify::RecursiveMode::NonRecursive);
+                        }
+                        watched = wanted;
+                    };
+                sync_watches(debouncer.watcher(), &ignore);
+
                 // Listen for events and signal main thread
                 // Keep debouncer alive throughout the thread
-                #[allow(unused_variables)]
-                let _debouncer = debouncer;
                 loop {
                     // Check for shutdown signal
                     if let Ok(_) | Err(std::sync::mpsc::TryRecvError::Disconnected) =
@@ -596,7 +1677,28 @@ impl MercuryApp {
 
                     match debouncer_rx.recv_timeout(Duration::from_millis(200)) {
                         Ok(Ok(events)) => {
-                            if !events.is_empty() {
+                            let rules_changed = events.iter().any(|event| {
+                                event.path.file_name()
+                                    == Some(crate::core::constants::IGNORE_FILE.as_ref())
+                            });
+                            if rules_changed {
+                                ignore = collection::workspace_ignore(&workspace_path);
+                            }
+                            // Example sidecars never appear in the tree, so skip rebuilds for them
+                            let affects_tree = rules_changed
+                                || events.iter().any(|event| {
+                                    !event
+                                        .path
+                                        .components()
+                                        .any(|c| c.as_os_str() == examples::EXAMPLES_DIR)
+                                        && !collection::is_ignored(
+                                            &ignore,
+                                            &event.path,
+                                            event.path.is_dir(),
+                                        )
+                                });
+                            if affects_tree {
+                                sync_watches(debouncer.watcher(), &ignore);
                                 let _ = tx.send(Ok(()));
                             }
                         }
@@ -621,59 +1723,6 @@ impl MercuryApp {
         }
     }
 
-    #[allow(clippy::only_used_in_recursion)]
-    fn scan_directory(&self, dir: &Path, workspace_root: &Path) -> Vec<CollectionItem> {
-        let mut folders = Vec::new();
-        let mut requests = Vec::new();
-
-        if let Ok(entries) = fs::read_dir(dir) {
-            let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).collect();
-            entries.sort_by_key(|e| e.path());
-
-            for entry in entries {
-                let path = entry.path();
-                let name = path
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
-
-                // Skip hidden files and env files
-                if name.starts_with('.') {
-                    continue;
-                }
-
-                if path.is_dir() {
-                    let children = self.scan_directory(&path, workspace_root);
-                    // Check saved state; expand all folders on first load (when expanded_folders is empty)
-                    let is_expanded = self.expanded_folders.contains(&path);
-                    folders.push(CollectionItem::Folder {
-                        name,
-                        path: path.clone(),
-                        expanded: is_expanded || self.expanded_folders.is_empty(),
-                        children,
-                    });
-                } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                    let method = if let Ok(content) = fs::read_to_string(&path) {
-                        parse_request_file(&content).ok().map(|r| r.method)
-                    } else {
-                        None
-                    };
-
-                    requests.push(CollectionItem::Request {
-                        name,
-                        path: path.clone(),
-                        method,
-                    });
-                }
-            }
-        }
-
-        // Combine folders first, then requests
-        folders.extend(requests);
-        folders
-    }
-
     fn create_new_request(&mut self, parent_path: &Path, name: &str) -> Result<(), MercuryError> {
         let file_name = if name.ends_with(".json") {
             name.to_string()
@@ -724,15 +1773,117 @@ impl MercuryApp {
                 path: path.display().to_string(),
                 reason: e.to_string(),
             })?;
+            examples::delete_all_examples(path);
         }
 
         self.build_collection_tree();
-        if self.current_file.as_ref() == Some(&path.to_path_buf()) {
+        // Deleting a folder also removes the open request if it lived inside
+        if self
+            .current_file
+            .as_ref()
+            .is_some_and(|f| f.starts_with(path))
+        {
             self.clear_request_form();
         }
         Ok(())
     }
 
+    /// Delete every top-level selected item. Returns how many were deleted and the
+    /// first error, if any; the rest are still attempted.
+    fn delete_selected(&mut self) -> (usize, Option<MercuryError>) {
+        let mut deleted = 0;
+        let mut first_error = None;
+        for path in self.tree_selection.top_level() {
+            match self.delete_item(&path) {
+                Ok(()) => deleted += 1,
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        self.tree_selection.clear();
+        (deleted, first_error)
+    }
+
+    /// Move every top-level selected item into `destination`
+    fn move_selected(&mut self, destination: &Path) -> (usize, Option<MercuryError>) {
+        let mut moved = 0;
+        let mut first_error = None;
+        for path in self.tree_selection.top_level() {
+            match self.move_item(&path, destination) {
+                Ok(()) => moved += 1,
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        self.tree_selection.clear();
+        self.build_collection_tree();
+        (moved, first_error)
+    }
+
+    fn move_item(&mut self, path: &Path, destination: &Path) -> Result<(), MercuryError> {
+        let file_name = path
+            .file_name()
+            .ok_or(MercuryError::FileNotFound(path.display().to_string()))?;
+        let new_path = destination.join(file_name);
+        if new_path == path {
+            return Ok(());
+        }
+        // A folder can't go inside itself
+        if destination.starts_with(path) {
+            return Err(MercuryError::RenameFailed {
+                from: path.display().to_string(),
+                to: new_path.display().to_string(),
+                reason: "destination is inside the folder being moved".to_string(),
+            });
+        }
+        if new_path.exists() {
+            return Err(MercuryError::AlreadyExists {
+                kind: "Name".to_string(),
+                name: file_name.to_string_lossy().into_owned(),
+            });
+        }
+
+        fs::rename(path, &new_path).map_err(|e| MercuryError::RenameFailed {
+            from: path.display().to_string(),
+            to: new_path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+
+        if new_path.is_file() {
+            examples::rename_examples(path, &new_path);
+        }
+
+        // Keep the open request pointing at its new location
+        if let Some(current) = self.current_file.clone() {
+            if let Ok(rest) = current.strip_prefix(path) {
+                self.current_file = Some(new_path.join(rest));
+            }
+        }
+        Ok(())
+    }
+
+    /// Duplicate every selected request file next to its original
+    fn duplicate_selected(&mut self) -> (usize, Option<MercuryError>) {
+        let mut copied = 0;
+        let mut first_error = None;
+        for path in self.tree_selection.top_level() {
+            if !path.is_file() {
+                continue;
+            }
+            let name = Self::next_copy_name(&path);
+            match self.copy_request_file(&path, &name) {
+                Ok(_) => copied += 1,
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        self.build_collection_tree();
+        (copied, first_error)
+    }
+
     fn rename_item(&mut self, old_path: &Path, new_name: &str) -> Result<(), MercuryError> {
         let parent = old_path.parent().ok_or(MercuryError::FileNotFound(
             "No parent directory".to_string(),
@@ -752,6 +1903,10 @@ impl MercuryApp {
             reason: e.to_string(),
         })?;
 
+        if new_path.is_file() {
+            examples::rename_examples(old_path, &new_path);
+        }
+
         // Update current file if it was renamed
         if self.current_file.as_ref() == Some(&old_path.to_path_buf()) {
             self.current_file = Some(new_path.clone());
@@ -780,11 +1935,41 @@ impl MercuryApp {
         Ok(())
     }
 
-    fn duplicate_request(&mut self, path: &Path) -> Result<(), MercuryError> {
+    /// Suggest the first free `{stem}_copyN` name next to `path`
+    fn next_copy_name(path: &Path) -> String {
+        let parent = path.parent().unwrap_or(Path::new(""));
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let ext = path.extension().unwrap_or_default().to_string_lossy();
+
+        let mut counter = 1;
+        loop {
+            let name = format!("{}_copy{}", stem, counter);
+            if !parent.join(format!("{}.{}", name, ext)).exists() {
+                return name;
+            }
+            counter += 1;
+        }
+    }
+
+    /// Copy a request file next to the original under `name` and open the copy
+    fn duplicate_request(&mut self, path: &Path, name: &str) -> Result<(), MercuryError> {
+        let new_path = self.copy_request_file(path, name)?;
+        self.build_collection_tree();
+        self.load_file(&new_path);
+        Ok(())
+    }
+
+    /// Copy a request file next to the original under `name`, returning the new path
+    fn copy_request_file(&mut self, path: &Path, name: &str) -> Result<PathBuf, MercuryError> {
         if !path.is_file() {
             return Err(MercuryError::FileNotFound(path.display().to_string()));
         }
 
+        // Make sure the copy includes any unsaved edits to the source
+        if self.has_unsaved_changes && self.current_file.as_deref() == Some(path) {
+            self.save_current_file();
+        }
+
         let content = fs::read_to_string(path).map_err(|e| MercuryError::FileRead {
             path: path.display().to_string(),
             reason: e.to_string(),
@@ -793,17 +1978,19 @@ impl MercuryApp {
         let parent = path.parent().ok_or(MercuryError::FileNotFound(
             "No parent directory".to_string(),
         ))?;
-        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
         let ext = path.extension().unwrap_or_default().to_string_lossy();
+        let file_name = if name.ends_with(&format!(".{}", ext)) {
+            name.to_string()
+        } else {
+            format!("{}.{}", name, ext)
+        };
+        let new_path = parent.join(&file_name);
 
-        let mut counter = 1;
-        let mut new_path;
-        loop {
-            new_path = parent.join(format!("{}_copy{}.{}", stem, counter, ext));
-            if !new_path.exists() {
-                break;
-            }
-            counter += 1;
+        if new_path.exists() {
+            return Err(MercuryError::AlreadyExists {
+                kind: "File".to_string(),
+                name: file_name,
+            });
         }
 
         fs::write(&new_path, content).map_err(|e| MercuryError::FileWrite {
@@ -811,8 +1998,7 @@ impl MercuryApp {
             reason: e.to_string(),
         })?;
 
-        self.build_collection_tree();
-        Ok(())
+        Ok(new_path)
     }
 
     fn create_new_env(&mut self, name: &str) -> Result<(), MercuryError> {
@@ -839,143 +2025,1206 @@ impl MercuryApp {
         }
     }
 
-    pub fn execute_request(&mut self, ctx: &egui::Context) {
-        let url = substitute_variables(&self.url, &self.env_variables);
-        let headers_text = substitute_variables(&self.headers_text, &self.env_variables);
-        let body = substitute_variables(&self.body_text, &self.env_variables);
+    /// Store the current response as a named example of the open request
+    fn save_response_as_example(&mut self, name: &str) -> Result<(), MercuryError> {
+        let path = self.current_file.clone().ok_or(MercuryError::NoWorkspace)?;
+        if let Some(response) = &self.response {
+            examples::save_example(&path, name, &Response::from(response))?;
+            self.current_examples = examples::list_examples(&path);
+            self.baseline_cache = None;
+        }
+        Ok(())
+    }
 
-        // Parse headers
-        let mut headers = HashMap::new();
-        for line in headers_text.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
+    /// Show a saved example in the response panel without sending
+    pub fn view_example(&mut self, name: &str) {
+        if let Some(path) = &self.current_file {
+            if let Some(example) = examples::load_example(path, name) {
+                self.response = Some(example.into());
+                self.response_request = None;
+                self.request_error = None;
+                self.formatted_response_cache = None;
+                self.response_shape_cache = None;
+                self.response_json_cache = None;
+                self.grpc_error_cache = None;
+                self.ndjson_cache = None;
+                self.response_preview_cache = None;
+                self.baseline_cache = None;
+                self.hex_offset = 0;
+                self.rate_limit_until = None;
             }
-            if let Some((key, value)) = line.split_once(':') {
-                headers.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    pub fn delete_example(&mut self, name: &str) -> Result<(), MercuryError> {
+        if let Some(path) = self.current_file.clone() {
+            examples::delete_example(&path, name)?;
+            self.current_examples = examples::list_examples(&path);
+        }
+        Ok(())
+    }
+
+    /// Replace the tags stored in a request file
+    fn set_request_tags(&mut self, path: &Path, tags: Vec<String>) -> Result<(), MercuryError> {
+        // The open request keeps unsaved edits, so save through the form instead
+        if self.current_file.as_deref() == Some(path) {
+            self.request_tags = tags;
+            self.save_current_file();
+            self.build_collection_tree();
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| MercuryError::FileRead {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        let mut request = parse_request_file(&content)?;
+        request.tags = tags;
+        fs::write(path, serialize_request_file(&request)?).map_err(|e| {
+            MercuryError::FileWrite {
+                path: path.display().to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+
+        self.build_collection_tree();
+        Ok(())
+    }
+
+    /// Merge pasted `KEY=value` lines into the selected environment file.
+    /// Returns the number of variables written.
+    fn apply_env_paste(&mut self) -> Result<usize, MercuryError> {
+        let workspace = self
+            .workspace_path
+            .clone()
+            .ok_or(MercuryError::NoWorkspace)?;
+        if self.selected_env == 0 || self.selected_env >= self.env_files.len() {
+            return Err(MercuryError::NoEnvironment);
+        }
+
+        let vars = parse_env_str(&self.paste_env_text);
+        let env_path = workspace.join(&self.env_files[self.selected_env]);
+        serialize_env_file(&env_path, &vars).map_err(|e| MercuryError::FileWrite {
+            path: env_path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+
+        self.load_env();
+        Ok(vars.len())
+    }
+
+    /// Write one variable into the selected environment file
+    fn set_env_variable(&mut self, name: &str, value: &str) -> Result<(), MercuryError> {
+        let workspace = self
+            .workspace_path
+            .clone()
+            .ok_or(MercuryError::NoWorkspace)?;
+        if self.selected_env == 0 || self.selected_env >= self.env_files.len() {
+            return Err(MercuryError::NoEnvironment);
+        }
+
+        let env_path = workspace.join(&self.env_files[self.selected_env]);
+        serialize_env_file(&env_path, &[(name.to_string(), value.to_string())]).map_err(|e| {
+            MercuryError::FileWrite {
+                path: env_path.display().to_string(),
+                reason: e.to_string(),
             }
+        })?;
+
+        self.load_env();
+        Ok(())
+    }
+
+    /// Write the variables `.env.schema` expects but the selected environment
+    /// lacks into its file, with their defaults. Returns how many were added.
+    pub fn add_missing_env_vars(&mut self) -> Result<usize, MercuryError> {
+        let workspace = self
+            .workspace_path
+            .clone()
+            .ok_or(MercuryError::NoWorkspace)?;
+        if self.selected_env == 0 || self.selected_env >= self.env_files.len() {
+            return Err(MercuryError::NoEnvironment);
         }
 
+        let vars: Vec<(String, String)> = self
+            .missing_env_vars
+            .iter()
+            .map(|var| (var.name.clone(), var.default.clone()))
+            .collect();
+        let env_path = workspace.join(&self.env_files[self.selected_env]);
+        serialize_env_file(&env_path, &vars).map_err(|e| MercuryError::FileWrite {
+            path: env_path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+
+        self.load_env();
+        Ok(vars.len())
+    }
+
+    /// Fetch the spec at `openapi_url` with the shared client and import it into
+    /// the workspace, or a folder the user picks when none is open
+    fn import_openapi_url(&mut self) {
+        let url = self.openapi_url.trim().to_string();
+        let mut headers = HashMap::new();
+        if let Some((name, value)) = self.openapi_header.split_once(':') {
+            headers.insert(name.trim().to_string(), value.trim().to_string());
+        }
+        headers.insert(
+            "Accept".to_string(),
+            "application/json, application/yaml;q=0.9, */*;q=0.8".to_string(),
+        );
         let request = JsonRequest {
-            method: self.method.clone(),
-            url,
+            method: HttpMethod::GET,
+            url: url.clone(),
             headers,
-            body,
+            ..Default::default()
+        };
+        let client = self.http_client.clone();
+        let cookie_jar = self.cookie_jar.clone();
+        let timeouts = self.settings.timeouts();
+        let follow_redirects = self.settings.follow_redirects;
+        let current_workspace = self.workspace_path.clone();
+        let folder_tx = self.folder_tx.clone();
+        let toast_tx = self.toast_tx.clone();
+
+        std::thread::spawn(move || {
+            let spec = execute_request(
+                &request,
+                timeouts,
+                follow_redirects,
+                Some(&client),
+                Some(&cookie_jar),
+                None,
+            )
+            .and_then(|response| crate::importer::openapi::spec_from_response(&response));
+            let spec = match spec {
+                Ok(spec) => spec,
+                Err(e) => {
+                    let _ = toast_tx.send(Toast::new(format!("Import failed: {}", e), true));
+                    return;
+                }
+            };
+
+            let target_folder = current_workspace.or_else(|| {
+                rfd::FileDialog::new()
+                    .set_title("Choose where to save imported requests")
+                    .set_directory(
+                        dirs::document_dir().unwrap_or_else(|| std::path::PathBuf::from("~")),
+                    )
+                    .set_file_name("Mercury")
+                    .pick_folder()
+            });
+
+            if let Some(folder_path) = target_folder {
+                match crate::importer::openapi::import_openapi_spec(&spec, &folder_path, Some(&url))
+                {
+                    Ok((req_count, _)) => {
+                        let _ = toast_tx.send(import_toast(req_count, &folder_path));
+                        let _ = folder_tx.send(folder_path);
+                    }
+                    Err(e) => {
+                        let _ = toast_tx.send(Toast::new(format!("Import failed: {}", e), true));
+                    }
+                }
+            }
+        });
+    }
+
+    /// Store the keychain dialog's secret, then reference it from the selected
+    /// environment if a variable name was given. Returns the status message.
+    fn store_keychain_entry(&mut self) -> Result<String, MercuryError> {
+        let reference = KeychainRef {
+            service: self.keychain_service.trim().to_string(),
+            account: self.keychain_account.trim().to_string(),
+        };
+        keychain::store_secret(&reference, &self.keychain_secret)?;
+        self.keychain_secret.clear();
+
+        let variable = self.keychain_variable.trim().to_string();
+        if variable.is_empty() {
+            return Ok(format!("Stored {}", reference.token()));
+        }
+        self.set_env_variable(&variable, &reference.token())?;
+        Ok(format!("Stored {} as {}", reference.token(), variable))
+    }
+
+    fn secrets_path(&self) -> Result<PathBuf, MercuryError> {
+        self.workspace_path
+            .as_ref()
+            .map(|w| w.join(crate::core::constants::SECRETS_FILE))
+            .ok_or(MercuryError::NoWorkspace)
+    }
+
+    /// Start decrypting `.env.enc` with the entered passphrase; the result
+    /// arrives on `unlock_rx`
+    fn unlock_secrets(&mut self, ctx: &egui::Context) -> Result<(), MercuryError> {
+        let path = self.secrets_path()?;
+        let content = fs::read_to_string(&path).map_err(|e| MercuryError::FileRead {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        let passphrase = self.secrets_passphrase_input.clone();
+        let tx = self.unlock_tx.clone();
+        let ctx = ctx.clone();
+        self.unlocking_secrets = true;
+        std::thread::spawn(move || {
+            let plaintext = secrets::decrypt(&content, &passphrase);
+            let _ = tx.send((path, passphrase, plaintext));
+            ctx.request_repaint();
+        });
+        Ok(())
+    }
+
+    /// Apply a finished unlock, unless the workspace changed while it ran
+    fn finish_unlock(
+        &mut self,
+        path: PathBuf,
+        passphrase: String,
+        plaintext: Result<String, MercuryError>,
+        time: f64,
+    ) {
+        self.unlocking_secrets = false;
+        if self.secrets_path().ok() != Some(path) {
+            return;
+        }
+        match plaintext {
+            Ok(plaintext) => {
+                self.secret_variables = parse_env_str(&plaintext);
+                self.secrets_passphrase = Some(passphrase);
+                self.secrets_passphrase_input.clear();
+                self.show_unlock_secrets_dialog = false;
+                self.load_env();
+                self.last_action_message = Some((
+                    format!("Unlocked {} secrets", self.secret_variables.len()),
+                    time,
+                    false,
+                ));
+            }
+            Err(MercuryError::SecretsError(reason)) => self.secrets_error = Some(reason),
+            Err(e) => self.secrets_error = Some(e.to_string()),
+        }
+    }
+
+    /// Start adding or updating the secret dialog's variable and re-encrypting
+    /// `.env.enc`, creating it with the entered passphrase if there isn't one yet.
+    /// The result arrives on `secret_rx`.
+    fn save_secret(&mut self, ctx: &egui::Context) -> Result<(), MercuryError> {
+        let path = self.secrets_path()?;
+        let passphrase = match &self.secrets_passphrase {
+            Some(passphrase) => passphrase.clone(),
+            None if path.exists() => {
+                return Err(MercuryError::SecretsError(
+                    "Unlock the secrets file first".to_string(),
+                ))
+            }
+            None => self.secrets_passphrase_input.clone(),
+        };
+
+        let name = self.secret_name.trim().to_string();
+        let mut vars = self.secret_variables.clone();
+        match vars.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, value)) => *value = self.secret_value.clone(),
+            None => vars.push((name.clone(), self.secret_value.clone())),
+        }
+
+        let tx = self.secret_tx.clone();
+        let ctx = ctx.clone();
+        self.saving_secret = true;
+        std::thread::spawn(move || {
+            let saved = secrets::encrypt(&format_env(&vars), &passphrase).and_then(|sealed| {
+                fs::write(&path, sealed).map_err(|e| MercuryError::FileWrite {
+                    path: path.display().to_string(),
+                    reason: e.to_string(),
+                })?;
+                Ok(SavedSecret {
+                    path,
+                    passphrase,
+                    name,
+                    variables: vars,
+                })
+            });
+            let _ = tx.send(saved);
+            ctx.request_repaint();
+        });
+        Ok(())
+    }
+
+    /// Apply a secret written to `.env.enc`, unless the workspace changed meanwhile
+    fn finish_save_secret(&mut self, saved: Result<SavedSecret, MercuryError>, time: f64) {
+        self.saving_secret = false;
+        match saved {
+            Ok(saved) if self.secrets_path().ok() == Some(saved.path.clone()) => {
+                self.secret_variables = saved.variables;
+                self.secrets_passphrase = Some(saved.passphrase);
+                self.show_secret_dialog = false;
+                self.load_env();
+                self.last_action_message =
+                    Some((format!("Encrypted {} in .env.enc", saved.name), time, false));
+            }
+            Ok(_) => {}
+            Err(e) => self.last_action_message = Some((e.to_string(), time, true)),
+        }
+    }
+
+    pub fn execute_request(&mut self, ctx: &egui::Context) {
+        // One request at a time: responses are matched to `ongoing_request` by id,
+        // so a second send would orphan the first
+        if self.ongoing_request.is_some() {
+            return;
+        }
+
+        let request = match self.build_request(&self.env_variables, self.active_override()) {
+            Ok(request) => request,
+            Err(e) => {
+                self.response = None;
+                self.request_error = Some(e);
+                return;
+            }
+        };
+        let sent = SentRequest {
+            form: Request {
+                method: self.method.clone(),
+                url: self.url.clone(),
+                headers: self.headers_text.clone(),
+                body: self.body_text.clone(),
+            },
+            request,
+            grpc: self
+                .grpc_call
+                .clone()
+                .map(|call| (self.resolve_proto_path(&call.proto), call)),
         };
+        self.send(sent, ctx);
+    }
+
+    /// Send the last request again exactly as it went out, even if the form or
+    /// environment has changed since
+    pub fn retry_last(&mut self, ctx: &egui::Context) {
+        if self.ongoing_request.is_some() {
+            return;
+        }
+        if let Some(sent) = self.last_sent.clone() {
+            self.send(sent, ctx);
+        }
+    }
 
-        // Execute async request in background thread
+    /// Run `sent` on a background thread; the response arrives on `response_rx`
+    fn send(&mut self, sent: SentRequest, ctx: &egui::Context) {
         let ctx = ctx.clone();
         let tx = self.response_tx.clone();
-        let client = self.http_client.clone();
+        let (client, follow_redirects) = self.client_for(sent.request.follow_redirects);
+        let cookie_jar = self.cookie_jar.clone();
+        let timeouts = self.settings.timeouts();
 
         // Assign new ID
         self.request_id_counter += 1;
         let request_id = self.request_id_counter;
         let start_time = ctx.input(|i| i.time);
 
+        // A new request supersedes any still in flight
+        self.cancel_request();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.ongoing_cancel = Some(cancel.clone());
         self.ongoing_request = Some((request_id, start_time));
 
+        let SentRequest { request, grpc, .. } = sent.clone();
+        self.last_sent = Some(sent);
         std::thread::spawn(move || {
-            let response =
-                execute_request(&request, 30, true, Some(&client)).map_err(|e| e.to_string());
+            let response = match grpc {
+                Some((proto_path, call)) => fs::read_to_string(&proto_path)
+                    .map_err(|e| MercuryError::FileRead {
+                        path: proto_path.display().to_string(),
+                        reason: e.to_string(),
+                    })
+                    .and_then(|source| parse_proto(&source))
+                    .and_then(|proto| {
+                        grpc::execute_grpc_call(
+                            &request,
+                            &call,
+                            &proto,
+                            timeouts,
+                            Some(&client),
+                            Some(&cookie_jar),
+                            Some(&cancel),
+                        )
+                    }),
+                None => execute_request(
+                    &request,
+                    timeouts,
+                    follow_redirects,
+                    Some(&client),
+                    Some(&cookie_jar),
+                    Some(&cancel),
+                ),
+            };
             let _ = tx.send((request_id, response));
             ctx.request_repaint();
         });
     }
 
-    /// Cancel the currently running request (soft cancel)
-    /// We can't easily kill the thread, so we just ignore its result
-    pub fn cancel_request(&mut self) {
-        self.ongoing_request = None;
-    }
-
-    fn generate_curl(&self) -> String {
-        let url = substitute_variables(&self.url, &self.env_variables);
-        let headers_text = substitute_variables(&self.headers_text, &self.env_variables);
-        let body = substitute_variables(&self.body_text, &self.env_variables);
-
-        let mut curl = format!("curl -X {} '{}'", self.method.as_str(), url);
-
-        // Add headers
-        for line in headers_text.lines() {
-            if let Some((key, value)) = line.split_once(':') {
-                curl.push_str(&format!(" \\\n  -H '{}: {}'", key.trim(), value.trim()));
-            }
+    /// The open request with `vars` filled in and `env_override` applied, as
+    /// `execute_request` sends it
+    fn build_request(
+        &self,
+        vars: &HashMap<String, String>,
+        env_override: Option<&RequestOverride>,
+    ) -> Result<JsonRequest, MercuryError> {
+        let url = substitute_variables(&self.url, vars);
+
+        // Catch a missing base URL here rather than as a cryptic error from the client
+        let invalid_url = match Self::extract_variables(&url).first() {
+            Some(name) => Some(MercuryError::InvalidUrl(format!(
+                "{{{{{}}}}} is not set in the current environment",
+                name
+            ))),
+            // Keychain references are only resolved when the request is sent
+            None if url.contains("{{") => None,
+            None => crate::core::request::validate_resolved_url(&self.url, &url).err(),
+        };
+        if let Some(e) = invalid_url {
+            return Err(e);
         }
 
-        // Add body
-        if !body.is_empty() {
-            curl.push_str(&format!(" \\\n  -d '{}'", body.replace('\'', "'\\''")));
+        // A header repeated in any case is sent once, with its last value
+        let headers_text = substitute_variables(&self.headers_text, vars);
+        let mut headers = crate::utils::parse_header_lines(&headers_text);
+        self.folder_defaults
+            .substituted(vars)
+            .apply_headers(&mut headers);
+        let mut body_text = self.body_text.clone();
+        if let Some(env_override) = env_override {
+            // The body is substituted below, per field for forms
+            let env_override = RequestOverride {
+                headers: env_override
+                    .headers
+                    .iter()
+                    .map(|(k, v)| (substitute_variables(k, vars), substitute_variables(v, vars)))
+                    .collect(),
+                body: env_override.body.clone(),
+            };
+            crate::utils::apply_request_override(&mut headers, &mut body_text, &env_override);
         }
+        crate::utils::apply_default_user_agent(&mut headers, &self.settings.user_agent);
+
+        let is_form = headers.iter().any(|(k, v)| {
+            k.eq_ignore_ascii_case("Content-Type") && crate::utils::is_form_content_type(v)
+        });
+        let body = if is_form && !self.body_hex {
+            // Substitute per field so values like `a&b` are encoded, not split
+            let fields: Vec<_> = crate::utils::parse_form_body(&body_text)
+                .into_iter()
+                .map(|mut f| {
+                    f.key = substitute_variables(&f.key, vars);
+                    f.value = substitute_variables(&f.value, vars);
+                    f
+                })
+                .collect();
+            crate::utils::build_form_body(&fields)
+        } else {
+            substitute_variables(&body_text, vars)
+        };
 
-        curl
+        let resolve = substitute_variables(self.resolve_override.trim(), vars);
+        Ok(JsonRequest {
+            method: self.method.clone(),
+            url,
+            headers,
+            body,
+            body_hex: self.body_hex,
+            resolve: Some(resolve).filter(|r| !r.is_empty()),
+            timeout_secs: self.timeout_override,
+            follow_redirects: self.follow_redirects_override,
+            delay_ms: self.send_delay_ms,
+            save: self.save_rule(),
+            expect_content_type: Some(self.expect_content_type.trim().to_string())
+                .filter(|t| !t.is_empty()),
+            ..Default::default()
+        })
     }
 
-    fn copy_as_curl(&self, ctx: &egui::Context) {
-        let curl = self.generate_curl();
-        ctx.copy_text(curl);
+    /// The request's save rule, once both the path and the variable are filled in
+    fn save_rule(&self) -> Option<SaveRule> {
+        let variable = self
+            .save_variable
+            .trim()
+            .trim_start_matches("{{")
+            .trim_end_matches("}}")
+            .trim();
+        let path = self.save_path.trim();
+        (!variable.is_empty() && !path.is_empty()).then(|| SaveRule {
+            variable: variable.to_string(),
+            path: path.to_string(),
+        })
     }
-}
 
-impl MercuryApp {
-    /// Save app state to disk
-    pub fn save_state(&self) {
-        let state = AppState {
-            workspace_path: self
-                .workspace_path
-                .as_ref()
-                .map(|p| p.to_string_lossy().to_string()),
-            method: self.method.as_str().to_string(),
-            url: self.url.clone(),
-            headers_text: self.headers_text.clone(), // Auth is now in headers_text
-            body_text: self.body_text.clone(),
-            auth_text: String::new(), // Deprecated - auth now in headers_text
-            selected_tab: self.selected_tab,
-            selected_env: self.selected_env,
+    /// Keep the value `rule` picks out of a 2xx response as a session variable
+    fn apply_save_rule(&mut self, rule: &SaveRule, time: f64) {
+        let Some(response) = self
+            .response
+            .as_ref()
+            .filter(|r| (200..300).contains(&r.status))
+        else {
+            return;
         };
-        persistence::save_state(&state);
+        match crate::utils::extract_json_value(&response.body, &rule.path) {
+            Some(value) => {
+                match self
+                    .session_overrides
+                    .iter_mut()
+                    .find(|(name, _)| *name == rule.variable)
+                {
+                    Some((_, existing)) => *existing = value,
+                    None => self.session_overrides.push((rule.variable.clone(), value)),
+                }
+                self.load_env();
+                self.last_action_message =
+                    Some((format!("Saved {{{{{}}}}}", rule.variable), time, false));
+            }
+            None => self.toasts.push(Toast::new(
+                format!(
+                    "{} isn't in the response; {{{{{}}}}} wasn't saved",
+                    rule.path, rule.variable
+                ),
+                true,
+            )),
+        }
     }
 
-    /// Save recent requests to disk
-    pub fn save_recent_requests(&self) {
-        persistence::save_recent_requests(&self.recent_requests);
+    /// Seconds left before a delayed request goes out; `None` once it's on its way
+    pub fn send_countdown(&self, now: f64) -> Option<f64> {
+        let (_, start_time) = self.ongoing_request?;
+        let delay_ms = self.last_sent.as_ref()?.request.delay_ms?;
+        Some(start_time + delay_ms as f64 / 1000.0 - now).filter(|left| *left > 0.0)
     }
 
-    /// Clear timeline history from both memory and disk
-    pub fn clear_history(&mut self) {
-        self.timeline.clear();
-        persistence::clear_history();
+    /// The client for a request with this redirect override, and whether it follows redirects
+    fn client_for(&self, follow_redirects: Option<bool>) -> (Arc<reqwest::blocking::Client>, bool) {
+        let follow_redirects = follow_redirects.unwrap_or(self.settings.follow_redirects);
+        // Redirect handling is a client setting; keep the cookie jar either way
+        let client = if follow_redirects == self.settings.follow_redirects {
+            self.http_client.clone()
+        } else {
+            Arc::new(build_http_client(
+                &self.cookie_jar,
+                follow_redirects,
+                self.settings.timeouts(),
+            ))
+        };
+        (client, follow_redirects)
     }
 
-    pub fn render_collection_tree(
-        &mut self,
-        ui: &mut egui::Ui,
-        items: &mut [CollectionItem],
-        depth: usize,
-    ) {
-        let search = self.search_query.to_lowercase();
+    /// Resolve a `.proto` path: absolute as-is, otherwise next to the request file
+    /// (or the workspace root for unsaved requests)
+    pub fn resolve_proto_path(&self, proto: &str) -> PathBuf {
+        let path = Path::new(proto.trim());
+        if path.is_absolute() {
+            return path.to_path_buf();
+        }
+        self.proto_base_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
 
-        for item in items {
-            match item {
-                CollectionItem::Folder {
-                    name,
-                    path,
-                    expanded,
-                    children,
-                } => {
-                    // If searching, check if any child matches
-                    let folder_matches = if search.is_empty() {
-                        true
-                    } else {
-                        // Folder matches if its name or any descendant matches
-                        name.to_lowercase().contains(&search)
-                            || Self::folder_has_matching_children(children, &search)
-                    };
+    /// Where relative `.proto` paths start from
+    fn proto_base_dir(&self) -> Option<&Path> {
+        self.current_file
+            .as_ref()
+            .and_then(|file| file.parent())
+            .or(self.workspace_path.as_deref())
+    }
+
+    /// Run every request under `folder` with the configured concurrency and rate limit.
+    /// Variables are resolved from the selected environment when the run starts.
+    fn run_folder(&mut self, folder: &Path, ctx: &egui::Context) {
+        if self.workspace_path.is_none() {
+            return;
+        }
+        let ignore = self
+            .workspace_path
+            .as_deref()
+            .map(collection::workspace_ignore)
+            .unwrap_or_else(ignore::gitignore::Gitignore::empty);
+        let scan = collection::scan_collection(folder, &self.expanded_folders, &ignore);
+        let paths = collection::request_paths(&scan.items);
+
+        let env_vars = self.environment_only_variables();
+        let workspace = self.workspace_path.clone().unwrap_or_default();
+        let env_file = self
+            .env_files
+            .get(self.selected_env)
+            .filter(|_| self.selected_env > 0);
+        let mut entries = Vec::new();
+        let mut requests = Vec::new();
+        for path in paths {
+            let Some(request) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| parse_request_file(&content).ok())
+            else {
+                continue;
+            };
+            // gRPC calls need their proto file; folder runs send plain HTTP only
+            if request.grpc.is_some() {
+                continue;
+            }
+
+            entries.push(RunEntry {
+                name: path
+                    .strip_prefix(folder)
+                    .unwrap_or(&path)
+                    .with_extension("")
+                    .display()
+                    .to_string(),
+                method: request.method.clone(),
+                status: RunStatus::Pending,
+                response: None,
+            });
+            let inherited = folder_defaults::load_inherited(&path, &workspace);
+            let mut vars = env_vars.clone();
+            inherited.apply_variables(&mut vars);
+            let vars = &vars;
+
+            let mut headers = request.headers.clone();
+            let mut body = request.body.clone();
+            if let Some(env_override) =
+                env_file.and_then(|file| crate::utils::request_override(&request.overrides, file))
+            {
+                crate::utils::apply_request_override(&mut headers, &mut body, env_override);
+            }
+            let mut headers: HashMap<String, String> = headers
+                .iter()
+                .map(|(k, v)| (substitute_variables(k, vars), substitute_variables(v, vars)))
+                .collect();
+            inherited.substituted(vars).apply_headers(&mut headers);
+            crate::utils::apply_default_user_agent(&mut headers, &self.settings.user_agent);
+            requests.push(JsonRequest {
+                url: substitute_variables(&request.url, vars),
+                headers,
+                body: substitute_variables(&body, vars),
+                ..request
+            });
+        }
+
+        // One run at a time
+        if let Some(run) = &self.folder_run {
+            run.cancel.store(true, Ordering::Relaxed);
+        }
+        self.run_id_counter += 1;
+        let id = self.run_id_counter;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.folder_run = Some(FolderRun {
+            id,
+            folder_name: folder
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            entries,
+            cancel: cancel.clone(),
+        });
+        self.show_folder_run = true;
+
+        let ctx = ctx.clone();
+        let tx = self.run_tx.clone();
+        let client = self.http_client.clone();
+        let cookie_jar = self.cookie_jar.clone();
+        let concurrency = self.settings.run_concurrency;
+        let rate_limit = self.settings.run_rate_limit;
+        let timeouts = self.settings.timeouts();
+        let follow_redirects = self.settings.follow_redirects;
+        std::thread::spawn(move || {
+            runner::run_requests(
+                &requests,
+                concurrency,
+                rate_limit,
+                timeouts,
+                follow_redirects,
+                &client,
+                &cookie_jar,
+                &cancel,
+                |index| {
+                    let _ = tx.send((id, index, RunStatus::Running, None));
+                    ctx.request_repaint();
+                },
+                |index, result| {
+                    let _ = tx.send((id, index, RunStatus::of(&result), result.ok()));
+                    ctx.request_repaint();
+                },
+            );
+        });
+    }
+
+    /// Send the open request once to each environment in `envs` (indices into
+    /// `env_files`), all at once, and compare the responses side by side
+    pub fn compare_environments(&mut self, envs: &[usize], ctx: &egui::Context) {
+        // One comparison at a time
+        if let Some(comparison) = &self.env_compare {
+            comparison.cancel.store(true, Ordering::Relaxed);
+        }
+        self.run_id_counter += 1;
+        let id = self.run_id_counter;
+        let cancel = Arc::new(AtomicBool::new(false));
+        let request_name = self
+            .current_file
+            .as_ref()
+            .and_then(|file| file.file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.url.clone());
+        let names = envs
+            .iter()
+            .filter_map(|&index| self.env_files.get(index).cloned())
+            .collect();
+        let mut comparison =
+            EnvComparison::new(id, self.method.clone(), request_name, names, cancel.clone());
+
+        // A variable one environment doesn't set fails only that column
+        let mut columns = Vec::new();
+        let mut requests = Vec::new();
+        for (column, &index) in envs.iter().enumerate() {
+            let (vars, _) = self.env_layers(index);
+            let env_override = self
+                .env_files
+                .get(index)
+                .and_then(|file| crate::utils::request_override(&self.request_overrides, file));
+            match self.build_request(&vars, env_override) {
+                Ok(request) => {
+                    columns.push(column);
+                    requests.push(request);
+                }
+                Err(e) => comparison.update(column, RunStatus::Failed(e.to_string()), None),
+            }
+        }
+        self.env_compare = Some(comparison);
+        self.show_env_compare = true;
+
+        let ctx = ctx.clone();
+        let tx = self.compare_tx.clone();
+        let (client, follow_redirects) = self.client_for(self.follow_redirects_override);
+        let cookie_jar = self.cookie_jar.clone();
+        let rate_limit = self.settings.run_rate_limit;
+        let timeouts = self.settings.timeouts();
+        std::thread::spawn(move || {
+            runner::run_requests(
+                &requests,
+                requests.len(),
+                rate_limit,
+                timeouts,
+                follow_redirects,
+                &client,
+                &cookie_jar,
+                &cancel,
+                |index| {
+                    let _ = tx.send((id, columns[index], RunStatus::Running, None));
+                    ctx.request_repaint();
+                },
+                |index, result| {
+                    let response = result.as_ref().ok().map(Response::from);
+                    let _ = tx.send((id, columns[index], RunStatus::of(&result), response));
+                    ctx.request_repaint();
+                },
+            );
+        });
+    }
+
+    /// What quitting now would cut short, if anything
+    pub fn work_in_progress(&self) -> Option<&'static str> {
+        if self.ongoing_request.is_some() {
+            Some("A request is still running.")
+        } else if self.folder_run.as_ref().is_some_and(|run| !run.is_done()) {
+            Some("A folder run is still running.")
+        } else if self.env_compare.as_ref().is_some_and(|c| !c.is_done()) {
+            Some("An environment comparison is still running.")
+        } else {
+            None
+        }
+    }
+
+    /// Cancel the currently running request.
+    /// The worker thread stops at its next cancel check: between body chunks, or once
+    /// the server responds. Until then (or the timeout) it keeps waiting in the
+    /// background; its late result is ignored by id.
+    pub fn cancel_request(&mut self) {
+        if let Some(cancel) = self.ongoing_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        self.ongoing_request = None;
+    }
+
+    /// Variables the request uses that look like credentials, by name or because
+    /// they come from the encrypted secrets file
+    fn secret_variables_used(&self) -> Vec<String> {
+        let mut text = format!("{}\n{}\n{}", self.url, self.headers_text, self.body_text);
+        for header in &self.folder_defaults.headers {
+            text.push('\n');
+            text.push_str(&header.value);
+        }
+        let mut secrets: Vec<String> = Self::extract_variables(&text)
+            .into_iter()
+            .filter(|name| self.env_variables.contains_key(name))
+            .filter(|name| {
+                crate::utils::is_secret_name(name)
+                    || self.env_sources.get(name) == Some(&VarSource::Secret)
+            })
+            .collect();
+        secrets.sort();
+        secrets.dedup();
+        secrets
+    }
+
+    /// Credentials a copy or export of the request would carry: the secret
+    /// variables it uses, and literal credentials in headers, query, and body
+    fn export_secrets_used(&self) -> Vec<String> {
+        let mut secrets = self.secret_variables_used();
+        let (mut url, mut headers, mut body) = self.substituted_request(false);
+        secrets.extend(mask_literal_secrets(&mut url, &mut headers, &mut body));
+        secrets.sort();
+        secrets.dedup();
+        secrets
+    }
+
+    /// The request's URL, headers, and body with variables resolved. Unless
+    /// `inline_secrets`, secret variables stay as `{{NAME}}` placeholders and
+    /// literal credentials are swapped for them.
+    pub fn resolved_request(
+        &self,
+        inline_secrets: bool,
+    ) -> (String, Vec<(String, String)>, String) {
+        let (mut url, mut headers, mut body) = self.substituted_request(inline_secrets);
+        if !inline_secrets {
+            mask_literal_secrets(&mut url, &mut headers, &mut body);
+        }
+        (url, headers, body)
+    }
+
+    /// The request with variables filled in, secret ones only if `inline_secrets`.
+    /// Headers inherited from folders follow the request's own, unless it sets them.
+    fn substituted_request(&self, inline_secrets: bool) -> (String, Vec<(String, String)>, String) {
+        let mut variables = self.env_variables.clone();
+        if !inline_secrets {
+            for name in self.secret_variables_used() {
+                variables.remove(&name);
+            }
+        }
+        let url = substitute_variables(&self.url, &variables);
+        let headers_text = substitute_variables(&self.headers_text, &variables);
+        let body = substitute_variables(&self.body_text, &variables);
+
+        let mut headers: Vec<(String, String)> = headers_text
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+        for header in self.folder_defaults.substituted(&variables).headers {
+            if crate::utils::header_value(&headers_text, &header.name).is_none() {
+                headers.push((header.name, header.value));
+            }
+        }
+        (url, headers, body)
+    }
+
+    /// The request as a cURL command. Unless `inline_secrets`, secret variables
+    /// stay as `{{NAME}}` placeholders.
+    fn generate_curl(&self, inline_secrets: bool) -> String {
+        let (url, headers, body) = self.resolved_request(inline_secrets);
+        generate_curl(&self.method, &url, &headers, &body, self.body_hex)
+    }
+
+    /// The request and `response` as a Markdown example for API docs. Unless
+    /// `inline_secrets`, secrets stay as placeholders and credential headers
+    /// are masked.
+    pub fn generate_markdown_example(
+        &self,
+        response: &HttpResponse,
+        inline_secrets: bool,
+    ) -> String {
+        let options = MarkdownOptions {
+            include_headers: self.markdown_include_headers,
+            redact_secrets: !inline_secrets,
+        };
+        let (url, headers, body) = self.resolved_request(inline_secrets);
+        generate_markdown(&self.method, &url, &headers, &body, response, options)
+    }
+
+    /// Copy or export the request, asking first whether to inline the
+    /// credentials it would carry. A redacted Markdown copy never asks.
+    pub fn export_request(&mut self, ctx: &egui::Context, export: SecretExport) {
+        if export == SecretExport::Markdown && self.markdown_redact_secrets {
+            self.finish_export(ctx, export, false);
+            return;
+        }
+        self.export_secrets = match export {
+            SecretExport::ResponseCurl => self
+                .response_request
+                .as_ref()
+                .map(|sent| sent.secrets.clone())
+                .unwrap_or_default(),
+            _ => self.export_secrets_used(),
+        };
+        if self.export_secrets.is_empty() {
+            self.finish_export(ctx, export, true);
+        } else {
+            self.export_inline_secrets = false;
+            self.secrets_prompt = Some(export);
+        }
+    }
+
+    fn finish_export(&mut self, ctx: &egui::Context, export: SecretExport, inline_secrets: bool) {
+        let time = ctx.input(|i| i.time);
+        match export {
+            SecretExport::Curl => {
+                ctx.copy_text(self.generate_curl(inline_secrets));
+                self.copied_feedback_until = time + 2.0;
+                self.last_action_message = Some(("Copied as cURL".to_string(), time, false));
+            }
+            SecretExport::ResponseCurl => {
+                let Some(sent) = &self.response_request else {
+                    return;
+                };
+                let (url, headers, body) = if inline_secrets {
+                    (&sent.url, &sent.headers, &sent.body)
+                } else {
+                    (&sent.masked.0, &sent.masked.1, &sent.masked.2)
+                };
+                ctx.copy_text(generate_curl(
+                    &sent.method,
+                    url,
+                    headers,
+                    body,
+                    sent.body_hex,
+                ));
+                self.last_action_message = Some(("Copied as cURL".to_string(), time, false));
+            }
+            SecretExport::Markdown => {
+                let Some(response) = &self.response else {
+                    return;
+                };
+                ctx.copy_text(self.generate_markdown_example(response, inline_secrets));
+                self.last_action_message = Some(("Copied as Markdown".to_string(), time, false));
+            }
+        }
+        ctx.request_repaint();
+    }
+
+    /// Save the request as a shell script. When secrets stay as placeholders,
+    /// a `.env.example` beside it lists them. Returns the toast reporting the
+    /// outcome, or None if the dialog was cancelled.
+    fn save_curl_script(&self, inline_secrets: bool) -> Option<Toast> {
+        let path = rfd::FileDialog::new()
+            .set_title("Save as cURL Script")
+            .set_file_name("request.sh")
+            .save_file()?;
+        let write = |path: &Path, content: String| {
+            fs::write(path, content).map_err(|e| MercuryError::FileWrite {
+                path: path.display().to_string(),
+                reason: e.to_string(),
+            })
+        };
+
+        let script = format!("#!/bin/sh\n\n{}\n", self.generate_curl(inline_secrets));
+        let mut result = write(&path, script);
+        if result.is_ok() && !inline_secrets && !self.export_secrets.is_empty() {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let mut example = format!("# Values for the placeholders in {}\n", name);
+            for secret in &self.export_secrets {
+                example.push_str(&format!("{}=\n", secret));
+            }
+            result = write(&path.with_file_name(".env.example"), example);
+        }
+
+        Some(match result {
+            Ok(()) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                Toast::new(format!("Saved {}", name), false)
+                    .with_action("Open", ToastAction::Open(path.clone()))
+            }
+            Err(e) => Toast::new(format!("Failed to save script: {}", e), true),
+        })
+    }
+}
+
+impl MercuryApp {
+    /// Collapse `panel`, or restore it if it already is. Only one can be collapsed.
+    pub fn toggle_collapsed(&mut self, panel: CollapsedPanel) {
+        self.collapsed_panel = if self.collapsed_panel == Some(panel) {
+            None
+        } else {
+            Some(panel)
+        };
+    }
+
+    /// Save app state to disk
+    pub fn save_state(&mut self) {
+        // State and settings belong to the first window; extra windows are separate
+        // processes, so their writes would race its own
+        if self.extra_window {
+            return;
+        }
+
+        self.reap_child_windows();
+        let state = AppState {
+            workspace_path: self
+                .workspace_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+            method: self.method.as_str().to_string(),
+            url: self.url.clone(),
+            headers_text: self.headers_text.clone(), // Auth is now in headers_text
+            body_text: self.body_text.clone(),
+            auth_text: String::new(), // Deprecated - auth now in headers_text
+            selected_tab: self.selected_tab,
+            selected_env: self.selected_env,
+            settings: self.settings.clone(),
+            recent_workspaces: self
+                .recent_workspaces
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect(),
+            current_file: self
+                .current_file
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+            collapsed_panel: self.collapsed_panel,
+            response_view: self.response_view,
+            response_tab: self.response_tab,
+            show_timeline: self.show_timeline,
+            unescape_unicode: self.unescape_unicode,
+            open_windows: self
+                .child_windows
+                .iter()
+                .map(|(workspace, _)| workspace.to_string_lossy().to_string())
+                .collect(),
+        };
+        persistence::save_state(&state);
+    }
+
+    /// Persist settings and apply the ones that need more than a re-render
+    pub fn apply_settings(&mut self) {
+        self.http_client = Arc::new(build_http_client(
+            &self.cookie_jar,
+            self.settings.follow_redirects,
+            self.settings.timeouts(),
+        ));
+        self.save_state();
+    }
+
+    /// Save recent requests to disk
+    pub fn save_recent_requests(&self) {
+        persistence::save_recent_requests(&self.recent_requests);
+    }
+
+    /// Put `pending_request_body` into the request body, asking first if that
+    /// would overwrite a body that isn't empty
+    fn use_as_request_body(&mut self, now: f64) {
+        if self.body_text.trim().is_empty() || self.body_text == self.pending_request_body {
+            let body = std::mem::take(&mut self.pending_request_body);
+            self.set_request_body(body, now);
+        } else {
+            self.show_replace_body_confirm = true;
+        }
+    }
+
+    fn set_request_body(&mut self, body: String, now: f64) {
+        self.set_body(body);
+        // A JSON type already set, like `application/vnd.api+json`, is kept
+        let is_json = crate::utils::header_value(&self.headers_text, "Content-Type")
+            .is_some_and(|ct| crate::utils::BodyFormat::Json.matches_content_type(&ct));
+        if !is_json {
+            self.headers_text = crate::utils::set_header_line(
+                &self.headers_text,
+                "Content-Type",
+                "application/json",
+            );
+        }
+        self.selected_tab = 0;
+        self.last_action_message = Some(("Copied to the request body".to_string(), now, false));
+    }
+
+    /// Requests whose contents match the search box, refreshing the index first
+    /// if the workspace changed since it was last used
+    pub fn content_search_hits(&mut self) -> Vec<ContentHit> {
+        if self.content_index_stale {
+            let paths = collection::request_paths(&self.collection_tree);
+            self.content_index
+                .refresh(paths.iter().map(PathBuf::as_path));
+            self.content_index_stale = false;
+            self.content_hits = None;
+        }
+        let query = self.search_query.trim();
+        match &self.content_hits {
+            Some((searched, hits)) if searched == query => hits.clone(),
+            _ => {
+                let hits = self
+                    .content_index
+                    .search(query, crate::core::constants::MAX_CONTENT_SEARCH_RESULTS);
+                self.content_hits = Some((query.to_string(), hits.clone()));
+                hits
+            }
+        }
+    }
+
+    /// Read the response preview for the history entry at `timestamp` on a worker,
+    /// since that means parsing the whole history file; it lands in `history_previews`
+    pub fn load_history_preview(&mut self, timestamp: f64, ctx: &egui::Context) {
+        let key = timestamp.to_bits();
+        if !self.history_previews_loading.insert(key) {
+            return;
+        }
+        let tx = self.history_preview_tx.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let preview = persistence::load_history_entry(timestamp)
+                .map(|entry| {
+                    crate::utils::body_preview(
+                        &entry.response.body,
+                        crate::core::constants::HISTORY_PREVIEW_CHARS,
+                    )
+                })
+                .unwrap_or_default();
+            let _ = tx.send((key, preview));
+            ctx.request_repaint();
+        });
+    }
+
+    /// Clear timeline history from both memory and disk
+    pub fn clear_history(&mut self) {
+        self.timeline.clear();
+        self.run_stats_cache = None;
+        self.last_status.clear();
+        self.history_previews.clear();
+        persistence::clear_history();
+    }
+
+    pub fn render_collection_tree(
+        &mut self,
+        ui: &mut egui::Ui,
+        items: &mut [CollectionItem],
+        depth: usize,
+    ) {
+        let search = self.search_query.to_lowercase();
+
+        for item in items {
+            match item {
+                CollectionItem::Folder {
+                    name,
+                    path,
+                    expanded,
+                    children,
+                } => {
+                    // If searching, check if any child matches
+                    let folder_matches = if search.is_empty() {
+                        true
+                    } else {
+                        // Folder matches if its name or any descendant matches
+                        Self::folder_name_matches(name, &search)
+                            || Self::folder_has_matching_children(children, &search)
+                    };
 
                     if !folder_matches {
                         continue;
                     }
 
+                    self.tree_selection.push_row(path);
+                    // Placeholder behind the row, filled in once the row rect is known
+                    let row_bg = ui.painter().add(egui::Shape::Noop);
+
                     let folder_row = ui.horizontal(|ui| {
                         ui.add_space(
                             (depth * crate::theme::Indent::TREE_LEVEL as usize) as f32 + 12.0,
@@ -1011,17 +3260,27 @@ impl MercuryApp {
                         egui::pos2(row_rect.min.x, row_rect.min.y),
                         egui::pos2(ui.available_width() + row_rect.min.x, row_rect.max.y),
                     );
+                    self.paint_selection_bg(ui, row_bg, full_rect, path);
                     let folder_response = ui.interact(
                         full_rect,
                         egui::Id::new(("folder", path.as_path())),
                         egui::Sense::click(),
                     );
+                    folder_response.widget_info(|| {
+                        let state = if *expanded { "expanded" } else { "collapsed" };
+                        egui::WidgetInfo::labeled(
+                            egui::WidgetType::CollapsingHeader,
+                            true,
+                            format!("Folder {}, {}", name, state),
+                        )
+                    });
 
                     if folder_response.hovered() {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
                     }
 
-                    if folder_response.clicked() {
+                    let modifiers = ui.input(|i| i.modifiers);
+                    if folder_response.clicked() && !self.tree_selection.click(path, modifiers) {
                         *expanded = !*expanded;
                         self.selected_folder = Some(path.clone());
                     }
@@ -1035,12 +3294,23 @@ impl MercuryApp {
                         self.render_collection_tree(ui, children, depth + 1);
                     }
                 }
-                CollectionItem::Request { name, path, method } => {
+                CollectionItem::Request {
+                    name,
+                    path,
+                    method,
+                    url,
+                    tags,
+                } => {
                     // If searching, skip non-matching requests
-                    if !search.is_empty() && !name.to_lowercase().contains(&search) {
+                    if !search.is_empty()
+                        && !crate::utils::request_matches_search(name, tags, &search)
+                    {
                         continue;
                     }
 
+                    self.tree_selection.push_row(path);
+                    let row_bg = ui.painter().add(egui::Shape::Noop);
+
                     let request_row = ui.horizontal(|ui| {
                         ui.add_space(
                             (depth * crate::theme::Indent::TREE_LEVEL as usize) as f32 + 14.0,
@@ -1073,6 +3343,18 @@ impl MercuryApp {
                         }
 
                         ui.label(name_text);
+
+                        for tag in tags.iter() {
+                            ui.label(
+                                egui::RichText::new(tag.as_str())
+                                    .size(crate::theme::FontSize::XS)
+                                    .color(crate::theme::Colors::tag_color(tag)),
+                            );
+                        }
+
+                        if let Some(method) = method {
+                            self.render_status_badge(ui, method, url);
+                        }
                     });
 
                     // Create interactive area covering the full row
@@ -1081,17 +3363,25 @@ impl MercuryApp {
                         egui::pos2(row_rect.min.x, row_rect.min.y),
                         egui::pos2(ui.available_width() + row_rect.min.x, row_rect.max.y),
                     );
-                    let request_response = ui.interact(
-                        full_rect,
-                        egui::Id::new(("request", path.as_path())),
-                        egui::Sense::click(),
-                    );
+                    self.paint_selection_bg(ui, row_bg, full_rect, path);
+                    let display_name = name.strip_suffix(".json").unwrap_or(name);
+                    let request_response = ui
+                        .interact(
+                            full_rect,
+                            egui::Id::new(("request", path.as_path())),
+                            egui::Sense::click(),
+                        )
+                        .accessible_button(&match method {
+                            Some(method) => format!("{} {}", method.as_str(), display_name),
+                            None => display_name.to_string(),
+                        });
 
                     if request_response.hovered() {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
                     }
 
-                    if request_response.clicked() {
+                    let modifiers = ui.input(|i| i.modifiers);
+                    if request_response.clicked() && !self.tree_selection.click(path, modifiers) {
                         self.load_file(path);
                     }
 
@@ -1099,14 +3389,444 @@ impl MercuryApp {
                         self.render_request_context_menu(ui, name.clone(), path.clone());
                     });
                 }
+                CollectionItem::HttpFile {
+                    name,
+                    path,
+                    expanded,
+                    requests,
+                } => {
+                    if !search.is_empty() && !Self::http_file_matches(name, requests, &search) {
+                        continue;
+                    }
+
+                    let file_row = ui.horizontal(|ui| {
+                        ui.add_space(
+                            (depth * crate::theme::Indent::TREE_LEVEL as usize) as f32 + 12.0,
+                        );
+                        let chevron_icon = if *expanded {
+                            Icons::CHEVRON_DOWN
+                        } else {
+                            Icons::CHEVRON_RIGHT
+                        };
+                        ui.label(
+                            egui::RichText::new(chevron_icon).size(crate::theme::FontSize::SM),
+                        );
+                        ui.label(egui::RichText::new(Icons::FILE).size(crate::theme::FontSize::SM));
+                        ui.add_space(crate::theme::Spacing::XS);
+                        ui.label(
+                            egui::RichText::new(name.as_str()).size(crate::theme::FontSize::SM),
+                        );
+                    });
+
+                    let row_rect = file_row.response.rect;
+                    let full_rect = egui::Rect::from_min_max(
+                        egui::pos2(row_rect.min.x, row_rect.min.y),
+                        egui::pos2(ui.available_width() + row_rect.min.x, row_rect.max.y),
+                    );
+                    let file_response = ui.interact(
+                        full_rect,
+                        egui::Id::new(("http_file", path.as_path())),
+                        egui::Sense::click(),
+                    );
+                    file_response.widget_info(|| {
+                        let state = if *expanded { "expanded" } else { "collapsed" };
+                        egui::WidgetInfo::labeled(
+                            egui::WidgetType::CollapsingHeader,
+                            true,
+                            format!("{}, {} requests, {}", name, requests.len(), state),
+                        )
+                    });
+                    if file_response.hovered() {
+                        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                    }
+                    if file_response.clicked() {
+                        *expanded = !*expanded;
+                    }
+
+                    if !*expanded && search.is_empty() {
+                        continue;
+                    }
+                    for (index, entry) in requests.iter().enumerate() {
+                        if !search.is_empty()
+                            && !Self::folder_name_matches(name, &search)
+                            && !crate::utils::request_matches_search(&entry.name, &[], &search)
+                        {
+                            continue;
+                        }
+                        self.render_http_file_request(ui, path, index, entry, depth + 1);
+                    }
+                }
             }
         }
     }
 
-    /// Helper to render common context menu items (Rename, Delete, Copy Path)
-    fn render_context_menu_common(&mut self, ui: &mut egui::Ui, name: String, path: PathBuf) {
-        if menu_button(ui, Icons::EDIT, "Rename") {
-            self.context_menu_item = Some(path.clone());
+    /// One request row under a `.http` file; clicking it opens that request
+    fn render_http_file_request(
+        &mut self,
+        ui: &mut egui::Ui,
+        path: &Path,
+        index: usize,
+        entry: &HttpFileEntry,
+        depth: usize,
+    ) {
+        let is_current =
+            self.current_file.as_deref() == Some(path) && self.current_block == Some(index);
+        let row = ui.horizontal(|ui| {
+            ui.add_space((depth * crate::theme::Indent::TREE_LEVEL as usize) as f32 + 14.0);
+            ui.label(
+                egui::RichText::new(entry.method.as_str())
+                    .color(crate::theme::Colors::method_color(entry.method.as_str()))
+                    .size(crate::theme::FontSize::XS)
+                    .strong(),
+            );
+            ui.add_space(crate::theme::Spacing::XS);
+            let mut name_text =
+                egui::RichText::new(entry.name.as_str()).size(crate::theme::FontSize::SM);
+            if is_current {
+                name_text = name_text
+                    .strong()
+                    .color(crate::theme::Colors::SELECTED_ITEM);
+            }
+            ui.label(name_text);
+            self.render_status_badge(ui, &entry.method, &entry.url);
+        });
+
+        let row_rect = row.response.rect;
+        let full_rect = egui::Rect::from_min_max(
+            egui::pos2(row_rect.min.x, row_rect.min.y),
+            egui::pos2(ui.available_width() + row_rect.min.x, row_rect.max.y),
+        );
+        let response = ui
+            .interact(
+                full_rect,
+                egui::Id::new(("http_request", path, index)),
+                egui::Sense::click(),
+            )
+            .accessible_button(&format!("{} {}", entry.method.as_str(), entry.name));
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
+        if response.clicked() {
+            self.load_http_request(path, index);
+        }
+    }
+
+    /// Last response status for a request row, when tree badges are on
+    fn render_status_badge(&self, ui: &mut egui::Ui, method: &HttpMethod, url: &str) {
+        if !self.settings.tree_status_badges || url.is_empty() {
+            return;
+        }
+        let Some(&status) = self.last_status.get(&status_key(method, url)) else {
+            return;
+        };
+        let text = if status == 0 {
+            Icons::WARNING.to_string()
+        } else {
+            status.to_string()
+        };
+        ui.label(
+            egui::RichText::new(text)
+                .size(crate::theme::FontSize::XS)
+                .color(crate::theme::Colors::status_color(status)),
+        )
+        .on_hover_text(if status == 0 {
+            "Last send failed".to_string()
+        } else {
+            format!("Last response: {}", status)
+        });
+    }
+
+    /// Variable names of the active environment and where each value comes from.
+    /// Values stay hidden; they may be secrets.
+    fn env_sources_tooltip(&self, ui: &mut egui::Ui) {
+        if self.env_sources.is_empty() {
+            ui.label("No variables");
+            return;
+        }
+
+        let mut names: Vec<_> = self.env_sources.iter().collect();
+        names.sort_by(|a, b| a.0.cmp(b.0));
+        egui::Grid::new("env_sources")
+            .num_columns(2)
+            .spacing([crate::theme::Spacing::LG, crate::theme::Spacing::XS])
+            .show(ui, |ui| {
+                for (name, source) in names {
+                    ui.label(egui::RichText::new(name).monospace());
+                    let (text, color) = match source {
+                        VarSource::Own => ("", crate::theme::Colors::TEXT_MUTED),
+                        VarSource::Inherited => {
+                            ("inherited from .env", crate::theme::Colors::TEXT_MUTED)
+                        }
+                        VarSource::Overridden => ("overrides .env", crate::theme::Colors::WARNING),
+                        VarSource::Secret => ("from .env.enc", crate::theme::Colors::SUCCESS),
+                        VarSource::Folder => {
+                            ("from .folder.json", crate::theme::Colors::TEXT_MUTED)
+                        }
+                        VarSource::Session => ("session override", crate::theme::Colors::WARNING),
+                    };
+                    ui.label(
+                        egui::RichText::new(text)
+                            .size(crate::theme::FontSize::SM)
+                            .color(color),
+                    );
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Folders in tree order as `(depth, name, path)`, for picking a move destination
+    fn collect_folders(
+        items: &[CollectionItem],
+        depth: usize,
+        out: &mut Vec<(usize, String, PathBuf)>,
+    ) {
+        for item in items {
+            if let CollectionItem::Folder {
+                name,
+                path,
+                children,
+                ..
+            } = item
+            {
+                out.push((depth, name.clone(), path.clone()));
+                Self::collect_folders(children, depth + 1, out);
+            }
+        }
+    }
+
+    /// Status bar message for a bulk tree action: the count, or the first error
+    fn report_bulk_outcome(
+        &mut self,
+        verb: &str,
+        (count, error): (usize, Option<MercuryError>),
+        time: f64,
+    ) {
+        let items = if count == 1 { "item" } else { "items" };
+        self.last_action_message = Some(match error {
+            Some(e) => (
+                format!("{} {} {}; {}", verb, count, items, e.user_message()),
+                time,
+                true,
+            ),
+            None => (format!("{} {} {}", verb, count, items), time, false),
+        });
+    }
+
+    /// Route each dropped file to the importer it belongs to: request files and
+    /// `.http` files are added to the workspace like "Add to Workspace", while
+    /// collections and specs import in the background like the Import menu
+    fn import_dropped(&mut self, paths: Vec<PathBuf>, time: f64) {
+        let mut adds = Vec::new();
+        let mut imports = Vec::new();
+        for path in paths {
+            match detect::detect_import(&path) {
+                Some(ImportKind::Request | ImportKind::HttpFile) => adds.push(path),
+                Some(kind) => imports.push((kind, path)),
+                None => self.toasts.push(Toast::new(
+                    format!(
+                        "Can't import {}: not a collection, spec, or request file",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    true,
+                )),
+            }
+        }
+
+        if !adds.is_empty() {
+            match self.workspace_path.clone() {
+                Some(workspace) => {
+                    // A drop during a rename prompt joins the add already underway
+                    if self.add_queue.is_empty() {
+                        self.add_destination = Some(workspace);
+                        self.add_report = AddReport::default();
+                        self.add_queue.extend(adds);
+                        self.process_add_queue(time);
+                    } else {
+                        self.add_queue.extend(adds);
+                    }
+                }
+                None => self
+                    .toasts
+                    .push(Toast::new("Open a workspace to add requests to", true)),
+            }
+        }
+
+        if imports.is_empty() {
+            return;
+        }
+        let current_workspace = self.workspace_path.clone();
+        let folder_tx = self.folder_tx.clone();
+        let toast_tx = self.toast_tx.clone();
+        std::thread::spawn(move || {
+            let target_folder = current_workspace.or_else(|| {
+                rfd::FileDialog::new()
+                    .set_title("Choose where to save imported collection")
+                    .set_directory(
+                        dirs::document_dir().unwrap_or_else(|| std::path::PathBuf::from("~")),
+                    )
+                    .set_file_name("Mercury")
+                    .pick_folder()
+            });
+            let Some(folder_path) = target_folder else {
+                return;
+            };
+
+            let mut imported = false;
+            for (kind, file_path) in imports {
+                let result = match kind {
+                    ImportKind::Postman => {
+                        crate::importer::import_postman_collection(&file_path, &folder_path).map(
+                            |report| {
+                                if report.needs_review > 0 {
+                                    let _ = toast_tx.send(Toast::new(
+                                        format!(
+                                            "{} tagged {}: Postman syntax Mercury can't run",
+                                            report.needs_review,
+                                            crate::importer::postman::NEEDS_REVIEW_TAG
+                                        ),
+                                        false,
+                                    ));
+                                }
+                                report.requests
+                            },
+                        )
+                    }
+                    ImportKind::Insomnia => {
+                        crate::importer::import_insomnia_collection(&file_path, &folder_path)
+                            .map(|(count, _)| count)
+                    }
+                    ImportKind::OpenApi => {
+                        crate::importer::import_openapi_file(&file_path, &folder_path)
+                            .map(|(count, _)| count)
+                    }
+                    ImportKind::HttpFile | ImportKind::Request => continue,
+                };
+                match result {
+                    Ok(count) => {
+                        imported = true;
+                        let _ = toast_tx.send(import_toast(count, &folder_path));
+                    }
+                    Err(e) => {
+                        let _ = toast_tx.send(Toast::new(
+                            format!("Import of {} failed: {}", kind.label(), e),
+                            true,
+                        ));
+                    }
+                }
+            }
+            if imported {
+                let _ = folder_tx.send(folder_path);
+            }
+        });
+    }
+
+    /// Add queued files to `add_destination` until one collides with an existing
+    /// entry, which then waits on the rename prompt
+    fn process_add_queue(&mut self, time: f64) {
+        let Some(destination) = self.add_destination.clone() else {
+            self.add_queue.clear();
+            return;
+        };
+
+        while let Some(source) = self.add_queue.first().cloned() {
+            let name = workspace_files::entry_name(&source);
+            if workspace_files::entry_path(&source, &destination, &name).exists() {
+                self.add_rename_text = Self::next_free_entry_name(&source, &destination, &name);
+                self.show_add_rename_dialog = true;
+                return;
+            }
+            self.add_queue.remove(0);
+            if let Err(e) = workspace_files::add_to_workspace(
+                &source,
+                &destination,
+                &name,
+                &mut self.add_report,
+            ) {
+                self.add_report.skipped.push(e.user_message().to_string());
+            }
+        }
+        self.finish_add(time);
+    }
+
+    /// Add the head of the queue under `name` (or skip it), then carry on
+    fn resolve_add_collision(&mut self, name: Option<&str>, time: f64) {
+        if self.add_queue.is_empty() {
+            return;
+        }
+        let source = self.add_queue.remove(0);
+        if let (Some(name), Some(destination)) = (name, self.add_destination.clone()) {
+            if let Err(e) =
+                workspace_files::add_to_workspace(&source, &destination, name, &mut self.add_report)
+            {
+                self.add_report.skipped.push(e.user_message().to_string());
+            }
+        }
+        self.process_add_queue(time);
+    }
+
+    /// Rebuild the tree and report what was added and what was left out
+    fn finish_add(&mut self, time: f64) {
+        self.add_queue.clear();
+        let report = std::mem::take(&mut self.add_report);
+        self.build_collection_tree();
+
+        let requests = if report.added == 1 {
+            "request"
+        } else {
+            "requests"
+        };
+        self.last_action_message = Some(match report.skipped.first() {
+            Some(first) => (
+                format!(
+                    "Added {} {}; skipped {} (first: {})",
+                    report.added,
+                    requests,
+                    report.skipped.len(),
+                    first
+                ),
+                time,
+                true,
+            ),
+            None => (format!("Added {} {}", report.added, requests), time, false),
+        });
+    }
+
+    /// Suggest the first free `{name}-N` for `source` in `destination`
+    fn next_free_entry_name(source: &Path, destination: &Path, name: &str) -> String {
+        let mut counter = 2;
+        loop {
+            let candidate = format!("{}-{}", name, counter);
+            if !workspace_files::entry_path(source, destination, &candidate).exists() {
+                return candidate;
+            }
+            counter += 1;
+        }
+    }
+
+    /// Highlight a multi-selected tree row behind its contents
+    fn paint_selection_bg(
+        &self,
+        ui: &egui::Ui,
+        row_bg: egui::layers::ShapeIdx,
+        rect: egui::Rect,
+        path: &Path,
+    ) {
+        if self.tree_selection.contains(path) {
+            ui.painter().set(
+                row_bg,
+                egui::Shape::rect_filled(
+                    rect,
+                    crate::theme::Radius::SM,
+                    crate::theme::Colors::popup_selection_bg(),
+                ),
+            );
+        }
+    }
+
+    /// Helper to render common context menu items (Rename, Delete, Copy Path)
+    fn render_context_menu_common(&mut self, ui: &mut egui::Ui, name: String, path: PathBuf) {
+        if menu_button(ui, Icons::EDIT, "Rename") {
+            self.context_menu_item = Some(path.clone());
             self.show_rename_dialog = true;
             self.rename_text = name;
             ui.close();
@@ -1139,42 +3859,100 @@ impl MercuryApp {
             self.new_folder_name = String::new();
             ui.close();
         }
+        if menu_button(ui, Icons::PLAY, "Run Folder") {
+            self.context_menu_item = Some(path.clone());
+            self.should_run_folder = true;
+            ui.close();
+        }
+        if menu_button(ui, Icons::PACKAGE, "Add to Folder...") {
+            self.add_destination = Some(path.clone());
+            self.show_add_dialog = true;
+            ui.close();
+        }
         ui.separator();
         self.render_context_menu_common(ui, name, path);
     }
 
     /// Context menu for requests
     fn render_request_context_menu(&mut self, ui: &mut egui::Ui, name: String, path: PathBuf) {
-        if menu_button(ui, Icons::DUPLICATE, "Duplicate") {
-            let _ = self.duplicate_request(&path);
+        if menu_button(ui, Icons::DUPLICATE, "Duplicate...") {
+            self.duplicate_name = Self::next_copy_name(&path);
+            self.context_menu_item = Some(path.clone());
+            self.show_duplicate_dialog = true;
+            ui.close();
+        }
+        if menu_button(ui, Icons::TAG, "Set tags...") {
+            let tags = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| parse_request_file(&content).ok())
+                .map(|request| request.tags)
+                .unwrap_or_default();
+            self.context_menu_item = Some(path.clone());
+            self.tags_text = tags.join(", ");
+            self.show_tags_dialog = true;
             ui.close();
         }
         self.render_context_menu_common(ui, name, path);
     }
 
+    /// Folder names only match plain searches, never `tag:` filters
+    fn folder_name_matches(name: &str, search: &str) -> bool {
+        !search.starts_with("tag:") && name.to_lowercase().contains(search)
+    }
+
+    /// A `.http` file matches by its own name or any of its requests' names
+    fn http_file_matches(name: &str, requests: &[HttpFileEntry], search: &str) -> bool {
+        Self::folder_name_matches(name, search)
+            || requests
+                .iter()
+                .any(|entry| crate::utils::request_matches_search(&entry.name, &[], search))
+    }
+
     /// Helper to check if a folder has any matching children
     fn folder_has_matching_children(children: &[CollectionItem], search: &str) -> bool {
         for child in children {
             match child {
-                CollectionItem::Request { name, .. } => {
-                    if name.to_lowercase().contains(search) {
+                CollectionItem::Request { name, tags, .. } => {
+                    if crate::utils::request_matches_search(name, tags, search) {
                         return true;
                     }
                 }
                 CollectionItem::Folder { name, children, .. } => {
-                    if name.to_lowercase().contains(search) {
+                    if Self::folder_name_matches(name, search) {
                         return true;
                     }
                     if Self::folder_has_matching_children(children, search) {
                         return true;
                     }
                 }
+                CollectionItem::HttpFile { name, requests, .. } => {
+                    if Self::http_file_matches(name, requests, search) {
+                        return true;
+                    }
+                }
             }
         }
         false
     }
 
+    fn run_toast_action(&mut self, action: ToastAction) {
+        match action {
+            ToastAction::Retry => self.should_execute_request = true,
+            ToastAction::Open(path) => {
+                let _ = open::that(path);
+            }
+            ToastAction::ShowFolderRun => self.show_folder_run = self.folder_run.is_some(),
+        }
+    }
+
     fn render_status_bar(&mut self, ctx: &egui::Context) {
+        if let Some((message, _, is_error)) = self.last_action_message.take() {
+            self.toasts.push(Toast::new(message, is_error));
+        }
+        while let Ok(toast) = self.toast_rx.try_recv() {
+            self.toasts.push(toast);
+        }
+
         egui::TopBottomPanel::bottom("status_bar")
             .exact_height(crate::theme::Layout::STATUS_BAR_HEIGHT)
             .frame(
@@ -1188,9 +3966,19 @@ impl MercuryApp {
             )
             .show(ctx, |ui| {
                 ui.horizontal(|ui| {
-                    if let Some((msg, timestamp, is_error)) = &self.last_action_message {
-                        if super::components::fading_toast(ui, ctx, msg, *timestamp, *is_error) {
-                            ctx.request_repaint();
+                    let waiting = self.toasts.waiting();
+                    if let Some(toast) = self.toasts.front_mut() {
+                        toast.shown_at.get_or_insert(ctx.input(|i| i.time));
+                        match super::components::fading_toast(ui, ctx, toast, waiting) {
+                            ToastEvent::Visible => ctx.request_repaint(),
+                            ToastEvent::Expired | ToastEvent::Dismissed => {
+                                self.toasts.pop();
+                                ctx.request_repaint();
+                            }
+                            ToastEvent::Clicked(action) => {
+                                self.toasts.pop();
+                                self.run_toast_action(action);
+                            }
                         }
                     }
 
@@ -1205,6 +3993,7 @@ impl MercuryApp {
                                 .sense(egui::Sense::click()),
                             )
                             .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Keyboard shortcuts")
                             .clicked()
                         {
                             self.show_shortcuts = true;
@@ -1212,6 +4001,99 @@ impl MercuryApp {
 
                         ui.add_space(crate::theme::Spacing::SM * 2.0);
 
+                        // Replays what failed, not what the form says now
+                        if self.request_error.is_some()
+                            && self.last_sent.is_some()
+                            && self.ongoing_request.is_none()
+                        {
+                            let shortcut = crate::ui::shortcuts::combo_label(
+                                &self.settings.keybindings.get(Action::RetryLast),
+                            );
+                            if ui
+                                .add(
+                                    egui::Label::new(
+                                        egui::RichText::new("↻ Retry last")
+                                            .size(crate::theme::FontSize::SM)
+                                            .color(crate::theme::Colors::WARNING),
+                                    )
+                                    .sense(egui::Sense::click()),
+                                )
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .on_hover_text(format!(
+                                    "Send the failed request again as it was sent ({})",
+                                    shortcut
+                                ))
+                                .accessible_button("Retry last request")
+                                .clicked()
+                            {
+                                self.should_retry_last = true;
+                            }
+                            ui.add_space(crate::theme::Spacing::SM * 2.0);
+                        }
+
+                        // Last response, or the one on its way
+                        if self.ongoing_request.is_some() {
+                            let sending = match self.send_countdown(ctx.input(|i| i.time)) {
+                                Some(left) => format!("Sending in {}s...", left.ceil()),
+                                None => "Sending...".to_string(),
+                            };
+                            ui.label(
+                                egui::RichText::new(sending)
+                                    .size(crate::theme::FontSize::SM)
+                                    .color(crate::theme::Colors::TEXT_MUTED),
+                            );
+                            ui.add_space(crate::theme::Spacing::SM * 2.0);
+                        } else if let Some(response) = &self.response {
+                            ui.label(
+                                egui::RichText::new(format!("{}ms", response.duration_ms))
+                                    .size(crate::theme::FontSize::SM)
+                                    .color(crate::theme::Colors::TEXT_MUTED),
+                            );
+                            ui.label(
+                                egui::RichText::new(response.status.to_string())
+                                    .size(crate::theme::FontSize::SM)
+                                    .color(crate::theme::Colors::status_color(response.status)),
+                            );
+                            ui.add_space(crate::theme::Spacing::SM * 2.0);
+                        }
+
+                        // Opens the environment file in the system editor
+                        if self.selected_env > 0 {
+                            if let Some(workspace) = &self.workspace_path {
+                                let env_name = &self.env_files[self.selected_env];
+                                if ui
+                                    .add(
+                                        egui::Label::new(
+                                            egui::RichText::new(env_name)
+                                                .size(crate::theme::FontSize::SM)
+                                                .color(crate::theme::Colors::env_color(env_name)),
+                                        )
+                                        .sense(egui::Sense::click()),
+                                    )
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .on_hover_text(format!("Edit {}", env_name))
+                                    .accessible_button(&format!("Edit environment {}", env_name))
+                                    .clicked()
+                                {
+                                    let _ = open::that(workspace.join(env_name));
+                                }
+                                ui.add_space(crate::theme::Spacing::SM * 2.0);
+                            }
+                        }
+
+                        if self.has_unsaved_changes {
+                            let shortcut = crate::ui::shortcuts::combo_label(
+                                &self.settings.keybindings.get(Action::SaveRequest),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("{} Unsaved", Icons::DOT))
+                                    .size(crate::theme::FontSize::SM)
+                                    .color(crate::theme::Colors::WARNING),
+                            )
+                            .on_hover_text(format!("Save with {}", shortcut));
+                            ui.add_space(crate::theme::Spacing::SM * 2.0);
+                        }
+
                         if !self.workspace_name.is_empty() {
                             ui.label(
                                 egui::RichText::new(&self.workspace_name)
@@ -1227,6 +4109,30 @@ impl MercuryApp {
 
 impl eframe::App for MercuryApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Closing the window would drop requests still in flight, so ask first
+        if ctx.input(|i| i.viewport().close_requested())
+            && !self.quit_confirmed
+            && self.work_in_progress().is_some()
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.show_quit_confirm = true;
+        }
+
+        // Take the clipboard read for the body editor before the editor pastes it
+        if std::mem::take(&mut self.clipboard_probe) {
+            let pasted = ctx.input_mut(|i| {
+                let index = i
+                    .events
+                    .iter()
+                    .position(|e| matches!(e, egui::Event::Paste(_)))?;
+                match i.events.remove(index) {
+                    egui::Event::Paste(text) => Some(text),
+                    _ => None,
+                }
+            });
+            self.clipboard_body = pasted.and_then(|text| crate::utils::pasteable_body(&text));
+        }
+
         // Global Shortcuts
         // Escape cancels running request
         if self.ongoing_request.is_some() && ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
@@ -1237,7 +4143,8 @@ impl eframe::App for MercuryApp {
         // Check for changes and auto-save (every 5 seconds)
         let current_time = ctx.input(|i| i.time);
         self.check_for_changes();
-        if self.has_unsaved_changes
+        if self.settings.auto_save
+            && self.has_unsaved_changes
             && current_time - self.last_save_time > 5.0
             && self.save_current_file()
         {
@@ -1251,7 +4158,9 @@ impl eframe::App for MercuryApp {
                 .is_some_and(|(ongoing_id, _)| ongoing_id == id);
 
             if is_match {
+                let started = self.ongoing_request.map_or(0.0, |(_, start)| start);
                 self.ongoing_request = None;
+                self.ongoing_cancel = None;
                 self.ensure_history_loaded();
                 match result {
                     Ok(response) => {
@@ -1260,38 +4169,7 @@ impl eframe::App for MercuryApp {
                             .unwrap()
                             .as_secs_f64();
 
-                        // Format response type as string for history
-                        let response_type_str = format!("{:?}", response.response_type);
-
-                        let entry = TimelineEntry {
-                            timestamp: time,
-                            request: Request {
-                                method: self.method.clone(),
-                                url: self.url.clone(),
-                                headers: self.headers_text.clone(),
-                                body: self.body_text.clone(),
-                            },
-                            response: Response {
-                                status: response.status,
-                                status_text: response.status_text.clone(),
-                                headers: response.headers.clone(),
-                                body: response.body.clone(), // Store full response
-                                content_type: response.content_type.clone(),
-                                response_type: response_type_str,
-                                size_bytes: response.size_bytes,
-                                duration_ms: response.duration_ms,
-                            },
-                        };
-
-                        // Add summary to timeline for display
-                        self.timeline.push(TimelineSummary::from(&entry));
-
-                        if self.timeline.len() > crate::core::constants::MAX_TIMELINE_ENTRIES {
-                            self.timeline.remove(0);
-                        }
-
-                        // Save full entry to disk
-                        persistence::append_history_entry(&entry);
+                        self.record_history(Response::from(&response), time); // Store full response
 
                         // Save to Recent (only if not a saved file AND it's a new unique request)
                         if self.current_file.is_none() && !self.url.is_empty() {
@@ -1318,31 +4196,161 @@ impl eframe::App for MercuryApp {
                             }
                         }
 
+                        // A 429's Retry-After window, counted down in the response panel
+                        self.rate_limit_until = response
+                            .headers
+                            .iter()
+                            .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+                            .filter(|_| response.status == 429)
+                            .and_then(|(_, v)| crate::utils::parse_retry_after(v, time as u64))
+                            .map(|seconds| {
+                                let seconds =
+                                    seconds.max(crate::core::constants::MIN_RETRY_AFTER_SECS);
+                                ctx.input(|i| i.time) + seconds as f64
+                            });
+
+                        // HEAD responses are all headers, so open straight on them. The
+                        // form may have changed since, so go by what was sent.
+                        if self
+                            .last_sent
+                            .as_ref()
+                            .is_some_and(|sent| sent.request.method == HttpMethod::HEAD)
+                        {
+                            self.response_tab = ResponseTab::Headers;
+                        }
+                        if let Some(view) = self.settings.response_view {
+                            self.response_view = view;
+                        }
+
                         // Update response
                         self.response = Some(response);
+                        self.response_request =
+                            self.last_sent.as_ref().map(|sent| (&sent.request).into());
                         self.formatted_response_cache = None; // Invalidate cache
+                        self.response_shape_cache = None;
+                        self.response_json_cache = None;
+                        self.grpc_error_cache = None;
+                        self.ndjson_cache = None;
+                        self.response_preview_cache = None;
+                        self.baseline_cache = None;
+                        self.hex_offset = 0;
                         self.request_error = None;
                         self.last_action_message =
                             Some(("Request completed".to_string(), time, false));
+                        if let Some(rule) = self
+                            .last_sent
+                            .as_ref()
+                            .and_then(|sent| sent.request.save.clone())
+                        {
+                            self.apply_save_rule(&rule, ctx.input(|i| i.time));
+                        }
                     }
                     Err(e) => {
-                        self.request_error = Some(e.clone());
                         let time = ctx.input(|i| i.time);
-                        self.last_action_message =
-                            Some((format!("Request failed: {}", e), time, true));
+                        let duration_ms = ((time - started).max(0.0) * 1000.0) as u128;
+                        let timestamp = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap()
+                            .as_secs_f64();
+                        self.record_history(
+                            Response::failed(e.to_string(), duration_ms),
+                            timestamp,
+                        );
+
+                        let toast = Toast::new(format!("Request failed: {}", e), true);
+                        self.toasts.push(if e.is_recoverable() {
+                            toast.with_action("Retry", ToastAction::Retry)
+                        } else {
+                            toast
+                        });
+                        self.request_error = Some(e);
                         ctx.request_repaint();
                     }
                 }
             } // matched
         } // received
 
+        // Folder run progress
+        while let Ok((id, index, status, response)) = self.run_rx.try_recv() {
+            if let Some(run) = self.folder_run.as_mut().filter(|run| run.id == id) {
+                let was_done = run.is_done();
+                if let Some(entry) = run.entries.get_mut(index) {
+                    entry.status = status;
+                    entry.response = response.or(entry.response.take());
+                }
+                if !was_done && run.is_done() {
+                    let failed = run
+                        .entries
+                        .iter()
+                        .filter(|e| match &e.status {
+                            RunStatus::Done { status, .. } => *status >= 400,
+                            RunStatus::Failed(_) => true,
+                            _ => false,
+                        })
+                        .count();
+                    let message = format!(
+                        "Run of {} finished: {} passed, {} failed",
+                        run.folder_name,
+                        run.finished_count() - failed,
+                        failed
+                    );
+                    self.toasts.push(
+                        Toast::new(message, failed > 0)
+                            .with_action("View", ToastAction::ShowFolderRun),
+                    );
+                }
+            }
+        }
+
+        // Environment comparison progress
+        while let Ok((id, index, status, response)) = self.compare_rx.try_recv() {
+            if let Some(comparison) = self.env_compare.as_mut().filter(|c| c.id == id) {
+                comparison.update(index, status, response);
+            }
+        }
+
         // Check for folder selection from async dialog
         if let Ok(path) = self.folder_rx.try_recv() {
             self.load_workspace(path);
             ctx.request_repaint();
         }
 
-        // Check for file system changes from watcher
+        if let Some(warning) = self.scan_warning.take() {
+            self.last_action_message = Some((warning, ctx.input(|i| i.time), true));
+        }
+
+        // Files picked for "Add to Workspace"
+        if let Ok(sources) = self.add_rx.try_recv() {
+            self.add_queue.extend(sources);
+            self.add_report = AddReport::default();
+            self.process_add_queue(ctx.input(|i| i.time));
+            ctx.request_repaint();
+        }
+
+        // Files dropped on the window from the OS
+        let dropped: Vec<PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+        if !dropped.is_empty() {
+            self.import_dropped(dropped, ctx.input(|i| i.time));
+            ctx.request_repaint();
+        }
+
+        while let Ok((path, passphrase, plaintext)) = self.unlock_rx.try_recv() {
+            self.finish_unlock(path, passphrase, plaintext, ctx.input(|i| i.time));
+        }
+        while let Ok(saved) = self.secret_rx.try_recv() {
+            self.finish_save_secret(saved, ctx.input(|i| i.time));
+        }
+        while let Ok((key, preview)) = self.history_preview_rx.try_recv() {
+            self.history_previews_loading.remove(&key);
+            self.history_previews.insert(key, preview);
+        }
+
         // Check for file system changes from watcher
         let mut needs_rebuild = false;
         while let Ok(msg) = self.watcher_rx.try_recv() {
@@ -1355,7 +4363,9 @@ impl eframe::App for MercuryApp {
             }
         }
 
+        self.sync_folder_defaults(needs_rebuild);
         if needs_rebuild {
+            self.env_var_counts.clear();
             // Rebuild tree while preserving expanded state
             self.build_collection_tree();
 
@@ -1365,7 +4375,7 @@ impl eframe::App for MercuryApp {
                     self.current_file = None;
                     self.url.clear();
                     self.headers_text.clear();
-                    self.body_text.clear();
+                    self.set_body(String::new());
                     self.response = None;
                     self.last_action_message = Some((
                         "File was deleted externally".to_string(),
@@ -1378,6 +4388,11 @@ impl eframe::App for MercuryApp {
         }
 
         // Execute deferred actions (after keyboard input processing)
+        if self.should_open_previous_request {
+            self.should_open_previous_request = false;
+            self.open_previous_request();
+        }
+
         if self.should_create_new_request {
             self.should_create_new_request = false;
             self.clear_request_form();
@@ -1387,25 +4402,122 @@ impl eframe::App for MercuryApp {
             ctx.request_repaint();
         }
 
+        // Once a 429's Retry-After window ends, resend if asked to
+        if let Some(until) = self.rate_limit_until {
+            if ctx.input(|i| i.time) >= until {
+                self.rate_limit_until = None;
+                if self.auto_resend_rate_limited {
+                    self.should_execute_request = true;
+                }
+            } else {
+                ctx.request_repaint_after(Duration::from_millis(250));
+            }
+        }
+
         if self.should_execute_request {
             self.should_execute_request = false;
+            self.rate_limit_until = None;
             self.execute_request(ctx);
         }
 
-        if self.should_open_folder_dialog {
-            self.should_open_folder_dialog = false;
-            let tx = self.folder_tx.clone();
-            std::thread::spawn(move || {
-                if let Some(path) = rfd::FileDialog::new().pick_folder() {
-                    let _ = tx.send(path);
-                }
-            });
+        if self.should_retry_last {
+            self.should_retry_last = false;
+            self.rate_limit_until = None;
+            self.retry_last(ctx);
         }
 
-        if self.should_open_insomnia_import {
-            self.should_open_insomnia_import = false;
+        if self.should_update_baseline {
+            self.should_update_baseline = false;
+            self.last_action_message = Some(
+                match self.save_response_as_example(baseline::BASELINE_EXAMPLE) {
+                    Ok(()) => ("Baseline updated".to_string(), ctx.input(|i| i.time), false),
+                    Err(e) => (e.user_message().to_string(), ctx.input(|i| i.time), true),
+                },
+            );
+        }
+
+        if self.should_use_as_body {
+            self.should_use_as_body = false;
+            self.use_as_request_body(ctx.input(|i| i.time));
+        }
+
+        if self.should_run_folder {
+            self.should_run_folder = false;
+            if let Some(folder) = self.context_menu_item.clone() {
+                self.run_folder(&folder, ctx);
+            }
+        }
+
+        if self.should_duplicate_selection {
+            self.should_duplicate_selection = false;
+            let outcome = self.duplicate_selected();
+            self.report_bulk_outcome("Duplicated", outcome, ctx.input(|i| i.time));
+        }
+
+        if self.should_open_new_window {
+            self.should_open_new_window = false;
+            let tx = self.window_tx.clone();
+            std::thread::spawn(move || {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Open Workspace in New Window")
+                    .pick_folder()
+                {
+                    let _ = tx.send(path);
+                }
+            });
+        }
+        while let Ok(workspace) = self.window_rx.try_recv() {
+            self.open_window(workspace);
+        }
+
+        if self.should_pick_proto {
+            self.should_pick_proto = false;
+            let tx = self.proto_tx.clone();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Protocol Buffers", &["proto"])
+                    .pick_file()
+                {
+                    let _ = tx.send(path);
+                    ctx.request_repaint();
+                }
+            });
+        }
+        while let Ok(picked) = self.proto_rx.try_recv() {
+            // Keep paths relative so the request file stays portable
+            let proto = self
+                .proto_base_dir()
+                .and_then(|dir| picked.strip_prefix(dir).ok())
+                .unwrap_or(&picked)
+                .display()
+                .to_string();
+            if let Some(call) = self.grpc_call.as_mut() {
+                call.proto = proto;
+            }
+        }
+        self.reap_child_windows();
+
+        // The window that opened this one quit; close too (asking first if busy)
+        if self.opener_closed.swap(false, Ordering::Relaxed) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+
+        if self.should_open_folder_dialog {
+            self.should_open_folder_dialog = false;
+            let tx = self.folder_tx.clone();
+            std::thread::spawn(move || {
+                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                    let _ = tx.send(path);
+                }
+            });
+        }
+
+        if self.should_open_insomnia_import {
+            self.should_open_insomnia_import = false;
             let current_workspace = self.workspace_path.clone();
             let folder_tx = self.folder_tx.clone();
+            let toast_tx = self.toast_tx.clone();
 
             std::thread::spawn(move || {
                 if let Some(file_path) = rfd::FileDialog::new()
@@ -1432,12 +4544,14 @@ impl eframe::App for MercuryApp {
                     if let Some(folder_path) = target_folder {
                         match crate::importer::import_insomnia_collection(&file_path, &folder_path)
                         {
-                            Ok((_req_count, _env_count)) => {
+                            Ok((req_count, _env_count)) => {
+                                let _ = toast_tx.send(import_toast(req_count, &folder_path));
                                 // Always reload workspace (if we picked a new one, or just refreshed current)
                                 let _ = folder_tx.send(folder_path);
                             }
-                            Err(_e) => {
-                                // Import failed silently - user will see empty workspace
+                            Err(e) => {
+                                let _ = toast_tx
+                                    .send(Toast::new(format!("Import failed: {}", e), true));
                             }
                         }
                     }
@@ -1449,6 +4563,7 @@ impl eframe::App for MercuryApp {
             self.should_open_postman_import = false;
             let current_workspace = self.workspace_path.clone();
             let folder_tx = self.folder_tx.clone();
+            let toast_tx = self.toast_tx.clone();
 
             std::thread::spawn(move || {
                 if let Some(file_path) = rfd::FileDialog::new()
@@ -1474,12 +4589,106 @@ impl eframe::App for MercuryApp {
 
                     if let Some(folder_path) = target_folder {
                         match crate::importer::import_postman_collection(&file_path, &folder_path) {
-                            Ok((_req_count, _env_count)) => {
+                            Ok(report) => {
+                                let _ = toast_tx.send(import_toast(report.requests, &folder_path));
+                                if report.needs_review > 0 {
+                                    let _ = toast_tx.send(Toast::new(
+                                        format!(
+                                            "{} tagged {}: Postman syntax Mercury can't run",
+                                            report.needs_review,
+                                            crate::importer::postman::NEEDS_REVIEW_TAG
+                                        ),
+                                        false,
+                                    ));
+                                }
                                 // Always reload workspace (if we picked a new one, or just refreshed current)
                                 let _ = folder_tx.send(folder_path);
                             }
-                            Err(_e) => {
-                                // Import failed silently - user will see empty workspace
+                            Err(e) => {
+                                let _ = toast_tx
+                                    .send(Toast::new(format!("Import failed: {}", e), true));
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        if self.should_open_openapi_import {
+            self.should_open_openapi_import = false;
+            let current_workspace = self.workspace_path.clone();
+            let folder_tx = self.folder_tx.clone();
+            let toast_tx = self.toast_tx.clone();
+
+            std::thread::spawn(move || {
+                if let Some(file_path) = rfd::FileDialog::new()
+                    .add_filter("OpenAPI Spec", &["json", "yaml", "yml"])
+                    .set_title("Select OpenAPI or Swagger File")
+                    .pick_file()
+                {
+                    let target_folder = if let Some(ws_path) = current_workspace {
+                        Some(ws_path)
+                    } else {
+                        rfd::FileDialog::new()
+                            .set_title("Choose where to save imported requests")
+                            .set_directory(
+                                dirs::document_dir()
+                                    .unwrap_or_else(|| std::path::PathBuf::from("~")),
+                            )
+                            .set_file_name("Mercury")
+                            .pick_folder()
+                    };
+
+                    if let Some(folder_path) = target_folder {
+                        match crate::importer::import_openapi_file(&file_path, &folder_path) {
+                            Ok((req_count, _)) => {
+                                let _ = toast_tx.send(import_toast(req_count, &folder_path));
+                                let _ = folder_tx.send(folder_path);
+                            }
+                            Err(e) => {
+                                let _ = toast_tx
+                                    .send(Toast::new(format!("Import failed: {}", e), true));
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        if self.should_open_http_import {
+            self.should_open_http_import = false;
+            let current_workspace = self.workspace_path.clone();
+            let folder_tx = self.folder_tx.clone();
+            let toast_tx = self.toast_tx.clone();
+
+            std::thread::spawn(move || {
+                if let Some(file_path) = rfd::FileDialog::new()
+                    .add_filter("HTTP File", &["http", "rest"])
+                    .set_title("Select .http File")
+                    .pick_file()
+                {
+                    let target_folder = if let Some(ws_path) = current_workspace {
+                        Some(ws_path)
+                    } else {
+                        rfd::FileDialog::new()
+                            .set_title("Choose where to save imported requests")
+                            .set_directory(
+                                dirs::document_dir()
+                                    .unwrap_or_else(|| std::path::PathBuf::from("~")),
+                            )
+                            .set_file_name("Mercury")
+                            .pick_folder()
+                    };
+
+                    if let Some(folder_path) = target_folder {
+                        match crate::importer::import_http_file(&file_path, &folder_path) {
+                            Ok((req_count, _)) => {
+                                let _ = toast_tx.send(import_toast(req_count, &folder_path));
+                                let _ = folder_tx.send(folder_path);
+                            }
+                            Err(e) => {
+                                let _ = toast_tx
+                                    .send(Toast::new(format!("Import failed: {}", e), true));
                             }
                         }
                     }
@@ -1492,13 +4701,23 @@ impl eframe::App for MercuryApp {
             ctx.memory_mut(|mem| mem.request_focus(egui::Id::new("search_box")));
         }
 
+        // Credentials only reach the clipboard when asked for
         if self.should_copy_curl {
             self.should_copy_curl = false;
-            self.copy_as_curl(ctx);
-            let time = ctx.input(|i| i.time);
-            self.copied_feedback_until = time + 2.0;
-            self.last_action_message = Some(("Copied as cURL".to_string(), time, false));
-            ctx.request_repaint();
+            self.export_request(ctx, SecretExport::Curl);
+        }
+        if self.should_copy_markdown {
+            self.should_copy_markdown = false;
+            self.export_request(ctx, SecretExport::Markdown);
+        }
+        if self.should_copy_response_curl {
+            self.should_copy_response_curl = false;
+            self.export_request(ctx, SecretExport::ResponseCurl);
+        }
+
+        if self.should_open_response_in_browser {
+            self.should_open_response_in_browser = false;
+            self.open_response_in_browser(ctx);
         }
 
         // Top panel
@@ -1507,8 +4726,25 @@ impl eframe::App for MercuryApp {
             ctx.request_repaint();
         }
 
+        // How often the open request was sent, from history
+        let run_stats = if self.current_file.is_some() {
+            self.ensure_history_loaded();
+            let key = status_key(&self.method, &self.url);
+            match &self.run_stats_cache {
+                Some((cached, stats)) if *cached == key => *stats,
+                _ => {
+                    let stats = crate::utils::run_stats(&self.timeline, &self.method, &self.url)
+                        .map(|(count, last)| (count, last.timestamp, last.status));
+                    self.run_stats_cache = Some((key, stats));
+                    stats
+                }
+            }
+        } else {
+            None
+        };
+
         // Top panel with breadcrumb navigation
-        egui::TopBottomPanel::top("top_panel")
+        let top_panel = egui::TopBottomPanel::top("top_panel")
             .exact_height(crate::theme::Layout::TOPBAR_HEIGHT)
             .frame(
                 egui::Frame::NONE
@@ -1593,6 +4829,46 @@ impl eframe::App for MercuryApp {
                                 )
                                 .on_hover_text("Unsaved changes");
                             }
+
+                            if let Some((count, last_run, status)) = run_stats {
+                                let runs = if count == 1 {
+                                    "1 run".to_string()
+                                } else {
+                                    format!("{} runs", count)
+                                };
+                                let last_run = Self::format_timestamp(last_run);
+                                let (status_text, status_color) = if status == 0 {
+                                    ("failed".to_string(), crate::theme::Colors::ERROR)
+                                } else {
+                                    (
+                                        status.to_string(),
+                                        crate::theme::Colors::status_color(status),
+                                    )
+                                };
+                                let hover = format!(
+                                    "Sent {} in history; last sent {}, {}",
+                                    if count == 1 {
+                                        "once".to_string()
+                                    } else {
+                                        format!("{} times", count)
+                                    },
+                                    last_run.to_lowercase(),
+                                    status_text
+                                );
+                                ui.add_space(crate::theme::Spacing::SM);
+                                ui.label(
+                                    egui::RichText::new(format!("{} · {} ·", runs, last_run))
+                                        .size(crate::theme::FontSize::SM)
+                                        .color(crate::theme::Colors::TEXT_MUTED),
+                                )
+                                .on_hover_text(&hover);
+                                ui.label(
+                                    egui::RichText::new(status_text)
+                                        .size(crate::theme::FontSize::SM)
+                                        .color(status_color),
+                                )
+                                .on_hover_text(&hover);
+                            }
                         } else {
                             ui.label(
                                 egui::RichText::new("/")
@@ -1619,24 +4895,38 @@ impl eframe::App for MercuryApp {
                     ui.add(
                         egui::TextEdit::singleline(&mut self.search_query)
                             .hint_text(
-                                egui::RichText::new("Search (Cmd+K)")
-                                    .color(crate::theme::Colors::PLACEHOLDER),
+                                egui::RichText::new(format!(
+                                    "{} ({})",
+                                    if self.search_contents {
+                                        "Search contents"
+                                    } else {
+                                        "Search"
+                                    },
+                                    combo_label(
+                                        &self.settings.keybindings.get(Action::FocusSearch)
+                                    )
+                                ))
+                                .color(crate::theme::Colors::PLACEHOLDER),
                             )
                             .desired_width(crate::theme::Layout::POPUP_WIDE_WIDTH)
                             .frame(false)
                             .id(egui::Id::new("search_box")),
                     );
+                    if ui
+                        .selectable_label(
+                            self.search_contents,
+                            egui::RichText::new("Contents").size(crate::theme::FontSize::SM),
+                        )
+                        .on_hover_text("Search request URLs, headers, and bodies instead of names")
+                        .clicked()
+                    {
+                        self.search_contents = !self.search_contents;
+                    }
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         // Environment selector - borderless, just text
                         let env_name = &self.env_files[self.selected_env];
-                        let env_color = if env_name.contains("prod") {
-                            crate::theme::Colors::ERROR
-                        } else if env_name.contains("stag") {
-                            crate::theme::Colors::WARNING
-                        } else {
-                            crate::theme::Colors::TEXT_SECONDARY
-                        };
+                        let env_color = crate::theme::Colors::env_color(env_name);
 
                         // Show disabled state if no workspace
                         let env_display = if self.workspace_path.is_none() && env_name == "None" {
@@ -1655,7 +4945,9 @@ impl eframe::App for MercuryApp {
                                 )
                                 .sense(egui::Sense::click()),
                             )
-                            .on_hover_cursor(egui::CursorIcon::PointingHand);
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button(&format!("Environment: {}", env_display))
+                            .on_hover_ui(|ui| self.env_sources_tooltip(ui));
                         // Clone env_files to avoid borrow issues
                         let env_files_clone: Vec<_> = self.env_files.clone();
                         let current_selection = self.selected_env;
@@ -1668,31 +4960,164 @@ impl eframe::App for MercuryApp {
                             |ui| {
                                 ui.set_min_height(100.0);
                                 for (i, env) in env_files_clone.iter().enumerate() {
-                                    let color = if env.contains("prod") {
-                                        crate::theme::Colors::ERROR
-                                    } else if env.contains("stag") {
-                                        crate::theme::Colors::WARNING
+                                    let color = crate::theme::Colors::env_color(env);
+                                    let mut label = egui::text::LayoutJob::default();
+                                    let font = egui::FontId::proportional(
+                                        crate::theme::FontSize::MD,
+                                    );
+                                    label.append(
+                                        env,
+                                        0.0,
+                                        egui::TextFormat::simple(font.clone(), color),
+                                    );
+                                    // An empty environment leaves every variable undefined
+                                    let count = if i == 0 {
+                                        None
                                     } else {
-                                        crate::theme::Colors::TEXT_SECONDARY
+                                        self.env_var_count(env)
+                                    };
+                                    let count = match count {
+                                        None => None,
+                                        Some(0) => Some((
+                                            "empty".to_string(),
+                                            crate::theme::Colors::WARNING,
+                                        )),
+                                        Some(1) => Some((
+                                            "1 var".to_string(),
+                                            crate::theme::Colors::TEXT_MUTED,
+                                        )),
+                                        Some(n) => Some((
+                                            format!("{} vars", n),
+                                            crate::theme::Colors::TEXT_MUTED,
+                                        )),
                                     };
+                                    if let Some((text, count_color)) = count {
+                                        label.append(
+                                            &text,
+                                            crate::theme::Spacing::SM,
+                                            egui::TextFormat::simple(
+                                                egui::FontId::proportional(
+                                                    crate::theme::FontSize::SM,
+                                                ),
+                                                count_color,
+                                            ),
+                                        );
+                                    }
                                     if ui
-                                        .selectable_label(
-                                            current_selection == i,
-                                            egui::RichText::new(env).color(color),
-                                        )
+                                        .selectable_label(current_selection == i, label)
                                         .clicked()
                                     {
                                         new_selection = Some(i);
                                         ui.close();
                                     }
                                 }
+                                ui.separator();
+                                if current_selection > 0
+                                    && ui.selectable_label(false, "Paste variables...").clicked()
+                                {
+                                    self.paste_env_text.clear();
+                                    self.show_paste_env_dialog = true;
+                                    ui.close();
+                                }
+                                // Comparing needs two environments besides "None"
+                                if ui
+                                    .add_enabled(
+                                        env_files_clone.len() > 2
+                                            && self.grpc_call.is_none()
+                                            && !self.url.trim().is_empty(),
+                                        egui::Button::selectable(
+                                            false,
+                                            "Run across environments...",
+                                        ),
+                                    )
+                                    .clicked()
+                                {
+                                    self.env_compare_selection =
+                                        (1..env_files_clone.len()).collect();
+                                    self.env_compare_prod_confirmed = false;
+                                    self.show_env_compare_picker = true;
+                                    ui.close();
+                                }
+                                if ui.selectable_label(false, "Override variables...").clicked() {
+                                    self.session_overrides_text =
+                                        format_env(&self.session_overrides);
+                                    self.show_session_overrides_dialog = true;
+                                    ui.close();
+                                }
+                                if ui.selectable_label(false, "Store in keychain...").clicked() {
+                                    self.keychain_secret.clear();
+                                    self.show_keychain_dialog = true;
+                                    ui.close();
+                                }
+                                let secrets_locked = self.secrets_passphrase.is_none()
+                                    && self.secrets_path().is_ok_and(|p| p.exists());
+                                if secrets_locked {
+                                    if ui.selectable_label(false, "Unlock secrets...").clicked() {
+                                        self.secrets_passphrase_input.clear();
+                                        self.secrets_error = None;
+                                        self.show_unlock_secrets_dialog = true;
+                                        ui.close();
+                                    }
+                                } else if ui
+                                    .selectable_label(false, "Set encrypted secret...")
+                                    .clicked()
+                                {
+                                    self.secret_name.clear();
+                                    self.secret_value.clear();
+                                    self.secrets_passphrase_input.clear();
+                                    self.secrets_passphrase_confirm.clear();
+                                    self.show_secret_dialog = true;
+                                    ui.close();
+                                }
                             },
                         );
 
                         // Apply selection change after popup closes
                         if let Some(i) = new_selection {
-                            self.selected_env = i;
-                            self.load_env();
+                            self.select_env(i);
+                        }
+
+                        // Overrides stay visible while they're in effect
+                        if !self.session_overrides.is_empty() {
+                            ui.add_space(crate::theme::Spacing::SM);
+                            let count = self.session_overrides.len();
+                            let label = if count == 1 {
+                                "1 override".to_string()
+                            } else {
+                                format!("{} overrides", count)
+                            };
+                            let overrides_response = ui
+                                .add(
+                                    egui::Label::new(
+                                        egui::RichText::new(label)
+                                            .size(crate::theme::FontSize::SM)
+                                            .color(crate::theme::Colors::WARNING),
+                                    )
+                                    .sense(egui::Sense::click()),
+                                )
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .accessible_button("Session overrides")
+                                .on_hover_ui(|ui| {
+                                    for (name, value) in &self.session_overrides {
+                                        ui.label(
+                                            egui::RichText::new(format!("{}={}", name, value))
+                                                .monospace(),
+                                        );
+                                    }
+                                    ui.label(
+                                        egui::RichText::new(if self.pin_session_overrides {
+                                            "Pinned; not saved to disk"
+                                        } else {
+                                            "Cleared when you switch environments; not saved to disk"
+                                        })
+                                        .size(crate::theme::FontSize::SM)
+                                        .color(crate::theme::Colors::TEXT_MUTED),
+                                    );
+                                });
+                            if overrides_response.clicked() {
+                                self.session_overrides_text = format_env(&self.session_overrides);
+                                self.show_session_overrides_dialog = true;
+                            }
                         }
 
                         ui.add_space(crate::theme::Spacing::XL);
@@ -1707,7 +5132,8 @@ impl eframe::App for MercuryApp {
                                 )
                                 .sense(egui::Sense::click()),
                             )
-                            .on_hover_cursor(egui::CursorIcon::PointingHand);
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Open");
 
                         popup_menu(
                             ui,
@@ -1723,6 +5149,28 @@ impl eframe::App for MercuryApp {
                                     });
                                     ui.close();
                                 }
+                                if ui
+                                    .selectable_label(false, "Recent Workspaces...")
+                                    .on_hover_text(combo_label(
+                                        &self.settings.keybindings.get(Action::RecentWorkspaces),
+                                    ))
+                                    .clicked()
+                                {
+                                    self.workspace_switcher_query.clear();
+                                    self.show_workspace_switcher = true;
+                                    ui.close();
+                                }
+                                if ui
+                                    .selectable_label(false, "New Window...")
+                                    .on_hover_text(combo_label(
+                                        &self.settings.keybindings.get(Action::NewWindow),
+                                    ))
+                                    .clicked()
+                                {
+                                    self.should_open_new_window = true;
+                                    ui.close();
+                                }
+                                ui.separator();
                                 if ui.selectable_label(false, "Import Insomnia...").clicked() {
                                     self.should_open_insomnia_import = true;
                                     ui.close();
@@ -1731,6 +5179,45 @@ impl eframe::App for MercuryApp {
                                     self.should_open_postman_import = true;
                                     ui.close();
                                 }
+                                if ui.selectable_label(false, "Import OpenAPI...").clicked() {
+                                    self.should_open_openapi_import = true;
+                                    ui.close();
+                                }
+                                if ui
+                                    .selectable_label(false, "Import OpenAPI from URL...")
+                                    .on_hover_text("Fetch an openapi.json or swagger.json")
+                                    .clicked()
+                                {
+                                    self.show_openapi_url_dialog = true;
+                                    ui.close();
+                                }
+                                if ui
+                                    .selectable_label(false, "Import .http File...")
+                                    .on_hover_text("Convert each request in it to a request file")
+                                    .clicked()
+                                {
+                                    self.should_open_http_import = true;
+                                    ui.close();
+                                }
+                                if self.workspace_path.is_some()
+                                    && ui
+                                        .selectable_label(false, "API Docs")
+                                        .on_hover_text("Browse the operations imported from OpenAPI")
+                                        .clicked()
+                                {
+                                    self.open_api_docs();
+                                    ui.close();
+                                }
+                                if self.workspace_path.is_some()
+                                    && ui
+                                        .selectable_label(false, "Add to Workspace...")
+                                        .on_hover_text("Copy request files or a folder in")
+                                        .clicked()
+                                {
+                                    self.add_destination = self.workspace_path.clone();
+                                    self.show_add_dialog = true;
+                                    ui.close();
+                                }
                             },
                         );
 
@@ -1746,7 +5233,8 @@ impl eframe::App for MercuryApp {
                                 )
                                 .sense(egui::Sense::click()),
                             )
-                            .on_hover_cursor(egui::CursorIcon::PointingHand);
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Help");
 
                         popup_menu(
                             ui,
@@ -1757,6 +5245,10 @@ impl eframe::App for MercuryApp {
                                     self.show_shortcuts = true;
                                     ui.close();
                                 }
+                                if ui.selectable_label(false, "Settings...").clicked() {
+                                    self.show_settings = true;
+                                    ui.close();
+                                }
                                 if ui.selectable_label(false, "About Mercury").clicked() {
                                     self.show_about = true;
                                     ui.close();
@@ -1780,27 +5272,70 @@ impl eframe::App for MercuryApp {
                 });
             });
 
+        if self.ongoing_request.is_some() {
+            sending_bar(ctx, top_panel.response.rect, ctx.input(|i| i.time));
+        }
+
         // Render panels using new modular methods
         if !self.focus_mode {
             self.render_sidebar_panel(ctx);
         }
 
-        self.render_response_panel_new(ctx);
+        // A collapsed panel becomes a strip; the request editor's strip leaves the
+        // center to the response
+        if self.collapsed_panel == Some(CollapsedPanel::Request) {
+            self.render_collapsed_strip(ctx, CollapsedPanel::Request);
+            self.render_response_panel_central(ctx);
+        } else {
+            if self.collapsed_panel == Some(CollapsedPanel::Response) {
+                self.render_collapsed_strip(ctx, CollapsedPanel::Response);
+            } else {
+                self.render_response_panel_new(ctx);
+            }
 
-        // Center: Request editor
-        egui::CentralPanel::default()
-            .frame(
-                egui::Frame::NONE
-                    .fill(crate::theme::Colors::BG_BASE)
-                    .inner_margin(egui::Margin::same(crate::theme::Spacing::MD as i8)),
-            )
-            .show(ctx, |ui| {
-                self.render_request_panel(ui, ctx);
-            });
+            // Center: Request editor
+            egui::CentralPanel::default()
+                .frame(
+                    egui::Frame::NONE
+                        .fill(crate::theme::Colors::BG_BASE)
+                        .inner_margin(egui::Margin::same(crate::theme::Spacing::MD as i8)),
+                )
+                .show(ctx, |ui| {
+                    self.collapse_button(ui, CollapsedPanel::Request);
+                    self.render_request_panel(ui, ctx);
+                });
+        }
 
         // Status bar at bottom
         self.render_status_bar(ctx);
 
+        // Files dragged over the window from the OS
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let screen = ctx.content_rect();
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("drop_overlay"),
+            ));
+            painter.rect_filled(
+                screen,
+                0.0,
+                crate::theme::Colors::BG_BASE.gamma_multiply(0.85),
+            );
+            painter.rect_stroke(
+                screen.shrink(crate::theme::Spacing::MD),
+                crate::theme::Radius::MD,
+                egui::Stroke::new(2.0, crate::theme::Colors::PRIMARY),
+                egui::StrokeKind::Inside,
+            );
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                "Drop to import or add to the workspace",
+                egui::FontId::proportional(crate::theme::FontSize::LG),
+                crate::theme::Colors::TEXT_PRIMARY,
+            );
+        }
+
         // New Request Dialog
         self.show_new_request_dialog = show_modal(
             ctx,
@@ -1845,49 +5380,1145 @@ impl eframe::App for MercuryApp {
                     if ui.button("Cancel").clicked() {
                         *open = false;
                     }
-                });
+                });
+            },
+        );
+
+        // New Folder Dialog
+        self.show_new_folder_dialog = show_modal(
+            ctx,
+            "New Folder",
+            self.show_new_folder_dialog,
+            |ui, open| {
+                let response = modal_input_field(ui, "Folder name:", &mut self.new_folder_name);
+                if response.lost_focus()
+                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    && !self.new_folder_name.is_empty()
+                {
+                    if let Some(parent) = self.context_menu_item.clone() {
+                        let name = self.new_folder_name.clone();
+                        if let Err(e) = self.create_new_folder(&parent, &name) {
+                            self.last_action_message =
+                                Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
+                        }
+                    }
+                    *open = false;
+                }
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if ui.button("Create").clicked() && !self.new_folder_name.is_empty() {
+                        if let Some(parent) = self.context_menu_item.clone() {
+                            let name = self.new_folder_name.clone();
+                            if let Err(e) = self.create_new_folder(&parent, &name) {
+                                self.last_action_message = Some((
+                                    e.user_message().to_string(),
+                                    ctx.input(|i| i.time),
+                                    true,
+                                ));
+                            } else {
+                                self.last_action_message = Some((
+                                    "Folder created".to_string(),
+                                    ctx.input(|i| i.time),
+                                    false,
+                                ));
+                            }
+                        }
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        // Rename Dialog
+        self.show_rename_dialog = show_modal(ctx, "Rename", self.show_rename_dialog, |ui, open| {
+            let response = modal_input_field(ui, "New name:", &mut self.rename_text);
+            if response.lost_focus()
+                && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                && !self.rename_text.is_empty()
+            {
+                if let Some(old_path) = self.context_menu_item.clone() {
+                    let new_name = self.rename_text.clone();
+                    if let Err(e) = self.rename_item(&old_path, &new_name) {
+                        self.last_action_message =
+                            Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
+                    }
+                }
+                *open = false;
+            }
+            ui.add_space(crate::theme::Spacing::SM);
+            ui.horizontal(|ui| {
+                if ui.button("Rename").clicked() && !self.rename_text.is_empty() {
+                    if let Some(old_path) = self.context_menu_item.clone() {
+                        let new_name = self.rename_text.clone();
+                        if let Err(e) = self.rename_item(&old_path, &new_name) {
+                            self.last_action_message =
+                                Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
+                        } else {
+                            self.last_action_message = Some((
+                                "Renamed successfully".to_string(),
+                                ctx.input(|i| i.time),
+                                false,
+                            ));
+                        }
+                    }
+                    *open = false;
+                }
+                if ui.button("Cancel").clicked() {
+                    *open = false;
+                }
+            });
+        });
+
+        // Duplicate Dialog
+        self.show_duplicate_dialog = show_modal(
+            ctx,
+            "Duplicate Request",
+            self.show_duplicate_dialog,
+            |ui, open| {
+                let response =
+                    modal_input_field(ui, "Name for the copy:", &mut self.duplicate_name);
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if (ui.button("Duplicate").clicked() || submitted)
+                        && !self.duplicate_name.is_empty()
+                    {
+                        if let Some(source) = self.context_menu_item.clone() {
+                            let name = self.duplicate_name.clone();
+                            if let Err(e) = self.duplicate_request(&source, &name) {
+                                self.last_action_message = Some((
+                                    e.user_message().to_string(),
+                                    ctx.input(|i| i.time),
+                                    true,
+                                ));
+                            }
+                        }
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        // Delete Confirmation Dialog
+        self.show_delete_confirm = show_modal(
+            ctx,
+            "Confirm Delete",
+            self.show_delete_confirm,
+            |ui, open| {
+                let target_info = self.delete_target.as_ref().map(|t| {
+                    (
+                        t.file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .into_owned(),
+                        t.clone(),
+                    )
+                });
+
+                if let Some((name, target_path)) = target_info {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(Icons::WARNING)
+                                .color(crate::theme::Colors::ERROR)
+                                .size(crate::theme::FontSize::LG),
+                        );
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Are you sure you want to delete '{}'?",
+                                name
+                            ))
+                            .color(crate::theme::Colors::TEXT_PRIMARY),
+                        );
+                    });
+                    ui.add_space(crate::theme::Spacing::SM);
+                    ui.label(
+                        egui::RichText::new("This action cannot be undone.")
+                            .color(crate::theme::Colors::TEXT_MUTED)
+                            .size(crate::theme::FontSize::SM),
+                    );
+                    ui.add_space(crate::theme::Spacing::MD);
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(
+                                egui::RichText::new("Delete")
+                                    .color(crate::theme::Colors::ERROR)
+                                    .strong(),
+                            )
+                            .clicked()
+                        {
+                            if let Err(e) = self.delete_item(&target_path) {
+                                self.last_action_message = Some((
+                                    e.user_message().to_string(),
+                                    ctx.input(|i| i.time),
+                                    true,
+                                ));
+                            } else {
+                                self.last_action_message =
+                                    Some(("Deleted".to_string(), ctx.input(|i| i.time), false));
+                            }
+                            *open = false;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            *open = false;
+                        }
+                    });
+                } else {
+                    *open = false;
+                }
+            },
+        );
+
+        // New Environment Dialog
+        self.show_new_env_dialog = show_modal(
+            ctx,
+            "New Environment",
+            self.show_new_env_dialog,
+            |ui, open| {
+                let response = modal_input_field(
+                    ui,
+                    "Environment name (e.g., 'staging', 'production'):",
+                    &mut self.new_env_name,
+                );
+                if response.lost_focus()
+                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    && !self.new_env_name.is_empty()
+                {
+                    let name = self.new_env_name.clone();
+                    if let Err(e) = self.create_new_env(&name) {
+                        self.last_action_message =
+                            Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
+                    } else {
+                        self.last_action_message = Some((
+                            "Environment created".to_string(),
+                            ctx.input(|i| i.time),
+                            false,
+                        ));
+                    }
+                    *open = false;
+                }
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if ui.button("Create").clicked() && !self.new_env_name.is_empty() {
+                        let name = self.new_env_name.clone();
+                        if let Err(e) = self.create_new_env(&name) {
+                            self.last_action_message =
+                                Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
+                        } else {
+                            self.last_action_message = Some((
+                                "Environment created".to_string(),
+                                ctx.input(|i| i.time),
+                                false,
+                            ));
+                        }
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        // Save Example Dialog
+        self.show_save_example_dialog = show_modal(
+            ctx,
+            "Save as Example",
+            self.show_save_example_dialog,
+            |ui, open| {
+                let response = modal_input_field(
+                    ui,
+                    "Example name (e.g., 'success', '404 case'):",
+                    &mut self.example_name,
+                );
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if (ui.button("Save").clicked() || submitted) && !self.example_name.is_empty() {
+                        let name = self.example_name.clone();
+                        match self.save_response_as_example(&name) {
+                            Ok(()) => {
+                                self.last_action_message = Some((
+                                    "Example saved".to_string(),
+                                    ctx.input(|i| i.time),
+                                    false,
+                                ));
+                            }
+                            Err(e) => {
+                                self.last_action_message = Some((
+                                    e.user_message().to_string(),
+                                    ctx.input(|i| i.time),
+                                    true,
+                                ));
+                            }
+                        }
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        // Set Variable Dialog
+        self.show_set_variable_dialog = show_modal(
+            ctx,
+            "Set as Variable",
+            self.show_set_variable_dialog,
+            |ui, open| {
+                let response = modal_input_field(ui, "Variable name:", &mut self.variable_name);
+                ui.label(
+                    egui::RichText::new(crate::utils::body_preview(&self.variable_value, 120))
+                        .monospace()
+                        .size(crate::theme::FontSize::SM)
+                        .color(crate::theme::Colors::TEXT_MUTED),
+                );
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if (ui.button("Save").clicked() || submitted)
+                        && !self.variable_name.trim().is_empty()
+                    {
+                        let name = self.variable_name.trim().to_string();
+                        let value = self.variable_value.clone();
+                        self.last_action_message =
+                            Some(match self.set_env_variable(&name, &value) {
+                                Ok(()) => (
+                                    format!(
+                                        "Saved {{{{{}}}}} to {}",
+                                        name, self.env_files[self.selected_env]
+                                    ),
+                                    ctx.input(|i| i.time),
+                                    false,
+                                ),
+                                Err(MercuryError::NoEnvironment) => (
+                                    "Select an environment first".to_string(),
+                                    ctx.input(|i| i.time),
+                                    true,
+                                ),
+                                Err(e) => {
+                                    (e.user_message().to_string(), ctx.input(|i| i.time), true)
+                                }
+                            });
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        self.show_replace_body_confirm = show_modal(
+            ctx,
+            "Replace Body",
+            self.show_replace_body_confirm,
+            |ui, open| {
+                ui.label("Replace the current request body?");
+                ui.label(
+                    egui::RichText::new(crate::utils::body_preview(
+                        &self.pending_request_body,
+                        120,
+                    ))
+                    .monospace()
+                    .size(crate::theme::FontSize::SM)
+                    .color(crate::theme::Colors::TEXT_MUTED),
+                );
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if ui.button("Replace").clicked() {
+                        let body = std::mem::take(&mut self.pending_request_body);
+                        self.set_request_body(body, ctx.input(|i| i.time));
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        // Store in Keychain Dialog
+        self.show_keychain_dialog = show_modal(
+            ctx,
+            "Store in Keychain",
+            self.show_keychain_dialog,
+            |ui, open| {
+                egui::Grid::new("keychain_grid")
+                    .num_columns(2)
+                    .spacing([crate::theme::Spacing::MD, crate::theme::Spacing::SM])
+                    .show(ui, |ui| {
+                        ui.label("Service");
+                        ui.text_edit_singleline(&mut self.keychain_service);
+                        ui.end_row();
+                        ui.label("Account");
+                        ui.text_edit_singleline(&mut self.keychain_account);
+                        ui.end_row();
+                        ui.label("Secret");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.keychain_secret).password(true),
+                        );
+                        ui.end_row();
+                        ui.label("Variable");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.keychain_variable)
+                                .hint_text("optional, e.g. API_TOKEN"),
+                        );
+                        ui.end_row();
+                    });
+
+                let service = self.keychain_service.trim();
+                let account = self.keychain_account.trim();
+                let valid = !service.is_empty()
+                    && !service.contains('/')
+                    && !account.is_empty()
+                    && !self.keychain_secret.is_empty();
+                if valid {
+                    let token = KeychainRef {
+                        service: service.to_string(),
+                        account: account.to_string(),
+                    }
+                    .token();
+                    ui.add_space(crate::theme::Spacing::XS);
+                    ui.label(
+                        egui::RichText::new(format!("Reference it as {}", token))
+                            .monospace()
+                            .size(crate::theme::FontSize::SM)
+                            .color(crate::theme::Colors::TEXT_MUTED),
+                    );
+                }
+
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(valid, egui::Button::new("Store")).clicked() {
+                        self.last_action_message = Some(match self.store_keychain_entry() {
+                            Ok(message) => (message, ctx.input(|i| i.time), false),
+                            Err(MercuryError::NoEnvironment) => (
+                                "Stored; select an environment to save the variable".to_string(),
+                                ctx.input(|i| i.time),
+                                true,
+                            ),
+                            Err(e) => (e.to_string(), ctx.input(|i| i.time), true),
+                        });
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+        if !self.show_keychain_dialog {
+            self.keychain_secret.clear();
+        }
+
+        // Import OpenAPI from URL Dialog
+        self.show_openapi_url_dialog = show_modal(
+            ctx,
+            "Import OpenAPI from URL",
+            self.show_openapi_url_dialog,
+            |ui, open| {
+                egui::Grid::new("openapi_url_grid")
+                    .num_columns(2)
+                    .spacing([crate::theme::Spacing::MD, crate::theme::Spacing::SM])
+                    .show(ui, |ui| {
+                        ui.label("URL");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.openapi_url)
+                                .hint_text("https://api.example.com/openapi.json"),
+                        );
+                        ui.end_row();
+                        ui.label("Header");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.openapi_header)
+                                .hint_text("optional, e.g. Authorization: Bearer …"),
+                        );
+                        ui.end_row();
+                    });
+
+                let url = self.openapi_url.trim();
+                let valid = url.starts_with("http://") || url.starts_with("https://");
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(valid, egui::Button::new("Import")).clicked() {
+                        self.import_openapi_url();
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        // Unlock Secrets Dialog
+        self.show_unlock_secrets_dialog = show_modal(
+            ctx,
+            "Unlock Secrets",
+            self.show_unlock_secrets_dialog,
+            |ui, open| {
+                ui.label(format!(
+                    "This workspace has encrypted secrets in {}.",
+                    crate::core::constants::SECRETS_FILE
+                ));
+                ui.add_space(crate::theme::Spacing::SM);
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.secrets_passphrase_input)
+                        .password(true)
+                        .hint_text("Passphrase"),
+                );
+                response.request_focus();
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                if let Some(error) = &self.secrets_error {
+                    ui.label(
+                        egui::RichText::new(error)
+                            .size(crate::theme::FontSize::SM)
+                            .color(crate::theme::Colors::ERROR),
+                    );
+                }
+
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    let unlock = ui
+                        .add_enabled(!self.unlocking_secrets, egui::Button::new("Unlock"))
+                        .clicked()
+                        || (submitted && !self.unlocking_secrets);
+                    if unlock {
+                        self.secrets_error = None;
+                        if let Err(e) = self.unlock_secrets(ctx) {
+                            self.secrets_error = Some(e.to_string());
+                        }
+                    }
+                    if self.unlocking_secrets {
+                        ui.spinner();
+                        ui.label(
+                            egui::RichText::new("Unlocking...")
+                                .size(crate::theme::FontSize::SM)
+                                .color(crate::theme::Colors::TEXT_MUTED),
+                        );
+                    }
+                    if ui.button("Not Now").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+        if !self.show_unlock_secrets_dialog {
+            self.secrets_passphrase_input.clear();
+        }
+
+        // Set Encrypted Secret Dialog
+        self.show_secret_dialog = show_modal(
+            ctx,
+            "Set Encrypted Secret",
+            self.show_secret_dialog,
+            |ui, open| {
+                // Without an unlocked file, saving creates one with a new passphrase
+                let creating = self.secrets_passphrase.is_none();
+                egui::Grid::new("secret_grid")
+                    .num_columns(2)
+                    .spacing([crate::theme::Spacing::MD, crate::theme::Spacing::SM])
+                    .show(ui, |ui| {
+                        ui.label("Variable");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.secret_name)
+                                .hint_text("e.g. API_TOKEN"),
+                        );
+                        ui.end_row();
+                        ui.label("Value");
+                        ui.add(egui::TextEdit::singleline(&mut self.secret_value).password(true));
+                        ui.end_row();
+                        if creating {
+                            ui.label("Passphrase");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.secrets_passphrase_input)
+                                    .password(true),
+                            );
+                            ui.end_row();
+                            ui.label("Confirm");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.secrets_passphrase_confirm)
+                                    .password(true),
+                            );
+                            ui.end_row();
+                        }
+                    });
+
+                let name = self.secret_name.trim();
+                let mut valid =
+                    !name.is_empty() && !name.contains('=') && !name.contains(char::is_whitespace);
+                if creating {
+                    let mismatch = self.secrets_passphrase_input != self.secrets_passphrase_confirm;
+                    valid &= !self.secrets_passphrase_input.is_empty() && !mismatch;
+                    ui.add_space(crate::theme::Spacing::XS);
+                    let (note, color) = if mismatch && !self.secrets_passphrase_confirm.is_empty() {
+                        ("Passphrases don't match", crate::theme::Colors::WARNING)
+                    } else {
+                        (
+                            "Creates .env.enc. Share the passphrase out of band; it can't be recovered.",
+                            crate::theme::Colors::TEXT_MUTED,
+                        )
+                    };
+                    ui.label(
+                        egui::RichText::new(note)
+                            .size(crate::theme::FontSize::SM)
+                            .color(color),
+                    );
+                }
+
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    let save = egui::Button::new("Save");
+                    if ui.add_enabled(valid && !self.saving_secret, save).clicked() {
+                        if let Err(e) = self.save_secret(ctx) {
+                            let time = ctx.input(|i| i.time);
+                            self.last_action_message = Some((e.to_string(), time, true));
+                            *open = false;
+                        }
+                    }
+                    if self.saving_secret {
+                        ui.spinner();
+                        ui.label(
+                            egui::RichText::new("Encrypting...")
+                                .size(crate::theme::FontSize::SM)
+                                .color(crate::theme::Colors::TEXT_MUTED),
+                        );
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+        if !self.show_secret_dialog {
+            self.secret_value.clear();
+            self.secrets_passphrase_confirm.clear();
+            if !self.show_unlock_secrets_dialog {
+                self.secrets_passphrase_input.clear();
+            }
+        }
+
+        // Bulk Delete Confirmation Dialog
+        self.show_bulk_delete_confirm = show_modal(
+            ctx,
+            "Confirm Delete",
+            self.show_bulk_delete_confirm,
+            |ui, open| {
+                let targets = self.tree_selection.top_level();
+                if targets.is_empty() {
+                    *open = false;
+                    return;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(Icons::WARNING)
+                            .color(crate::theme::Colors::ERROR)
+                            .size(crate::theme::FontSize::LG),
+                    );
+                    ui.label(
+                        egui::RichText::new(format!("Delete {} items?", targets.len()))
+                            .color(crate::theme::Colors::TEXT_PRIMARY),
+                    );
+                });
+                ui.add_space(crate::theme::Spacing::SM);
+                egui::ScrollArea::vertical()
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for target in &targets {
+                            let workspace = self.workspace_path.as_deref().unwrap_or(Path::new(""));
+                            let shown = target.strip_prefix(workspace).unwrap_or(target);
+                            let icon = if target.is_dir() {
+                                Icons::FOLDER
+                            } else {
+                                Icons::FILE
+                            };
+                            ui.label(
+                                egui::RichText::new(format!("{} {}", icon, shown.display()))
+                                    .size(crate::theme::FontSize::SM),
+                            );
+                        }
+                    });
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.label(
+                    egui::RichText::new(
+                        "Folders are deleted with everything in them. This action cannot be undone.",
+                    )
+                    .color(crate::theme::Colors::TEXT_MUTED)
+                    .size(crate::theme::FontSize::SM),
+                );
+                ui.add_space(crate::theme::Spacing::MD);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(
+                            egui::RichText::new(format!("Delete {}", targets.len()))
+                                .color(crate::theme::Colors::ERROR)
+                                .strong(),
+                        )
+                        .clicked()
+                    {
+                        let outcome = self.delete_selected();
+                        self.report_bulk_outcome("Deleted", outcome, ctx.input(|i| i.time));
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        // Move Selected Items Dialog
+        self.show_move_dialog = show_modal(ctx, "Move Items", self.show_move_dialog, |ui, open| {
+            let count = self.tree_selection.top_level().len();
+            ui.label(format!("Move {} items to:", count));
+            ui.add_space(crate::theme::Spacing::SM);
+
+            let mut folders = Vec::new();
+            if let Some(workspace) = &self.workspace_path {
+                folders.push((0, self.workspace_name.clone(), workspace.clone()));
+            }
+            Self::collect_folders(&self.collection_tree, 1, &mut folders);
+
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    for (depth, name, path) in folders {
+                        ui.horizontal(|ui| {
+                            ui.add_space(depth as f32 * crate::theme::Indent::TREE_LEVEL);
+                            let selected = self.move_destination.as_ref() == Some(&path);
+                            if ui
+                                .selectable_label(selected, format!("{} {}", Icons::FOLDER, name))
+                                .clicked()
+                            {
+                                self.move_destination = Some(path);
+                            }
+                        });
+                    }
+                });
+
+            ui.add_space(crate::theme::Spacing::MD);
+            ui.horizontal(|ui| {
+                let can_move = self.move_destination.is_some() && count > 0;
+                if ui
+                    .add_enabled(can_move, egui::Button::new("Move"))
+                    .clicked()
+                {
+                    if let Some(destination) = self.move_destination.take() {
+                        let outcome = self.move_selected(&destination);
+                        self.report_bulk_outcome("Moved", outcome, ctx.input(|i| i.time));
+                    }
+                    *open = false;
+                }
+                if ui.button("Cancel").clicked() {
+                    *open = false;
+                }
+            });
+        });
+
+        // Add to Workspace Dialog
+        self.show_add_dialog =
+            show_modal(ctx, "Add to Workspace", self.show_add_dialog, |ui, open| {
+                ui.label("Copy requests, .http files, or a folder into:");
+                ui.add_space(crate::theme::Spacing::SM);
+
+                let mut folders = Vec::new();
+                if let Some(workspace) = &self.workspace_path {
+                    folders.push((0, self.workspace_name.clone(), workspace.clone()));
+                }
+                Self::collect_folders(&self.collection_tree, 1, &mut folders);
+
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        for (depth, name, path) in folders {
+                            ui.horizontal(|ui| {
+                                ui.add_space(depth as f32 * crate::theme::Indent::TREE_LEVEL);
+                                let selected = self.add_destination.as_ref() == Some(&path);
+                                if ui
+                                    .selectable_label(
+                                        selected,
+                                        format!("{} {}", Icons::FOLDER, name),
+                                    )
+                                    .clicked()
+                                {
+                                    self.add_destination = Some(path);
+                                }
+                            });
+                        }
+                    });
+
+                ui.add_space(crate::theme::Spacing::MD);
+                ui.horizontal(|ui| {
+                    let can_add = self.add_destination.is_some();
+                    if ui
+                        .add_enabled(can_add, egui::Button::new("Choose Files..."))
+                        .clicked()
+                    {
+                        let tx = self.add_tx.clone();
+                        std::thread::spawn(move || {
+                            if let Some(files) = rfd::FileDialog::new()
+                                .add_filter("Requests", &["json", "http", "rest"])
+                                .set_title("Select Files to Add")
+                                .pick_files()
+                            {
+                                let _ = tx.send(files);
+                            }
+                        });
+                        *open = false;
+                    }
+                    if ui
+                        .add_enabled(can_add, egui::Button::new("Choose Folder..."))
+                        .clicked()
+                    {
+                        let tx = self.add_tx.clone();
+                        std::thread::spawn(move || {
+                            if let Some(folder) = rfd::FileDialog::new()
+                                .set_title("Select Folder to Add")
+                                .pick_folder()
+                            {
+                                let _ = tx.send(vec![folder]);
+                            }
+                        });
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            });
+
+        // Name Collision Dialog for "Add to Workspace"
+        let was_renaming = self.show_add_rename_dialog;
+        let mut decision: Option<Option<String>> = None;
+        self.show_add_rename_dialog = show_modal(
+            ctx,
+            "Name Already Exists",
+            self.show_add_rename_dialog,
+            |ui, open| {
+                let (source, destination) = match (self.add_queue.first(), &self.add_destination) {
+                    (Some(source), Some(destination)) => (source.clone(), destination.clone()),
+                    _ => {
+                        *open = false;
+                        return;
+                    }
+                };
+                let name = workspace_files::entry_name(&source);
+                ui.label(format!(
+                    "'{}' already exists in {}.",
+                    name,
+                    destination
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                ));
+                ui.add_space(crate::theme::Spacing::SM);
+                let response = modal_input_field(ui, "Add it as:", &mut self.add_rename_text);
+                response.request_focus();
+
+                let new_name = self.add_rename_text.trim().to_string();
+                let taken = new_name.is_empty()
+                    || workspace_files::entry_path(&source, &destination, &new_name).exists();
+                if !new_name.is_empty() && taken {
+                    ui.label(
+                        egui::RichText::new("That name is taken too")
+                            .size(crate::theme::FontSize::SM)
+                            .color(crate::theme::Colors::WARNING),
+                    );
+                }
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!taken, egui::Button::new("Add")).clicked()
+                        || (submitted && !taken)
+                    {
+                        decision = Some(Some(new_name.clone()));
+                    }
+                    if ui.button("Skip").clicked() {
+                        decision = Some(None);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+        let time = ctx.input(|i| i.time);
+        if let Some(name) = decision {
+            self.show_add_rename_dialog = false;
+            self.resolve_add_collision(name.as_deref(), time);
+        } else if was_renaming && !self.show_add_rename_dialog {
+            // Cancel (or Esc) drops the rest of the queue; what was already added stays
+            self.finish_add(time);
+        }
+
+        // Set Tags Dialog
+        self.show_tags_dialog = show_modal(ctx, "Set Tags", self.show_tags_dialog, |ui, open| {
+            let response = modal_input_field(
+                ui,
+                "Comma-separated tags (e.g., 'smoke, auth'):",
+                &mut self.tags_text,
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            ui.add_space(crate::theme::Spacing::SM);
+            ui.horizontal(|ui| {
+                if ui.button("Save").clicked() || submitted {
+                    if let Some(path) = self.context_menu_item.clone() {
+                        let tags = crate::utils::parse_tags(&self.tags_text);
+                        if let Err(e) = self.set_request_tags(&path, tags) {
+                            self.last_action_message =
+                                Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
+                        }
+                    }
+                    *open = false;
+                }
+                if ui.button("Cancel").clicked() {
+                    *open = false;
+                }
+            });
+        });
+
+        // Quit while work is running
+        let mut quit = false;
+        let running = self.work_in_progress();
+        self.show_quit_confirm =
+            show_modal(ctx, "Quit Mercury?", self.show_quit_confirm, |ui, open| {
+                let Some(running) = running else {
+                    // Finished while the dialog was up: nothing left to lose
+                    quit = true;
+                    *open = false;
+                    return;
+                };
+                ui.label(running);
+                ui.label(
+                    egui::RichText::new("Mercury will quit when it finishes.")
+                        .size(crate::theme::FontSize::SM)
+                        .color(crate::theme::Colors::TEXT_MUTED),
+                );
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if ui.button("Quit Anyway").clicked() {
+                        quit = true;
+                        *open = false;
+                    }
+                    if ui.button("Don't Quit").clicked() {
+                        *open = false;
+                    }
+                });
+            });
+        if quit {
+            self.quit_confirmed = true;
+            self.cancel_request();
+            if let Some(run) = &self.folder_run {
+                run.cancel.store(true, Ordering::Relaxed);
+            }
+            if let Some(comparison) = &self.env_compare {
+                comparison.cancel.store(true, Ordering::Relaxed);
+            }
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+
+        // Copying or exporting a request that carries secrets
+        if let Some(export) = self.secrets_prompt {
+            let mut copy = false;
+            let mut save_script = false;
+            let secrets = self.export_secrets.join(", ");
+            let inline = &mut self.export_inline_secrets;
+            let (title, what) = match export {
+                SecretExport::Curl | SecretExport::ResponseCurl => ("Copy as cURL", "command"),
+                SecretExport::Markdown => ("Copy as Markdown", "example"),
+            };
+            let open = show_modal(ctx, title, true, |ui, open| {
+                ui.label(format!("This request carries secret values: {}", secrets));
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.checkbox(inline, "Inline secret values");
+                let (note, color) = if *inline {
+                    (
+                        format!(
+                            "{} The copied {} will contain the real values of {}.",
+                            Icons::WARNING,
+                            what,
+                            secrets
+                        ),
+                        crate::theme::Colors::ERROR,
+                    )
+                } else {
+                    (
+                        "They stay as {{NAME}} placeholders, safe to paste into a chat or commit."
+                            .to_string(),
+                        crate::theme::Colors::TEXT_MUTED,
+                    )
+                };
+                ui.label(
+                    egui::RichText::new(note)
+                        .size(crate::theme::FontSize::SM)
+                        .color(color),
+                );
+                ui.add_space(crate::theme::Spacing::MD);
+                ui.horizontal(|ui| {
+                    if ui.button("Copy").clicked() {
+                        copy = true;
+                        *open = false;
+                    }
+                    if export == SecretExport::Curl
+                        && ui
+                            .button("Save as Script...")
+                            .on_hover_text(
+                                "Save a shell script, with a .env.example listing the placeholders",
+                            )
+                            .clicked()
+                    {
+                        save_script = true;
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            });
+            if !open {
+                self.secrets_prompt = None;
+            }
+            if copy {
+                self.finish_export(ctx, export, self.export_inline_secrets);
+            }
+            if save_script {
+                if let Some(toast) = self.save_curl_script(self.export_inline_secrets) {
+                    self.toasts.push(toast);
+                }
+            }
+        }
+
+        // Settings
+        self.render_settings_modal(ctx);
+
+        // Folder Run
+        self.render_folder_run_modal(ctx);
+        self.render_env_compare_picker(ctx);
+        self.render_env_compare_modal(ctx);
+        self.render_api_docs_modal(ctx);
+
+        // Recent Workspaces
+        let mut workspace_to_open = None;
+        self.show_workspace_switcher = show_modal(
+            ctx,
+            "Recent Workspaces",
+            self.show_workspace_switcher,
+            |ui, open| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.workspace_switcher_query)
+                        .hint_text("Filter by name or path")
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                ui.add_space(crate::theme::Spacing::SM);
+
+                // Folders that are gone were dropped at startup, or when opening them failed
+                let query = self.workspace_switcher_query.to_lowercase();
+                let matches: Vec<&PathBuf> = self
+                    .recent_workspaces
+                    .iter()
+                    .filter(|p| p.to_string_lossy().to_lowercase().contains(&query))
+                    .collect();
+
+                if matches.is_empty() {
+                    let message = if query.is_empty() {
+                        "No recent workspaces"
+                    } else {
+                        "No matching workspaces"
+                    };
+                    ui.label(egui::RichText::new(message).color(crate::theme::Colors::TEXT_MUTED));
+                }
+                for path in &matches {
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path.display().to_string());
+                    let current = self.workspace_path.as_ref() == Some(*path);
+                    if ui
+                        .selectable_label(current, name)
+                        .on_hover_text(path.display().to_string())
+                        .clicked()
+                    {
+                        workspace_to_open = Some((*path).clone());
+                        *open = false;
+                    }
+                }
+
+                // Enter opens the first (most recent) match
+                if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if let Some(first) = matches.first() {
+                        workspace_to_open = Some((*first).clone());
+                        *open = false;
+                    }
+                }
             },
         );
+        if let Some(path) = workspace_to_open {
+            if self.workspace_path.as_ref() != Some(&path) {
+                self.load_workspace(path);
+            }
+        }
 
-        // New Folder Dialog
-        self.show_new_folder_dialog = show_modal(
+        // Session Overrides Dialog
+        self.show_session_overrides_dialog = show_modal(
             ctx,
-            "New Folder",
-            self.show_new_folder_dialog,
+            "Override Variables",
+            self.show_session_overrides_dialog,
             |ui, open| {
-                let response = modal_input_field(ui, "Folder name:", &mut self.new_folder_name);
-                if response.lost_focus()
-                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                    && !self.new_folder_name.is_empty()
-                {
-                    if let Some(parent) = self.context_menu_item.clone() {
-                        let name = self.new_folder_name.clone();
-                        if let Err(e) = self.create_new_folder(&parent, &name) {
-                            self.last_action_message =
-                                Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
-                        }
-                    }
-                    *open = false;
-                }
+                ui.label(
+                    egui::RichText::new(
+                        "KEY=value lines that win over the environment for this session:",
+                    )
+                    .color(crate::theme::Colors::TEXT_SECONDARY),
+                );
+                ui.add_space(crate::theme::Spacing::XS);
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.session_overrides_text)
+                        .font(egui::TextStyle::Monospace)
+                        .hint_text("FEATURE_FLAG=on")
+                        .desired_rows(6)
+                        .desired_width(f32::INFINITY),
+                );
+                ui.checkbox(
+                    &mut self.pin_session_overrides,
+                    "Keep when switching environments",
+                );
+                ui.label(
+                    egui::RichText::new("Overrides are never written to your .env files.")
+                        .size(crate::theme::FontSize::SM)
+                        .color(crate::theme::Colors::TEXT_MUTED),
+                );
                 ui.add_space(crate::theme::Spacing::SM);
                 ui.horizontal(|ui| {
-                    if ui.button("Create").clicked() && !self.new_folder_name.is_empty() {
-                        if let Some(parent) = self.context_menu_item.clone() {
-                            let name = self.new_folder_name.clone();
-                            if let Err(e) = self.create_new_folder(&parent, &name) {
-                                self.last_action_message = Some((
-                                    e.user_message().to_string(),
-                                    ctx.input(|i| i.time),
-                                    true,
-                                ));
-                            } else {
-                                self.last_action_message = Some((
-                                    "Folder created".to_string(),
-                                    ctx.input(|i| i.time),
-                                    false,
-                                ));
-                            }
-                        }
+                    if ui.button("Apply").clicked() {
+                        self.apply_session_overrides();
+                        *open = false;
+                    }
+                    if ui
+                        .add_enabled(
+                            !self.session_overrides.is_empty(),
+                            egui::Button::new("Clear All"),
+                        )
+                        .clicked()
+                    {
+                        self.session_overrides_text.clear();
+                        self.apply_session_overrides();
                         *open = false;
                     }
                     if ui.button("Cancel").clicked() {
@@ -1897,157 +6528,70 @@ impl eframe::App for MercuryApp {
             },
         );
 
-        // Rename Dialog
-        self.show_rename_dialog = show_modal(ctx, "Rename", self.show_rename_dialog, |ui, open| {
-            let response = modal_input_field(ui, "New name:", &mut self.rename_text);
-            if response.lost_focus()
-                && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                && !self.rename_text.is_empty()
-            {
-                if let Some(old_path) = self.context_menu_item.clone() {
-                    let new_name = self.rename_text.clone();
-                    if let Err(e) = self.rename_item(&old_path, &new_name) {
-                        self.last_action_message =
-                            Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
-                    }
-                }
-                *open = false;
-            }
-            ui.add_space(crate::theme::Spacing::SM);
-            ui.horizontal(|ui| {
-                if ui.button("Rename").clicked() && !self.rename_text.is_empty() {
-                    if let Some(old_path) = self.context_menu_item.clone() {
-                        let new_name = self.rename_text.clone();
-                        if let Err(e) = self.rename_item(&old_path, &new_name) {
-                            self.last_action_message =
-                                Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
-                        } else {
-                            self.last_action_message = Some((
-                                "Renamed successfully".to_string(),
-                                ctx.input(|i| i.time),
-                                false,
-                            ));
-                        }
-                    }
-                    *open = false;
-                }
-                if ui.button("Cancel").clicked() {
-                    *open = false;
-                }
-            });
-        });
-
-        // Delete Confirmation Dialog
-        self.show_delete_confirm = show_modal(
+        // Paste Variables Dialog
+        self.show_paste_env_dialog = show_modal(
             ctx,
-            "Confirm Delete",
-            self.show_delete_confirm,
+            "Paste Variables",
+            self.show_paste_env_dialog,
             |ui, open| {
-                let target_info = self.delete_target.as_ref().map(|t| {
-                    (
-                        t.file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .into_owned(),
-                        t.clone(),
-                    )
-                });
+                ui.label(
+                    egui::RichText::new("Paste KEY=value lines (export prefixes are fine):")
+                        .color(crate::theme::Colors::TEXT_SECONDARY),
+                );
+                ui.add_space(crate::theme::Spacing::XS);
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.paste_env_text)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_rows(8)
+                        .desired_width(f32::INFINITY),
+                );
 
-                if let Some((name, target_path)) = target_info {
-                    ui.horizontal(|ui| {
-                        ui.label(
-                            egui::RichText::new(Icons::WARNING)
-                                .color(crate::theme::Colors::ERROR)
-                                .size(crate::theme::FontSize::LG),
-                        );
-                        ui.label(
-                            egui::RichText::new(format!(
-                                "Are you sure you want to delete '{}'?",
-                                name
-                            ))
-                            .color(crate::theme::Colors::TEXT_PRIMARY),
-                        );
-                    });
+                // Preview which keys are new vs. overwritten
+                let vars = parse_env_str(&self.paste_env_text);
+                if !vars.is_empty() {
                     ui.add_space(crate::theme::Spacing::SM);
-                    ui.label(
-                        egui::RichText::new("This action cannot be undone.")
-                            .color(crate::theme::Colors::TEXT_MUTED)
-                            .size(crate::theme::FontSize::SM),
-                    );
-                    ui.add_space(crate::theme::Spacing::MD);
+                    egui::ScrollArea::vertical()
+                        .max_height(120.0)
+                        .show(ui, |ui| {
+                            for (key, _) in &vars {
+                                let (tag, color) = if self.env_variables.contains_key(key) {
+                                    ("overwrite", crate::theme::Colors::WARNING)
+                                } else {
+                                    ("new", crate::theme::Colors::SUCCESS)
+                                };
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        egui::RichText::new(tag)
+                                            .color(color)
+                                            .size(crate::theme::FontSize::SM),
+                                    );
+                                    ui.label(egui::RichText::new(key).monospace());
+                                });
+                            }
+                        });
+                }
 
-                    ui.horizontal(|ui| {
-                        if ui
-                            .button(
-                                egui::RichText::new("Delete")
-                                    .color(crate::theme::Colors::ERROR)
-                                    .strong(),
-                            )
-                            .clicked()
-                        {
-                            if let Err(e) = self.delete_item(&target_path) {
+                ui.add_space(crate::theme::Spacing::SM);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!vars.is_empty(), egui::Button::new("Merge"))
+                        .clicked()
+                    {
+                        match self.apply_env_paste() {
+                            Ok(count) => {
+                                self.last_action_message = Some((
+                                    format!("Merged {} variables", count),
+                                    ctx.input(|i| i.time),
+                                    false,
+                                ));
+                            }
+                            Err(e) => {
                                 self.last_action_message = Some((
                                     e.user_message().to_string(),
                                     ctx.input(|i| i.time),
                                     true,
                                 ));
-                            } else {
-                                self.last_action_message =
-                                    Some(("Deleted".to_string(), ctx.input(|i| i.time), false));
                             }
-                            *open = false;
-                        }
-                        if ui.button("Cancel").clicked() {
-                            *open = false;
-                        }
-                    });
-                } else {
-                    *open = false;
-                }
-            },
-        );
-
-        // New Environment Dialog
-        self.show_new_env_dialog = show_modal(
-            ctx,
-            "New Environment",
-            self.show_new_env_dialog,
-            |ui, open| {
-                let response = modal_input_field(
-                    ui,
-                    "Environment name (e.g., 'staging', 'production'):",
-                    &mut self.new_env_name,
-                );
-                if response.lost_focus()
-                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                    && !self.new_env_name.is_empty()
-                {
-                    let name = self.new_env_name.clone();
-                    if let Err(e) = self.create_new_env(&name) {
-                        self.last_action_message =
-                            Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
-                    } else {
-                        self.last_action_message = Some((
-                            "Environment created".to_string(),
-                            ctx.input(|i| i.time),
-                            false,
-                        ));
-                    }
-                    *open = false;
-                }
-                ui.add_space(crate::theme::Spacing::SM);
-                ui.horizontal(|ui| {
-                    if ui.button("Create").clicked() && !self.new_env_name.is_empty() {
-                        let name = self.new_env_name.clone();
-                        if let Err(e) = self.create_new_env(&name) {
-                            self.last_action_message =
-                                Some((e.user_message().to_string(), ctx.input(|i| i.time), true));
-                        } else {
-                            self.last_action_message = Some((
-                                "Environment created".to_string(),
-                                ctx.input(|i| i.time),
-                                false,
-                            ));
                         }
                         *open = false;
                     }
@@ -2070,25 +6614,23 @@ impl eframe::App for MercuryApp {
                     .num_columns(2)
                     .spacing([40.0, 12.0])
                     .show(ui, |ui| {
-                        let shortcuts = [
-                            ("Send Request", "⌘ + Enter"),
-                            ("New Request", "⌘ + N"),
-                            ("Save Request", "⌘ + S"),
-                            ("Format JSON", "⌘ + I"),
-                            ("Clear Console", "⌘ + K"),
-                            ("Switch Environment", "⌘ + E"),
-                            ("History", "⌘ + H"),
-                            ("Focus URL Bar", "⌘ + L"),
-                            ("Close Modal", "Esc"),
-                        ];
-
-                        for (action, key) in shortcuts {
+                        // Active bindings, then the fixed keys
+                        let keymap = &self.settings.keybindings;
+                        let shortcuts: Vec<(&str, Vec<String>)> = Action::ALL
+                            .iter()
+                            .map(|action| (action.label(), combo_keys(&keymap.get(*action))))
+                            .chain([
+                                ("Keyboard Shortcuts", vec!["?".to_string()]),
+                                ("Close Modal", vec!["Esc".to_string()]),
+                            ])
+                            .collect();
+
+                        for (action, keys) in shortcuts {
                             ui.label(
                                 egui::RichText::new(action)
                                     .color(crate::theme::Colors::TEXT_SECONDARY),
                             );
                             ui.horizontal(|ui| {
-                                let keys: Vec<&str> = key.split('+').collect();
                                 for (i, k) in keys.iter().enumerate() {
                                     if i > 0 {
                                         ui.label(
@@ -2107,7 +6649,7 @@ impl eframe::App for MercuryApp {
                                         .inner_margin(egui::Margin::symmetric(6, 2))
                                         .show(ui, |ui| {
                                             ui.label(
-                                                egui::RichText::new(k.trim())
+                                                egui::RichText::new(k)
                                                     .color(crate::theme::Colors::PRIMARY)
                                                     .strong()
                                                     .size(crate::theme::FontSize::XS)
@@ -2120,6 +6662,41 @@ impl eframe::App for MercuryApp {
                         }
                     });
 
+                // Directives understood when importing `.http` files, from the parser's own list
+                ui.add_space(crate::theme::Spacing::LG);
+                ui.label(
+                    egui::RichText::new(".http Directives")
+                        .strong()
+                        .color(crate::theme::Colors::TEXT_PRIMARY),
+                );
+                ui.add_space(crate::theme::Spacing::SM);
+                egui::Grid::new("directives_grid")
+                    .num_columns(2)
+                    .spacing([40.0, 12.0])
+                    .show(ui, |ui| {
+                        for directive in crate::parser::http_file::DIRECTIVES {
+                            ui.label(
+                                egui::RichText::new(directive.syntax)
+                                    .color(crate::theme::Colors::PRIMARY)
+                                    .size(crate::theme::FontSize::XS)
+                                    .monospace(),
+                            );
+                            ui.vertical(|ui| {
+                                ui.label(
+                                    egui::RichText::new(directive.description)
+                                        .color(crate::theme::Colors::TEXT_SECONDARY),
+                                );
+                                ui.label(
+                                    egui::RichText::new(directive.example)
+                                        .color(crate::theme::Colors::TEXT_MUTED)
+                                        .size(crate::theme::FontSize::XS)
+                                        .monospace(),
+                                );
+                            });
+                            ui.end_row();
+                        }
+                    });
+
                 ui.add_space(crate::theme::Spacing::LG);
                 ui.separator();
                 ui.add_space(crate::theme::Spacing::MD);
@@ -2141,19 +6718,19 @@ impl eframe::App for MercuryApp {
             },
         );
 
-        // Handle keyboard shortcuts
+        // Handle keyboard shortcuts; bindings come from Settings
+        let keymap = self.settings.keybindings.clone();
+        // Cmd+Shift+arrows also select text, so leave them to a focused text field
+        let typing = ctx.wants_keyboard_input();
         ctx.input(|i| {
-            // Cmd/Ctrl + N: New request
-            if i.key_pressed(egui::Key::N) && i.modifiers.command {
+            let pressed = |action: Action| combo_pressed(i, &keymap.get(action));
+
+            if pressed(Action::NewRequest) {
                 self.should_create_new_request = true;
             }
 
-            // Cmd/Ctrl + S: Save temp request (if not already saved)
-            if i.key_pressed(egui::Key::S)
-                && i.modifiers.command
-                && self.current_file.is_none()
-                && !self.url.is_empty()
-            {
+            // Save temp request (if not already saved)
+            if pressed(Action::SaveRequest) && self.current_file.is_none() && !self.url.is_empty() {
                 if let Some(workspace) = self.workspace_path.as_ref() {
                     self.show_new_request_dialog = true;
                     self.new_request_name = String::new();
@@ -2169,9 +6746,8 @@ impl eframe::App for MercuryApp {
                 }
             }
 
-            // Cmd/Ctrl + S: Save current file (if already saved)
-            if i.key_pressed(egui::Key::S)
-                && i.modifiers.command
+            // Save current file (if already saved)
+            if pressed(Action::SaveRequest)
                 && self.current_file.is_some()
                 && self.has_unsaved_changes
                 && self.save_current_file()
@@ -2180,43 +6756,61 @@ impl eframe::App for MercuryApp {
                 self.last_action_message = Some(("Saved".to_string(), i.time, false));
             }
 
-            // Cmd/Ctrl + Enter: Send request
-            if i.key_pressed(egui::Key::Enter)
-                && i.modifiers.command
-                && self.ongoing_request.is_none()
-            {
+            if pressed(Action::SendRequest) {
                 self.should_execute_request = true;
             }
 
-            // Cmd/Ctrl + K: Focus search
-            if i.key_pressed(egui::Key::K) && i.modifiers.command {
+            if pressed(Action::RetryLast) {
+                self.should_retry_last = true;
+            }
+
+            // Caught a wrong send: stop it and go straight to fixing the URL.
+            // Its late result no longer matches `ongoing_request`, so it's dropped.
+            if pressed(Action::StopAndEditUrl) && self.ongoing_request.is_some() {
+                self.cancel_request();
+                self.should_focus_url_bar = true;
+            }
+
+            if pressed(Action::FocusSearch) {
                 self.should_focus_search = true;
             }
 
-            // Cmd/Ctrl + L: Focus URL bar
-            if i.key_pressed(egui::Key::L) && i.modifiers.command {
+            if pressed(Action::FocusUrlBar) {
                 self.should_focus_url_bar = true;
             }
 
-            // Cmd/Ctrl + Shift + C: Copy as cURL
-            if i.key_pressed(egui::Key::C) && i.modifiers.command && i.modifiers.shift {
+            if pressed(Action::PreviousRequest) {
+                self.should_open_previous_request = true;
+            }
+
+            if pressed(Action::CopyAsCurl) {
                 self.should_copy_curl = true;
             }
 
-            // Cmd/Ctrl + O: Open folder
-            if i.key_pressed(egui::Key::O) && i.modifiers.command {
+            if pressed(Action::OpenFolder) {
                 self.should_open_folder_dialog = true;
             }
 
-            // Cmd/Ctrl + R: Toggle raw view (if response exists)
-            if i.key_pressed(egui::Key::R) && i.modifiers.command && self.response.is_some() {
-                self.response_view_raw = !self.response_view_raw;
+            if pressed(Action::NewWindow) {
+                self.should_open_new_window = true;
             }
 
-            // Cmd/Ctrl + E: Cycle through environments
-            if i.key_pressed(egui::Key::E) && i.modifiers.command && !self.env_files.is_empty() {
-                self.selected_env = (self.selected_env + 1) % self.env_files.len();
-                self.load_env();
+            if pressed(Action::RecentWorkspaces) {
+                self.workspace_switcher_query.clear();
+                self.show_workspace_switcher = true;
+            }
+
+            // Toggle raw view (if response exists)
+            if pressed(Action::ToggleRawView) && self.response.is_some() {
+                self.response_view = match self.response_view {
+                    ResponseView::Raw => ResponseView::Pretty,
+                    _ => ResponseView::Raw,
+                };
+            }
+
+            // Cycle through environments
+            if pressed(Action::CycleEnvironment) && !self.env_files.is_empty() {
+                self.select_env((self.selected_env + 1) % self.env_files.len());
             }
 
             // Escape: Clear search
@@ -2231,13 +6825,24 @@ impl eframe::App for MercuryApp {
                 self.show_shortcuts = !self.show_shortcuts;
             }
 
-            // Cmd+Shift+F: Focus Mode
-            if i.key_pressed(egui::Key::F) && i.modifiers.command && i.modifiers.shift {
+            if pressed(Action::FocusMode) {
                 self.focus_mode = !self.focus_mode;
             }
 
-            // Cmd+H: Toggle Timeline/History
-            if i.key_pressed(egui::Key::H) && i.modifiers.command {
+            for (action, panel) in [
+                (Action::CollapseRequest, CollapsedPanel::Request),
+                (Action::CollapseResponse, CollapsedPanel::Response),
+            ] {
+                if pressed(action) && !typing {
+                    self.toggle_collapsed(panel);
+                }
+            }
+
+            if pressed(Action::Settings) {
+                self.show_settings = true;
+            }
+
+            if pressed(Action::History) {
                 self.show_timeline = !self.show_timeline;
             }
         });
@@ -2250,6 +6855,9 @@ impl eframe::App for MercuryApp {
         }
         // Save app state when app closes
         self.save_state();
+        for path in &self.browser_temp_files {
+            let _ = fs::remove_file(path);
+        }
     }
 }
 
@@ -2346,6 +6954,28 @@ mod history_tests {
         assert_eq!(deserialized[1].request.method.as_str(), "DELETE");
     }
 
+    #[test]
+    fn test_failed_entry_roundtrip() {
+        let entry = TimelineEntry {
+            timestamp: 1702400200.0,
+            request: Request {
+                method: HttpMethod::GET,
+                url: "https://offline.example.com".to_string(),
+                headers: "".to_string(),
+                body: "".to_string(),
+            },
+            response: Response::failed("Host unreachable: dns error".to_string(), 42),
+        };
+
+        let json = serde_json::to_string(&entry).expect("Failed to serialize");
+        let deserialized: TimelineEntry =
+            serde_json::from_str(&json).expect("Failed to deserialize");
+
+        assert!(deserialized.response.is_failure());
+        assert_eq!(deserialized.response.body, "Host unreachable: dns error");
+        assert!(TimelineSummary::from(&deserialized).is_failure());
+    }
+
     #[test]
     fn test_history_expiry_constant() {
         // 7 days in seconds
@@ -4,8 +4,40 @@
 //! Includes status badges, method badges, buttons, tabs, and syntax highlighting.
 
 use super::icons::Icons;
-use super::theme::{Animation, Colors, FontSize, Radius, Spacing, StrokeWidth};
+use super::theme::{Animation, Colors, FontSize, Indent, Radius, Spacing, StrokeWidth};
+use crate::core::baseline::{BaselineDiff, HeaderChange};
+use crate::core::constants::MAX_DISPLAY_LINE_CHARS;
+use crate::core::types::JsonFormat;
+use crate::core::MercuryError;
+use crate::core::{format_json_with, format_xml, HttpResponse, ResponseType};
+use crate::parser::SchemaVar;
+use crate::utils::{
+    break_long_lines, format_bytes, hex_dump_line, html_blocks, markdown_blocks, parse_offset,
+    DiffLine, JsonShape, QueryParam, ShapeField, TextBlock, HEX_BYTES_PER_LINE,
+};
 use egui::{self, Color32, RichText, Ui};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// =============================================================================
+// Accessibility
+// =============================================================================
+
+/// Gives a custom clickable an accessible name. A `Label` used as a button is
+/// otherwise read out as plain text (icons as the bare glyph), and a painted row
+/// as nothing at all.
+pub trait AccessibleButton {
+    fn accessible_button(self, name: &str) -> Self;
+}
+
+impl AccessibleButton for egui::Response {
+    fn accessible_button(self, name: &str) -> Self {
+        let enabled = self.enabled();
+        self.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, enabled, name));
+        self
+    }
+}
 
 // =============================================================================
 // Modal/Dialog Helpers
@@ -106,12 +138,93 @@ pub fn status_badge(ui: &mut Ui, status: u16, status_text: &str) {
         });
 }
 
+/// gRPC status badge (from `grpc-status`), shown next to the HTTP status for gRPC calls
+pub fn grpc_status_badge(ui: &mut Ui, code: u32, message: &str) {
+    let (color, bg) = if code == 0 {
+        (Colors::SUCCESS, Colors::SUCCESS_BG)
+    } else {
+        (Colors::ERROR, Colors::ERROR_BG)
+    };
+
+    let response = egui::Frame::NONE
+        .fill(bg)
+        .corner_radius(Radius::SM)
+        .inner_margin(egui::Margin::symmetric(
+            Spacing::SM as i8,
+            Spacing::XS as i8,
+        ))
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new(format!(
+                    "gRPC {} {}",
+                    code,
+                    crate::core::grpc::grpc_status_name(code)
+                ))
+                .color(color)
+                .strong()
+                .size(FontSize::MD),
+            );
+        })
+        .response;
+
+    if !message.is_empty() {
+        response.on_hover_text(message);
+    }
+}
+
 /// Metric display (time, size, etc.)
 pub fn metric(ui: &mut Ui, value: &str, color: Option<Color32>) {
     let text_color = color.unwrap_or(Colors::TEXT_MUTED);
     ui.label(RichText::new(value).color(text_color).size(FontSize::SM));
 }
 
+/// Size in human units, with the exact byte count on hover
+pub fn size_metric(ui: &mut Ui, size_bytes: usize) {
+    ui.label(
+        RichText::new(format_bytes(size_bytes))
+            .color(Colors::TEXT_MUTED)
+            .size(FontSize::SM),
+    )
+    .on_hover_text(exact_bytes(size_bytes));
+}
+
+/// `12 lines · 340 chars · 1.2 KB` for a body being edited, given its
+/// `text_counts`
+pub fn body_counts(ui: &mut Ui, text: &str, (lines, chars): (usize, usize)) {
+    let plural = |n: usize, word: &str| {
+        if n == 1 {
+            format!("1 {}", word)
+        } else {
+            format!("{} {}s", n, word)
+        }
+    };
+    ui.label(
+        RichText::new(format!(
+            "{} · {} · {}",
+            plural(lines, "line"),
+            plural(chars, "char"),
+            format_bytes(text.len())
+        ))
+        .color(Colors::TEXT_MUTED)
+        .size(FontSize::SM),
+    )
+    .on_hover_text(exact_bytes(text.len()));
+}
+
+/// `12,345 bytes`
+fn exact_bytes(size_bytes: usize) -> String {
+    let digits = size_bytes.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    let unit = if size_bytes == 1 { "byte" } else { "bytes" };
+    format!("{} {}", grouped, unit)
+}
+
 /// Response time metric with color coding and tooltip
 /// Green (<200ms): Fast - Yellow (200-1000ms): Normal - Red (>1000ms): Slow
 pub fn response_time_metric(ui: &mut Ui, duration_ms: u128) {
@@ -130,6 +243,103 @@ pub fn response_time_metric(ui: &mut Ui, duration_ms: u128) {
     .on_hover_text(tooltip);
 }
 
+/// Timing tab: this response's total time and, for saved requests, how it compares
+/// with recent runs from history
+pub fn timing_section(ui: &mut Ui, duration_ms: u128, size_bytes: usize, samples: &[u128]) {
+    egui::Grid::new("response_timing")
+        .num_columns(2)
+        .spacing([Spacing::LG, Spacing::XS])
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new("Total")
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+            response_time_metric(ui, duration_ms);
+            ui.end_row();
+            ui.label(
+                RichText::new("Size")
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+            size_metric(ui, size_bytes);
+            ui.end_row();
+
+            if samples.len() >= 2 {
+                let mut sorted = samples.to_vec();
+                sorted.sort_unstable();
+                ui.label(
+                    RichText::new(format!("Last {} runs", samples.len()))
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED),
+                );
+                ui.horizontal(|ui| {
+                    sparkline(ui, samples);
+                    metric(
+                        ui,
+                        &format!(
+                            "min {}ms · median {}ms · max {}ms",
+                            sorted[0],
+                            sorted[sorted.len() / 2],
+                            sorted[sorted.len() - 1]
+                        ),
+                        None,
+                    );
+                });
+                ui.end_row();
+            }
+        });
+}
+
+/// Tiny line chart of recent response times, oldest to newest.
+/// The latest point turns red when it's more than twice the median.
+pub fn sparkline(ui: &mut Ui, samples: &[u128]) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(60.0, 16.0), egui::Sense::hover());
+    if samples.len() < 2 {
+        return response;
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    let median = sorted[sorted.len() / 2];
+    let range = (max - min).max(1) as f32;
+
+    let points: Vec<egui::Pos2> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &ms)| {
+            let x = rect.left() + rect.width() * i as f32 / (samples.len() - 1) as f32;
+            let y = rect.bottom() - rect.height() * (ms - min) as f32 / range;
+            egui::pos2(x, y)
+        })
+        .collect();
+
+    let painter = ui.painter();
+    painter.add(egui::Shape::line(
+        points.clone(),
+        egui::Stroke::new(StrokeWidth::THIN, Colors::TEXT_MUTED),
+    ));
+    let last = samples[samples.len() - 1];
+    let last_color = if last > median * 2 {
+        Colors::ERROR
+    } else {
+        Colors::PRIMARY
+    };
+    if let Some(&end) = points.last() {
+        painter.circle_filled(end, 2.0, last_color);
+    }
+
+    response.on_hover_text(format!(
+        "Last {} runs: min {}ms, median {}ms, max {}ms",
+        samples.len(),
+        min,
+        median,
+        max
+    ))
+}
+
 /// Popup menu component
 /// Renders a clickable label that opens a styled popup menu
 /// Returns the Response for the trigger element
@@ -142,21 +352,64 @@ pub fn popup_menu(
     egui::Popup::menu(trigger_response)
         .width(width)
         .gap(4.0)
-        .frame(
-            egui::Frame::popup(&ui.ctx().style())
-                .fill(Colors::BG_MODAL)
-                .corner_radius(Radius::MD)
-                .stroke(egui::Stroke::new(StrokeWidth::THIN, Colors::BORDER_SUBTLE))
-                .inner_margin(Spacing::SM),
-        )
-        .style(|style: &mut egui::Style| {
-            // Use subtle selection colors for menu items - same as HTTP method dropdown
-            style.visuals.selection.bg_fill = Colors::popup_selection_bg();
-            style.visuals.widgets.hovered.bg_fill = Colors::popup_hover_bg();
-        })
+        .frame(popup_frame(ui))
+        .style(popup_style)
         .show(add_contents);
 }
 
+/// Like `popup_menu`, but clicks inside keep it open, so it can hold options;
+/// call `ui.close()` from an action that should dismiss it
+pub fn popup_options(
+    ui: &mut Ui,
+    trigger_response: &egui::Response,
+    width: f32,
+    add_contents: impl FnOnce(&mut Ui),
+) {
+    egui::Popup::menu(trigger_response)
+        .close_behavior(egui::PopupCloseBehavior::CloseOnClickOutside)
+        .width(width)
+        .gap(4.0)
+        .frame(popup_frame(ui))
+        .style(popup_style)
+        .show(add_contents);
+}
+
+/// Dropdown shown below `anchor` for as long as it's called, e.g. suggestions
+/// under a text field. Returns whether the pointer is over it, so the caller
+/// can keep it open while a click moves focus away from the field.
+pub fn dropdown_popup(
+    ui: &mut Ui,
+    anchor: &egui::Response,
+    add_contents: impl FnOnce(&mut Ui),
+) -> bool {
+    egui::Popup::from_response(anchor)
+        .id(anchor.id.with("dropdown"))
+        .open(true)
+        .width(anchor.rect.width())
+        .gap(4.0)
+        .frame(popup_frame(ui))
+        .style(popup_style)
+        .show(|ui| {
+            ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Truncate);
+            add_contents(ui);
+        })
+        .is_some_and(|inner| inner.response.contains_pointer())
+}
+
+fn popup_frame(ui: &Ui) -> egui::Frame {
+    egui::Frame::popup(&ui.ctx().style())
+        .fill(Colors::BG_MODAL)
+        .corner_radius(Radius::MD)
+        .stroke(egui::Stroke::new(StrokeWidth::THIN, Colors::BORDER_SUBTLE))
+        .inner_margin(Spacing::SM)
+}
+
+fn popup_style(style: &mut egui::Style) {
+    // Use subtle selection colors for menu items - same as HTTP method dropdown
+    style.visuals.selection.bg_fill = Colors::popup_selection_bg();
+    style.visuals.widgets.hovered.bg_fill = Colors::popup_hover_bg();
+}
+
 /// Method badge with color
 pub fn method_badge(ui: &mut Ui, method: &str) -> egui::Response {
     let color = Colors::method_color(method);
@@ -211,16 +464,31 @@ pub fn loading_state(ui: &mut Ui, message: &str) {
     });
 }
 
-/// Error state
-pub fn error_state(ui: &mut Ui, error: &str) {
+/// Error state with an actionable hint.
+/// Returns true when Retry is clicked (only offered for recoverable errors).
+pub fn error_state(ui: &mut Ui, error: &MercuryError) -> bool {
+    let mut retry = false;
+    let title = match error {
+        MercuryError::HostUnreachable(_) => "Can't Reach Host",
+        MercuryError::ConnectTimeout(_) => "Connection Timed Out",
+        MercuryError::InvalidUrl(_) => "Request Not Sent",
+        _ => "Request Failed",
+    };
+
     ui.vertical_centered(|ui| {
         ui.add_space(Spacing::XL);
         ui.label(
-            RichText::new("Request Failed")
+            RichText::new(title)
                 .size(FontSize::LG)
                 .color(Colors::ERROR)
                 .strong(),
         );
+        ui.add_space(Spacing::XS);
+        ui.label(
+            RichText::new(error.user_message())
+                .size(FontSize::SM)
+                .color(Colors::TEXT_SECONDARY),
+        );
         ui.add_space(Spacing::SM);
 
         egui::Frame::NONE
@@ -229,13 +497,121 @@ pub fn error_state(ui: &mut Ui, error: &str) {
             .inner_margin(Spacing::SM)
             .show(ui, |ui| {
                 ui.label(
-                    RichText::new(error)
+                    RichText::new(error.to_string())
                         .color(Colors::ERROR)
                         .monospace()
                         .size(FontSize::SM),
                 );
             });
+
+        if error.is_recoverable() {
+            ui.add_space(Spacing::MD);
+            retry = ui.button("Retry").clicked();
+        }
     });
+
+    retry
+}
+
+/// Banner for a 429 response: a countdown while the server's `Retry-After` window
+/// runs, with an option to resend when it ends. Returns true if "Send again" was clicked.
+pub fn rate_limit_banner(
+    ui: &mut Ui,
+    remaining_secs: Option<u64>,
+    has_retry_after: bool,
+    auto_resend: &mut bool,
+) -> bool {
+    let mut send = false;
+
+    egui::Frame::NONE
+        .fill(Colors::WARNING_BG)
+        .corner_radius(Radius::SM)
+        .inner_margin(Spacing::SM)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!("{} Rate limited", Icons::WARNING))
+                        .color(Colors::WARNING)
+                        .strong(),
+                );
+                match remaining_secs {
+                    Some(secs) => {
+                        let countdown = if secs < 60 {
+                            format!("{}s", secs)
+                        } else {
+                            format!("{}m {:02}s", secs / 60, secs % 60)
+                        };
+                        ui.label(
+                            RichText::new(format!("Retry in {}", countdown))
+                                .monospace()
+                                .color(Colors::TEXT_PRIMARY),
+                        );
+                        ui.checkbox(auto_resend, "Resend when it ends");
+                    }
+                    None => {
+                        let note = if has_retry_after {
+                            "The Retry-After window has passed"
+                        } else {
+                            "The server didn't send Retry-After"
+                        };
+                        ui.label(
+                            RichText::new(note)
+                                .size(FontSize::SM)
+                                .color(Colors::TEXT_SECONDARY),
+                        );
+                        send = ui.small_button("Send again").clicked();
+                    }
+                }
+            });
+        });
+
+    send
+}
+
+/// Banner for an environment missing variables `.env.schema` lists; hovering
+/// shows their descriptions. Returns true if "Add missing" was clicked.
+pub fn env_schema_banner(ui: &mut Ui, env_name: &str, missing: &[SchemaVar]) -> bool {
+    let mut add = false;
+
+    egui::Frame::NONE
+        .fill(Colors::WARNING_BG)
+        .corner_radius(Radius::SM)
+        .inner_margin(Spacing::SM)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                let names: Vec<&str> = missing.iter().map(|var| var.name.as_str()).collect();
+                ui.label(
+                    RichText::new(format!("{} {} is missing", Icons::WARNING, env_name))
+                        .color(Colors::WARNING)
+                        .strong(),
+                );
+                ui.label(
+                    RichText::new(names.join(", "))
+                        .monospace()
+                        .color(Colors::TEXT_PRIMARY),
+                )
+                .on_hover_ui(|ui| {
+                    for var in missing {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(&var.name).monospace().strong());
+                            if !var.description.is_empty() {
+                                ui.label(
+                                    RichText::new(&var.description).color(Colors::TEXT_SECONDARY),
+                                );
+                            }
+                        });
+                    }
+                });
+                add = ui
+                    .small_button("Add missing")
+                    .on_hover_text("Add them to the file, blank or with the schema's default")
+                    .clicked();
+            });
+        });
+
+    add
 }
 
 /// Variable indicator (for smart variables)
@@ -254,21 +630,106 @@ pub fn variable_indicator(ui: &mut Ui, name: &str, is_defined: bool) {
     );
 }
 
+/// What a toast's action button does; the app runs it after the frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastAction {
+    /// Send the current request again
+    Retry,
+    /// Open a file or folder with the system's default app
+    Open(PathBuf),
+    /// Bring back the folder run modal
+    ShowFolderRun,
+}
+
+/// A status bar notification, optionally with a labeled action button
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub message: String,
+    pub is_error: bool,
+    pub action: Option<(String, ToastAction)>,
+    /// When it reached the front of the queue (egui time); the fade starts here
+    pub shown_at: Option<f64>,
+}
+
+impl Toast {
+    pub fn new(message: impl Into<String>, is_error: bool) -> Self {
+        Self {
+            message: message.into(),
+            is_error,
+            action: None,
+            shown_at: None,
+        }
+    }
+
+    pub fn with_action(mut self, label: &str, action: ToastAction) -> Self {
+        self.action = Some((label.to_string(), action));
+        self
+    }
+}
+
+/// Toasts waiting their turn in the status bar, oldest first
+#[derive(Debug, Default)]
+pub struct ToastQueue {
+    toasts: VecDeque<Toast>,
+}
+
+impl ToastQueue {
+    /// Queue `toast`. Repeating the newest message replaces it instead, so
+    /// saving five times doesn't stack five "Saved" toasts.
+    pub fn push(&mut self, toast: Toast) {
+        if let Some(last) = self.toasts.back_mut() {
+            if last.message == toast.message && last.is_error == toast.is_error {
+                *last = toast;
+                return;
+            }
+        }
+        self.toasts.push_back(toast);
+        // Drop the oldest waiting toast, never the one on screen
+        while self.toasts.len() > crate::core::constants::MAX_QUEUED_TOASTS {
+            self.toasts.remove(1);
+        }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut Toast> {
+        self.toasts.front_mut()
+    }
+
+    /// Toasts queued behind the one on screen
+    pub fn waiting(&self) -> usize {
+        self.toasts.len().saturating_sub(1)
+    }
+
+    pub fn pop(&mut self) {
+        self.toasts.pop_front();
+    }
+}
+
+/// What happened to the toast on screen this frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastEvent {
+    Visible,
+    /// Faded out, or gave way to a waiting toast
+    Expired,
+    Dismissed,
+    Clicked(ToastAction),
+}
+
 /// Fading toast message with optional copy-to-clipboard on click.
 /// Shows truncated message with full text in tooltip. For errors, clicking copies to clipboard.
-/// Returns true if the toast is still visible (for repaint scheduling).
-pub fn fading_toast(
-    ui: &mut Ui,
-    ctx: &egui::Context,
-    message: &str,
-    timestamp: f64,
-    is_error: bool,
-) -> bool {
+/// With other toasts `waiting`, a toast without an action gives way after
+/// `TOAST_MIN_SECONDS` instead of fading out fully.
+pub fn fading_toast(ui: &mut Ui, ctx: &egui::Context, toast: &Toast, waiting: usize) -> ToastEvent {
     let current_time = ui.input(|i| i.time);
-    let elapsed = current_time - timestamp;
-
-    if elapsed >= crate::core::constants::FADE_DURATION_SECONDS {
-        return false;
+    let elapsed = current_time - toast.shown_at.unwrap_or(current_time);
+    let message = toast.message.as_str();
+    let is_error = toast.is_error;
+
+    if elapsed >= crate::core::constants::FADE_DURATION_SECONDS
+        || (waiting > 0
+            && toast.action.is_none()
+            && elapsed >= crate::core::constants::TOAST_MIN_SECONDS)
+    {
+        return ToastEvent::Expired;
     }
 
     // Track copy confirmation state in egui memory
@@ -351,7 +812,42 @@ pub fn fading_toast(
         label.on_hover_text(message);
     }
 
-    true // Still visible
+    let mut event = ToastEvent::Visible;
+    if let Some((label, action)) = &toast.action {
+        if ui.small_button(label).clicked() {
+            event = ToastEvent::Clicked(action.clone());
+        }
+    }
+    if waiting > 0 {
+        ui.label(
+            RichText::new(format!("+{}", waiting))
+                .size(FontSize::XS)
+                .color(Colors::TEXT_MUTED),
+        )
+        .on_hover_text(if waiting == 1 {
+            "1 more notification".to_string()
+        } else {
+            format!("{} more notifications", waiting)
+        });
+    }
+    if ui
+        .add(
+            egui::Label::new(
+                RichText::new(Icons::CROSS)
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            )
+            .sense(egui::Sense::click()),
+        )
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .on_hover_text("Dismiss")
+        .accessible_button("Dismiss notification")
+        .clicked()
+    {
+        event = ToastEvent::Dismissed;
+    }
+
+    event
 }
 
 /// Generic action icon button with visual feedback
@@ -389,6 +885,7 @@ pub fn action_icon_button(
     );
 
     let clicked = response
+        .accessible_button(tooltip)
         .on_hover_cursor(egui::CursorIcon::PointingHand)
         .on_hover_text(if show_confirmed {
             confirm_tooltip
@@ -424,10 +921,20 @@ pub fn clear_icon_button(ui: &mut Ui, ctx: &egui::Context, id: &str) -> bool {
 
 /// Send/Stop button (Send = Play/Primary, Stop = Square/Primary with Pulse)
 pub fn send_stop_button(ui: &mut Ui, executing: bool, time: f64) -> egui::Response {
-    let (icon, base_color, tooltip) = if executing {
-        (Icons::STOP, Colors::PRIMARY, "Cancel request (Esc)")
+    let (icon, base_color, tooltip, name) = if executing {
+        (
+            Icons::STOP,
+            Colors::PRIMARY,
+            "Cancel request (Esc)",
+            "Cancel request",
+        )
     } else {
-        (Icons::PLAY, Colors::PRIMARY, "Send request (⌘+Enter)")
+        (
+            Icons::PLAY,
+            Colors::PRIMARY,
+            "Send request (⌘+Enter)",
+            "Send request",
+        )
     };
 
     // Calculate pulse effect (0.0 to 1.0)
@@ -454,7 +961,8 @@ pub fn send_stop_button(ui: &mut Ui, executing: bool, time: f64) -> egui::Respon
                 .sense(egui::Sense::click()),
         )
         .on_hover_cursor(egui::CursorIcon::PointingHand)
-        .on_hover_text(tooltip);
+        .on_hover_text(tooltip)
+        .accessible_button(name);
 
     // Draw glow effect if executing
     if executing {
@@ -472,6 +980,26 @@ pub fn send_stop_button(ui: &mut Ui, executing: bool, time: f64) -> egui::Respon
     response
 }
 
+/// Indeterminate progress bar along the bottom edge of `rect`, shown while a request
+/// is in flight. Call after the panel is shown so the bar covers its border.
+pub fn sending_bar(ctx: &egui::Context, rect: egui::Rect, time: f64) {
+    let track = egui::Rect::from_min_max(
+        egui::pos2(rect.left(), rect.bottom() - StrokeWidth::THICK),
+        rect.right_bottom(),
+    );
+    let width = track.width() * 0.25;
+    let phase = (time * Animation::SWEEP_SPEED as f64).fract() as f32;
+    let left = track.left() - width + phase * (track.width() + width);
+    let bar = egui::Rect::from_x_y_ranges(
+        left.max(track.left())..=(left + width).min(track.right()),
+        track.y_range(),
+    );
+
+    let painter = ctx.layer_painter(egui::LayerId::background());
+    painter.rect_filled(bar, 0.0, Colors::PRIMARY);
+    ctx.request_repaint();
+}
+
 /// Standard close button using Icons::CROSS
 pub fn close_button(ui: &mut Ui, size: f32) -> egui::Response {
     let response = ui.add(
@@ -483,18 +1011,19 @@ pub fn close_button(ui: &mut Ui, size: f32) -> egui::Response {
         .sense(egui::Sense::click()),
     );
 
-    response.on_hover_cursor(egui::CursorIcon::PointingHand)
+    response
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .accessible_button("Close")
 }
 
 // =============================================================================
-// Collapsible Section Component
+// Key/Value Section Component
 // =============================================================================
 
-use super::theme::Layout;
 use egui::ScrollArea;
 
-/// A collapsible section with header, optional copy button, and scrollable content.
-/// Used for Headers, Cookies, and similar response panel sections.
+/// A key/value list with header, optional copy button, and scrollable content
+/// filling the rest of the panel. Used for the Headers and Cookies response tabs.
 ///
 /// # Arguments
 /// * `ui` - The egui UI context
@@ -502,41 +1031,41 @@ use egui::ScrollArea;
 /// * `title` - Section title (e.g., "Headers", "Cookies")
 /// * `id` - Unique identifier for the section (used for ScrollArea and copy button)
 /// * `items` - Key-value pairs to display (key in PRIMARY, value in TEXT_SECONDARY)
-/// * `show_copy` - Whether to show the copy button
-/// * `copy_text` - Text to copy when copy button is clicked (if show_copy is true)
-pub fn collapsible_section(
+/// * `copy_text` - Text for the copy button; no button when `None`
+/// * `row_action` - Label of a button at the end of each row; returns the index
+///   of the row whose button was clicked
+pub fn key_value_section(
     ui: &mut Ui,
     ctx: &egui::Context,
     title: &str,
     id: &str,
     items: &[(String, String)],
-    show_copy: bool,
     copy_text: Option<&str>,
-) {
+    row_action: Option<&str>,
+) -> Option<usize> {
     // Header with title and optional copy button
     ui.horizontal(|ui| {
         ui.label(RichText::new(title).size(FontSize::SM).strong());
-        if show_copy {
+        if let Some(text) = copy_text {
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if copy_icon_button(ui, ctx, id) {
-                    if let Some(text) = copy_text {
-                        ctx.copy_text(text.to_string());
-                    }
+                    ctx.copy_text(text.to_string());
                 }
             });
         }
     });
 
     // Scrollable content
+    let mut clicked = None;
     ScrollArea::both()
         .id_salt(id)
-        .max_height(Layout::HEADERS_MAX_HEIGHT)
+        .auto_shrink([false, false])
         .show(ui, |ui| {
             let max_width = ui.available_width();
             ui.set_max_width(max_width);
             ui.set_min_width(max_width);
 
-            for (key, value) in items {
+            for (index, (key, value)) in items.iter().enumerate() {
                 ui.horizontal(|ui| {
                     ui.label(
                         RichText::new(format!("{}: ", key))
@@ -550,12 +1079,393 @@ pub fn collapsible_section(
                             .color(Colors::TEXT_SECONDARY)
                             .monospace(),
                     );
+                    if let Some(label) = row_action {
+                        if ui.small_button(label).clicked() {
+                            clicked = Some(index);
+                        }
+                    }
                 });
             }
         });
+    clicked
+}
+
+/// Security header checklist: present headers in green, missing ones in amber
+pub fn security_headers_section(ui: &mut Ui, report: &[(&str, bool)]) {
+    let present = report.iter().filter(|(_, found)| *found).count();
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Security").size(FontSize::SM).strong());
+        ui.label(
+            RichText::new(format!("{}/{}", present, report.len()))
+                .size(FontSize::SM)
+                .color(Colors::TEXT_MUTED),
+        );
+    });
+
+    for (name, found) in report {
+        let (icon, color) = if *found {
+            (Icons::CHECK, Colors::SUCCESS)
+        } else {
+            (Icons::WARNING, Colors::WARNING)
+        };
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(icon).size(FontSize::SM).color(color));
+            ui.label(
+                RichText::new(*name)
+                    .size(FontSize::SM)
+                    .color(color)
+                    .monospace(),
+            );
+        });
+    }
+
+    ui.add_space(Spacing::SM);
+    ui.separator();
+}
+
+/// Unchanged lines kept around each change in a diff; longer runs are folded
+const DIFF_CONTEXT_LINES: usize = 2;
+
+/// Unified-style line diff: removed lines in red, added in green
+pub fn diff_view(ui: &mut Ui, lines: &[DiffLine]) {
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !matches!(l, DiffLine::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+    let near_change = |i: usize| changed.iter().any(|&c| c.abs_diff(i) <= DIFF_CONTEXT_LINES);
+
+    let mut folded = false;
+    for (i, line) in lines.iter().enumerate() {
+        let (prefix, text, color) = match line {
+            DiffLine::Same(text) => (" ", text, Colors::TEXT_MUTED),
+            DiffLine::Removed(text) => ("-", text, Colors::ERROR),
+            DiffLine::Added(text) => ("+", text, Colors::SUCCESS),
+        };
+        if matches!(line, DiffLine::Same(_)) && !near_change(i) {
+            if !folded {
+                ui.label(
+                    RichText::new("⋯")
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED),
+                );
+                folded = true;
+            }
+            continue;
+        }
+        folded = false;
+        ui.label(
+            RichText::new(format!("{} {}", prefix, text))
+                .size(FontSize::SM)
+                .color(color)
+                .monospace(),
+        );
+    }
+}
+
+/// Comparison with the saved baseline: a pass/changed summary, changed headers,
+/// and the body diff. Returns true when the user saves this response as the baseline.
+pub fn baseline_section(ui: &mut Ui, diff: Option<&BaselineDiff>) -> bool {
+    let mut update = false;
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Baseline").size(FontSize::SM).strong());
+        let (summary, color, button) = match diff {
+            None => (
+                "No baseline saved for this request".to_string(),
+                Colors::TEXT_MUTED,
+                "Save as baseline",
+            ),
+            Some(diff) if diff.is_match() => (
+                format!("{} Matches", Icons::CHECK),
+                Colors::SUCCESS,
+                "Update baseline",
+            ),
+            Some(diff) => {
+                let mut parts = Vec::new();
+                if let Some((old, new)) = diff.status {
+                    parts.push(format!("status {} → {}", old, new));
+                }
+                if !diff.headers.is_empty() {
+                    parts.push(format!("{} header(s)", diff.headers.len()));
+                }
+                if diff.changed_lines() > 0 {
+                    parts.push(format!("{} body line(s)", diff.changed_lines()));
+                }
+                (
+                    format!("{} Changed: {}", Icons::WARNING, parts.join(" · ")),
+                    Colors::WARNING,
+                    "Update baseline",
+                )
+            }
+        };
+        ui.label(RichText::new(summary).size(FontSize::SM).color(color));
+        update = ui
+            .small_button(button)
+            .on_hover_text("Keep this response as the expected one")
+            .clicked();
+    });
+
+    if let Some(diff) = diff.filter(|d| !d.is_match()) {
+        for change in &diff.headers {
+            let (text, color) = match change {
+                HeaderChange::Added(name, value) => {
+                    (format!("+ {}: {}", name, value), Colors::SUCCESS)
+                }
+                HeaderChange::Removed(name, value) => {
+                    (format!("- {}: {}", name, value), Colors::ERROR)
+                }
+                HeaderChange::Changed { name, old, new } => {
+                    (format!("~ {}: {} → {}", name, old, new), Colors::WARNING)
+                }
+            };
+            ui.label(
+                RichText::new(text)
+                    .size(FontSize::SM)
+                    .color(color)
+                    .monospace(),
+            );
+        }
+        if diff.changed_lines() > 0 {
+            ui.add_space(Spacing::XS);
+            egui::ScrollArea::vertical()
+                .id_salt("baseline_diff")
+                .max_height(240.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| diff_view(ui, &diff.body));
+        }
+    }
+
+    ui.add_space(Spacing::SM);
+    ui.separator();
+    update
+}
+
+/// What the user asked the JSON extract toolbar to do with the selected value
+pub enum JsonExtractAction {
+    CopyValue(String),
+    CopyPath(String),
+    SetVariable(String),
+    /// Pretty-printed JSON of the value, for the request body
+    UseAsBody(String),
+}
+
+/// Toolbar to pick a value out of a JSON response by path (typed or picked from
+/// the document's leaf paths) and act on it
+pub fn json_extract_section(
+    ui: &mut Ui,
+    path: &mut String,
+    doc: &serde_json::Value,
+) -> Option<JsonExtractAction> {
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Extract").size(FontSize::SM).strong());
+        ui.add(
+            egui::TextEdit::singleline(path)
+                .font(egui::TextStyle::Monospace)
+                .hint_text("$.data[0].id")
+                .desired_width(240.0),
+        );
+        let picker = ui
+            .add(
+                egui::Button::new(RichText::new(Icons::CHEVRON_DOWN).size(FontSize::SM))
+                    .frame(false),
+            )
+            .on_hover_cursor(egui::CursorIcon::PointingHand)
+            .on_hover_text("Pick a value from the response");
+        popup_menu(ui, &picker, 280.0, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    for leaf in crate::utils::json_leaf_paths(doc, 100) {
+                        if ui
+                            .selectable_label(*path == leaf, RichText::new(&leaf).monospace())
+                            .clicked()
+                        {
+                            *path = leaf;
+                            ui.close();
+                        }
+                    }
+                });
+        });
+    });
+
+    if !path.trim().is_empty() {
+        match crate::utils::json_path_lookup(doc, path) {
+            Some(value) => {
+                let text = crate::utils::json_value_text(value);
+                // A string holding serialized JSON can be shown parsed. It's parsed
+                // once per string rather than every frame.
+                let embedded_id = ui.id().with("extract_embedded").with(&text);
+                let embedded: Option<Arc<str>> = ui.data_mut(|d| {
+                    d.get_temp_mut_or_insert_with(embedded_id, || {
+                        crate::utils::embedded_json(value)
+                            .and_then(|parsed| serde_json::to_string_pretty(&parsed).ok())
+                            .map(Arc::from)
+                    })
+                    .clone()
+                });
+                let parsed_id = ui.id().with("extract_parsed");
+                let mut show_parsed =
+                    embedded.is_some() && ui.data(|d| d.get_temp(parsed_id).unwrap_or(false));
+                match embedded.as_ref().filter(|_| show_parsed) {
+                    Some(pretty) => {
+                        egui::ScrollArea::vertical()
+                            .id_salt("extract_parsed")
+                            .max_height(240.0)
+                            .show(ui, |ui| json_syntax_highlight(ui, pretty));
+                    }
+                    None => {
+                        ui.label(
+                            RichText::new(crate::utils::body_preview(&text, 200))
+                                .size(FontSize::SM)
+                                .monospace()
+                                .color(Colors::TEXT_SECONDARY),
+                        );
+                    }
+                }
+                ui.horizontal(|ui| {
+                    if embedded.is_some() {
+                        let label = if show_parsed {
+                            "Show as string"
+                        } else {
+                            "Parse as JSON"
+                        };
+                        if ui
+                            .small_button(label)
+                            .on_hover_text("This string holds serialized JSON")
+                            .clicked()
+                        {
+                            show_parsed = !show_parsed;
+                            ui.data_mut(|d| d.insert_temp(parsed_id, show_parsed));
+                        }
+                    }
+                    if ui.small_button("Copy value").clicked() {
+                        let copied = match embedded.as_ref().filter(|_| show_parsed) {
+                            Some(pretty) => pretty.to_string(),
+                            None => text.clone(),
+                        };
+                        action = Some(JsonExtractAction::CopyValue(copied));
+                    }
+                    if ui.small_button("Copy JSONPath").clicked() {
+                        let normalized =
+                            crate::utils::normalize_json_path(path).unwrap_or(path.clone());
+                        action = Some(JsonExtractAction::CopyPath(normalized));
+                    }
+                    if ui
+                        .small_button("Set as variable")
+                        .on_hover_text("Save into the selected environment")
+                        .clicked()
+                    {
+                        action = Some(JsonExtractAction::SetVariable(text.clone()));
+                    }
+                    if ui
+                        .small_button("Use as body")
+                        .on_hover_text("Put this value in the request body as JSON")
+                        .clicked()
+                    {
+                        // Serialized JSON goes in as the document, not a quoted string
+                        let json = match &embedded {
+                            Some(pretty) => pretty.to_string(),
+                            None => serde_json::to_string_pretty(value).unwrap_or(text.clone()),
+                        };
+                        action = Some(JsonExtractAction::UseAsBody(json));
+                    }
+                });
+            }
+            None => {
+                ui.label(
+                    RichText::new("No value at this path")
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED),
+                );
+            }
+        }
+    }
 
     ui.add_space(Spacing::SM);
     ui.separator();
+    action
+}
+
+/// Color for a shape's type name, matching the JSON syntax highlighting
+fn shape_color(shape: &JsonShape) -> Color32 {
+    match shape {
+        JsonShape::Null => Colors::JSON_NULL,
+        JsonShape::Bool => Colors::JSON_BOOLEAN,
+        JsonShape::Number => Colors::JSON_NUMBER,
+        JsonShape::String => Colors::JSON_STRING,
+        _ => Colors::JSON_BRACKET,
+    }
+}
+
+/// Inferred structure of a JSON response as a collapsible tree of `key: type` rows.
+/// Objects inside arrays are merged, so `items` lists the keys of all its elements.
+pub fn json_shape_tree(ui: &mut Ui, shape: &JsonShape) {
+    ui.label(
+        RichText::new(shape.type_name())
+            .size(FontSize::SM)
+            .monospace()
+            .color(shape_color(shape)),
+    );
+    if let Some(fields) = shape.fields() {
+        shape_fields(ui, fields, egui::Id::new("json_shape"), 0);
+    }
+}
+
+fn shape_fields(ui: &mut Ui, fields: &[ShapeField], id: egui::Id, depth: usize) {
+    for field in fields {
+        let id = id.with(&field.name);
+        let children = field.shape.fields().filter(|c| !c.is_empty());
+        // The first two levels start expanded
+        let mut expanded = ui.data(|d| d.get_temp::<bool>(id)).unwrap_or(depth < 2);
+
+        let row = ui.horizontal(|ui| {
+            ui.add_space(depth as f32 * Indent::TREE_LEVEL);
+            let icon = match (&children, expanded) {
+                (None, _) => " ",
+                (Some(_), true) => Icons::CHEVRON_DOWN,
+                (Some(_), false) => Icons::CHEVRON_RIGHT,
+            };
+            ui.label(
+                RichText::new(icon)
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+            let name = if field.optional {
+                format!("{}?:", field.name)
+            } else {
+                format!("{}:", field.name)
+            };
+            ui.label(
+                RichText::new(name)
+                    .size(FontSize::SM)
+                    .monospace()
+                    .color(Colors::JSON_KEY),
+            );
+            ui.label(
+                RichText::new(field.shape.type_name())
+                    .size(FontSize::SM)
+                    .monospace()
+                    .color(shape_color(&field.shape)),
+            );
+        });
+
+        if let Some(children) = children {
+            let response = row
+                .response
+                .interact(egui::Sense::click())
+                .on_hover_cursor(egui::CursorIcon::PointingHand);
+            if response.clicked() {
+                expanded = !expanded;
+                ui.data_mut(|d| d.insert_temp(id, expanded));
+            }
+            if expanded {
+                shape_fields(ui, children, id, depth + 1);
+            }
+        }
+    }
 }
 
 // =============================================================================
@@ -582,11 +1492,88 @@ impl KeyValueRow {
     fn is_empty(&self) -> bool {
         self.key.is_empty() && self.value.is_empty()
     }
-}
+}
+
+/// Result indicating if the key-value editor data was modified
+pub struct KeyValueEditorResult {
+    pub changed: bool,
+    /// `{{variable}}` under the pointer, with the field it's in
+    pub hovered_var: Option<(egui::Response, String)>,
+}
+
+/// The `{{variable}}` under the pointer in a text edit, with the edit's response
+/// to attach a tooltip to
+pub fn hovered_variable(
+    output: &egui::text_edit::TextEditOutput,
+    text: &str,
+) -> Option<(egui::Response, String)> {
+    let pointer = output.response.hover_pos()?;
+    let rect = output.galley.rect.translate(output.galley_pos.to_vec2());
+    if !rect.contains(pointer) {
+        return None;
+    }
+    let cursor = output.galley.cursor_from_pos(pointer - output.galley_pos);
+    let name = crate::utils::variable_at(text, cursor.index)?;
+    Some((output.response.clone(), name))
+}
+
+/// Right-click menu that base64- or URL-encodes/decodes the selected text in a
+/// text edit. Returns true if `text` changed. The edit's own undo (Cmd+Z)
+/// reverts it.
+pub fn text_transform_menu(
+    ui: &Ui,
+    output: &egui::text_edit::TextEditOutput,
+    text: &mut String,
+) -> bool {
+    // A right-click collapses the selection before the menu opens, so keep the
+    // last selection from before it
+    let id = output.response.id;
+    let key = id.with("transform_selection");
+    let secondary_pressed =
+        output.response.hovered() && ui.input(|i| i.pointer.secondary_pressed());
+    if !secondary_pressed && !output.response.context_menu_opened() {
+        let range = output.state.cursor.char_range().map(|range| {
+            let [start, end] = range.sorted_cursors();
+            (start.index, end.index)
+        });
+        ui.data_mut(|d| d.insert_temp(key, range));
+    }
+    let selection = ui
+        .data(|d| d.get_temp::<Option<(usize, usize)>>(key))
+        .flatten()
+        .filter(|(start, end)| start < end);
 
-/// Result indicating if the key-value editor data was modified
-pub struct KeyValueEditorResult {
-    pub changed: bool,
+    let mut changed = false;
+    output.response.context_menu(|ui| {
+        let Some((start, end)) = selection else {
+            ui.label(
+                RichText::new("Select text to transform")
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+            return;
+        };
+        for transform in crate::utils::TextTransform::ALL {
+            let result = crate::utils::transform_selection(text, start, end, transform);
+            let button = ui.add_enabled(result.is_some(), egui::Button::new(transform.label()));
+            if let (true, Some((new_text, start, end))) = (button.clicked(), result) {
+                *text = new_text;
+                // Keep the replacement selected so it can be transformed back
+                let mut state = output.state.clone();
+                state
+                    .cursor
+                    .set_char_range(Some(egui::text::CCursorRange::two(
+                        egui::text::CCursor::new(start),
+                        egui::text::CCursor::new(end),
+                    )));
+                state.store(ui.ctx(), id);
+                ui.data_mut(|d| d.insert_temp(key, Some((start, end))));
+                changed = true;
+                ui.close();
+            }
+        }
+    });
+    changed
 }
 
 /// Key-value editor with bulk edit mode toggle.
@@ -619,18 +1606,21 @@ pub fn key_value_editor(
     // Save cursor for overlay button
     let top_right = ui.cursor().min + egui::vec2(ui.available_width(), 0.0);
 
-    let changed = if *bulk_edit_mode {
+    let result = if *bulk_edit_mode {
         // Bulk edit mode - raw text
         let font_id = egui::FontId::monospace(FontSize::SM);
-        let response = ui.add(
-            egui::TextEdit::multiline(text)
-                .hint_text(RichText::new(hint_text).color(Colors::PLACEHOLDER))
-                .desired_width(ui.available_width())
-                .desired_rows(8)
-                .frame(false)
-                .font(font_id),
-        );
-        response.changed()
+        let output = egui::TextEdit::multiline(text)
+            .hint_text(RichText::new(hint_text).color(Colors::PLACEHOLDER))
+            .desired_width(ui.available_width())
+            .desired_rows(8)
+            .frame(false)
+            .font(font_id)
+            .show(ui);
+        let transformed = text_transform_menu(ui, &output, text);
+        KeyValueEditorResult {
+            changed: output.response.changed() || transformed,
+            hovered_var: hovered_variable(&output, text),
+        }
     } else {
         // Key-Value mode
         let mut rows = parse_text_to_rows(text, separator);
@@ -639,13 +1629,13 @@ pub fn key_value_editor(
         if result.changed {
             *text = rows_to_text(&rows, separator);
         }
-        result.changed
+        result
     };
 
     // Overlay toggle button
     render_mode_toggle(ui, top_right, bulk_edit_mode);
 
-    KeyValueEditorResult { changed }
+    result
 }
 
 /// Serialize rows to text (for params URL sync)
@@ -713,6 +1703,7 @@ fn render_key_value_rows(
     }
 
     let mut changed = false;
+    let mut hovered_var = None;
     let mut to_remove: Option<usize> = None;
     let font_id = egui::FontId::monospace(FontSize::SM);
 
@@ -728,29 +1719,32 @@ fn render_key_value_rows(
 
             // Use push_id with idx to ensure stable IDs for the text inputs
             ui.push_id(idx, |ui| {
-                let key_resp = ui.add(
-                    egui::TextEdit::singleline(&mut row.key)
-                        .hint_text(RichText::new("Key").color(Colors::PLACEHOLDER))
-                        .desired_width(Layout::INPUT_FIELD_WIDTH)
-                        .frame(false)
-                        .text_color(Colors::PRIMARY)
-                        .font(font_id.clone()),
-                );
+                let key_out = egui::TextEdit::singleline(&mut row.key)
+                    .hint_text(RichText::new("Key").color(Colors::PLACEHOLDER))
+                    .desired_width(Layout::INPUT_FIELD_WIDTH)
+                    .frame(false)
+                    .text_color(Colors::PRIMARY)
+                    .font(font_id.clone())
+                    .show(ui);
 
                 ui.label(RichText::new(separator).color(Colors::TEXT_MUTED));
 
-                let val_resp = ui.add(
-                    egui::TextEdit::singleline(&mut row.value)
-                        .hint_text(RichText::new("Value").color(Colors::PLACEHOLDER))
-                        .desired_width(ui.available_width() - 40.0)
-                        .frame(false)
-                        .text_color(Colors::TEXT_SECONDARY)
-                        .font(font_id.clone()),
-                );
+                let val_out = egui::TextEdit::singleline(&mut row.value)
+                    .hint_text(RichText::new("Value").color(Colors::PLACEHOLDER))
+                    .desired_width(ui.available_width() - 40.0)
+                    .frame(false)
+                    .text_color(Colors::TEXT_SECONDARY)
+                    .font(font_id.clone())
+                    .show(ui);
 
-                if key_resp.changed() || val_resp.changed() {
+                let transformed = text_transform_menu(ui, &val_out, &mut row.value);
+                if key_out.response.changed() || val_out.response.changed() || transformed {
                     changed = true;
                 }
+                hovered_var = hovered_var
+                    .take()
+                    .or_else(|| hovered_variable(&key_out, &row.key))
+                    .or_else(|| hovered_variable(&val_out, &row.value));
             });
 
             if !row.is_empty()
@@ -768,7 +1762,10 @@ fn render_key_value_rows(
         changed = true;
     }
 
-    KeyValueEditorResult { changed }
+    KeyValueEditorResult {
+        changed,
+        hovered_var,
+    }
 }
 
 fn render_mode_toggle(ui: &mut Ui, top_right: egui::Pos2, bulk_edit_mode: &mut bool) {
@@ -791,6 +1788,7 @@ fn render_mode_toggle(ui: &mut Ui, top_right: egui::Pos2, bulk_edit_mode: &mut b
         )
         .on_hover_cursor(egui::CursorIcon::PointingHand)
         .on_hover_text("Toggle edit mode")
+        .accessible_button(&format!("Switch to {} editing", text))
         .clicked()
     {
         *bulk_edit_mode = !*bulk_edit_mode;
@@ -914,34 +1912,30 @@ pub fn json_syntax_highlight(ui: &mut Ui, json: &str) {
             _ if in_string => {
                 current_token.push(ch);
             }
-            _ if !in_string => {
-                // Collect non-string tokens (numbers, booleans, null, whitespace)
-                if ch.is_whitespace() {
-                    if !current_token.is_empty() {
-                        let color = detect_json_value_color(&current_token);
-                        job.append(
-                            &current_token,
-                            0.0,
-                            TextFormat {
-                                font_id: font_id.clone(),
-                                color,
-                                ..Default::default()
-                            },
-                        );
-                        current_token.clear();
-                    }
+            // Non-string tokens (numbers, booleans, null) end at whitespace
+            _ if !in_string && ch.is_whitespace() => {
+                if !current_token.is_empty() {
+                    let color = detect_json_value_color(&current_token);
                     job.append(
-                        &ch.to_string(),
+                        &current_token,
                         0.0,
                         TextFormat {
                             font_id: font_id.clone(),
-                            color: Colors::TEXT_PRIMARY,
+                            color,
                             ..Default::default()
                         },
                     );
-                } else {
-                    current_token.push(ch);
+                    current_token.clear();
                 }
+                job.append(
+                    &ch.to_string(),
+                    0.0,
+                    TextFormat {
+                        font_id: font_id.clone(),
+                        color: Colors::TEXT_PRIMARY,
+                        ..Default::default()
+                    },
+                );
             }
             _ => {
                 current_token.push(ch);
@@ -980,7 +1974,50 @@ fn detect_json_value_color(token: &str) -> Color32 {
     }
 }
 
+/// Byte offsets of brackets that have no partner: stray/mismatched closers and unclosed openers.
+/// Brackets inside strings are ignored.
+fn unbalanced_bracket_positions(text: &str) -> std::collections::HashSet<usize> {
+    let mut unbalanced = std::collections::HashSet::new();
+    let mut stack: Vec<(usize, char)> = Vec::new();
+    let mut in_string = false;
+    let mut escape_next = false;
+
+    for (index, ch) in text.char_indices() {
+        if in_string {
+            if escape_next {
+                escape_next = false;
+            } else if ch == '\\' {
+                escape_next = true;
+            } else if ch == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' => in_string = true,
+            '{' | '[' => stack.push((index, ch)),
+            '}' | ']' => {
+                let opener = if ch == '}' { '{' } else { '[' };
+                match stack.last() {
+                    Some(&(_, open)) if open == opener => {
+                        stack.pop();
+                    }
+                    _ => {
+                        unbalanced.insert(index);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    unbalanced.extend(stack.into_iter().map(|(index, _)| index));
+    unbalanced
+}
+
 /// Create a LayoutJob for JSON syntax highlighting - for use with TextEdit.layouter()
+/// Unmatched brackets and an unterminated string are drawn in the error color.
 pub fn json_layout_job(text: &str, wrap_width: f32) -> egui::text::LayoutJob {
     use egui::text::{LayoutJob, TextFormat};
 
@@ -1004,13 +2041,19 @@ pub fn json_layout_job(text: &str, wrap_width: f32) -> egui::text::LayoutJob {
         return job;
     }
 
-    let chars = text.chars().peekable();
+    // Balance checking walks the whole text on every edit, so skip it for huge bodies
+    let unbalanced = if text.len() <= crate::core::constants::MAX_HIGHLIGHT_SIZE {
+        unbalanced_bracket_positions(text)
+    } else {
+        std::collections::HashSet::new()
+    };
+
     let mut current_token = String::new();
     let mut in_string = false;
     let mut escape_next = false;
     let mut is_key = true;
 
-    for ch in chars {
+    for (index, ch) in text.char_indices() {
         if escape_next {
             current_token.push(ch);
             escape_next = false;
@@ -1100,12 +2143,17 @@ pub fn json_layout_job(text: &str, wrap_width: f32) -> egui::text::LayoutJob {
                     current_token.clear();
                 }
                 is_key = ch == '{';
+                let color = if unbalanced.contains(&index) {
+                    Colors::ERROR
+                } else {
+                    Colors::JSON_BRACKET
+                };
                 job.append(
                     &ch.to_string(),
                     0.0,
                     TextFormat {
                         font_id: font_id.clone(),
-                        color: Colors::JSON_BRACKET,
+                        color,
                         ..Default::default()
                     },
                 );
@@ -1113,33 +2161,29 @@ pub fn json_layout_job(text: &str, wrap_width: f32) -> egui::text::LayoutJob {
             _ if in_string => {
                 current_token.push(ch);
             }
-            _ if !in_string => {
-                if ch.is_whitespace() {
-                    if !current_token.is_empty() {
-                        let color = detect_json_value_color(&current_token);
-                        job.append(
-                            &current_token,
-                            0.0,
-                            TextFormat {
-                                font_id: font_id.clone(),
-                                color,
-                                ..Default::default()
-                            },
-                        );
-                        current_token.clear();
-                    }
+            _ if !in_string && ch.is_whitespace() => {
+                if !current_token.is_empty() {
+                    let color = detect_json_value_color(&current_token);
                     job.append(
-                        &ch.to_string(),
+                        &current_token,
                         0.0,
                         TextFormat {
                             font_id: font_id.clone(),
-                            color: Colors::TEXT_PRIMARY,
+                            color,
                             ..Default::default()
                         },
                     );
-                } else {
-                    current_token.push(ch);
+                    current_token.clear();
                 }
+                job.append(
+                    &ch.to_string(),
+                    0.0,
+                    TextFormat {
+                        font_id: font_id.clone(),
+                        color: Colors::TEXT_PRIMARY,
+                        ..Default::default()
+                    },
+                );
             }
             _ => {
                 current_token.push(ch);
@@ -1147,9 +2191,13 @@ pub fn json_layout_job(text: &str, wrap_width: f32) -> egui::text::LayoutJob {
         }
     }
 
-    // Flush remaining
+    // Flush remaining - a string still open here was never closed
     if !current_token.is_empty() {
-        let color = detect_json_value_color(&current_token);
+        let color = if in_string {
+            Colors::ERROR
+        } else {
+            detect_json_value_color(&current_token)
+        };
         job.append(
             &current_token,
             0.0,
@@ -1253,6 +2301,173 @@ pub fn html_syntax_highlight(ui: &mut Ui, html: &str) {
     xml_syntax_highlight(ui, html);
 }
 
+/// Decoded fields of a form-urlencoded body as a key/value table
+pub fn form_fields_table(ui: &mut Ui, fields: &[QueryParam]) {
+    egui::Grid::new("form_fields")
+        .num_columns(2)
+        .striped(true)
+        .spacing([Spacing::LG, Spacing::XS])
+        .show(ui, |ui| {
+            for field in fields {
+                ui.label(RichText::new(&field.key).monospace().color(Colors::PRIMARY));
+                ui.add(
+                    egui::Label::new(
+                        RichText::new(&field.value)
+                            .monospace()
+                            .color(Colors::TEXT_PRIMARY),
+                    )
+                    .wrap(),
+                );
+                ui.end_row();
+            }
+        });
+}
+
+/// One collapsible section per NDJSON record: `(line, pretty)`, where `pretty` is
+/// None for a line that isn't valid JSON
+pub fn ndjson_records_view(ui: &mut Ui, records: &[(String, Option<String>)]) {
+    use crate::core::constants::{NDJSON_OPEN_RECORDS, NDJSON_TITLE_CHARS};
+
+    for (i, (line, pretty)) in records.iter().enumerate() {
+        let mut title: String = line.chars().take(NDJSON_TITLE_CHARS).collect();
+        if title.len() < line.len() {
+            title.push('…');
+        }
+        let color = if pretty.is_some() {
+            Colors::TEXT_SECONDARY
+        } else {
+            Colors::ERROR
+        };
+        egui::CollapsingHeader::new(
+            RichText::new(format!("{}  {}", i + 1, title))
+                .monospace()
+                .size(FontSize::SM)
+                .color(color),
+        )
+        .id_salt(("ndjson_record", i))
+        .default_open(records.len() <= NDJSON_OPEN_RECORDS)
+        .show(ui, |ui| match pretty {
+            Some(pretty) => json_syntax_highlight(ui, pretty),
+            None => {
+                ui.label(
+                    RichText::new("Not valid JSON")
+                        .size(FontSize::SM)
+                        .color(Colors::ERROR),
+                );
+            }
+        });
+    }
+}
+
+/// `hexdump -C` style view of one page of `bytes` from `offset`, with paging and
+/// a jump-to-offset field. Only the current page is formatted.
+pub fn hex_view(ui: &mut Ui, bytes: &[u8], offset: &mut usize, offset_text: &mut String) {
+    let page = crate::core::constants::HEX_PAGE_BYTES;
+    if *offset >= bytes.len() {
+        *offset = 0;
+    }
+    let end = (*offset + page).min(bytes.len());
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Hex").size(FontSize::SM).strong());
+        ui.label(
+            RichText::new(format!(
+                "{:#x}–{:#x} of {}",
+                offset,
+                end,
+                format_bytes(bytes.len())
+            ))
+            .size(FontSize::SM)
+            .color(Colors::TEXT_MUTED),
+        );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui
+                .add_enabled(end < bytes.len(), egui::Button::new("Next"))
+                .clicked()
+            {
+                *offset = end;
+            }
+            if ui
+                .add_enabled(*offset > 0, egui::Button::new("Prev"))
+                .clicked()
+            {
+                *offset = offset.saturating_sub(page);
+            }
+            let response = ui.add(
+                egui::TextEdit::singleline(offset_text)
+                    .desired_width(90.0)
+                    .hint_text("Go to 0x…"),
+            );
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                if let Some(target) = parse_offset(offset_text).filter(|t| *t < bytes.len()) {
+                    // Start the page on the line holding the target
+                    *offset = target - target % HEX_BYTES_PER_LINE;
+                }
+            }
+        });
+    });
+    ui.add_space(Spacing::XS);
+
+    let start = *offset;
+    let end = (start + page).min(bytes.len());
+    let lines = (end - start).div_ceil(HEX_BYTES_PER_LINE);
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    // Each page gets its own scroll state, so paging starts at the top
+    egui::ScrollArea::both()
+        .id_salt(("response_hex", start))
+        .auto_shrink([false, false])
+        .show_rows(ui, row_height, lines, |ui, rows| {
+            for row in rows {
+                let line_start = start + row * HEX_BYTES_PER_LINE;
+                let line_end = (line_start + HEX_BYTES_PER_LINE).min(end);
+                ui.label(
+                    RichText::new(hex_dump_line(line_start, &bytes[line_start..line_end]))
+                        .monospace()
+                        .color(Colors::TEXT_SECONDARY),
+                );
+            }
+        });
+}
+
+/// A failed gRPC-Web or Connect call, shown in place of its binary body: the
+/// status name, the message, and any error details the server sent
+pub fn grpc_error_view(ui: &mut Ui, error: &crate::core::grpc::GrpcErrorDetails) {
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(&error.code)
+                .color(Colors::ERROR)
+                .strong()
+                .size(FontSize::MD),
+        );
+        if !error.message.is_empty() {
+            ui.label(RichText::new(&error.message).color(Colors::TEXT_PRIMARY));
+        }
+    });
+    if error.details.is_empty() {
+        return;
+    }
+
+    ui.add_space(Spacing::SM);
+    ui.label(
+        RichText::new(format!("Details ({})", error.details.len()))
+            .size(FontSize::SM)
+            .strong(),
+    );
+    ScrollArea::both()
+        .id_salt("grpc_error_details")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for detail in &error.details {
+                ui.label(
+                    RichText::new(serde_json::to_string_pretty(detail).unwrap_or_default())
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_SECONDARY)
+                        .monospace(),
+                );
+            }
+        });
+}
+
 /// Binary content placeholder - shows type info to help user decide
 pub fn binary_placeholder(ui: &mut Ui, content_type: &str, size_bytes: usize) {
     let (icon, label) = get_content_type_info(content_type);
@@ -1279,7 +2494,8 @@ pub fn binary_placeholder(ui: &mut Ui, content_type: &str, size_bytes: usize) {
             RichText::new(format_bytes(size_bytes))
                 .size(FontSize::MD)
                 .color(Colors::TEXT_SECONDARY),
-        );
+        )
+        .on_hover_text(exact_bytes(size_bytes));
         ui.add_space(Spacing::SM);
         ui.label(
             RichText::new("Click 'Save' to download")
@@ -1289,6 +2505,183 @@ pub fn binary_placeholder(ui: &mut Ui, content_type: &str, size_bytes: usize) {
     });
 }
 
+/// Why the body view breaks lines differently from the response
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reflow {
+    /// Minified JSON sent as another type, laid out one value per line
+    FormattedJson,
+    /// Lines too long to lay out quickly, broken into pieces
+    WrappedLines,
+}
+
+impl Reflow {
+    pub fn note(&self) -> &'static str {
+        match self {
+            Reflow::FormattedJson => "Minified JSON formatted for display",
+            Reflow::WrappedLines => "Long lines wrapped for display",
+        }
+    }
+}
+
+/// A response body as the Pretty view shows it, built once per response.
+/// Raw shows the body exactly as it arrived, so it needs none.
+pub struct DisplayBody {
+    pub unescape_unicode: bool,
+    pub text: String,
+    pub reflow: Option<Reflow>,
+}
+
+impl DisplayBody {
+    pub fn new(response: &HttpResponse, unescape_unicode: bool, json_format: &JsonFormat) -> Self {
+        let text = match &response.response_type {
+            ResponseType::Json => {
+                let formatted = format_json_with(&response.body, json_format);
+                if unescape_unicode {
+                    crate::utils::unescape_unicode(&formatted)
+                } else {
+                    formatted
+                }
+            }
+            ResponseType::Xml => format_xml(&response.body),
+            _ => response.body.clone(),
+        };
+        let display = |text: String, reflow: Option<Reflow>| Self {
+            unescape_unicode,
+            text,
+            reflow,
+        };
+
+        let Some(wrapped) = break_long_lines(&text, MAX_DISPLAY_LINE_CHARS) else {
+            return display(text, None);
+        };
+        // JSON sent as text reads better formatted
+        if response.response_type != ResponseType::Json
+            && serde_json::from_str::<serde_json::Value>(&text).is_ok()
+        {
+            let formatted = format_json_with(&text, json_format);
+            if break_long_lines(&formatted, MAX_DISPLAY_LINE_CHARS).is_none() {
+                return display(formatted, Some(Reflow::FormattedJson));
+            }
+        }
+        display(wrapped, Some(Reflow::WrappedLines))
+    }
+}
+
+/// A response rendered for the Preview view, built once per response
+pub enum ResponsePreview {
+    Text(Vec<TextBlock>),
+    /// The texture, and the image's own size when it had to be scaled down
+    Image(egui::TextureHandle, [u32; 2]),
+    /// Why there's nothing to show
+    Unavailable(String),
+}
+
+impl ResponsePreview {
+    pub fn new(ctx: &egui::Context, response: &HttpResponse) -> Self {
+        match response.response_type {
+            ResponseType::Image => {
+                let Some(bytes) = &response.raw_bytes else {
+                    return Self::Unavailable("The image data wasn't kept".to_string());
+                };
+                match image::load_from_memory(bytes) {
+                    Ok(image) => {
+                        let original = [image.width(), image.height()];
+                        // The GPU can't hold a texture wider or taller than this
+                        let max_side = ctx.input(|i| i.max_texture_side) as u32;
+                        let image = if original.iter().any(|side| *side > max_side) {
+                            image.thumbnail(max_side, max_side)
+                        } else {
+                            image
+                        };
+                        let rgba = image.to_rgba8();
+                        let size = [rgba.width() as usize, rgba.height() as usize];
+                        let image = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
+                        let texture =
+                            ctx.load_texture("response_preview", image, Default::default());
+                        Self::Image(texture, original)
+                    }
+                    Err(e) => Self::Unavailable(format!("Can't preview this image: {}", e)),
+                }
+            }
+            ResponseType::Html => Self::Text(html_blocks(&response.body)),
+            _ => Self::Text(markdown_blocks(&response.body)),
+        }
+    }
+}
+
+/// Render a response preview: images fit to the panel width, text as blocks
+pub fn response_preview_view(ui: &mut Ui, preview: &ResponsePreview) {
+    match preview {
+        ResponsePreview::Image(texture, [width, height]) => {
+            let scaled = texture.size() != [*width as usize, *height as usize];
+            let size = if scaled {
+                format!("{} × {} (shown smaller)", width, height)
+            } else {
+                format!("{} × {}", width, height)
+            };
+            ui.label(
+                RichText::new(size)
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+            ui.add(
+                egui::Image::new(texture)
+                    .max_width(ui.available_width())
+                    .shrink_to_fit(),
+            );
+        }
+        ResponsePreview::Unavailable(reason) => {
+            ui.label(
+                RichText::new(reason)
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+        }
+        ResponsePreview::Text(blocks) if blocks.is_empty() => {
+            ui.label(
+                RichText::new("Nothing to show")
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+        }
+        ResponsePreview::Text(blocks) => {
+            for block in blocks {
+                match block {
+                    TextBlock::Heading(level, text) => {
+                        let size = match level {
+                            1 => FontSize::ICON,
+                            2 => FontSize::LG,
+                            _ => FontSize::MD,
+                        };
+                        ui.add_space(Spacing::SM);
+                        ui.label(RichText::new(text).size(size).strong());
+                    }
+                    TextBlock::Paragraph(text) => {
+                        ui.label(RichText::new(text).color(Colors::TEXT_SECONDARY));
+                    }
+                    TextBlock::ListItem(text) => {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.add_space(Spacing::SM);
+                            ui.label(RichText::new(Icons::DOT).color(Colors::TEXT_MUTED));
+                            ui.label(RichText::new(text).color(Colors::TEXT_SECONDARY));
+                        });
+                    }
+                    TextBlock::Code(text) => {
+                        egui::Frame::NONE
+                            .fill(Colors::BG_CODE)
+                            .corner_radius(Radius::SM)
+                            .inner_margin(Spacing::SM)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new(text).monospace().size(FontSize::SM));
+                            });
+                    }
+                }
+                ui.add_space(Spacing::XS);
+            }
+        }
+    }
+}
+
 /// Get icon and label for content type
 fn get_content_type_info(content_type: &str) -> (&'static str, &'static str) {
     let ct = content_type.to_lowercase();
@@ -1415,10 +2808,15 @@ pub fn too_large_placeholder(ui: &mut Ui, size_bytes: usize) {
         );
         ui.add_space(Spacing::XS);
         ui.label(
-            RichText::new(format!("{} (limit: 10MB)", format_bytes(size_bytes)))
-                .size(FontSize::MD)
-                .color(Colors::TEXT_SECONDARY),
-        );
+            RichText::new(format!(
+                "{} (limit: {})",
+                format_bytes(size_bytes),
+                format_bytes(crate::core::constants::MAX_RESPONSE_SIZE)
+            ))
+            .size(FontSize::MD)
+            .color(Colors::TEXT_SECONDARY),
+        )
+        .on_hover_text(exact_bytes(size_bytes));
         ui.add_space(Spacing::SM);
         ui.label(
             RichText::new("Response was not loaded to prevent memory issues")
@@ -1452,7 +2850,8 @@ pub fn large_text_placeholder(ui: &mut Ui, content_type: &str, size_bytes: usize
             RichText::new(format_bytes(size_bytes))
                 .size(FontSize::MD)
                 .color(Colors::TEXT_SECONDARY),
-        );
+        )
+        .on_hover_text(exact_bytes(size_bytes));
         ui.add_space(Spacing::SM);
         ui.label(
             RichText::new("Response exceeds 1MB inline display limit")
@@ -1468,12 +2867,40 @@ pub fn large_text_placeholder(ui: &mut Ui, content_type: &str, size_bytes: usize
     });
 }
 
-/// Empty response placeholder (204 or Error with empty body)
-pub fn empty_response_placeholder(ui: &mut Ui, status: u16, status_text: &str) {
+/// What an empty response with `status` means, and the headers that say more
+/// about it, for the statuses where empty is the point
+pub fn empty_status_note(status: u16) -> Option<(&'static str, &'static [&'static str])> {
+    match status {
+        202 => Some((
+            "Accepted — the server will finish the work later. Poll the Location, if given, for the result.",
+            &["Location", "Retry-After"],
+        )),
+        204 => Some(("No Content — the request succeeded and there's nothing to send back", &[])),
+        304 => Some((
+            "Not Modified — your cached copy is still valid",
+            &["ETag", "Last-Modified", "Cache-Control", "Expires"],
+        )),
+        _ => None,
+    }
+}
+
+/// Empty response placeholder (204, HEAD, or Error with empty body)
+/// Shows the status and `message`, and for statuses with a meaning of their
+/// own (see `empty_status_note`), that and the headers behind it
+pub fn empty_response_placeholder(
+    ui: &mut Ui,
+    status: u16,
+    status_text: &str,
+    message: &str,
+    headers: &[(String, String)],
+) {
+    let note = empty_status_note(status);
     ui.vertical_centered(|ui| {
         // Determine icon and color based on status
         let (icon, color) = if (200..300).contains(&status) {
             (Icons::CHECK, Colors::SUCCESS)
+        } else if status == 304 {
+            (Icons::CHECK, Colors::status_color(status))
         } else {
             (Icons::WARNING, Colors::WARNING)
         };
@@ -1489,31 +2916,117 @@ pub fn empty_response_placeholder(ui: &mut Ui, status: u16, status_text: &str) {
         );
         ui.add_space(Spacing::XS);
         ui.label(
-            RichText::new("The server returned an empty response")
+            RichText::new(note.map_or(message, |(meaning, _)| meaning))
                 .size(FontSize::SM)
                 .color(Colors::TEXT_MUTED),
         );
-    });
-}
-
-/// Format bytes to human readable string
-fn format_bytes(bytes: usize) -> String {
-    const KB: usize = 1024;
-    const MB: usize = KB * 1024;
 
-    if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} bytes", bytes)
-    }
+        let shown: Vec<&(String, String)> = note
+            .map(|(_, names)| {
+                names
+                    .iter()
+                    .flat_map(|name| headers.iter().filter(|(k, _)| k.eq_ignore_ascii_case(name)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !shown.is_empty() {
+            ui.add_space(Spacing::MD);
+            egui::Grid::new("empty_response_headers")
+                .num_columns(2)
+                .spacing([Spacing::MD, Spacing::XS])
+                .show(ui, |ui| {
+                    for (name, value) in shown {
+                        ui.label(
+                            RichText::new(name)
+                                .size(FontSize::SM)
+                                .color(Colors::TEXT_SECONDARY),
+                        );
+                        ui.add(egui::Label::new(RichText::new(value).monospace()).selectable(true));
+                        ui.end_row();
+                    }
+                });
+        }
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_empty_status_note() {
+        let (meaning, headers) = empty_status_note(304).unwrap();
+        assert!(meaning.starts_with("Not Modified"));
+        assert!(headers.contains(&"ETag"));
+        assert_eq!(empty_status_note(202).unwrap().1[0], "Location");
+        assert!(empty_status_note(204).unwrap().1.is_empty());
+        assert!(empty_status_note(200).is_none());
+    }
+
+    #[test]
+    fn test_toast_queue() {
+        let mut queue = ToastQueue::default();
+        queue.push(Toast::new("Imported 3 requests", false));
+        queue.push(Toast::new("Request failed", true).with_action("Retry", ToastAction::Retry));
+        assert_eq!(queue.waiting(), 1);
+
+        // Repeating the newest message replaces it rather than queueing again
+        queue.push(Toast::new("Request failed", true).with_action("Retry", ToastAction::Retry));
+        assert_eq!(queue.waiting(), 1);
+
+        for i in 0..10 {
+            queue.push(Toast::new(format!("Saved {}", i), false));
+        }
+        assert_eq!(
+            queue.waiting(),
+            crate::core::constants::MAX_QUEUED_TOASTS - 1
+        );
+        // The toast on screen is kept; the oldest waiting ones are dropped
+        assert_eq!(queue.front_mut().unwrap().message, "Imported 3 requests");
+        queue.pop();
+        assert_eq!(queue.front_mut().unwrap().message, "Saved 6");
+    }
+
+    #[test]
+    fn test_display_body_reflows_long_lines() {
+        let items: Vec<String> = (0..200).map(|i| format!(r#"{{"id":{}}}"#, i)).collect();
+        let minified = format!("[{}]", items.join(","));
+        let response = HttpResponse {
+            status: 200,
+            status_text: "200 OK".to_string(),
+            headers: Vec::new(),
+            cookies: Vec::new(),
+            body: minified.clone(),
+            raw_bytes: None,
+            duration_ms: 0,
+            size_bytes: minified.len(),
+            content_type: "text/plain".to_string(),
+            response_type: ResponseType::PlainText,
+            lossy_utf8: false,
+        };
+        let format = JsonFormat::default();
+
+        let pretty = DisplayBody::new(&response, false, &format);
+        assert_eq!(pretty.reflow, Some(Reflow::FormattedJson));
+        assert!(pretty.text.starts_with("[\n  {\n    \"id\": 0"));
+
+        // Text that isn't JSON keeps its characters and only gains line breaks
+        let text = HttpResponse {
+            body: minified.replace(',', ";"),
+            ..response.clone()
+        };
+        let wrapped = DisplayBody::new(&text, false, &format);
+        assert_eq!(wrapped.reflow, Some(Reflow::WrappedLines));
+        assert_eq!(wrapped.text.replace('\n', ""), text.body);
+
+        let short = HttpResponse {
+            body: "[1, 2]".to_string(),
+            ..response
+        };
+        let pretty = DisplayBody::new(&short, false, &format);
+        assert_eq!((pretty.text.as_str(), pretty.reflow), ("[1, 2]", None));
+    }
+
     #[test]
     fn test_extension_for_json() {
         assert_eq!(get_extension_for_content_type("application/json"), ".json");
@@ -1570,12 +3083,11 @@ mod tests {
     }
 
     #[test]
-    fn test_format_bytes() {
-        assert_eq!(format_bytes(500), "500 bytes");
-        assert_eq!(format_bytes(1024), "1.0 KB");
-        assert_eq!(format_bytes(2048), "2.0 KB");
-        assert_eq!(format_bytes(1_048_576), "1.0 MB");
-        assert_eq!(format_bytes(5_242_880), "5.0 MB");
+    fn test_exact_bytes() {
+        assert_eq!(exact_bytes(1), "1 byte");
+        assert_eq!(exact_bytes(842), "842 bytes");
+        assert_eq!(exact_bytes(12_345), "12,345 bytes");
+        assert_eq!(exact_bytes(3_250_586), "3,250,586 bytes");
     }
 
     #[test]
@@ -1644,4 +3156,29 @@ mod tests {
         assert_eq!(rows[0].key, "Key");
         assert_eq!(rows[0].value, "   ");
     }
+
+    #[test]
+    fn test_unbalanced_brackets_balanced_json() {
+        assert!(unbalanced_bracket_positions(r#"{"a": [1, {"b": 2}]}"#).is_empty());
+        // Brackets inside strings don't count
+        assert!(unbalanced_bracket_positions(r#"{"a": "}]\"{"}"#).is_empty());
+    }
+
+    #[test]
+    fn test_unbalanced_brackets_mismatch() {
+        // `]` never closed; `}` closes `[` instead
+        let text = r#"{"a": [1, 2}"#;
+        let positions = unbalanced_bracket_positions(text);
+        assert!(positions.contains(&text.find('}').unwrap()));
+        assert!(positions.contains(&0));
+        assert!(positions.contains(&text.find('[').unwrap()));
+    }
+
+    #[test]
+    fn test_unbalanced_brackets_stray_closer() {
+        let text = r#"{"a": 1}}"#;
+        let positions = unbalanced_bracket_positions(text);
+        assert_eq!(positions.len(), 1);
+        assert!(positions.contains(&(text.len() - 1)));
+    }
 }
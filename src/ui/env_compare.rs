@@ -0,0 +1,244 @@
+//! Environment Comparison Module
+//!
+//! Picking the environments to send the open request to, and the side-by-side
+//! view of their responses, one column per environment.
+
+use super::app::MercuryApp;
+use super::components::{diff_view, method_badge, show_modal};
+use super::icons::Icons;
+use super::theme::{Colors, FontSize, Spacing};
+use crate::core::env_compare::is_production;
+use crate::core::format_json_with;
+use crate::core::runner::RunStatus;
+use egui::{self, Context, RichText};
+use std::sync::atomic::Ordering;
+
+impl MercuryApp {
+    /// Render the environment picker; production environments need an extra tick
+    pub fn render_env_compare_picker(&mut self, ctx: &Context) {
+        if !self.show_env_compare_picker {
+            return;
+        }
+
+        let envs: Vec<(usize, String)> =
+            self.env_files.iter().cloned().enumerate().skip(1).collect();
+        let selection = &mut self.env_compare_selection;
+        let confirmed = &mut self.env_compare_prod_confirmed;
+        let mut run = None;
+
+        self.show_env_compare_picker = show_modal(
+            ctx,
+            "Run Across Environments",
+            self.show_env_compare_picker,
+            |ui, open| {
+                ui.label(
+                    RichText::new(
+                        "Send this request to each environment and compare the responses",
+                    )
+                    .color(Colors::TEXT_SECONDARY),
+                );
+                ui.add_space(Spacing::SM);
+
+                for (index, name) in &envs {
+                    let mut checked = selection.contains(index);
+                    if ui
+                        .checkbox(
+                            &mut checked,
+                            RichText::new(name).color(Colors::env_color(name)),
+                        )
+                        .changed()
+                    {
+                        if checked {
+                            selection.insert(*index);
+                        } else {
+                            selection.remove(index);
+                        }
+                    }
+                }
+
+                let production: Vec<&str> = envs
+                    .iter()
+                    .filter(|(index, name)| selection.contains(index) && is_production(name))
+                    .map(|(_, name)| name.as_str())
+                    .collect();
+                if !production.is_empty() {
+                    ui.add_space(Spacing::SM);
+                    ui.label(
+                        RichText::new(format!(
+                            "{} This sends a real request to {}",
+                            Icons::WARNING,
+                            production.join(", ")
+                        ))
+                        .size(FontSize::SM)
+                        .color(Colors::ERROR),
+                    );
+                    ui.checkbox(confirmed, "Send to production too");
+                }
+
+                let ready = selection.len() >= 2 && (production.is_empty() || *confirmed);
+                ui.add_space(Spacing::MD);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(ready, egui::Button::new("Run")).clicked() {
+                        let mut envs: Vec<usize> = selection.iter().copied().collect();
+                        envs.sort_unstable();
+                        run = Some(envs);
+                        *open = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open = false;
+                    }
+                });
+            },
+        );
+
+        if let Some(envs) = run {
+            self.compare_environments(&envs, ctx);
+        }
+    }
+
+    /// Render the comparison, one column per environment; closing it stops
+    /// requests still in flight
+    pub fn render_env_compare_modal(&mut self, ctx: &Context) {
+        let Some(comparison) = &self.env_compare else {
+            self.show_env_compare = false;
+            return;
+        };
+
+        let title = format!("Compare: {}", comparison.request_name);
+        let was_open = self.show_env_compare;
+
+        self.show_env_compare = show_modal(ctx, &title, self.show_env_compare, |ui, open| {
+            let done = comparison.is_done();
+            let reference = comparison.reference();
+
+            ui.horizontal(|ui| {
+                method_badge(ui, comparison.method.as_str());
+                let (summary, color) = if !done {
+                    ("Running...", Colors::TEXT_MUTED)
+                } else if reference.is_none() {
+                    ("No environment responded", Colors::ERROR)
+                } else if comparison.all_match() {
+                    ("All environments match", Colors::SUCCESS)
+                } else {
+                    ("Environments differ", Colors::WARNING)
+                };
+                ui.label(RichText::new(summary).size(FontSize::SM).color(color));
+            });
+            ui.add_space(Spacing::SM);
+
+            ui.columns(comparison.columns.len(), |uis| {
+                for (i, (ui, column)) in uis.iter_mut().zip(&comparison.columns).enumerate() {
+                    ui.label(
+                        RichText::new(&column.env)
+                            .strong()
+                            .color(Colors::env_color(&column.env)),
+                    );
+
+                    ui.horizontal(|ui| match &column.status {
+                        RunStatus::Pending => {
+                            ui.label(
+                                RichText::new("—")
+                                    .size(FontSize::SM)
+                                    .color(Colors::TEXT_MUTED),
+                            );
+                        }
+                        RunStatus::Running => {
+                            ui.spinner();
+                        }
+                        RunStatus::Done {
+                            status,
+                            duration_ms,
+                        } => {
+                            ui.label(
+                                RichText::new(status.to_string())
+                                    .size(FontSize::SM)
+                                    .strong()
+                                    .color(Colors::status_color(*status)),
+                            );
+                            ui.label(
+                                RichText::new(format!("{}ms", duration_ms))
+                                    .size(FontSize::SM)
+                                    .color(Colors::TEXT_MUTED),
+                            );
+                        }
+                        RunStatus::Failed(message) => {
+                            ui.label(
+                                RichText::new("Failed")
+                                    .size(FontSize::SM)
+                                    .color(Colors::ERROR),
+                            )
+                            .on_hover_text(message);
+                        }
+                    });
+
+                    // How this column differs from the reference
+                    if Some(i) == reference {
+                        ui.label(
+                            RichText::new("Reference")
+                                .size(FontSize::SM)
+                                .color(Colors::TEXT_MUTED),
+                        );
+                    } else if let Some(diff) = &column.diff {
+                        let (text, color) = if diff.is_match() {
+                            ("Matches".to_string(), Colors::SUCCESS)
+                        } else {
+                            let mut parts = Vec::new();
+                            if let Some((old, new)) = diff.status {
+                                parts.push(format!("status {} → {}", old, new));
+                            }
+                            if !diff.headers.is_empty() {
+                                parts.push(format!("{} headers", diff.headers.len()));
+                            }
+                            if diff.changed_lines() > 0 {
+                                parts.push(format!("{} lines", diff.changed_lines()));
+                            }
+                            (format!("Differs: {}", parts.join(", ")), Colors::WARNING)
+                        };
+                        ui.label(RichText::new(text).size(FontSize::SM).color(color));
+                    }
+                    ui.add_space(Spacing::SM);
+
+                    let Some(response) = &column.response else {
+                        continue;
+                    };
+                    egui::ScrollArea::both()
+                        .id_salt(("env_compare", i))
+                        .max_height(360.0)
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| {
+                            match column.diff.as_ref().filter(|d| d.changed_lines() > 0) {
+                                Some(diff) => diff_view(ui, &diff.body),
+                                None => {
+                                    ui.label(
+                                        RichText::new(format_json_with(
+                                            &response.body,
+                                            &self.settings.json_format,
+                                        ))
+                                        .size(FontSize::SM)
+                                        .color(Colors::TEXT_SECONDARY)
+                                        .monospace(),
+                                    );
+                                }
+                            }
+                        });
+                }
+            });
+
+            ui.add_space(Spacing::MD);
+            ui.horizontal(|ui| {
+                if !done && ui.button("Stop").clicked() {
+                    comparison.cancel.store(true, Ordering::Relaxed);
+                }
+                if ui.button("Close").clicked() {
+                    *open = false;
+                }
+            });
+        });
+
+        if was_open && !self.show_env_compare {
+            if let Some(comparison) = self.env_compare.take() {
+                comparison.cancel.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Folder Run Module
+//!
+//! Progress modal for folder runs: an aggregate progress bar and per-request status,
+//! and saving every response of a finished run into one folder.
+
+use super::app::MercuryApp;
+use super::components::{
+    get_extension_for_content_type, method_badge, show_modal, Toast, ToastAction,
+};
+use super::theme::{Colors, FontSize, Spacing};
+use crate::core::constants::RUN_MANIFEST_FILE;
+use crate::core::error::MercuryError;
+use crate::core::runner::{FolderRun, RunStatus};
+use egui::{self, Context, RichText};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+/// Write each response of `run` into `dir`, in the run's folder layout and with the
+/// extension its content type suggests, plus a manifest with every request's
+/// status and timing. Returns the number of responses written.
+pub fn save_run_responses(run: &FolderRun, dir: &Path) -> Result<usize, MercuryError> {
+    let write = |path: &Path, data: &[u8]| {
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, data))
+            .map_err(|e| MercuryError::FileWrite {
+                path: path.display().to_string(),
+                reason: e.to_string(),
+            })
+    };
+
+    let mut saved = 0;
+    let mut requests = Vec::new();
+    // A root-level request named like the manifest gets a numbered file instead
+    let mut used_names = HashSet::from([RUN_MANIFEST_FILE.to_string()]);
+    for entry in &run.entries {
+        let mut record = serde_json::json!({
+            "name": entry.name,
+            "method": entry.method.as_str(),
+        });
+        match &entry.status {
+            RunStatus::Done {
+                status,
+                duration_ms,
+            } => {
+                record["status"] = (*status).into();
+                record["duration_ms"] = (*duration_ms as u64).into();
+            }
+            RunStatus::Failed(message) => record["error"] = message.as_str().into(),
+            RunStatus::Pending | RunStatus::Running => record["skipped"] = true.into(),
+        }
+        if let Some(response) = &entry.response {
+            let extension = get_extension_for_content_type(&response.content_type);
+            let mut file = format!("{}{}", entry.name, extension);
+            let mut counter = 2;
+            while !used_names.insert(file.clone()) {
+                file = format!("{}-{}{}", entry.name, counter, extension);
+                counter += 1;
+            }
+            let data = response
+                .raw_bytes
+                .as_deref()
+                .unwrap_or(response.body.as_bytes());
+            write(&dir.join(&file), data)?;
+            record["file"] = file.into();
+            saved += 1;
+        }
+        requests.push(record);
+    }
+
+    let manifest = serde_json::json!({
+        "folder": run.folder_name,
+        "requests": requests,
+    });
+    let content = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| MercuryError::JsonError(e.to_string()))?;
+    write(&dir.join(RUN_MANIFEST_FILE), content.as_bytes())?;
+    Ok(saved)
+}
+
+impl MercuryApp {
+    /// Render the folder run modal; closing it stops a run that's still going
+    pub fn render_folder_run_modal(&mut self, ctx: &Context) {
+        let Some(run) = &self.folder_run else {
+            self.show_folder_run = false;
+            return;
+        };
+
+        let title = format!("Run: {}", run.folder_name);
+        let was_open = self.show_folder_run;
+        let mut save_all = false;
+
+        self.show_folder_run = show_modal(ctx, &title, self.show_folder_run, |ui, open| {
+            let total = run.entries.len();
+            if total == 0 {
+                ui.label(RichText::new("No requests in this folder").color(Colors::TEXT_MUTED));
+                ui.add_space(Spacing::MD);
+                if ui.button("Close").clicked() {
+                    *open = false;
+                }
+                return;
+            }
+
+            let finished = run.finished_count();
+            let failed = run
+                .entries
+                .iter()
+                .filter(|e| match &e.status {
+                    RunStatus::Done { status, .. } => *status >= 400,
+                    RunStatus::Failed(_) => true,
+                    _ => false,
+                })
+                .count();
+            let done = run.is_done();
+            let stopped = run.cancel.load(Ordering::Relaxed);
+
+            ui.add(
+                egui::ProgressBar::new(finished as f32 / total as f32)
+                    .text(format!("{} / {}", finished, total)),
+            );
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!("{} passed", finished - failed))
+                        .size(FontSize::SM)
+                        .color(Colors::SUCCESS),
+                );
+                ui.label(
+                    RichText::new(format!("{} failed", failed))
+                        .size(FontSize::SM)
+                        .color(if failed > 0 {
+                            Colors::ERROR
+                        } else {
+                            Colors::TEXT_MUTED
+                        }),
+                );
+                if stopped && done && finished < total {
+                    ui.label(
+                        RichText::new(format!("{} skipped", total - finished))
+                            .size(FontSize::SM)
+                            .color(Colors::WARNING),
+                    );
+                }
+            });
+            ui.add_space(Spacing::SM);
+
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for entry in &run.entries {
+                        ui.horizontal(|ui| {
+                            method_badge(ui, entry.method.as_str());
+                            ui.label(RichText::new(&entry.name).size(FontSize::SM));
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| match &entry.status {
+                                    RunStatus::Pending => {
+                                        ui.label(
+                                            RichText::new("—")
+                                                .size(FontSize::SM)
+                                                .color(Colors::TEXT_MUTED),
+                                        );
+                                    }
+                                    RunStatus::Running => {
+                                        ui.spinner();
+                                    }
+                                    RunStatus::Done {
+                                        status,
+                                        duration_ms,
+                                    } => {
+                                        ui.label(
+                                            RichText::new(format!("{}ms", duration_ms))
+                                                .size(FontSize::SM)
+                                                .color(Colors::TEXT_MUTED),
+                                        );
+                                        let color = if *status < 400 {
+                                            Colors::SUCCESS
+                                        } else {
+                                            Colors::ERROR
+                                        };
+                                        ui.label(
+                                            RichText::new(status.to_string())
+                                                .size(FontSize::SM)
+                                                .strong()
+                                                .color(color),
+                                        );
+                                    }
+                                    RunStatus::Failed(message) => {
+                                        ui.label(
+                                            RichText::new("Failed")
+                                                .size(FontSize::SM)
+                                                .color(Colors::ERROR),
+                                        )
+                                        .on_hover_text(message);
+                                    }
+                                },
+                            );
+                        });
+                    }
+                });
+
+            ui.add_space(Spacing::MD);
+            ui.horizontal(|ui| {
+                if !done && ui.button("Stop").clicked() {
+                    run.cancel.store(true, Ordering::Relaxed);
+                }
+                let has_responses = run.entries.iter().any(|e| e.response.is_some());
+                if ui
+                    .add_enabled(done && has_responses, egui::Button::new("Save All..."))
+                    .on_hover_text("Save every response and a manifest into a folder")
+                    .clicked()
+                {
+                    save_all = true;
+                }
+                if ui.button("Close").clicked() {
+                    *open = false;
+                }
+            });
+        });
+
+        if save_all {
+            let folder = rfd::FileDialog::new()
+                .set_title("Save Responses")
+                .pick_folder();
+            if let (Some(dir), Some(run)) = (folder, &self.folder_run) {
+                self.toasts.push(match save_run_responses(run, &dir) {
+                    Ok(count) => Toast::new(
+                        format!("Saved {} responses to {}", count, dir.display()),
+                        false,
+                    )
+                    .with_action("Open", ToastAction::Open(dir)),
+                    Err(e) => Toast::new(format!("Failed to save responses: {}", e), true),
+                });
+            }
+        }
+
+        if was_open && !self.show_folder_run {
+            if let Some(run) = self.folder_run.take() {
+                run.cancel.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::runner::RunEntry;
+    use crate::core::types::HttpMethod;
+    use crate::core::{HttpResponse, ResponseType};
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn json_response(body: &str) -> HttpResponse {
+        HttpResponse {
+            status: 200,
+            status_text: "200 OK".to_string(),
+            headers: Vec::new(),
+            cookies: Vec::new(),
+            body: body.to_string(),
+            raw_bytes: None,
+            duration_ms: 12,
+            size_bytes: body.len(),
+            content_type: "application/json".to_string(),
+            response_type: ResponseType::Json,
+            lossy_utf8: false,
+        }
+    }
+
+    #[test]
+    fn test_save_run_responses() {
+        let dir = TempDir::new().unwrap();
+        let run = FolderRun {
+            id: 1,
+            folder_name: "api".to_string(),
+            entries: vec![
+                RunEntry {
+                    name: "users/list".to_string(),
+                    method: HttpMethod::GET,
+                    status: RunStatus::Done {
+                        status: 200,
+                        duration_ms: 12,
+                    },
+                    response: Some(json_response("[]")),
+                },
+                RunEntry {
+                    name: "manifest".to_string(),
+                    method: HttpMethod::GET,
+                    status: RunStatus::Done {
+                        status: 200,
+                        duration_ms: 5,
+                    },
+                    response: Some(json_response("{}")),
+                },
+                RunEntry {
+                    name: "health".to_string(),
+                    method: HttpMethod::GET,
+                    status: RunStatus::Failed("Connection failed".to_string()),
+                    response: None,
+                },
+            ],
+            cancel: Arc::new(AtomicBool::new(false)),
+        };
+
+        assert_eq!(save_run_responses(&run, dir.path()).unwrap(), 2);
+        let saved = dir.path().join("users").join("list.json");
+        assert_eq!(fs::read_to_string(saved).unwrap(), "[]");
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join(RUN_MANIFEST_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(manifest["requests"][0]["file"], "users/list.json");
+        assert_eq!(manifest["requests"][0]["status"], 200);
+        assert_eq!(manifest["requests"][1]["file"], "manifest-2.json");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("manifest-2.json")).unwrap(),
+            "{}"
+        );
+        assert_eq!(manifest["requests"][2]["error"], "Connection failed");
+        assert!(manifest["requests"][2].get("file").is_none());
+    }
+}
@@ -20,6 +20,7 @@ impl Icons {
     pub const DUPLICATE: &'static str = "📋";
     pub const SAVE: &'static str = "💾";
     pub const FORMAT: &'static str = "✨";
+    pub const TAG: &'static str = "🏷";
 
     // Status/Indicator Icons
     pub const CHECK: &'static str = "✅";
@@ -38,12 +39,14 @@ impl Icons {
     pub const PLAY: &'static str = "▶";
     pub const STOP: &'static str = "■";
     pub const HISTORY: &'static str = "🕐";
+    pub const GLOBE: &'static str = "🌐";
     pub const ROCKET: &'static str = "🚀";
     pub const WAVE: &'static str = "👋";
     pub const LIGHTBULB: &'static str = "💡";
     pub const CMD_KEY: &'static str = "⌘";
 
-    // Chevron/Expand Icons (⏵⏷ are from same Unicode block for consistent sizing)
+    // Chevron/Expand Icons (⏴⏵⏷ are from same Unicode block for consistent sizing)
+    pub const CHEVRON_LEFT: &'static str = "⏴";
     pub const CHEVRON_RIGHT: &'static str = "⏵";
     pub const CHEVRON_DOWN: &'static str = "⏷";
 }
@@ -2,10 +2,16 @@
 //!
 //! User interface: main app, panels, components, and theming.
 
+pub mod api_docs;
 pub mod app;
 pub mod components;
+pub mod env_compare;
+pub mod folder_run;
 pub mod icons;
 pub mod panels;
+pub mod selection;
+pub mod settings;
+pub mod shortcuts;
 pub mod theme;
 
 // Re-export main app
@@ -2,14 +2,24 @@
 //!
 //! Main UI panel layouts - sidebar, request editor, response viewer.
 
-use super::app::{AuthMode, MercuryApp};
+use super::app::{AuthMode, MercuryApp, ResponseRequest, ResponseTab};
 use super::components::*;
 use super::icons::Icons;
 use super::theme::{Colors, FontSize, Layout, Radius, Spacing};
-use crate::core::{format_json, format_xml, ResponseType};
+use crate::core::codegen::{generate_mock, MockFormat};
+use crate::core::keybindings::Action;
+use crate::core::patch::{
+    check_json_patch, parse_json_patch, patch_media_type, to_json_patch, PatchOp, PatchOpKind,
+    JSON_PATCH_CONTENT_TYPE, MERGE_PATCH_CONTENT_TYPE,
+};
+use crate::core::types::{CollapsedPanel, GrpcCall, ResponseView};
+use crate::core::{format_json_with, MercuryError, ResponseType};
 use crate::parser::HttpMethod;
 use egui::{self, Context, ScrollArea, Ui};
 
+/// Temp-data key holding the Send button's widget id, for moving focus to it
+const SEND_BUTTON_ID: &str = "send_button";
+
 impl MercuryApp {
     /// Render left sidebar with collection tree
     pub fn render_sidebar_panel(&mut self, ctx: &Context) {
@@ -224,6 +234,7 @@ impl MercuryApp {
                                         .sense(egui::Sense::click()),
                                     )
                                     .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .accessible_button("Import your collection")
                                     .clicked()
                                 {
                                     self.should_open_insomnia_import = true;
@@ -262,14 +273,22 @@ impl MercuryApp {
                                         .sense(egui::Sense::click()),
                                     )
                                     .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .accessible_button("Import Insomnia collection")
                                     .clicked()
                                 {
                                     self.should_open_insomnia_import = true;
                                 }
                             });
+                        } else if self.search_contents && !self.search_query.trim().is_empty() {
+                            self.render_content_search_results(ui);
                         } else {
+                            if !self.tree_selection.is_empty() {
+                                self.render_selection_bar(ui);
+                            }
+
                             // Note: render_collection_tree modifies expanded state in-place
                             // No clone needed since we own the tree
+                            self.tree_selection.begin_frame();
                             let tree = std::mem::take(&mut self.collection_tree);
                             let mut tree = tree; // Make mutable
                             self.render_collection_tree(ui, &mut tree, 0);
@@ -279,6 +298,117 @@ impl MercuryApp {
             });
     }
 
+    /// Requests whose URL, headers, or body match the search, with the first
+    /// matching line under each; clicking one opens it
+    fn render_content_search_results(&mut self, ui: &mut Ui) {
+        let hits = self.content_search_hits();
+        let workspace = self.workspace_path.clone().unwrap_or_default();
+
+        ui.add_space(Spacing::SM);
+        ui.horizontal(|ui| {
+            ui.add_space(Spacing::SM);
+            let summary = match hits.len() {
+                0 => "No request contains this".to_string(),
+                1 => "1 request".to_string(),
+                n if n == crate::core::constants::MAX_CONTENT_SEARCH_RESULTS => {
+                    format!("First {} requests", n)
+                }
+                n => format!("{} requests", n),
+            };
+            ui.label(
+                egui::RichText::new(summary)
+                    .size(FontSize::XS)
+                    .color(Colors::TEXT_MUTED),
+            );
+        });
+        ui.add_space(Spacing::XS);
+
+        let mut open = None;
+        for hit in &hits {
+            let name = hit.path.strip_prefix(&workspace).unwrap_or(&hit.path);
+            let name = name.to_string_lossy();
+            let name = name.strip_suffix(".json").unwrap_or(&name);
+            let is_current = self.current_file.as_ref() == Some(&hit.path);
+
+            let row = ui
+                .vertical(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.add_space(Spacing::SM);
+                        if let Some(method) = &hit.method {
+                            ui.label(
+                                egui::RichText::new(method.as_str())
+                                    .color(Colors::method_color(method.as_str()))
+                                    .size(FontSize::XS)
+                                    .strong(),
+                            );
+                        }
+                        let mut text = egui::RichText::new(name).size(FontSize::SM);
+                        if is_current {
+                            text = text.strong().color(Colors::SELECTED_ITEM);
+                        }
+                        ui.label(text);
+                        if hit.matches > 1 {
+                            ui.label(
+                                egui::RichText::new(format!("+{}", hit.matches - 1))
+                                    .size(FontSize::XS)
+                                    .color(Colors::TEXT_MUTED),
+                            )
+                            .on_hover_text(format!("{} matching lines", hit.matches));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.add_space(Spacing::LG);
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(&hit.snippet)
+                                    .size(FontSize::XS)
+                                    .monospace()
+                                    .color(Colors::TEXT_SECONDARY),
+                            )
+                            .truncate(),
+                        );
+                    });
+                })
+                .response;
+
+            let response = ui
+                .interact(
+                    row.rect,
+                    egui::Id::new(("content_hit", hit.path.as_path())),
+                    egui::Sense::click(),
+                )
+                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                .accessible_button(name);
+            if response.clicked() {
+                open = Some(hit.path.clone());
+            }
+            ui.add_space(Spacing::XS);
+        }
+
+        if let Some(path) = open {
+            self.load_file(&path);
+        }
+    }
+
+    fn response_panel_frame() -> egui::Frame {
+        egui::Frame::NONE
+            .fill(Colors::BG_CARD)
+            .stroke(egui::Stroke::new(
+                super::theme::StrokeWidth::THIN,
+                Colors::BORDER_SUBTLE,
+            ))
+            .inner_margin(Spacing::MD)
+    }
+
+    fn render_response_contents(&mut self, ui: &mut Ui) {
+        self.collapse_button(ui, CollapsedPanel::Response);
+        if self.show_timeline {
+            self.render_timeline_content(ui);
+        } else {
+            self.render_response_body(ui);
+        }
+    }
+
     /// Render right response panel - unified, no split
     pub fn render_response_panel_new(&mut self, ctx: &Context) {
         egui::SidePanel::right("response_panel")
@@ -286,6 +416,40 @@ impl MercuryApp {
             .max_width(Layout::RESPONSE_MAX)
             .default_width(Layout::RESPONSE_DEFAULT)
             .resizable(true)
+            .frame(Self::response_panel_frame())
+            .show(ctx, |ui| self.render_response_contents(ui));
+    }
+
+    /// The response filling the center while the request editor is collapsed.
+    /// The side panel isn't shown, so its width is kept for when it comes back.
+    pub fn render_response_panel_central(&mut self, ctx: &Context) {
+        egui::CentralPanel::default()
+            .frame(Self::response_panel_frame())
+            .show(ctx, |ui| self.render_response_contents(ui));
+    }
+
+    /// Thin strip standing in for a collapsed panel; clicking it restores the panel
+    pub fn render_collapsed_strip(&mut self, ctx: &Context, panel: CollapsedPanel) {
+        let (id, side, icon, name, action) = match panel {
+            CollapsedPanel::Request => (
+                "request_panel_collapsed",
+                egui::panel::Side::Left,
+                Icons::CHEVRON_RIGHT,
+                "Show request",
+                Action::CollapseRequest,
+            ),
+            CollapsedPanel::Response => (
+                "response_panel_collapsed",
+                egui::panel::Side::Right,
+                Icons::CHEVRON_LEFT,
+                "Show response",
+                Action::CollapseResponse,
+            ),
+        };
+        let shortcut = super::shortcuts::combo_label(&self.settings.keybindings.get(action));
+        egui::SidePanel::new(side, id)
+            .exact_width(Layout::COLLAPSED_PANEL_WIDTH)
+            .resizable(false)
             .frame(
                 egui::Frame::NONE
                     .fill(Colors::BG_CARD)
@@ -293,19 +457,78 @@ impl MercuryApp {
                         super::theme::StrokeWidth::THIN,
                         Colors::BORDER_SUBTLE,
                     ))
-                    .inner_margin(Spacing::MD),
+                    .inner_margin(egui::Margin::symmetric(0, Spacing::MD as i8)),
             )
             .show(ctx, |ui| {
-                if self.show_timeline {
-                    self.render_timeline_content(ui);
-                } else {
-                    self.render_response_body(ui);
-                }
+                ui.vertical_centered(|ui| {
+                    if ui
+                        .add(
+                            egui::Label::new(
+                                egui::RichText::new(icon)
+                                    .size(FontSize::MD)
+                                    .color(Colors::TEXT_MUTED),
+                            )
+                            .sense(egui::Sense::click()),
+                        )
+                        .on_hover_cursor(egui::CursorIcon::PointingHand)
+                        .accessible_button(name)
+                        .on_hover_text(format!("{} ({})", name, shortcut))
+                        .clicked()
+                    {
+                        self.collapsed_panel = None;
+                    }
+                });
             });
     }
 
+    /// Chevron in the panel's outer top corner (inside its margin) that collapses it
+    pub fn collapse_button(&mut self, ui: &mut Ui, panel: CollapsedPanel) {
+        let inner = ui.max_rect();
+        let (x, icon, name, action) = match panel {
+            CollapsedPanel::Request => (
+                inner.left() - Spacing::MD / 2.0,
+                Icons::CHEVRON_LEFT,
+                "Collapse request",
+                Action::CollapseRequest,
+            ),
+            CollapsedPanel::Response => (
+                inner.right() + Spacing::MD / 2.0,
+                Icons::CHEVRON_RIGHT,
+                "Collapse response",
+                Action::CollapseResponse,
+            ),
+        };
+        let rect = egui::Rect::from_center_size(
+            egui::pos2(x, inner.top() + Spacing::SM),
+            egui::vec2(Spacing::MD, Spacing::LG),
+        );
+        let shortcut = super::shortcuts::combo_label(&self.settings.keybindings.get(action));
+        // Interact rather than allocate, so the content below doesn't move down
+        let response = ui.interact(rect, ui.id().with(name), egui::Sense::click());
+        let color = if response.hovered() {
+            Colors::TEXT_PRIMARY
+        } else {
+            Colors::TEXT_MUTED
+        };
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            icon,
+            egui::FontId::proportional(FontSize::XS),
+            color,
+        );
+        if response
+            .on_hover_cursor(egui::CursorIcon::PointingHand)
+            .accessible_button(name)
+            .on_hover_text(format!("{} ({})", name, shortcut))
+            .clicked()
+        {
+            self.collapsed_panel = Some(panel);
+        }
+    }
+
     /// Format timestamp as relative human-readable string
-    fn format_timestamp(timestamp: f64) -> String {
+    pub fn format_timestamp(timestamp: f64) -> String {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -325,6 +548,48 @@ impl MercuryApp {
         }
     }
 
+    /// Bulk actions for the items multi-selected in the tree
+    fn render_selection_bar(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(format!("{} selected", self.tree_selection.len()))
+                    .size(FontSize::SM)
+                    .strong(),
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if close_button(ui, FontSize::SM)
+                    .on_hover_text("Clear selection")
+                    .clicked()
+                {
+                    self.tree_selection.clear();
+                }
+                if ui
+                    .small_button(egui::RichText::new(Icons::DELETE).color(Colors::ERROR))
+                    .on_hover_text("Delete selected")
+                    .clicked()
+                {
+                    self.show_bulk_delete_confirm = true;
+                }
+                if ui
+                    .small_button(Icons::FOLDER)
+                    .on_hover_text("Move selected to a folder")
+                    .clicked()
+                {
+                    self.move_destination = None;
+                    self.show_move_dialog = true;
+                }
+                if ui
+                    .small_button(Icons::DUPLICATE)
+                    .on_hover_text("Duplicate selected requests")
+                    .clicked()
+                {
+                    self.should_duplicate_selection = true;
+                }
+            });
+        });
+        ui.add_space(Spacing::XS);
+    }
+
     /// Timeline content with proper scroll
     fn render_timeline_content(&mut self, ui: &mut Ui) {
         self.ensure_history_loaded();
@@ -382,6 +647,9 @@ impl MercuryApp {
             // Collect timestamp for deferred loading (avoids borrow issues)
             let mut entry_to_load: Option<f64> = None;
             let mut should_close_timeline = false;
+            let mut hovered: Option<(u64, f64)> = None;
+            let mut preview_to_load: Option<f64> = None;
+            let now = ui.input(|i| i.time);
 
             ScrollArea::vertical()
                 .id_salt("timeline_scroll")
@@ -395,7 +663,9 @@ impl MercuryApp {
                             continue;
                         }
 
-                        let status_color = if summary.status < 300 {
+                        let status_color = if summary.is_failure() {
+                            Colors::ERROR
+                        } else if summary.status < 300 {
                             Colors::SUCCESS
                         } else if summary.status < 400 {
                             Colors::WARNING
@@ -435,8 +705,13 @@ impl MercuryApp {
                                                 .size(FontSize::XS)
                                                 .color(Colors::TEXT_MUTED),
                                             );
+                                            let status_text = if summary.is_failure() {
+                                                format!("{} Failed", Icons::WARNING)
+                                            } else {
+                                                summary.status.to_string()
+                                            };
                                             ui.label(
-                                                egui::RichText::new(summary.status.to_string())
+                                                egui::RichText::new(status_text)
                                                     .size(FontSize::XS)
                                                     .color(status_color),
                                             );
@@ -456,6 +731,48 @@ impl MercuryApp {
                             .interact(egui::Sense::click())
                             .on_hover_cursor(egui::CursorIcon::PointingHand);
 
+                        // Response preview on hover, read from disk once the pointer settles
+                        let key = summary.timestamp.to_bits();
+                        let row_response = if row_response.hovered() {
+                            let since = match self.history_hover {
+                                Some((hover_key, since)) if hover_key == key => since,
+                                _ => now,
+                            };
+                            hovered = Some((key, since));
+
+                            match self.history_previews.get(&key) {
+                                Some(preview) => row_response.on_hover_ui(|ui| {
+                                    if preview.is_empty() {
+                                        ui.label(
+                                            egui::RichText::new("Empty response body")
+                                                .size(FontSize::XS)
+                                                .color(Colors::TEXT_MUTED),
+                                        );
+                                    } else {
+                                        ui.label(
+                                            egui::RichText::new(preview)
+                                                .monospace()
+                                                .size(FontSize::XS),
+                                        );
+                                    }
+                                }),
+                                None => {
+                                    let delay =
+                                        crate::core::constants::HISTORY_PREVIEW_HOVER_DELAY_SECONDS;
+                                    if now - since >= delay {
+                                        preview_to_load = Some(summary.timestamp);
+                                    } else {
+                                        ui.ctx().request_repaint_after(
+                                            std::time::Duration::from_secs_f64(delay),
+                                        );
+                                    }
+                                    row_response
+                                }
+                            }
+                        } else {
+                            row_response
+                        };
+
                         if row_response.clicked() {
                             // Capture timestamp for on-demand loading
                             entry_to_load = Some(summary.timestamp);
@@ -466,9 +783,15 @@ impl MercuryApp {
                     }
                 });
 
+            self.history_hover = hovered;
+            if let Some(timestamp) = preview_to_load {
+                self.load_history_preview(timestamp, ui.ctx());
+            }
+
             // Load full entry from disk and populate request + response
             if let Some(timestamp) = entry_to_load {
                 if let Some(entry) = crate::core::persistence::load_history_entry(timestamp) {
+                    self.response_request = Some((&entry.request).into());
                     // Load request data
                     self.load_request_data(
                         entry.request.method,
@@ -477,34 +800,24 @@ impl MercuryApp {
                         entry.request.body,
                     );
 
-                    // Create HttpResponse from stored Response for display
-                    use crate::core::request::ResponseType;
-                    let response_type = match entry.response.response_type.as_str() {
-                        "Json" => ResponseType::Json,
-                        "Xml" => ResponseType::Xml,
-                        "Html" => ResponseType::Html,
-                        "PlainText" => ResponseType::PlainText,
-                        "Image" => ResponseType::Image,
-                        "Binary" => ResponseType::Binary,
-                        "TooLarge" => ResponseType::TooLarge,
-                        "LargeText" => ResponseType::LargeText,
-                        "Empty" => ResponseType::Empty,
-                        _ => ResponseType::PlainText,
-                    };
-
-                    self.response = Some(crate::core::HttpResponse {
-                        status: entry.response.status,
-                        status_text: entry.response.status_text,
-                        headers: entry.response.headers,
-                        cookies: Vec::new(), // Cookies not stored in history
-                        body: entry.response.body,
-                        raw_bytes: None,
-                        duration_ms: entry.response.duration_ms,
-                        size_bytes: entry.response.size_bytes,
-                        content_type: entry.response.content_type,
-                        response_type,
-                    });
+                    if entry.response.is_failure() {
+                        // Show the stored error the way it appeared when the request failed
+                        self.response = None;
+                        self.request_error = Some(MercuryError::RequestFailed(entry.response.body));
+                    } else {
+                        // Create HttpResponse from stored Response for display
+                        self.response = Some(entry.response.into());
+                        self.request_error = None;
+                    }
                     self.formatted_response_cache = None; // Invalidate cache
+                    self.response_shape_cache = None;
+                    self.response_json_cache = None;
+                    self.grpc_error_cache = None;
+                    self.ndjson_cache = None;
+                    self.response_preview_cache = None;
+                    self.baseline_cache = None;
+                    self.hex_offset = 0;
+                    self.rate_limit_until = None;
                 }
             }
             if should_close_timeline {
@@ -515,26 +828,97 @@ impl MercuryApp {
 
     /// Response body with proper scroll
     fn render_response_body(&mut self, ui: &mut Ui) {
+        // Latency trend for saved requests, from history
+        let samples = if self.current_file.is_some() {
+            self.ensure_history_loaded();
+            crate::utils::response_time_samples(
+                &self.timeline,
+                &self.method,
+                &self.url,
+                crate::core::constants::SPARKLINE_SAMPLES,
+            )
+        } else {
+            Vec::new()
+        };
+
         if self.ongoing_request.is_some() {
-            loading_state(ui, "Sending request...");
+            match self.send_countdown(ui.input(|i| i.time)) {
+                Some(left) => {
+                    loading_state(
+                        ui,
+                        &format!("Sending in {}s... (Esc to cancel)", left.ceil()),
+                    );
+                    ui.ctx()
+                        .request_repaint_after(std::time::Duration::from_millis(100));
+                }
+                None => loading_state(ui, "Sending request..."),
+            }
         } else if let Some(response) = &self.response {
             // Status row
             ui.horizontal(|ui| {
                 status_badge(ui, response.status, &response.status_text);
+                if let Some((code, message)) =
+                    crate::core::grpc::grpc_status_from_headers(&response.headers)
+                {
+                    ui.add_space(Spacing::XS);
+                    grpc_status_badge(ui, code, &message);
+                }
                 ui.add_space(Spacing::SM);
                 response_time_metric(ui, response.duration_ms);
-                metric(
-                    ui,
-                    &format!(
-                        "{:.1}KB",
-                        response.size_bytes as f32 / super::theme::BYTES_PER_KB
-                    ),
-                    None,
-                );
+                if samples.len() >= 2 {
+                    sparkline(ui, &samples);
+                }
+                size_metric(ui, response.size_bytes);
+
+                // What the request expected when it was sent, not what the form says now
+                let mismatch = self
+                    .response_request
+                    .as_ref()
+                    .and_then(|sent| sent.expect_content_type.as_ref())
+                    .filter(|_| response.size_bytes > 0)
+                    .and_then(|expected| {
+                        crate::utils::content_type_mismatch(expected, &response.content_type)
+                    });
+                if let Some(warning) = mismatch {
+                    ui.add_space(Spacing::SM);
+                    ui.label(
+                        egui::RichText::new(format!("{} {}", Icons::WARNING, warning))
+                            .size(FontSize::SM)
+                            .color(Colors::WARNING),
+                    )
+                    .on_hover_text("Set in the request's Settings tab");
+                }
             });
 
             ui.add_space(Spacing::SM);
 
+            if response.status == 429 {
+                let now = ui.ctx().input(|i| i.time);
+                let remaining = self
+                    .rate_limit_until
+                    .map(|until| (until - now).max(0.0).ceil() as u64);
+                let has_retry_after = response
+                    .headers
+                    .iter()
+                    .any(|(k, _)| k.eq_ignore_ascii_case("retry-after"));
+                if rate_limit_banner(
+                    ui,
+                    remaining,
+                    has_retry_after,
+                    &mut self.auto_resend_rate_limited,
+                ) {
+                    self.should_execute_request = true;
+                }
+                ui.add_space(Spacing::SM);
+            }
+
+            if let Some(sent) = &self.response_request {
+                if render_response_request(ui, sent, self.settings.mask_secrets) {
+                    self.should_copy_response_curl = true;
+                }
+                ui.add_space(Spacing::SM);
+            }
+
             // Extract response type info BEFORE we use closures that need &mut self
             let is_text_response = matches!(
                 response.response_type,
@@ -542,36 +926,93 @@ impl MercuryApp {
                     | ResponseType::Xml
                     | ResponseType::Html
                     | ResponseType::PlainText
+                    | ResponseType::FormUrlEncoded
+                    | ResponseType::Ndjson
             );
             let needs_save_button = matches!(
                 response.response_type,
                 ResponseType::Binary | ResponseType::Image | ResponseType::LargeText
             );
+            let is_html = response.response_type == ResponseType::Html;
+            let is_json = response.response_type == ResponseType::Json;
+            let has_raw_bytes = response.raw_bytes.is_some();
+            // The chosen view, or the first this response has when it doesn't apply
+            let views =
+                crate::utils::response_views(&response.response_type, &response.content_type);
+            let view = if views.contains(&self.response_view) {
+                self.response_view
+            } else {
+                views.first().copied().unwrap_or_default()
+            };
+            let has_request_file = self.current_file.is_some();
             let headers_count = response.headers.len();
             let cookies_count = response.cookies.len();
 
             // Track if save was clicked (can't call method inside borrow)
             let mut save_clicked = false;
-            let mut raw_toggled = false;
+            let mut mock_format: Option<MockFormat> = None;
+            let mut copy_markdown = false;
+            let mut to_body_clicked = false;
+
+            // Tabs that don't apply to this response fall back to the body
+            let tabs = [
+                (ResponseTab::Body, "Body".to_string(), true),
+                (
+                    ResponseTab::Headers,
+                    format!("Headers ({})", headers_count),
+                    true,
+                ),
+                (
+                    ResponseTab::Cookies,
+                    format!("Cookies ({})", cookies_count),
+                    cookies_count > 0,
+                ),
+                (ResponseTab::Timing, "Timing".to_string(), true),
+                (ResponseTab::Security, "Security".to_string(), true),
+                (
+                    ResponseTab::Baseline,
+                    "Baseline".to_string(),
+                    has_request_file,
+                ),
+            ];
+            if !tabs
+                .iter()
+                .any(|(tab, _, shown)| *tab == self.response_tab && *shown)
+            {
+                self.response_tab = ResponseTab::Body;
+            }
 
             ui.horizontal(|ui| {
-                // Headers checkbox for all response types
-                let headers_label = format!("Headers ({})", headers_count);
-                ui.checkbox(&mut self.show_response_headers, headers_label);
-
-                // Cookies checkbox (only show if cookies present)
-                if cookies_count > 0 {
-                    let cookies_label = format!("Cookies ({})", cookies_count);
-                    ui.checkbox(&mut self.show_response_cookies, cookies_label);
-                }
-
-                // Raw only makes sense for text responses
-                if is_text_response {
-                    let was_raw = self.response_view_raw;
-                    ui.checkbox(&mut self.response_view_raw, "Raw");
-                    if self.response_view_raw != was_raw {
-                        raw_toggled = true;
+                for (tab, label, shown) in &tabs {
+                    if !shown {
+                        continue;
+                    }
+                    let color = if self.response_tab == *tab {
+                        Colors::PRIMARY
+                    } else {
+                        Colors::TEXT_MUTED
+                    };
+                    let response = ui
+                        .add(
+                            egui::Button::new(
+                                egui::RichText::new(label).size(FontSize::MD).color(color),
+                            )
+                            .frame(false),
+                        )
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    let response = match tab {
+                        ResponseTab::Security => {
+                            response.on_hover_text("Check for recommended security headers")
+                        }
+                        ResponseTab::Baseline => response.on_hover_text(
+                            "Compare with the response saved as this request's baseline",
+                        ),
+                        _ => response,
+                    };
+                    if response.clicked() {
+                        self.response_tab = *tab;
                     }
+                    ui.add_space(Spacing::SM);
                 }
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -587,6 +1028,7 @@ impl MercuryApp {
                                 .sense(egui::Sense::click()),
                             )
                             .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Save response to a file")
                             .clicked()
                         {
                             save_clicked = true;
@@ -594,6 +1036,118 @@ impl MercuryApp {
                         ui.add_space(Spacing::SM);
                     }
 
+                    if has_request_file {
+                        if ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new(format!("{} Example", Icons::SAVE))
+                                        .size(FontSize::SM)
+                                        .color(Colors::TEXT_MUTED),
+                                )
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Save as example")
+                            .on_hover_text("Save this response as a named example")
+                            .clicked()
+                        {
+                            self.example_name.clear();
+                            self.show_save_example_dialog = true;
+                        }
+                        ui.add_space(Spacing::SM);
+                    }
+
+                    if is_html {
+                        if ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new(format!("{} Browser", Icons::GLOBE))
+                                        .size(FontSize::SM)
+                                        .color(Colors::PRIMARY),
+                                )
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Open in browser")
+                            .on_hover_text("Open the rendered page in your browser")
+                            .clicked()
+                        {
+                            self.should_open_response_in_browser = true;
+                        }
+                        ui.add_space(Spacing::SM);
+                    }
+
+                    if is_json {
+                        let to_body = ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new(format!("{} To Body", Icons::COPY))
+                                        .size(FontSize::SM)
+                                        .color(Colors::TEXT_MUTED),
+                                )
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Use as request body")
+                            .on_hover_text(
+                                "Use this response as the request body (pick part of it with Extract)",
+                            );
+                        if to_body.clicked() {
+                            to_body_clicked = true;
+                        }
+                        ui.add_space(Spacing::SM);
+                    }
+
+                    if is_text_response {
+                        let mock_response = ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new(format!("{} Mock", Icons::COPY))
+                                        .size(FontSize::SM)
+                                        .color(Colors::TEXT_MUTED),
+                                )
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Copy as mock")
+                            .on_hover_text("Copy this response as a mock definition");
+                        popup_menu(ui, &mock_response, 140.0, |ui| {
+                            for format in MockFormat::ALL {
+                                if ui.selectable_label(false, format.label()).clicked() {
+                                    mock_format = Some(format);
+                                }
+                            }
+                        });
+                        ui.add_space(Spacing::SM);
+
+                        let docs_response = ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new(format!("{} Docs", Icons::COPY))
+                                        .size(FontSize::SM)
+                                        .color(Colors::TEXT_MUTED),
+                                )
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Copy as Markdown")
+                            .on_hover_text("Copy the request and response as a Markdown example");
+                        let include_headers = &mut self.markdown_include_headers;
+                        let redact_secrets = &mut self.markdown_redact_secrets;
+                        popup_options(ui, &docs_response, 160.0, |ui| {
+                            ui.checkbox(include_headers, "Include headers");
+                            ui.checkbox(redact_secrets, "Redact secrets").on_hover_text(
+                                "Keep secret variables as {{NAME}} and mask credential headers",
+                            );
+                            ui.separator();
+                            if ui.selectable_label(false, "Copy as Markdown").clicked() {
+                                copy_markdown = true;
+                                ui.close();
+                            }
+                        });
+                        ui.add_space(Spacing::SM);
+                    }
+
                     if ui
                         .add(
                             egui::Label::new(
@@ -604,6 +1158,7 @@ impl MercuryApp {
                             .sense(egui::Sense::click()),
                         )
                         .on_hover_cursor(egui::CursorIcon::PointingHand)
+                        .accessible_button("Request history")
                         .clicked()
                     {
                         self.show_timeline = true;
@@ -611,82 +1166,276 @@ impl MercuryApp {
                 });
             });
 
-            // Handle save after borrow is released
-            if save_clicked {
-                self.save_response_to_file();
-            }
-            // Invalidate cache when raw toggle changes
-            if raw_toggled {
-                self.formatted_response_cache = None;
-            }
+            // Ways to view the body
+            let on_body = self.response_tab == ResponseTab::Body;
+            if on_body && (is_json || has_raw_bytes || views.len() > 1) {
+                ui.add_space(Spacing::XS);
+                ui.horizontal(|ui| {
+                    if is_json {
+                        ui.checkbox(&mut self.show_response_extract, "Extract")
+                            .on_hover_text("Copy a value or save it as a variable");
+                        ui.checkbox(&mut self.show_response_inspect, "Inspect")
+                            .on_hover_text("Show the structure: keys and their types");
+                    }
 
-            ui.add_space(Spacing::SM);
+                    if has_raw_bytes {
+                        ui.checkbox(&mut self.show_response_hex, "Hex")
+                            .on_hover_text("Show the bytes as a hex and ASCII dump");
+                    }
+
+                    // Pretty, Raw, and Preview, where more than one applies
+                    if views.len() > 1 {
+                        ui.add_space(Spacing::SM);
+                        for option in views {
+                            if ui
+                                .selectable_label(view == *option, option.label())
+                                .clicked()
+                            {
+                                self.response_view = *option;
+                            }
+                        }
+                    }
+
+                    if is_json && view == ResponseView::Pretty && !self.show_response_inspect {
+                        ui.add_space(Spacing::SM);
+                        ui.checkbox(&mut self.unescape_unicode, "Unescape unicode")
+                            .on_hover_text(
+                                "Show \\u escapes as the characters they stand for, and \
+                                 control characters as visible symbols",
+                            );
+                    }
+                });
+            }
+
+            // Handle save after borrow is released
+            if save_clicked {
+                if let Some(toast) = self.save_response_to_file() {
+                    self.toasts.push(toast);
+                }
+            }
+            if to_body_clicked {
+                self.pending_request_body =
+                    format_json_with(&response.body, &self.settings.json_format);
+                self.should_use_as_body = true;
+            }
+            if let Some(format) = mock_format {
+                // Mock the endpoint that was actually called, not whatever the form
+                // holds now. Mocks never need the real credentials.
+                let (method, url) = match &self.response_request {
+                    Some(sent) if sent.resolved => (sent.method.clone(), sent.masked.0.clone()),
+                    // History keeps the URL as typed; fill in all but secret variables
+                    Some(sent) => {
+                        let variables = self
+                            .env_variables
+                            .iter()
+                            .filter(|(name, _)| {
+                                !crate::utils::is_secret_name(name)
+                                    && self.env_sources.get(*name)
+                                        != Some(&crate::parser::VarSource::Secret)
+                            })
+                            .map(|(name, value)| (name.clone(), value.clone()))
+                            .collect();
+                        let url = crate::parser::substitute_variables(&sent.masked.0, &variables);
+                        (sent.method.clone(), url)
+                    }
+                    None => (self.method.clone(), self.resolved_request(false).0),
+                };
+                let mock = generate_mock(format, &method, &url, response);
+                ui.ctx().copy_text(mock);
+                let now = ui.ctx().input(|i| i.time);
+                self.last_action_message = Some((format!("{} copied", format.label()), now, false));
+            }
+            if copy_markdown {
+                self.should_copy_markdown = true;
+            }
+
+            ui.add_space(Spacing::SM);
             ui.separator();
             ui.add_space(Spacing::SM);
 
-            // Headers section (collapsible) - uses shared component
-            if self.show_response_headers {
-                let ctx = ui.ctx().clone();
-                let header_items: Vec<(String, String)> = response
-                    .headers
-                    .iter()
-                    .map(|(k, v)| (k.clone(), v.clone()))
-                    .collect();
-                let headers_copy_text: String = response
-                    .headers
-                    .iter()
-                    .map(|(k, v)| format!("{}: {}", k, v))
-                    .collect::<Vec<_>>()
-                    .join("\n");
+            match self.response_tab {
+                ResponseTab::Body => {}
+                ResponseTab::Headers => {
+                    let ctx = ui.ctx().clone();
+                    let header_items: Vec<(String, String)> = response
+                        .headers
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect();
+                    let headers_copy_text: String = response
+                        .headers
+                        .iter()
+                        .map(|(k, v)| format!("{}: {}", k, v))
+                        .collect::<Vec<_>>()
+                        .join("\n");
 
-                collapsible_section(
-                    ui,
-                    &ctx,
-                    "Headers",
-                    "response_headers",
-                    &header_items,
-                    true,
-                    Some(&headers_copy_text),
-                );
+                    key_value_section(
+                        ui,
+                        &ctx,
+                        "Headers",
+                        "response_headers",
+                        &header_items,
+                        Some(&headers_copy_text),
+                        None,
+                    );
+                }
+                ResponseTab::Cookies => {
+                    let ctx = ui.ctx().clone();
+                    // Parse cookies to show name=value only (exclude attributes like Path, HttpOnly)
+                    let cookie_items: Vec<(String, String)> = response
+                        .cookies
+                        .iter()
+                        .filter_map(|c| {
+                            let main_part = c.split(';').next().unwrap_or(c);
+                            main_part
+                                .split_once('=')
+                                .map(|(k, v)| (k.to_string(), v.to_string()))
+                        })
+                        .collect();
+                    let cookies_copy_text: String = cookie_items
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", k, v))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+
+                    let save = key_value_section(
+                        ui,
+                        &ctx,
+                        "Cookies",
+                        "response_cookies",
+                        &cookie_items,
+                        Some(&cookies_copy_text),
+                        Some("Save to environment"),
+                    );
+                    if let Some((name, value)) = save.and_then(|i| cookie_items.get(i)) {
+                        self.variable_name = crate::utils::cookie_variable_name(name);
+                        self.variable_value = value.clone();
+                        self.show_set_variable_dialog = true;
+                    }
+                }
+                ResponseTab::Timing => {
+                    timing_section(ui, response.duration_ms, response.size_bytes, &samples);
+                }
+                ResponseTab::Security => {
+                    let report = crate::utils::security_header_report(&response.headers);
+                    ScrollArea::vertical()
+                        .id_salt("response_security")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| security_headers_section(ui, &report));
+                }
+                ResponseTab::Baseline => {
+                    if self.baseline_cache.is_none() {
+                        let current = crate::core::types::Response::from(response);
+                        self.baseline_cache = Some(
+                            self.current_file
+                                .as_deref()
+                                .and_then(|path| {
+                                    crate::core::examples::load_example(
+                                        path,
+                                        crate::core::baseline::BASELINE_EXAMPLE,
+                                    )
+                                })
+                                .map(|baseline| {
+                                    crate::core::baseline::compare(&baseline, &current)
+                                }),
+                        );
+                    }
+                    let diff = self.baseline_cache.as_ref().and_then(|d| d.as_ref());
+                    let update = ScrollArea::vertical()
+                        .id_salt("response_baseline")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| baseline_section(ui, diff))
+                        .inner;
+                    if update {
+                        self.should_update_baseline = true;
+                    }
+                }
             }
 
-            // Cookies section (collapsible) - uses shared component
-            if self.show_response_cookies && !response.cookies.is_empty() {
-                let ctx = ui.ctx().clone();
-                // Parse cookies to show name=value only (exclude attributes like Path, HttpOnly)
-                let cookie_items: Vec<(String, String)> = response
-                    .cookies
-                    .iter()
-                    .filter_map(|c| {
-                        let main_part = c.split(';').next().unwrap_or(c);
-                        main_part
-                            .split_once('=')
-                            .map(|(k, v)| (k.to_string(), v.to_string()))
-                    })
-                    .collect();
-                let cookies_copy_text: String = cookie_items
-                    .iter()
-                    .map(|(k, v)| format!("{}={}", k, v))
-                    .collect::<Vec<_>>()
-                    .join("\n");
+            if !on_body {
+                return;
+            }
 
-                collapsible_section(
-                    ui,
-                    &ctx,
-                    "Cookies",
-                    "response_cookies",
-                    &cookie_items,
-                    true,
-                    Some(&cookies_copy_text),
+            let mut extract_action = None;
+            if self.show_response_extract && is_json {
+                let doc = self
+                    .response_json_cache
+                    .get_or_insert_with(|| serde_json::from_str(&response.body).ok());
+                if let Some(doc) = doc {
+                    extract_action = json_extract_section(ui, &mut self.extract_path, doc);
+                }
+            }
+            if let Some(action) = extract_action {
+                let now = ui.ctx().input(|i| i.time);
+                match action {
+                    JsonExtractAction::CopyValue(text) => {
+                        ui.ctx().copy_text(text);
+                        self.last_action_message = Some(("Value copied".to_string(), now, false));
+                    }
+                    JsonExtractAction::CopyPath(path) => {
+                        ui.ctx().copy_text(path);
+                        self.last_action_message =
+                            Some(("JSONPath copied".to_string(), now, false));
+                    }
+                    JsonExtractAction::SetVariable(value) => {
+                        self.variable_name =
+                            crate::utils::json_path_variable_name(&self.extract_path);
+                        self.variable_value = value;
+                        self.show_set_variable_dialog = true;
+                    }
+                    JsonExtractAction::UseAsBody(body) => {
+                        self.pending_request_body = body;
+                        self.should_use_as_body = true;
+                    }
+                }
+            }
+
+            if response.lossy_utf8 {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} Invalid bytes for its charset; decoded lossily",
+                        Icons::WARNING
+                    ))
+                    .size(FontSize::SM)
+                    .color(Colors::WARNING),
+                )
+                .on_hover_text(
+                    "Bytes that aren't valid in the body's charset (UTF-8 if it names none) \
+                     show as �. Hex and Save use the original bytes.",
                 );
             }
 
             ui.add_space(Spacing::SM);
 
+            if response.response_type == ResponseType::Binary && self.grpc_error_cache.is_none() {
+                self.grpc_error_cache = Some(crate::core::grpc::grpc_error_details(response));
+            }
+
             // Body rendering based on ResponseType
             match &response.response_type {
+                // Decoded text keeps its original bytes too
+                _ if self.show_response_hex && response.raw_bytes.is_some() => {
+                    if let Some(bytes) = &response.raw_bytes {
+                        hex_view(ui, bytes, &mut self.hex_offset, &mut self.hex_offset_text);
+                    }
+                }
                 ResponseType::Empty => {
-                    empty_response_placeholder(ui, response.status, &response.status_text);
+                    let sent_head = self
+                        .response_request
+                        .as_ref()
+                        .is_some_and(|sent| sent.method == HttpMethod::HEAD);
+                    let message = if sent_head {
+                        "HEAD request — headers only"
+                    } else {
+                        "The server returned an empty response"
+                    };
+                    empty_response_placeholder(
+                        ui,
+                        response.status,
+                        &response.status_text,
+                        message,
+                        &response.headers,
+                    );
                 }
                 ResponseType::TooLarge => {
                     too_large_placeholder(ui, response.size_bytes);
@@ -695,14 +1444,107 @@ impl MercuryApp {
                     // Large text - show honest placeholder with Save option
                     large_text_placeholder(ui, &response.content_type, response.size_bytes);
                 }
+                _ if view == ResponseView::Preview => {
+                    if self.response_preview_cache.is_none() {
+                        self.response_preview_cache =
+                            Some(ResponsePreview::new(ui.ctx(), response));
+                    }
+                    ScrollArea::both()
+                        .id_salt("response_preview")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            if let Some(preview) = &self.response_preview_cache {
+                                response_preview_view(ui, preview);
+                            }
+                        });
+                }
+                ResponseType::Binary if matches!(self.grpc_error_cache, Some(Some(_))) => {
+                    if let Some(Some(error)) = &self.grpc_error_cache {
+                        grpc_error_view(ui, error);
+                    }
+                }
                 ResponseType::Binary | ResponseType::Image => {
                     // Binary content placeholder with Save option
                     binary_placeholder(ui, &response.content_type, response.size_bytes);
                 }
+                ResponseType::Json if self.show_response_inspect => {
+                    ui.label(egui::RichText::new("Structure").size(FontSize::SM).strong());
+                    if self.response_shape_cache.is_none() {
+                        self.response_shape_cache =
+                            serde_json::from_str::<serde_json::Value>(&response.body)
+                                .ok()
+                                .map(|doc| crate::utils::JsonShape::infer(&doc));
+                    }
+                    ScrollArea::both()
+                        .id_salt("response_shape")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| match &self.response_shape_cache {
+                            Some(shape) => json_shape_tree(ui, shape),
+                            None => {
+                                ui.label(
+                                    egui::RichText::new("The body isn't valid JSON")
+                                        .size(FontSize::SM)
+                                        .color(Colors::TEXT_MUTED),
+                                );
+                            }
+                        });
+                }
+                ResponseType::FormUrlEncoded if view == ResponseView::Pretty => {
+                    let fields = crate::utils::parse_form_body(&response.body);
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!("Fields ({})", fields.len()))
+                                .size(FontSize::SM)
+                                .strong(),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let ctx = ui.ctx().clone();
+                            if copy_icon_button(ui, &ctx, "response_body") {
+                                ui.ctx().copy_text(response.body.clone());
+                            }
+                        });
+                    });
+                    ScrollArea::both()
+                        .id_salt("response_form")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| form_fields_table(ui, &fields));
+                }
+                ResponseType::Ndjson if view == ResponseView::Pretty => {
+                    let records = self.ndjson_cache.get_or_insert_with(|| {
+                        crate::utils::ndjson_records(&response.body)
+                            .into_iter()
+                            .map(|record| match record {
+                                Ok(value) => {
+                                    (value.to_string(), serde_json::to_string_pretty(&value).ok())
+                                }
+                                Err(line) => (line, None),
+                            })
+                            .collect()
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!("Records ({})", records.len()))
+                                .size(FontSize::SM)
+                                .strong(),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let ctx = ui.ctx().clone();
+                            if copy_icon_button(ui, &ctx, "response_body") {
+                                ui.ctx().copy_text(response.body.clone());
+                            }
+                        });
+                    });
+                    ScrollArea::both()
+                        .id_salt("response_ndjson")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| ndjson_records_view(ui, records));
+                }
                 ResponseType::Json
                 | ResponseType::Xml
                 | ResponseType::Html
-                | ResponseType::PlainText => {
+                | ResponseType::PlainText
+                | ResponseType::FormUrlEncoded
+                | ResponseType::Ndjson => {
                     // Body header with copy button
                     ui.horizontal(|ui| {
                         ui.label(egui::RichText::new("Body").size(FontSize::SM).strong());
@@ -714,27 +1556,40 @@ impl MercuryApp {
                         });
                     });
 
-                    // Use cached formatted response to avoid expensive cloning every frame
-                    let body = if self.response_view_raw {
-                        &response.body
-                    } else if let Some(cached) = &self.formatted_response_cache {
-                        cached
+                    // Use cached formatted response to avoid expensive cloning every frame.
+                    // The cache holds the pretty body, so switching views doesn't redo it.
+                    // Raw shows the body byte for byte, escapes and long lines included.
+                    let unescape = self.unescape_unicode;
+                    let (body, reflow) = if view == ResponseView::Raw {
+                        (&response.body, None)
                     } else {
-                        // Cache miss - format once and store
-                        let formatted = match &response.response_type {
-                            ResponseType::Json => format_json(&response.body),
-                            ResponseType::Xml => format_xml(&response.body),
-                            _ => response.body.clone(),
-                        };
-                        self.formatted_response_cache = Some(formatted);
-                        self.formatted_response_cache.as_ref().unwrap()
+                        if self
+                            .formatted_response_cache
+                            .as_ref()
+                            .is_some_and(|cached| cached.unescape_unicode != unescape)
+                        {
+                            self.formatted_response_cache = None;
+                        }
+                        let display = self.formatted_response_cache.get_or_insert_with(|| {
+                            DisplayBody::new(response, unescape, &self.settings.json_format)
+                        });
+                        (&display.text, display.reflow)
                     };
 
+                    if let Some(reflow) = reflow {
+                        ui.label(
+                            egui::RichText::new(reflow.note())
+                                .size(FontSize::SM)
+                                .color(Colors::TEXT_MUTED),
+                        )
+                        .on_hover_text("Copy and Save use the body as it arrived");
+                    }
+
                     ScrollArea::both()
                         .id_salt("response_body")
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
-                            if self.response_view_raw {
+                            if view == ResponseView::Raw {
                                 ui.add(
                                     egui::TextEdit::multiline(&mut body.as_str())
                                         .desired_width(ui.available_width())
@@ -744,7 +1599,10 @@ impl MercuryApp {
                                 // Skip syntax highlighting for large responses to prevent UI lag
                                 use crate::core::constants::MAX_HIGHLIGHT_SIZE;
 
-                                if body.len() > MAX_HIGHLIGHT_SIZE {
+                                // Wrapped pieces would confuse the highlighters
+                                if body.len() > MAX_HIGHLIGHT_SIZE
+                                    || reflow == Some(Reflow::WrappedLines)
+                                {
                                     // Too large - use plain text editor
                                     ui.add(
                                         egui::TextEdit::multiline(&mut body.as_str())
@@ -771,7 +1629,10 @@ impl MercuryApp {
                 }
             }
         } else if let Some(error) = &self.request_error {
-            error_state(ui, error);
+            // Retry goes through the normal send path, so success lands in history
+            if error_state(ui, error) {
+                self.should_execute_request = true;
+            }
         } else {
             // Creative empty state for response panel
             ui.vertical_centered(|ui| {
@@ -849,7 +1710,7 @@ impl MercuryApp {
                 ui.add_space(Spacing::XS);
 
                 let tips = [
-                    "Paste a cURL command directly into the URL bar",
+                    "Paste a cURL command or fetch call directly into the URL bar",
                     "Use {{variable}} syntax for environment variables",
                     "⌘+S saves the current request to your collection",
                 ];
@@ -865,30 +1726,75 @@ impl MercuryApp {
         }
     }
 
-    /// Save the current response to a file with smart filename
-    fn save_response_to_file(&self) {
-        if let Some(response) = &self.response {
-            // Generate smart filename based on content type
-            let extension =
-                super::components::get_extension_for_content_type(&response.content_type);
-            let default_filename = format!("response{}", extension);
-
-            if let Some(path) = rfd::FileDialog::new()
-                .set_title("Save Response")
-                .set_file_name(&default_filename)
-                .save_file()
-            {
-                let data = if let Some(bytes) = &response.raw_bytes {
-                    bytes.clone()
-                } else {
-                    response.body.as_bytes().to_vec()
-                };
+    /// Save the current response to a file with smart filename. Returns the toast
+    /// reporting the outcome, or None if the dialog was cancelled.
+    fn save_response_to_file(&self) -> Option<Toast> {
+        let response = self.response.as_ref()?;
+        // Generate smart filename based on content type
+        let extension = super::components::get_extension_for_content_type(&response.content_type);
+        let default_filename = format!("response{}", extension);
+
+        let path = rfd::FileDialog::new()
+            .set_title("Save Response")
+            .set_file_name(&default_filename)
+            .save_file()?;
+        let data = if let Some(bytes) = &response.raw_bytes {
+            bytes.clone()
+        } else {
+            response.body.as_bytes().to_vec()
+        };
 
-                if let Err(e) = std::fs::write(&path, data) {
-                    eprintln!("Failed to save response: {}", e);
-                }
+        Some(match std::fs::write(&path, data) {
+            Ok(()) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                Toast::new(format!("Saved response to {}", name), false)
+                    .with_action("Open", ToastAction::Open(path.clone()))
             }
+            Err(e) => Toast::new(format!("Failed to save response: {}", e), true),
+        })
+    }
+
+    /// Write an HTML response to a temp file and open it in the default browser
+    pub fn open_response_in_browser(&mut self, ctx: &Context) {
+        let Some(response) = &self.response else {
+            return;
+        };
+
+        // Links resolve against the request that produced the response; saved
+        // examples have none and belong to the open request
+        let url = match &self.response_request {
+            Some(sent) if sent.resolved => sent.url.clone(),
+            Some(sent) => crate::parser::substitute_variables(&sent.url, &self.env_variables),
+            None => crate::parser::substitute_variables(&self.url, &self.env_variables),
+        };
+        let html = crate::utils::inject_base_href(&response.body, &url);
+        let path = std::env::temp_dir().join(format!(
+            "mercury-response-{}-{}.html",
+            std::process::id(),
+            self.browser_temp_files.len()
+        ));
+
+        if let Err(e) = std::fs::write(&path, html) {
+            let error = MercuryError::FileWrite {
+                path: path.display().to_string(),
+                reason: e.to_string(),
+            };
+            self.last_action_message = Some((
+                error.user_message().to_string(),
+                ctx.input(|i| i.time),
+                true,
+            ));
+            return;
+        }
+
+        if let Err(e) = open::that(&path) {
+            self.last_action_message = Some((
+                format!("Couldn't open a browser: {}", e),
+                ctx.input(|i| i.time),
+                true,
+            ));
         }
+        self.browser_temp_files.push(path);
     }
 
     /// Render center request panel
@@ -910,15 +1816,36 @@ impl MercuryApp {
                                 .size(FontSize::SM),
                         );
                         ui.label(
-                            egui::RichText::new("Cmd+Shift+F to exit")
-                                .color(Colors::TEXT_MUTED)
-                                .size(FontSize::XS),
+                            egui::RichText::new(format!(
+                                "{} to exit",
+                                super::shortcuts::combo_label(
+                                    &self.settings.keybindings.get(Action::FocusMode)
+                                )
+                            ))
+                            .color(Colors::TEXT_MUTED)
+                            .size(FontSize::XS),
                         );
                     });
                 });
             ui.add_space(Spacing::SM);
         }
 
+        // Variables `.env.schema` expects that this environment doesn't set
+        if !self.missing_env_vars.is_empty() {
+            let env_name = self.env_files[self.selected_env].clone();
+            if env_schema_banner(ui, &env_name, &self.missing_env_vars) {
+                self.last_action_message = Some(match self.add_missing_env_vars() {
+                    Ok(count) => (
+                        format!("Added {} variables to {}", count, env_name),
+                        ctx.input(|i| i.time),
+                        false,
+                    ),
+                    Err(e) => (e.user_message().to_string(), ctx.input(|i| i.time), true),
+                });
+            }
+            ui.add_space(Spacing::SM);
+        }
+
         // Check for undefined variables to style URL bar
         let all_vars: Vec<String> = [
             Self::extract_variables(&self.url),
@@ -956,9 +1883,7 @@ impl MercuryApp {
                 top: 0,
                 bottom: 0,
             })
-            .show(ui, |ui| {
-                self.render_url_bar_new(ui, ctx);
-            });
+            .show(ui, |ui| self.render_url_bar_new(ui));
 
         // Tooltip on the frame when hovering shows undefined vars
         if has_undefined {
@@ -993,10 +1918,39 @@ impl MercuryApp {
             .show(ui, |ui| {
                 self.render_request_body_new(ui);
             });
+
+        // Send sits in the URL bar but is added last, so Tab moves from the URL
+        // through the tabs and body before reaching it
+        let mut send_ui = ui.new_child(
+            egui::UiBuilder::new()
+                .max_rect(frame_response.inner)
+                .layout(egui::Layout::left_to_right(egui::Align::Center)),
+        );
+        self.render_send_button(&mut send_ui, ctx);
+    }
+
+    fn render_send_button(&mut self, ui: &mut Ui, ctx: &Context) {
+        let time = ctx.input(|i| i.time);
+        let is_executing = self.ongoing_request.is_some();
+        let send_response = send_stop_button(ui, is_executing, time);
+        ctx.data_mut(|d| d.insert_temp(egui::Id::new(SEND_BUTTON_ID), send_response.id));
+
+        if send_response.clicked() {
+            if is_executing {
+                self.cancel_request();
+            } else {
+                self.execute_request(ctx);
+            }
+        }
+
+        if is_executing {
+            ctx.request_repaint();
+        }
     }
 
-    /// URL bar content - minimal unified design
-    fn render_url_bar_new(&mut self, ui: &mut Ui, ctx: &Context) {
+    /// URL bar content - minimal unified design. Returns the space left for the
+    /// Send button.
+    fn render_url_bar_new(&mut self, ui: &mut Ui) -> egui::Rect {
         ui.horizontal(|ui| {
             ui.spacing_mut().item_spacing.x = super::theme::Spacing::SM;
 
@@ -1014,7 +1968,8 @@ impl MercuryApp {
                     )
                     .sense(egui::Sense::click()),
                 )
-                .on_hover_cursor(egui::CursorIcon::PointingHand);
+                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                .accessible_button(&format!("Method: {}", self.method.as_str()));
 
             // Use the reusable popup_menu component
             popup_menu(ui, &method_response, Layout::METHOD_POPUP_WIDTH, |ui| {
@@ -1045,16 +2000,17 @@ impl MercuryApp {
 
             // URL input - fills remaining space
             let available = ui.available_width() - super::theme::Indent::SEND_BUTTON_RESERVE;
-            let url_response = ui.add(
-                egui::TextEdit::singleline(&mut self.url)
-                    .hint_text(
-                        egui::RichText::new("https://example.com/ or paste cURL")
-                            .color(Colors::PLACEHOLDER),
-                    )
-                    .desired_width(available)
-                    .frame(false)
-                    .id(egui::Id::new("url_bar")),
-            );
+            let url_output = egui::TextEdit::singleline(&mut self.url)
+                .hint_text(
+                    egui::RichText::new("https://example.com/ or paste cURL")
+                        .color(Colors::PLACEHOLDER),
+                )
+                .desired_width(available)
+                .frame(false)
+                .id(egui::Id::new("url_bar"))
+                .show(ui);
+            self.show_variable_hover(hovered_variable(&url_output, &self.url));
+            let url_response = url_output.response;
 
             // Request focus if flag is set
             if self.should_focus_url_bar {
@@ -1062,22 +2018,90 @@ impl MercuryApp {
                 self.should_focus_url_bar = false;
             }
 
-            // Auto-detect cURL and parse it
-            if url_response.changed() && self.url.trim_start().starts_with("curl ") {
-                if let Ok(curl_req) = crate::parser::parse_curl(&self.url) {
-                    self.method = curl_req.method;
-                    self.url = curl_req.url;
+            // Previously used URLs while the bar is focused; a pasted cURL command
+            // or fetch call is handled below instead. Stays open while hovered so a click lands.
+            let hover_id = egui::Id::new("url_suggestions_hovered");
+            let hovered = ui
+                .ctx()
+                .data(|d| d.get_temp::<bool>(hover_id).unwrap_or(false));
+            let mut picked: Option<String> = None;
+            let mut still_hovered = false;
+            let pasted_command = self.url.trim_start().starts_with("curl ")
+                || crate::parser::is_fetch_call(&self.url);
+            if (url_response.has_focus() || hovered) && !pasted_command {
+                self.ensure_history_loaded();
+                let used = self
+                    .recent_requests
+                    .iter()
+                    .map(|r| (r.request.url.as_str(), r.timestamp))
+                    .chain(self.timeline.iter().map(|s| (s.url.as_str(), s.timestamp)));
+                let suggestions = crate::utils::rank_url_suggestions(
+                    used,
+                    &self.url,
+                    crate::core::constants::MAX_URL_SUGGESTIONS,
+                );
+                if !suggestions.is_empty() {
+                    still_hovered = dropdown_popup(ui, &url_response, |ui| {
+                        for url in &suggestions {
+                            if ui
+                                .selectable_label(
+                                    false,
+                                    egui::RichText::new(url).size(FontSize::SM),
+                                )
+                                .clicked()
+                            {
+                                picked = Some(url.clone());
+                            }
+                        }
+                    });
+                }
+            }
+            ui.ctx()
+                .data_mut(|d| d.insert_temp(hover_id, still_hovered && picked.is_none()));
+            if let Some(url) = picked {
+                // Bring back the method it was last sent with
+                let last_method = self
+                    .timeline
+                    .iter()
+                    .map(|s| (s.timestamp, &s.method, &s.url))
+                    .chain(
+                        self.recent_requests
+                            .iter()
+                            .map(|r| (r.timestamp, &r.request.method, &r.request.url)),
+                    )
+                    .filter(|(_, _, u)| u.trim() == url)
+                    .max_by(|a, b| a.0.total_cmp(&b.0))
+                    .map(|(_, method, _)| method.clone());
+                if let Some(method) = last_method {
+                    self.method = method;
+                }
+                self.url = url;
+                self.query_params = crate::utils::parse_query_params(&self.url);
+                url_response.surrender_focus();
+            }
+
+            // Auto-detect cURL or a browser's "Copy as fetch" and parse it
+            if url_response.changed() && pasted_command {
+                let parsed = if crate::parser::is_fetch_call(&self.url) {
+                    crate::parser::parse_fetch(&self.url)
+                        .map(|r| (r.method, r.url, r.headers, r.body))
+                } else {
+                    crate::parser::parse_curl(&self.url)
+                        .map(|r| (r.method, r.url, r.headers, r.body))
+                };
+                if let Ok((method, url, headers, body)) = parsed {
+                    self.method = method;
+                    self.url = url;
 
                     // Convert headers to text
-                    self.headers_text = curl_req
-                        .headers
+                    self.headers_text = headers
                         .iter()
                         .map(|(k, v)| format!("{}: {}", k, v))
                         .collect::<Vec<_>>()
                         .join("\n");
 
-                    if let Some(body) = curl_req.body {
-                        self.body_text = body;
+                    if let Some(body) = body {
+                        self.set_body(body);
                     }
 
                     // Sync query params from parsed URL
@@ -1088,24 +2112,49 @@ impl MercuryApp {
                 self.query_params = crate::utils::parse_query_params(&self.url);
             }
 
-            // Animated send button
-            // Send/Stop button
-            let time = ctx.input(|i| i.time);
-            let is_executing = self.ongoing_request.is_some();
-            let send_response = send_stop_button(ui, is_executing, time);
+            // Send/Stop button goes here, see render_send_button
+            let row_height = ui.min_rect().height();
+            ui.allocate_exact_size(egui::vec2(FontSize::ICON, row_height), egui::Sense::hover())
+                .0
+        })
+        .inner
+    }
 
-            if send_response.clicked() {
-                if is_executing {
-                    self.cancel_request();
-                } else {
-                    self.execute_request(ctx);
-                }
-            }
+    /// What the selected environment's override changes, since the editors show
+    /// the request as saved
+    fn render_active_override(&self, ui: &mut Ui) {
+        let Some(env_override) = self.active_override() else {
+            return;
+        };
+        let mut names: Vec<&String> = env_override.headers.keys().collect();
+        names.sort();
+        let mut changes: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        if env_override.body.is_some() {
+            changes.push("body".to_string());
+        }
+        if changes.is_empty() {
+            return;
+        }
 
-            if is_executing {
-                ctx.request_repaint();
-            }
-        });
+        let env_file = &self.env_files[self.selected_env];
+        let env_name = env_file.strip_prefix(".env.").unwrap_or(env_file);
+        let mut details: Vec<String> = names
+            .iter()
+            .map(|n| format!("{}: {}", n, env_override.headers[*n]))
+            .collect();
+        if env_override.body.is_some() {
+            details.push("Body replaced".to_string());
+        }
+        ui.label(
+            egui::RichText::new(format!("{} override: {}", env_name, changes.join(", ")))
+                .size(FontSize::SM)
+                .color(Colors::WARNING),
+        )
+        .on_hover_text(format!(
+            "Applied when sending with this environment:\n{}",
+            details.join("\n")
+        ));
+        ui.add_space(Spacing::SM);
     }
 
     /// Request body with tabs
@@ -1125,6 +2174,11 @@ impl MercuryApp {
             "Headers".to_string()
         };
 
+        // Examples belong to a saved file; fall back to Body for unsaved requests
+        if self.selected_tab == 4 && self.current_file.is_none() {
+            self.selected_tab = 0;
+        }
+
         // Tab bar using DRY approach
         ui.horizontal(|ui| {
             // Regular tabs using consistent styling
@@ -1255,43 +2309,204 @@ impl MercuryApp {
                     }
                 }
             });
-        });
-
-        ui.add_space(Spacing::SM);
-        ui.separator();
-        ui.add_space(Spacing::SM);
 
-        // Tab content with scroll
-        ScrollArea::vertical()
-            .id_salt("request_content")
-            .auto_shrink([false, false])
-            .max_height(ui.available_height())
-            .show(ui, |ui| {
-                match self.selected_tab {
-                    0 => {
-                        // Save cursor for overlay
-                        let top_right = ui.cursor().min + egui::vec2(ui.available_width(), 0.0);
+            // Examples tab - only saved requests can carry examples
+            if self.current_file.is_some() {
+                ui.add_space(Spacing::MD);
+                let examples_label = if self.current_examples.is_empty() {
+                    "Examples".to_string()
+                } else {
+                    format!("Examples ({})", self.current_examples.len())
+                };
+                let color = if self.selected_tab == 4 {
+                    Colors::PRIMARY
+                } else {
+                    Colors::TEXT_MUTED
+                };
+                if ui
+                    .add(
+                        egui::Button::new(
+                            egui::RichText::new(examples_label)
+                                .size(FontSize::MD)
+                                .color(color),
+                        )
+                        .frame(false),
+                    )
+                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    self.selected_tab = 4;
+                }
+            }
 
-                        // Body editor check syntax highlighting
-                        let mut layouter =
+            ui.add_space(Spacing::MD);
+            let color = if self.selected_tab == 5 {
+                Colors::PRIMARY
+            } else {
+                Colors::TEXT_MUTED
+            };
+            if ui
+                .add(
+                    egui::Button::new(egui::RichText::new("gRPC").size(FontSize::MD).color(color))
+                        .frame(false),
+                )
+                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                .clicked()
+            {
+                self.selected_tab = 5;
+            }
+
+            // Settings tab - counts the options this request sets itself
+            ui.add_space(Spacing::MD);
+            let custom_settings = [
+                self.timeout_override.is_some(),
+                self.follow_redirects_override.is_some(),
+                self.send_delay_ms.is_some(),
+                !self.save_path.trim().is_empty(),
+                !self.resolve_override.trim().is_empty(),
+                !self.expect_content_type.trim().is_empty(),
+            ]
+            .iter()
+            .filter(|set| **set)
+            .count();
+            let settings_label = if custom_settings > 0 {
+                format!("Settings ({})", custom_settings)
+            } else {
+                "Settings".to_string()
+            };
+            let color = if self.selected_tab == 6 {
+                Colors::PRIMARY
+            } else {
+                Colors::TEXT_MUTED
+            };
+            if ui
+                .add(
+                    egui::Button::new(
+                        egui::RichText::new(settings_label)
+                            .size(FontSize::MD)
+                            .color(color),
+                    )
+                    .frame(false),
+                )
+                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                .clicked()
+            {
+                self.selected_tab = 6;
+            }
+        });
+
+        ui.add_space(Spacing::SM);
+        ui.separator();
+        ui.add_space(Spacing::SM);
+        self.render_active_override(ui);
+
+        // Tab content with scroll
+        ScrollArea::vertical()
+            .id_salt("request_content")
+            .auto_shrink([false, false])
+            .max_height(ui.available_height())
+            .show(ui, |ui| {
+                match self.selected_tab {
+                    0 if self.body_hex => self.render_hex_body(ui),
+                    0 if self.patch_mode() == Some(JSON_PATCH_CONTENT_TYPE) => {
+                        self.render_json_patch_body(ui)
+                    }
+                    0 if self.is_form_body() => self.render_form_body(ui),
+                    0 => {
+                        self.render_body_mode(ui);
+
+                        // Save cursor for overlay
+                        let top_right = ui.cursor().min + egui::vec2(ui.available_width(), 0.0);
+
+                        // Body editor check syntax highlighting
+                        let mut layouter =
                             |ui: &egui::Ui, text: &dyn egui::TextBuffer, wrap_width: f32| {
                                 let job = json_layout_job(text.as_str(), wrap_width);
                                 ui.fonts_mut(|f| f.layout_job(job))
                             };
 
-                        ui.add(
-                            egui::TextEdit::multiline(&mut self.body_text)
-                                .hint_text(
-                                    egui::RichText::new(r#"{"key": "value"}"#)
-                                        .color(Colors::PLACEHOLDER),
-                                )
-                                .desired_width(ui.available_width())
-                                .desired_rows(15)
-                                .frame(false) // Transparent background
-                                .layouter(&mut layouter),
-                        );
+                        // Tab / Shift+Tab indent like a code editor while the body has focus
+                        let body_id = ui.make_persistent_id("body_editor");
+                        if ui.memory(|m| m.has_focus(body_id)) {
+                            let tab = ui.input_mut(|i| {
+                                i.consume_key(egui::Modifiers::NONE, egui::Key::Tab)
+                            });
+                            let shift_tab = ui.input_mut(|i| {
+                                i.consume_key(egui::Modifiers::SHIFT, egui::Key::Tab)
+                            });
+                            if tab || shift_tab {
+                                if let Some(mut state) =
+                                    egui::TextEdit::load_state(ui.ctx(), body_id)
+                                {
+                                    if let Some(range) = state.cursor.char_range() {
+                                        let [start, end] = range.sorted_cursors();
+                                        let (text, start, end) = crate::utils::apply_tab_indent(
+                                            &self.body_text,
+                                            start.index,
+                                            end.index,
+                                            shift_tab,
+                                        );
+                                        self.set_body(text);
+                                        state.cursor.set_char_range(Some(
+                                            egui::text::CCursorRange::two(
+                                                egui::text::CCursor::new(start),
+                                                egui::text::CCursor::new(end),
+                                            ),
+                                        ));
+                                        state.store(ui.ctx(), body_id);
+                                    }
+                                }
+                            }
+                        }
 
-                        // Overlay Format Button (Draw ON TOP of TextEdit)
+                        let body_output = egui::TextEdit::multiline(&mut self.body_text)
+                            .id(body_id)
+                            .hint_text(
+                                egui::RichText::new(r#"{"key": "value"}"#)
+                                    .color(Colors::PLACEHOLDER),
+                            )
+                            .desired_width(ui.available_width())
+                            .desired_rows(15)
+                            .lock_focus(true) // Keep focus on Tab; Escape still leaves
+                            .frame(false) // Transparent background
+                            .layouter(&mut layouter)
+                            .show(ui);
+                        self.show_variable_hover(hovered_variable(&body_output, &self.body_text));
+                        if text_transform_menu(ui, &body_output, &mut self.body_text)
+                            || body_output.response.changed()
+                        {
+                            self.body_generation += 1;
+                        }
+                        // Look for a payload to offer; nothing is pasted until asked
+                        if body_output.response.gained_focus() && self.body_text.trim().is_empty() {
+                            self.clipboard_probe = true;
+                            ui.ctx()
+                                .send_viewport_cmd(egui::ViewportCommand::RequestPaste);
+                            ui.ctx().request_repaint();
+                        }
+                        // Tab indents here, so Escape is the way on to Send
+                        if body_output.response.lost_focus()
+                            && ui.input(|i| i.key_pressed(egui::Key::Escape))
+                        {
+                            let send_id = ui
+                                .ctx()
+                                .data(|d| d.get_temp::<egui::Id>(egui::Id::new(SEND_BUTTON_ID)));
+                            if let Some(send_id) = send_id {
+                                ui.memory_mut(|m| m.request_focus(send_id));
+                            }
+                        }
+
+                        // Overlay Format Button (Draw ON TOP of TextEdit), for
+                        // whichever of JSON or XML the body is
+                        let content_type =
+                            crate::utils::header_value(&self.headers_text, "Content-Type");
+                        let format_label = match crate::utils::formattable_body_format(
+                            &self.body_text,
+                            content_type.as_deref(),
+                        ) {
+                            Some(format) => format!("Format {}", format.label()),
+                            None => "Format JSON or XML".to_string(),
+                        };
                         let button_rect = egui::Rect::from_min_size(
                             top_right - egui::vec2(30.0, 0.0),
                             egui::vec2(30.0, 20.0),
@@ -1307,17 +2522,30 @@ impl MercuryApp {
                                 .sense(egui::Sense::click()),
                             )
                             .on_hover_cursor(egui::CursorIcon::PointingHand)
-                            .on_hover_text("Format JSON")
+                            .accessible_button(&format_label)
+                            .on_hover_text(&format_label)
                             .clicked()
                         {
-                            if let Ok(value) =
-                                serde_json::from_str::<serde_json::Value>(&self.body_text)
-                            {
-                                if let Ok(pretty) = serde_json::to_string_pretty(&value) {
-                                    self.body_text = pretty;
+                            match crate::utils::format_request_body(
+                                &self.body_text,
+                                content_type.as_deref(),
+                                &self.settings.json_format,
+                            ) {
+                                Ok(formatted) => self.set_body(formatted),
+                                Err(e) => {
+                                    self.last_action_message =
+                                        Some((e, ui.ctx().input(|i| i.time), true));
                                 }
                             }
                         }
+
+                        self.render_content_type_hint(ui);
+                        self.render_clipboard_body_hint(ui);
+                        if !self.body_text.is_empty() {
+                            ui.add_space(Spacing::XS);
+                            let counts = self.body_counts();
+                            body_counts(ui, &self.body_text, counts);
+                        }
                     }
                     1 => {
                         // Query parameters editor
@@ -1421,7 +2649,12 @@ impl MercuryApp {
                                         &self.auth_password,
                                     );
                                     let ctx = ui.ctx().clone();
-                                    render_auth_preview(ui, &ctx, &auth_value);
+                                    render_auth_preview(
+                                        ui,
+                                        &ctx,
+                                        &auth_value,
+                                        self.settings.mask_secrets,
+                                    );
                                 }
                             }
                             AuthMode::Bearer => {
@@ -1458,7 +2691,12 @@ impl MercuryApp {
                                     let auth_value =
                                         crate::utils::generate_bearer_auth(&self.auth_token);
                                     let ctx = ui.ctx().clone();
-                                    render_auth_preview(ui, &ctx, &auth_value);
+                                    render_auth_preview(
+                                        ui,
+                                        &ctx,
+                                        &auth_value,
+                                        self.settings.mask_secrets,
+                                    );
                                 }
                             }
                             AuthMode::Custom => {
@@ -1511,24 +2749,259 @@ impl MercuryApp {
                             }
                         }
                     }
+                    4 => {
+                        self.render_examples(ui);
+                    }
+                    5 => {
+                        self.render_grpc(ui);
+                    }
+                    6 => {
+                        self.render_request_settings(ui);
+                    }
                     _ => {}
                 }
             });
     }
 
+    /// gRPC tab - send the request as a unary gRPC-Web call described by a .proto file
+    fn render_grpc(&mut self, ui: &mut Ui) {
+        let mut enabled = self.grpc_call.is_some();
+        if ui
+            .checkbox(&mut enabled, "Send as a gRPC-Web call")
+            .changed()
+        {
+            self.grpc_call = enabled.then(GrpcCall::default);
+        }
+
+        let proto_path = match &self.grpc_call {
+            Some(call) if !call.proto.trim().is_empty() => {
+                Some(self.resolve_proto_path(&call.proto))
+            }
+            _ => None,
+        };
+        let mut pick_proto = false;
+
+        let Some(call) = self.grpc_call.as_mut() else {
+            ui.add_space(Spacing::SM);
+            ui.label(
+                egui::RichText::new(
+                    "The URL becomes the server's gRPC-Web base URL and the body the request message as JSON.",
+                )
+                .color(Colors::TEXT_MUTED)
+                .size(FontSize::SM),
+            );
+            return;
+        };
+
+        ui.add_space(Spacing::SM);
+        egui::Grid::new("grpc_call")
+            .num_columns(2)
+            .spacing([Spacing::LG, Spacing::SM])
+            .show(ui, |ui| {
+                ui.label("Proto file");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut call.proto)
+                            .hint_text("protos/service.proto")
+                            .desired_width(260.0),
+                    );
+                    if ui.button("Browse...").clicked() {
+                        pick_proto = true;
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Service");
+                ui.add(
+                    egui::TextEdit::singleline(&mut call.service)
+                        .hint_text("package.Service")
+                        .desired_width(260.0),
+                );
+                ui.end_row();
+
+                ui.label("Method");
+                ui.add(
+                    egui::TextEdit::singleline(&mut call.method)
+                        .hint_text("GetUser")
+                        .desired_width(260.0),
+                );
+                ui.end_row();
+            });
+
+        // Services from the proto file, parsed once per path (ephemeral UI state)
+        if let Some(path) = proto_path {
+            type ProtoSummary = Result<Vec<(String, Vec<(String, bool)>)>, String>;
+            let cache_id = egui::Id::new("grpc_proto_summary");
+            let cached: Option<(std::path::PathBuf, ProtoSummary)> =
+                ui.ctx().data(|d| d.get_temp(cache_id));
+
+            let summary = match cached {
+                Some((cached_path, summary)) if cached_path == path => summary,
+                _ => {
+                    let summary: ProtoSummary = std::fs::read_to_string(&path)
+                        .map_err(|e| e.to_string())
+                        .and_then(|source| {
+                            crate::parser::parse_proto(&source).map_err(|e| e.to_string())
+                        })
+                        .map(|proto| {
+                            proto
+                                .services
+                                .iter()
+                                .map(|service| {
+                                    let methods = service
+                                        .methods
+                                        .iter()
+                                        .map(|m| {
+                                            let unary = !m.client_streaming && !m.server_streaming;
+                                            (m.name.clone(), unary)
+                                        })
+                                        .collect();
+                                    (service.name.clone(), methods)
+                                })
+                                .collect()
+                        });
+                    ui.ctx()
+                        .data_mut(|d| d.insert_temp(cache_id, (path.clone(), summary.clone())));
+                    summary
+                }
+            };
+
+            ui.add_space(Spacing::MD);
+            match summary {
+                Ok(services) => {
+                    for (service, methods) in services {
+                        ui.label(
+                            egui::RichText::new(&service)
+                                .size(FontSize::SM)
+                                .color(Colors::TEXT_SECONDARY),
+                        );
+                        ui.horizontal_wrapped(|ui| {
+                            for (method, unary) in methods {
+                                let selected = call.service == service && call.method == method;
+                                let button = ui.add_enabled(
+                                    unary,
+                                    egui::Button::selectable(selected, &method),
+                                );
+                                if button.clicked() {
+                                    call.service = service.clone();
+                                    call.method = method;
+                                } else {
+                                    button
+                                        .on_disabled_hover_text("Streaming calls aren't supported");
+                                }
+                            }
+                        });
+                    }
+                }
+                Err(message) => {
+                    ui.label(
+                        egui::RichText::new(message)
+                            .color(Colors::ERROR)
+                            .size(FontSize::SM),
+                    );
+                }
+            }
+
+            ui.add_space(Spacing::XS);
+            if ui.small_button("Reload proto").clicked() {
+                ui.ctx()
+                    .data_mut(|d| d.remove::<(std::path::PathBuf, ProtoSummary)>(cache_id));
+            }
+        }
+        if pick_proto {
+            self.should_pick_proto = true;
+        }
+
+        ui.add_space(Spacing::SM);
+        ui.label(
+            egui::RichText::new(
+                "Calls are POSTed to {URL}/{service}/{method} as grpc-web-text. Streaming isn't supported.",
+            )
+            .color(Colors::TEXT_MUTED)
+            .size(FontSize::SM),
+        );
+    }
+
+    /// Examples tab - saved responses for the open request
+    fn render_examples(&mut self, ui: &mut Ui) {
+        if self.current_examples.is_empty() {
+            ui.label(
+                egui::RichText::new(
+                    "No examples yet. Send the request, then click \"Example\" in the response panel.",
+                )
+                .color(Colors::TEXT_MUTED)
+                .font(egui::FontId::monospace(FontSize::SM)),
+            );
+            return;
+        }
+
+        let mut to_view = None;
+        let mut to_delete = None;
+        for name in &self.current_examples {
+            ui.horizontal(|ui| {
+                if ui
+                    .add(
+                        egui::Label::new(
+                            egui::RichText::new(name)
+                                .size(FontSize::MD)
+                                .color(Colors::TEXT_PRIMARY),
+                        )
+                        .sense(egui::Sense::click()),
+                    )
+                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                    .accessible_button(name)
+                    .on_hover_text("Show in the response panel")
+                    .clicked()
+                {
+                    to_view = Some(name.clone());
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add(
+                            egui::Button::new(
+                                egui::RichText::new(Icons::DELETE)
+                                    .size(FontSize::SM)
+                                    .color(Colors::TEXT_MUTED),
+                            )
+                            .frame(false),
+                        )
+                        .on_hover_text("Delete example")
+                        .clicked()
+                    {
+                        to_delete = Some(name.clone());
+                    }
+                });
+            });
+        }
+
+        if let Some(name) = to_view {
+            self.view_example(&name);
+        }
+        if let Some(name) = to_delete {
+            if let Err(e) = self.delete_example(&name) {
+                self.last_action_message = Some((
+                    e.user_message().to_string(),
+                    ui.ctx().input(|i| i.time),
+                    true,
+                ));
+            }
+        }
+    }
+
     /// Headers tab with variable indicators
     fn render_smart_headers(&mut self, ui: &mut Ui) {
         // Save cursor for undefined vars overlay
         let start_pos = ui.cursor().min;
 
         // Use the reusable key-value text editor with ":" separator
-        key_value_editor(
+        let result = key_value_editor(
             ui,
             &mut self.headers_text,
             ":",
             &mut self.headers_bulk_edit,
             "Content-Type: application/json\nAuthorization: Bearer {{token}}",
         );
+        self.show_variable_hover(result.hovered_var);
 
         // Overlay Undefined Warning (Rendered Last) - show names, not just count
         let undefined_vars: Vec<_> = Self::extract_variables(&self.headers_text)
@@ -1568,98 +3041,1043 @@ impl MercuryApp {
                 }
             });
         }
-    }
 
-    /// Query parameters editor with key-value table and URL sync
-    fn render_query_params(&mut self, ui: &mut Ui) {
-        // Convert query_params to params_text from URL bar changes (if not in bulk edit mode)
-        if !self.params_bulk_edit {
-            // Convert QueryParams to KeyValueRows then to text
-            let rows: Vec<KeyValueRow> = self
-                .query_params
-                .iter()
-                .filter(|p| !p.key.is_empty())
-                .map(|p| KeyValueRow::new(p.enabled, p.key.clone(), p.value.clone()))
-                .collect();
-            self.params_text = rows_to_text(&rows, "=");
+        // A repeated header is sent once, with its last value; say so
+        let duplicates = crate::utils::duplicate_headers(&self.headers_text);
+        if !duplicates.is_empty() {
+            ui.add_space(Spacing::SM);
+            for duplicate in &duplicates {
+                let color = if duplicate.single_value {
+                    Colors::ERROR
+                } else {
+                    Colors::WARNING
+                };
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} {} is set {} times; only the last is sent: {}",
+                        Icons::WARNING,
+                        duplicate.name,
+                        duplicate.count,
+                        duplicate.sent
+                    ))
+                    .size(FontSize::SM)
+                    .color(color),
+                );
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .small_button("Keep last")
+                    .on_hover_text("Remove the earlier lines; what's sent doesn't change")
+                    .clicked()
+                {
+                    self.headers_text = crate::utils::dedupe_headers(&self.headers_text, false);
+                }
+                if duplicates.iter().any(|d| !d.single_value)
+                    && ui
+                        .small_button("Merge")
+                        .on_hover_text(
+                            "Join the values of list headers like Accept into one line; \
+                             single-value headers keep the last",
+                        )
+                        .clicked()
+                {
+                    self.headers_text = crate::utils::dedupe_headers(&self.headers_text, true);
+                }
+            });
         }
 
-        // Use the reusable key-value text editor with "=" separator
-        let result = key_value_editor(
-            ui,
-            &mut self.params_text,
-            "=",
-            &mut self.params_bulk_edit,
-            "key=value\npage=1\n# disabled=param",
-        );
-
-        // Sync params_text back to query_params and URL if changed
-        if result.changed {
-            // Use the helper to parse text into rows, then convert to QueryParams
-            self.query_params = parse_text_to_rows(&self.params_text, "=")
-                .into_iter()
-                .map(|r| crate::utils::QueryParam {
-                    enabled: r.enabled,
-                    key: r.key,
-                    value: r.value,
-                })
-                .collect();
-
-            // Rebuild URL from params
-            self.url = crate::utils::build_url_with_params(&self.url, &self.query_params);
+        // The User-Agent Settings adds when the request has none, so it's never a mystery
+        let default_agent = self.settings.user_agent.trim();
+        if !default_agent.is_empty()
+            && crate::utils::header_value(&self.headers_text, "User-Agent").is_none()
+        {
+            ui.add_space(Spacing::SM);
+            ui.label(
+                egui::RichText::new(format!("User-Agent: {}", default_agent))
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED)
+                    .monospace(),
+            )
+            .on_hover_text("Default from Settings; add a User-Agent header to override it");
         }
 
-        // Show variable indicators for params
-        let all_vars: Vec<String> = self
-            .query_params
+        // Headers from `.folder.json` files that this request doesn't set itself
+        let inherited: Vec<_> = self
+            .folder_defaults
+            .headers
             .iter()
-            .flat_map(|p| {
-                let mut vars = super::app::MercuryApp::extract_variables(&p.key);
-                vars.extend(super::app::MercuryApp::extract_variables(&p.value));
-                vars
-            })
+            .filter(|h| crate::utils::header_value(&self.headers_text, &h.name).is_none())
             .collect();
-
-        if !all_vars.is_empty() {
+        if !inherited.is_empty() {
             ui.add_space(Spacing::SM);
-            ui.horizontal_wrapped(|ui| {
-                let unique_vars: std::collections::HashSet<_> = all_vars.into_iter().collect();
-                for var in unique_vars {
-                    variable_indicator(ui, &var, self.env_variables.contains_key(&var));
-                    ui.add_space(Spacing::SM);
-                }
-            });
+            for header in inherited {
+                ui.label(
+                    egui::RichText::new(format!("{}: {}", header.name, header.value))
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED)
+                        .monospace(),
+                )
+                .on_hover_text(format!(
+                    "From {}; add a {} header to override it",
+                    self.folder_defaults_label(&header.folder),
+                    header.name
+                ));
+            }
         }
     }
-}
 
-/// Render the auth header preview with monospace styling
-/// Used by Basic and Bearer auth modes to show the generated header
-fn render_auth_preview(ui: &mut Ui, ctx: &egui::Context, auth_text: &str) {
-    egui::Frame::NONE
-        .fill(Colors::BG_CODE)
-        .corner_radius(Radius::SM)
-        .inner_margin(Spacing::SM)
-        .show(ui, |ui| {
-            ui.horizontal(|ui| {
-                ui.label(
+    /// `users/.folder.json`, relative to the workspace
+    fn folder_defaults_label(&self, folder: &std::path::Path) -> String {
+        let relative = self
+            .workspace_path
+            .as_ref()
+            .and_then(|w| folder.strip_prefix(w).ok())
+            .unwrap_or(folder);
+        relative
+            .join(crate::core::constants::FOLDER_DEFAULTS_FILE)
+            .display()
+            .to_string()
+    }
+
+    /// Settings tab - this request's own network options. Unset ones follow Settings.
+    fn render_request_settings(&mut self, ui: &mut Ui) {
+        let muted = |text: &str| {
+            egui::RichText::new(text)
+                .size(FontSize::SM)
+                .color(Colors::TEXT_MUTED)
+        };
+        let default_timeout = self.settings.timeout_secs;
+        let default_redirects = if self.settings.follow_redirects {
+            "Default (follow)"
+        } else {
+            "Default (don't follow)"
+        };
+
+        egui::Grid::new("request_settings")
+            .num_columns(2)
+            .spacing([Spacing::LG, Spacing::SM])
+            .show(ui, |ui| {
+                ui.label(muted("Timeout"))
+                    .on_hover_text("How long to wait for this request before giving up");
+                ui.horizontal(|ui| match self.timeout_override.as_mut() {
+                    Some(secs) => {
+                        ui.add(egui::DragValue::new(secs).range(1..=3600).suffix(" s"));
+                        if ui
+                            .small_button("Reset")
+                            .on_hover_text(format!("Use the default, {} s", default_timeout))
+                            .clicked()
+                        {
+                            self.timeout_override = None;
+                        }
+                    }
+                    None => {
+                        ui.label(muted(&format!("{} s (default)", default_timeout)));
+                        if ui.small_button("Change").clicked() {
+                            self.timeout_override = Some(default_timeout);
+                        }
+                    }
+                });
+                ui.end_row();
+
+                ui.label(muted("Delay")).on_hover_text(
+                    "Wait before sending, to reproduce timing-dependent bugs. \
+                     Escape cancels while it counts down.",
+                );
+                ui.horizontal(|ui| match self.send_delay_ms.as_mut() {
+                    Some(ms) => {
+                        ui.add(
+                            egui::DragValue::new(ms)
+                                .range(0..=600_000)
+                                .speed(50)
+                                .suffix(" ms"),
+                        );
+                        if ui.small_button("Reset").clicked() {
+                            self.send_delay_ms = None;
+                        }
+                    }
+                    None => {
+                        ui.label(muted("None"));
+                        if ui.small_button("Add").clicked() {
+                            self.send_delay_ms = Some(1000);
+                        }
+                    }
+                });
+                ui.end_row();
+
+                ui.label(muted("Redirects"));
+                let selected = match self.follow_redirects_override {
+                    None => default_redirects,
+                    Some(true) => "Follow",
+                    Some(false) => "Don't follow",
+                };
+                egui::ComboBox::from_id_salt("request_follow_redirects")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        for (value, label) in [
+                            (None, default_redirects),
+                            (Some(true), "Follow"),
+                            (Some(false), "Don't follow"),
+                        ] {
+                            ui.selectable_value(&mut self.follow_redirects_override, value, label);
+                        }
+                    });
+                ui.end_row();
+
+                // Connect somewhere other than DNS says, keeping the URL's Host and SNI
+                ui.label(muted("Connect to")).on_hover_text(
+                    "Send this request to an IP address instead of the one the URL's host \
+                     resolves to. The Host header and TLS SNI still use the URL's host name.",
+                );
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.resolve_override)
+                        .hint_text(
+                            egui::RichText::new("IP address (optional)").color(Colors::PLACEHOLDER),
+                        )
+                        .desired_width(200.0),
+                );
+                ui.end_row();
+
+                ui.label(muted("On success")).on_hover_text(
+                    "After a 2xx JSON response, keep the value at this JSONPath as a session \
+                     variable, so the next request can use it",
+                );
+                ui.horizontal(|ui| {
+                    ui.label(muted("save"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.save_path)
+                            .hint_text(egui::RichText::new("$.token").color(Colors::PLACEHOLDER))
+                            .desired_width(110.0),
+                    );
+                    ui.label(muted("to"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.save_variable)
+                            .hint_text(egui::RichText::new("TOKEN").color(Colors::PLACEHOLDER))
+                            .desired_width(90.0),
+                    );
+                });
+                ui.end_row();
+
+                ui.label(muted("Expect type")).on_hover_text(
+                    "Warn when the response has another Content-Type, such as an HTML \
+                     error page instead of JSON. image/* matches any image.",
+                );
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.expect_content_type)
+                        .hint_text(
+                            egui::RichText::new("application/json (optional)")
+                                .color(Colors::PLACEHOLDER),
+                        )
+                        .desired_width(200.0),
+                );
+                ui.end_row();
+            });
+
+        if !self.resolve_override.trim().is_empty() && !self.resolve_override.contains("{{") {
+            let url = crate::parser::substitute_variables(&self.url, &self.env_variables);
+            if let Err(e) =
+                crate::core::request::parse_resolve_override(&self.resolve_override, &url)
+            {
+                ui.label(
+                    egui::RichText::new(e.to_string())
+                        .size(FontSize::XS)
+                        .color(Colors::ERROR),
+                );
+            }
+        }
+    }
+
+    /// Add a `Content-Type` line for `format` to the headers
+    fn add_content_type(&mut self, format: crate::utils::BodyFormat) {
+        let line = format!("Content-Type: {}", format.content_type());
+        if self.headers_text.trim().is_empty() {
+            self.headers_text = line;
+        } else {
+            self.headers_text = format!("{}\n{}", self.headers_text.trim_end(), line);
+        }
+    }
+
+    /// Offer the JSON or XML on the clipboard as the body, while the body is empty
+    fn render_clipboard_body_hint(&mut self, ui: &mut Ui) {
+        if !self.body_text.trim().is_empty() {
+            return;
+        }
+        let Some((body, format)) = self.clipboard_body.clone() else {
+            return;
+        };
+        let needs_content_type =
+            crate::utils::header_value(&self.headers_text, "Content-Type").is_none();
+
+        ui.add_space(Spacing::XS);
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(format!("Clipboard has {}", format.label()))
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+            let label = if needs_content_type {
+                "Paste as body and set Content-Type"
+            } else {
+                "Paste as body"
+            };
+            if ui.small_button(label).clicked() {
+                self.set_body(body);
+                if needs_content_type {
+                    self.add_content_type(format);
+                }
+                self.clipboard_body = None;
+            }
+            if close_button(ui, FontSize::SM)
+                .on_hover_text("Dismiss")
+                .clicked()
+            {
+                self.clipboard_body = None;
+            }
+        });
+    }
+
+    /// Advisory note under the body: suggest a Content-Type for the detected
+    /// format, or flag one that contradicts it. Never changes headers on its own.
+    fn render_content_type_hint(&mut self, ui: &mut Ui) {
+        if !self.settings.content_type_hints {
+            return;
+        }
+        let Some(format) = crate::utils::detect_body_format(&self.body_text) else {
+            return;
+        };
+        let content_type = crate::utils::header_value(&self.headers_text, "Content-Type");
+
+        match content_type {
+            None => {
+                ui.add_space(Spacing::XS);
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Body looks like {}, but no Content-Type is set",
+                            format.label()
+                        ))
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED),
+                    );
+                    if ui
+                        .small_button("Set")
+                        .on_hover_text(format!("Add Content-Type: {}", format.content_type()))
+                        .clicked()
+                    {
+                        self.add_content_type(format);
+                    }
+                });
+            }
+            Some(ct) if !ct.contains("{{") && !format.matches_content_type(&ct) => {
+                ui.add_space(Spacing::XS);
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} Body looks like {}, but Content-Type is {}",
+                        Icons::WARNING,
+                        format.label(),
+                        ct
+                    ))
+                    .size(FontSize::SM)
+                    .color(Colors::WARNING),
+                );
+            }
+            Some(_) => {}
+        }
+    }
+
+    /// Tooltip on a hovered `{{variable}}` with what it resolves to right now
+    fn show_variable_hover(&self, hovered: Option<(egui::Response, String)>) {
+        let Some((response, name)) = hovered else {
+            return;
+        };
+        response.on_hover_ui_at_pointer(|ui| {
+            ui.label(
+                egui::RichText::new(format!("{{{{{}}}}}", name))
+                    .monospace()
+                    .strong(),
+            );
+
+            if crate::core::keychain::KeychainRef::parse(&name).is_some() {
+                ui.label(
+                    egui::RichText::new("Read from the OS keychain when sent")
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED),
+                );
+                return;
+            }
+
+            if let Some((_, description)) = crate::parser::DYNAMIC_VARIABLES
+                .iter()
+                .find(|(n, _)| *n == name.trim())
+            {
+                ui.label(
+                    egui::RichText::new(format!("{}, new on every send", description))
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED),
+                );
+                return;
+            }
+
+            let Some(value) = self.env_variables.get(&name) else {
+                let env = match self.env_files.get(self.selected_env) {
+                    Some(env) if self.selected_env > 0 => env.as_str(),
+                    _ => "any environment (none selected)",
+                };
+                ui.label(
+                    egui::RichText::new(format!("Not defined in {}", env))
+                        .size(FontSize::SM)
+                        .color(Colors::ERROR),
+                );
+                return;
+            };
+
+            let source = self.env_sources.get(&name);
+            if self.settings.mask_secrets && source == Some(&crate::parser::VarSource::Secret) {
+                ui.label(
+                    egui::RichText::new("•••••• (encrypted secret)")
+                        .monospace()
+                        .color(Colors::TEXT_MUTED),
+                );
+            } else {
+                let shown =
+                    if value.chars().count() > crate::core::constants::VARIABLE_PREVIEW_CHARS {
+                        let cut: String = value
+                            .chars()
+                            .take(crate::core::constants::VARIABLE_PREVIEW_CHARS)
+                            .collect();
+                        format!("{}…", cut)
+                    } else {
+                        value.clone()
+                    };
+                ui.label(
+                    egui::RichText::new(shown)
+                        .monospace()
+                        .color(Colors::SUCCESS),
+                );
+            }
+            let from = match source {
+                Some(crate::parser::VarSource::Inherited) => "from .env".to_string(),
+                Some(crate::parser::VarSource::Secret) => "from .env.enc".to_string(),
+                Some(crate::parser::VarSource::Session) => "session override".to_string(),
+                Some(crate::parser::VarSource::Folder) => self
+                    .folder_defaults
+                    .variables
+                    .iter()
+                    .find(|v| v.name == name)
+                    .map(|v| format!("from {}", self.folder_defaults_label(&v.folder)))
+                    .unwrap_or_default(),
+                _ => self
+                    .env_files
+                    .get(self.selected_env)
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            ui.label(
+                egui::RichText::new(from)
+                    .size(FontSize::XS)
+                    .color(Colors::TEXT_MUTED),
+            );
+        });
+    }
+
+    fn is_form_body(&self) -> bool {
+        crate::utils::header_value(&self.headers_text, "Content-Type")
+            .is_some_and(|ct| crate::utils::is_form_content_type(&ct))
+    }
+
+    /// The patch format a PATCH request's Content-Type names, if either
+    fn patch_mode(&self) -> Option<&'static str> {
+        if self.method != HttpMethod::PATCH || self.body_hex {
+            return None;
+        }
+        crate::utils::header_value(&self.headers_text, "Content-Type")
+            .and_then(|ct| patch_media_type(&ct))
+    }
+
+    /// Back to a text body from hex, when the bytes are text
+    fn leave_hex_body(&mut self) {
+        if !self.body_hex {
+            return;
+        }
+        self.body_hex = false;
+        if let Some(text) = crate::utils::parse_hex(&self.body_text)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+        {
+            self.set_body(text);
+        }
+    }
+
+    /// Switch between a text body and raw bytes typed as hex, converting the
+    /// body when it reads the same both ways. PATCH requests also get the two
+    /// patch formats, which are picked by setting Content-Type.
+    fn render_body_mode(&mut self, ui: &mut Ui) {
+        let patch_mode = self.patch_mode();
+        ui.horizontal(|ui| {
+            if ui
+                .selectable_label(!self.body_hex && patch_mode.is_none(), "Text")
+                .clicked()
+            {
+                self.leave_hex_body();
+                if patch_mode.is_some() {
+                    self.headers_text = crate::utils::set_header_line(
+                        &self.headers_text,
+                        "Content-Type",
+                        "application/json",
+                    );
+                }
+            }
+            if self.method == HttpMethod::PATCH {
+                let modes = [
+                    (
+                        MERGE_PATCH_CONTENT_TYPE,
+                        "Merge Patch",
+                        "JSON Merge Patch (RFC 7386): just the fields to change",
+                    ),
+                    (
+                        JSON_PATCH_CONTENT_TYPE,
+                        "JSON Patch",
+                        "JSON Patch (RFC 6902): a list of add, remove, replace, move, copy, and test operations",
+                    ),
+                ];
+                for (content_type, label, hover) in modes {
+                    if ui
+                        .selectable_label(patch_mode == Some(content_type), label)
+                        .on_hover_text(hover)
+                        .clicked()
+                        && patch_mode != Some(content_type)
+                    {
+                        self.leave_hex_body();
+                        self.headers_text = crate::utils::set_header_line(
+                            &self.headers_text,
+                            "Content-Type",
+                            content_type,
+                        );
+                    }
+                }
+            }
+            if ui
+                .selectable_label(self.body_hex, "Binary (hex)")
+                .on_hover_text("Send the bytes typed as hex, like 48 65 6c 6c 6f")
+                .clicked()
+                && !self.body_hex
+            {
+                self.body_hex = true;
+                self.set_body(crate::utils::format_hex(self.body_text.as_bytes()));
+            }
+        });
+        if patch_mode == Some(MERGE_PATCH_CONTENT_TYPE) {
+            ui.label(
+                egui::RichText::new("Only the fields to change; null removes a field")
+                    .size(FontSize::SM)
+                    .color(Colors::TEXT_MUTED),
+            );
+        }
+        ui.add_space(Spacing::XS);
+    }
+
+    /// JSON Patch body: one row per operation, or the raw array. The body is
+    /// checked as it changes; it's checked again, with variables filled in, when sent.
+    fn render_json_patch_body(&mut self, ui: &mut Ui) {
+        self.render_body_mode(ui);
+
+        if self.body_text != self.json_patch_body {
+            self.json_patch_body = self.body_text.clone();
+            match parse_json_patch(&self.body_text) {
+                Ok(ops) => {
+                    self.json_patch_ops = ops;
+                    self.json_patch_check = Ok(());
+                    self.json_patch_unparsed = false;
+                }
+                Err(e) => {
+                    self.json_patch_check = Err(e);
+                    self.json_patch_unparsed = true;
+                }
+            }
+        }
+
+        ui.checkbox(&mut self.json_patch_raw, "Edit as text")
+            .on_hover_text("Edit the JSON Patch array directly");
+        ui.add_space(Spacing::SM);
+
+        // A body the rows can't show is edited as text until it parses
+        if self.json_patch_raw || self.json_patch_unparsed {
+            let output = egui::TextEdit::multiline(&mut self.body_text)
+                .id_salt("json_patch_text")
+                .font(egui::TextStyle::Monospace)
+                .hint_text(
+                    egui::RichText::new(
+                        r#"[{"op": "replace", "path": "/name", "value": "Alice"}]"#,
+                    )
+                    .color(Colors::PLACEHOLDER),
+                )
+                .desired_width(ui.available_width())
+                .desired_rows(15)
+                .frame(false)
+                .show(ui);
+            if output.response.changed() {
+                self.body_generation += 1;
+            }
+            self.show_variable_hover(hovered_variable(&output, &self.body_text));
+        } else {
+            let mut changed = false;
+            let mut removed = None;
+            egui::Grid::new("json_patch_ops")
+                .num_columns(4)
+                .spacing([Spacing::SM, Spacing::XS])
+                .show(ui, |ui| {
+                    for (i, op) in self.json_patch_ops.iter_mut().enumerate() {
+                        egui::ComboBox::from_id_salt(("json_patch_op", i))
+                            .selected_text(op.op.as_str())
+                            .width(80.0)
+                            .show_ui(ui, |ui| {
+                                for kind in PatchOpKind::ALL {
+                                    changed |= ui
+                                        .selectable_value(&mut op.op, kind, kind.as_str())
+                                        .changed();
+                                }
+                            });
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut op.path)
+                                    .font(egui::TextStyle::Monospace)
+                                    .hint_text("/path")
+                                    .desired_width(160.0),
+                            )
+                            .changed();
+                        if op.op.takes_from() {
+                            changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut op.from)
+                                        .font(egui::TextStyle::Monospace)
+                                        .hint_text("from /path")
+                                        .desired_width(200.0),
+                                )
+                                .changed();
+                        } else if op.op.takes_value() {
+                            let error = op.value_error();
+                            let mut edit = egui::TextEdit::singleline(&mut op.value)
+                                .font(egui::TextStyle::Monospace)
+                                .hint_text(r#""value""#)
+                                .desired_width(200.0);
+                            if error.is_some() {
+                                edit = edit.text_color(Colors::ERROR);
+                            }
+                            changed |= ui
+                                .add(edit)
+                                .on_hover_text(
+                                    error.unwrap_or("JSON, like \"Alice\", 42, or {\"a\": 1}"),
+                                )
+                                .changed();
+                        } else {
+                            ui.label("");
+                        }
+                        if ui
+                            .small_button(Icons::DELETE)
+                            .on_hover_text("Remove operation")
+                            .clicked()
+                        {
+                            removed = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = removed {
+                self.json_patch_ops.remove(i);
+                changed = true;
+            }
+            if ui
+                .small_button(format!("{} Add operation", Icons::ADD))
+                .clicked()
+            {
+                self.json_patch_ops.push(PatchOp::default());
+                changed = true;
+            }
+            if changed {
+                self.set_body(to_json_patch(&self.json_patch_ops));
+                self.json_patch_body = self.body_text.clone();
+                self.json_patch_check = check_json_patch(&self.body_text);
+            }
+        }
+
+        ui.add_space(Spacing::XS);
+        match &self.json_patch_check {
+            Ok(()) => {
+                let count = self.json_patch_ops.len();
+                let noun = if count == 1 {
+                    "operation"
+                } else {
+                    "operations"
+                };
+                ui.label(
+                    egui::RichText::new(format!("{} {}", count, noun))
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED),
+                );
+            }
+            Err(e) => {
+                ui.label(
+                    egui::RichText::new(format!("{} {}", Icons::WARNING, e))
+                        .size(FontSize::SM)
+                        .color(Colors::ERROR),
+                );
+            }
+        }
+    }
+
+    /// Hex body: the bytes to send as hex digits, checked as they're typed,
+    /// or loaded from a file
+    fn render_hex_body(&mut self, ui: &mut Ui) {
+        self.render_body_mode(ui);
+
+        let output = egui::TextEdit::multiline(&mut self.body_text)
+            .id_salt("hex_body_editor")
+            .hint_text(egui::RichText::new("48 65 6c 6c 6f").color(Colors::PLACEHOLDER))
+            .font(egui::TextStyle::Monospace)
+            .desired_width(ui.available_width())
+            .desired_rows(15)
+            .frame(false)
+            .show(ui);
+        if output.response.changed() {
+            self.body_generation += 1;
+        }
+        self.show_variable_hover(hovered_variable(&output, &self.body_text));
+
+        ui.add_space(Spacing::XS);
+        ui.horizontal(|ui| {
+            // Variables only become hex digits when the request is sent
+            if self.body_text.contains("{{") {
+                ui.label(
+                    egui::RichText::new("Checked when sent, after variables are filled in")
+                        .size(FontSize::SM)
+                        .color(Colors::TEXT_MUTED),
+                );
+            } else {
+                match crate::utils::parse_hex(&self.body_text) {
+                    Ok(bytes) => size_metric(ui, bytes.len()),
+                    Err(e) => {
+                        ui.label(
+                            egui::RichText::new(format!("{} {}", Icons::WARNING, e))
+                                .size(FontSize::SM)
+                                .color(Colors::ERROR),
+                        );
+                    }
+                }
+            }
+
+            if ui
+                .small_button("Load file...")
+                .on_hover_text("Replace the body with a file's bytes")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new().pick_file() {
+                    let max = crate::core::constants::MAX_HEX_BODY_BYTES;
+                    let message = match std::fs::read(&path) {
+                        Ok(bytes) if bytes.len() > max => Some(format!(
+                            "{} is larger than {}",
+                            path.display(),
+                            crate::utils::format_bytes(max)
+                        )),
+                        Ok(bytes) => {
+                            self.set_body(crate::utils::format_hex(&bytes));
+                            None
+                        }
+                        Err(e) => Some(format!("Couldn't read {}: {}", path.display(), e)),
+                    };
+                    if let Some(message) = message {
+                        self.last_action_message =
+                            Some((message, ui.ctx().input(|i| i.time), true));
+                    }
+                }
+            }
+        });
+    }
+
+    /// Form-urlencoded body: decoded fields in a key-value table, or the raw text
+    fn render_form_body(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.form_body_raw, "Edit as text")
+            .on_hover_text("Edit the encoded body directly");
+        ui.add_space(Spacing::SM);
+
+        if self.form_body_raw {
+            let output = ui.add(
+                egui::TextEdit::multiline(&mut self.body_text)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text(
+                        egui::RichText::new("username=john&password=secret")
+                            .color(Colors::PLACEHOLDER),
+                    )
+                    .desired_width(ui.available_width())
+                    .desired_rows(15)
+                    .frame(false),
+            );
+            if output.changed() {
+                self.body_generation += 1;
+            }
+            return;
+        }
+
+        // Disabled fields aren't in the body, so the rows are only rebuilt when
+        // the body was changed some other way
+        if crate::utils::build_form_body(&self.form_fields) != self.body_text.trim() {
+            self.form_fields = crate::utils::parse_form_body(&self.body_text);
+        }
+        if !self.form_fields_bulk_edit {
+            let rows: Vec<KeyValueRow> = self
+                .form_fields
+                .iter()
+                .map(|f| KeyValueRow::new(f.enabled, f.key.clone(), f.value.clone()))
+                .collect();
+            self.form_fields_text = rows_to_text(&rows, "=");
+        }
+
+        let result = key_value_editor(
+            ui,
+            &mut self.form_fields_text,
+            "=",
+            &mut self.form_fields_bulk_edit,
+            "username=john\npassword={{PASSWORD}}",
+        );
+        self.show_variable_hover(result.hovered_var);
+
+        // Disabled rows are left out of the body, like disabled query params
+        if result.changed {
+            self.form_fields = parse_text_to_rows(&self.form_fields_text, "=")
+                .into_iter()
+                .map(|r| crate::utils::QueryParam {
+                    enabled: r.enabled,
+                    key: r.key,
+                    value: r.value,
+                })
+                .collect();
+            self.set_body(crate::utils::build_form_body(&self.form_fields));
+        }
+
+        let vars: std::collections::HashSet<String> = self
+            .form_fields
+            .iter()
+            .flat_map(|f| {
+                let mut vars = super::app::MercuryApp::extract_variables(&f.key);
+                vars.extend(super::app::MercuryApp::extract_variables(&f.value));
+                vars
+            })
+            .collect();
+        if !vars.is_empty() {
+            ui.add_space(Spacing::SM);
+            ui.horizontal_wrapped(|ui| {
+                for var in vars {
+                    variable_indicator(ui, &var, self.env_variables.contains_key(&var));
+                    ui.add_space(Spacing::SM);
+                }
+            });
+        }
+    }
+
+    /// Query parameters editor with key-value table and URL sync
+    fn render_query_params(&mut self, ui: &mut Ui) {
+        // Convert query_params to params_text from URL bar changes (if not in bulk edit mode)
+        if !self.params_bulk_edit {
+            // Convert QueryParams to KeyValueRows then to text
+            let rows: Vec<KeyValueRow> = self
+                .query_params
+                .iter()
+                .filter(|p| !p.key.is_empty())
+                .map(|p| KeyValueRow::new(p.enabled, p.key.clone(), p.value.clone()))
+                .collect();
+            self.params_text = rows_to_text(&rows, "=");
+        }
+
+        // Use the reusable key-value text editor with "=" separator
+        let result = key_value_editor(
+            ui,
+            &mut self.params_text,
+            "=",
+            &mut self.params_bulk_edit,
+            "key=value\npage=1\n# disabled=param",
+        );
+        self.show_variable_hover(result.hovered_var);
+
+        // Sync params_text back to query_params and URL if changed
+        if result.changed {
+            // Use the helper to parse text into rows, then convert to QueryParams
+            self.query_params = parse_text_to_rows(&self.params_text, "=")
+                .into_iter()
+                .map(|r| crate::utils::QueryParam {
+                    enabled: r.enabled,
+                    key: r.key,
+                    value: r.value,
+                })
+                .collect();
+
+            // Rebuild URL from params
+            self.url = crate::utils::build_url_with_params(&self.url, &self.query_params);
+        }
+
+        // Show variable indicators for params
+        let all_vars: Vec<String> = self
+            .query_params
+            .iter()
+            .flat_map(|p| {
+                let mut vars = super::app::MercuryApp::extract_variables(&p.key);
+                vars.extend(super::app::MercuryApp::extract_variables(&p.value));
+                vars
+            })
+            .collect();
+
+        if !all_vars.is_empty() {
+            ui.add_space(Spacing::SM);
+            ui.horizontal_wrapped(|ui| {
+                let unique_vars: std::collections::HashSet<_> = all_vars.into_iter().collect();
+                for var in unique_vars {
+                    variable_indicator(ui, &var, self.env_variables.contains_key(&var));
+                    ui.add_space(Spacing::SM);
+                }
+            });
+        }
+    }
+}
+
+/// The request behind the response, collapsed by default. With `mask_secrets`,
+/// literal credentials in the URL, headers, and body show as placeholders.
+/// Returns true when "Copy as cURL" was clicked.
+fn render_response_request(ui: &mut Ui, sent: &ResponseRequest, mask_secrets: bool) -> bool {
+    let mut copy = false;
+    let (url, headers, body) = if mask_secrets {
+        (&sent.masked.0, &sent.masked.1, &sent.masked.2)
+    } else {
+        (&sent.url, &sent.headers, &sent.body)
+    };
+    egui::CollapsingHeader::new(
+        egui::RichText::new("Request")
+            .size(FontSize::SM)
+            .color(Colors::TEXT_SECONDARY),
+    )
+    .id_salt("response_request")
+    .default_open(false)
+    .show(ui, |ui| {
+        ui.horizontal(|ui| {
+            let note = if sent.resolved {
+                "As sent, with variables filled in"
+            } else {
+                "As saved in history, before variables were filled in"
+            };
+            ui.label(
+                egui::RichText::new(note)
+                    .size(FontSize::XS)
+                    .color(Colors::TEXT_MUTED),
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                copy = ui
+                    .small_button("Copy as cURL")
+                    .on_hover_text("Asks before including any secret values")
+                    .clicked();
+            });
+        });
+
+        egui::Frame::NONE
+            .fill(Colors::BG_CODE)
+            .corner_radius(Radius::SM)
+            .inner_margin(Spacing::SM)
+            .show(ui, |ui| {
+                ScrollArea::vertical()
+                    .id_salt("response_request_scroll")
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        ui.set_min_width(ui.available_width());
+                        ui.label(
+                            egui::RichText::new(format!("{} {}", sent.method.as_str(), url))
+                                .size(FontSize::XS)
+                                .color(Colors::method_color(sent.method.as_str()))
+                                .monospace(),
+                        );
+                        for (key, value) in headers {
+                            ui.label(
+                                egui::RichText::new(format!("{}: {}", key, value))
+                                    .size(FontSize::XS)
+                                    .color(Colors::TEXT_SECONDARY)
+                                    .monospace(),
+                            );
+                        }
+                        if !body.is_empty() {
+                            ui.add_space(Spacing::XS);
+                            ui.label(
+                                egui::RichText::new(body)
+                                    .size(FontSize::XS)
+                                    .color(Colors::TEXT_PRIMARY)
+                                    .monospace(),
+                            );
+                        }
+                    });
+            });
+    });
+    copy
+}
+
+/// Render the auth header preview with monospace styling
+/// Used by Basic and Bearer auth modes to show the generated header
+fn render_auth_preview(ui: &mut Ui, ctx: &egui::Context, auth_text: &str, mask_secrets: bool) {
+    // With masking on, Basic credentials stay encoded until revealed (safe for screen-shares)
+    let decoded = crate::utils::decode_basic_auth(auth_text);
+    let reveal_id = egui::Id::new("auth_preview_reveal");
+    let mut revealed: bool = ctx.memory(|m| m.data.get_temp(reveal_id).unwrap_or(!mask_secrets));
+
+    egui::Frame::NONE
+        .fill(Colors::BG_CODE)
+        .corner_radius(Radius::SM)
+        .inner_margin(Spacing::SM)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
                     egui::RichText::new("Authorization: ")
                         .size(FontSize::XS)
                         .color(Colors::PRIMARY)
                         .monospace(),
                 );
-                ui.label(
+                let value_label = ui.label(
                     egui::RichText::new(auth_text)
                         .size(FontSize::XS)
                         .color(Colors::TEXT_SECONDARY)
                         .monospace(),
                 );
+                if let Some(decoded) = &decoded {
+                    if revealed {
+                        value_label.on_hover_text(decoded);
+                    }
+                }
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if copy_icon_button(ui, ctx, "auth_preview_copy") {
                         ctx.copy_text(format!("Authorization: {}", auth_text));
                     }
+                    if decoded.is_some() {
+                        let label = if revealed { "Hide" } else { "Reveal" };
+                        if ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new(label)
+                                        .size(FontSize::XS)
+                                        .color(Colors::TEXT_MUTED),
+                                )
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .accessible_button("Show decoded credentials")
+                            .on_hover_text("Show the decoded user:pass")
+                            .clicked()
+                        {
+                            revealed = !revealed;
+                            ctx.memory_mut(|m| m.data.insert_temp(reveal_id, revealed));
+                        }
+                    }
                 });
             });
+
+            if let (Some(decoded), true) = (&decoded, revealed) {
+                ui.label(
+                    egui::RichText::new(format!("Decoded: {}", decoded))
+                        .size(FontSize::XS)
+                        .color(Colors::TEXT_MUTED)
+                        .monospace(),
+                );
+            }
         });
 }
 
@@ -0,0 +1,155 @@
+//! Tree Selection Module
+//!
+//! Multi-selection for the collection tree: Cmd/Ctrl+click toggles an item,
+//! Shift+click selects the visible range from the last clicked item.
+
+use egui::Modifiers;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+#[derive(Default)]
+pub struct TreeSelection {
+    pub items: HashSet<PathBuf>,
+    /// Last item clicked; the fixed end of a Shift+click range
+    anchor: Option<PathBuf>,
+    /// Visible rows in display order as of the previous frame
+    rows: Vec<PathBuf>,
+    next_rows: Vec<PathBuf>,
+}
+
+impl TreeSelection {
+    /// Call before rendering the tree; rows pushed last frame become the range order
+    pub fn begin_frame(&mut self) {
+        self.rows = std::mem::take(&mut self.next_rows);
+    }
+
+    /// Record a visible row, in display order
+    pub fn push_row(&mut self, path: &Path) {
+        self.next_rows.push(path.to_path_buf());
+    }
+
+    /// Update the selection for a click on `path`. Returns true when the click was a
+    /// selection gesture, so the row shouldn't also open or expand.
+    pub fn click(&mut self, path: &Path, modifiers: Modifiers) -> bool {
+        if modifiers.shift {
+            let anchor = self.anchor.as_deref().unwrap_or(path);
+            let start = self.rows.iter().position(|p| p == anchor);
+            let end = self.rows.iter().position(|p| p == path);
+            if let (Some(start), Some(end)) = (start, end) {
+                self.items = self.rows[start.min(end)..=start.max(end)]
+                    .iter()
+                    .cloned()
+                    .collect();
+                return true;
+            }
+        }
+
+        if modifiers.command || modifiers.shift {
+            if !self.items.remove(path) {
+                self.items.insert(path.to_path_buf());
+            }
+            self.anchor = Some(path.to_path_buf());
+            return true;
+        }
+
+        self.items.clear();
+        self.anchor = Some(path.to_path_buf());
+        false
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.items.contains(path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Selected paths in sorted order, without any whose parent folder is also
+    /// selected (acting on the folder already covers them)
+    pub fn top_level(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self
+            .items
+            .iter()
+            .filter(|p| {
+                !self
+                    .items
+                    .iter()
+                    .any(|other| other != *p && p.starts_with(other))
+            })
+            .cloned()
+            .collect();
+        paths.sort();
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection_with_rows(rows: &[&str]) -> TreeSelection {
+        let mut selection = TreeSelection::default();
+        for row in rows {
+            selection.push_row(Path::new(row));
+        }
+        selection.begin_frame();
+        selection
+    }
+
+    #[test]
+    fn test_command_click_toggles() {
+        let mut selection = selection_with_rows(&["/a", "/b"]);
+
+        assert!(!selection.click(Path::new("/a"), Modifiers::NONE));
+        assert!(selection.is_empty());
+
+        assert!(selection.click(Path::new("/a"), Modifiers::COMMAND));
+        assert!(selection.click(Path::new("/b"), Modifiers::COMMAND));
+        assert_eq!(selection.len(), 2);
+
+        selection.click(Path::new("/a"), Modifiers::COMMAND);
+        assert!(!selection.contains(Path::new("/a")));
+        assert!(selection.contains(Path::new("/b")));
+    }
+
+    #[test]
+    fn test_shift_click_selects_range() {
+        let mut selection = selection_with_rows(&["/a", "/b", "/c", "/d"]);
+
+        selection.click(Path::new("/d"), Modifiers::NONE);
+        assert!(selection.click(Path::new("/b"), Modifiers::SHIFT));
+
+        let mut items: Vec<_> = selection.items.iter().cloned().collect();
+        items.sort();
+        assert_eq!(
+            items,
+            vec![
+                PathBuf::from("/b"),
+                PathBuf::from("/c"),
+                PathBuf::from("/d")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_top_level_skips_nested() {
+        let mut selection = TreeSelection::default();
+        for path in ["/ws/users", "/ws/users/list.json", "/ws/health.json"] {
+            selection.items.insert(PathBuf::from(path));
+        }
+
+        assert_eq!(
+            selection.top_level(),
+            vec![PathBuf::from("/ws/health.json"), PathBuf::from("/ws/users")]
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Settings Module
+//!
+//! The Settings modal: one place for user preferences, persisted in `AppState` on close.
+
+use super::app::MercuryApp;
+use super::components::show_modal;
+use super::icons::Icons;
+use super::shortcuts::{combo_label, record_combo, Recorded};
+use super::theme::{Colors, FontSize, Spacing};
+use crate::core::constants::{MAX_RUN_CONCURRENCY, WORKSPACE_CONFIG_FILE};
+use crate::core::keybindings::{Action, Keymap, KeymapPreset};
+use crate::core::types::{JsonIndent, ResponseView};
+use crate::parser::HttpMethod;
+use egui::{self, Context, RichText, Ui};
+
+/// Section heading inside the Settings modal
+fn section_heading(ui: &mut Ui, title: &str) {
+    ui.add_space(Spacing::SM);
+    ui.label(RichText::new(title).size(FontSize::MD).strong());
+    ui.add_space(Spacing::XS);
+}
+
+impl MercuryApp {
+    /// Render the Settings modal; changes apply live and are saved when it closes
+    pub fn render_settings_modal(&mut self, ctx: &Context) {
+        let was_open = self.show_settings;
+        let json_format = self.settings.json_format.clone();
+
+        // Record before the modal sees the key, so Esc cancels instead of closing it
+        if let Some(action) = self.recording_binding.filter(|_| self.show_settings) {
+            match record_combo(ctx) {
+                Some(Recorded::Combo(combo)) => {
+                    self.settings.keybindings.set(action, combo);
+                    self.recording_binding = None;
+                }
+                Some(Recorded::Cancelled) => self.recording_binding = None,
+                Some(Recorded::NeedsModifier) | None => {}
+            }
+        }
+
+        let extra_window = self.extra_window;
+        self.show_settings = show_modal(ctx, "Settings", self.show_settings, |ui, open| {
+            if extra_window {
+                ui.label(
+                    RichText::new(
+                        "Changes here last until this window closes. Change settings in the first window to keep them.",
+                    )
+                    .size(FontSize::SM)
+                    .color(Colors::WARNING),
+                );
+                ui.add_space(Spacing::SM);
+            }
+            egui::ScrollArea::vertical()
+                .max_height(420.0)
+                .show(ui, |ui| {
+                    section_heading(ui, "General");
+                    egui::Grid::new("settings_general")
+                        .num_columns(2)
+                        .spacing([Spacing::XL, Spacing::SM])
+                        .show(ui, |ui| {
+                            ui.label("Auto-save");
+                            ui.checkbox(
+                                &mut self.settings.auto_save,
+                                "Save request files while editing",
+                            );
+                            ui.end_row();
+
+                            ui.label("Content-Type hints");
+                            ui.checkbox(
+                                &mut self.settings.content_type_hints,
+                                "Suggest a Content-Type that matches the body",
+                            );
+                            ui.end_row();
+
+                            ui.label("JSON indent");
+                            ui.horizontal(|ui| {
+                                for indent in JsonIndent::ALL {
+                                    let format = &mut self.settings.json_format;
+                                    if ui
+                                        .selectable_label(format.indent == indent, indent.label())
+                                        .clicked()
+                                    {
+                                        format.indent = indent;
+                                    }
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label("JSON keys");
+                            ui.checkbox(
+                                &mut self.settings.json_format.sort_keys,
+                                "Sort alphabetically when formatting",
+                            );
+                            ui.end_row();
+
+                            ui.label("Response view");
+                            ui.horizontal(|ui| {
+                                for (view, label) in [
+                                    (None, "Last used"),
+                                    (Some(ResponseView::Pretty), "Pretty"),
+                                    (Some(ResponseView::Raw), "Raw"),
+                                ] {
+                                    let selected = self.settings.response_view == view;
+                                    if ui.selectable_label(selected, label).clicked() {
+                                        self.settings.response_view = view;
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("The view each new response opens in");
+                            ui.end_row();
+
+                            ui.label("New request method");
+                            ui.horizontal_wrapped(|ui| {
+                                for method in [
+                                    HttpMethod::GET,
+                                    HttpMethod::POST,
+                                    HttpMethod::PUT,
+                                    HttpMethod::PATCH,
+                                    HttpMethod::DELETE,
+                                ] {
+                                    let defaults = &mut self.settings.request_defaults;
+                                    let selected = defaults.method == method;
+                                    if ui.selectable_label(selected, method.as_str()).clicked() {
+                                        defaults.method = method;
+                                    }
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label("New request headers");
+                            ui.add(
+                                egui::TextEdit::multiline(
+                                    &mut self.settings.request_defaults.headers,
+                                )
+                                .font(egui::TextStyle::Monospace)
+                                .hint_text("Accept: application/json")
+                                .desired_rows(3),
+                            );
+                            ui.end_row();
+                        });
+
+                    if self.workspace_request_defaults.is_some() {
+                        ui.label(
+                            RichText::new(format!(
+                                "This workspace's {} overrides the new request defaults.",
+                                WORKSPACE_CONFIG_FILE
+                            ))
+                            .color(Colors::WARNING)
+                            .size(FontSize::SM),
+                        );
+                    }
+
+                    section_heading(ui, "Network");
+                    egui::Grid::new("settings_network")
+                        .num_columns(2)
+                        .spacing([Spacing::XL, Spacing::SM])
+                        .show(ui, |ui| {
+                            ui.label("Connect timeout");
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.connect_timeout_secs)
+                                    .range(1..=120)
+                                    .suffix(" s"),
+                            )
+                            .on_hover_text("How long to wait for the host to accept a connection");
+                            ui.end_row();
+
+                            ui.label("Read timeout");
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.timeout_secs)
+                                    .range(1..=600)
+                                    .suffix(" s"),
+                            )
+                            .on_hover_text(
+                                "How long to wait for the response; a request's own timeout overrides it",
+                            );
+                            ui.end_row();
+
+                            ui.label("Redirects");
+                            ui.checkbox(
+                                &mut self.settings.follow_redirects,
+                                "Follow redirects (up to 10)",
+                            );
+                            ui.end_row();
+
+                            ui.label("User-Agent");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.settings.user_agent)
+                                    .font(egui::TextStyle::Monospace)
+                                    .hint_text("Empty: send none"),
+                            )
+                            .on_hover_text("Sent when a request doesn't set its own User-Agent");
+                            ui.end_row();
+
+                            ui.label("Folder runs");
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.run_concurrency)
+                                    .range(1..=MAX_RUN_CONCURRENCY)
+                                    .suffix(" in parallel"),
+                            );
+                            ui.end_row();
+
+                            ui.label("Rate limit");
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.run_rate_limit)
+                                    .range(0..=100)
+                                    .suffix(" req/s per host"),
+                            )
+                            .on_hover_text("0 = unlimited");
+                            ui.end_row();
+
+                            let log = &mut self.settings.request_log;
+                            ui.label("Request log");
+                            ui.checkbox(&mut log.enabled, "Append every sent request to a file");
+                            ui.end_row();
+
+                            ui.label("");
+                            ui.add_enabled(
+                                log.enabled,
+                                egui::TextEdit::singleline(&mut log.path)
+                                    .font(egui::TextStyle::Monospace)
+                                    .hint_text("~/.mercury/requests.log"),
+                            )
+                            .on_hover_text("One JSON line per request; rotated at 10 MB");
+                            ui.end_row();
+                        });
+
+                    section_heading(ui, "Security");
+                    egui::Grid::new("settings_security")
+                        .num_columns(2)
+                        .spacing([Spacing::XL, Spacing::SM])
+                        .show(ui, |ui| {
+                            ui.label("Credentials");
+                            ui.checkbox(
+                                &mut self.settings.mask_secrets,
+                                "Keep Basic auth credentials hidden until revealed",
+                            );
+                            ui.end_row();
+                        });
+
+                    section_heading(ui, "Appearance");
+                    egui::Grid::new("settings_appearance")
+                        .num_columns(2)
+                        .spacing([Spacing::XL, Spacing::SM])
+                        .show(ui, |ui| {
+                            ui.label("Interface scale");
+                            if ui
+                                .add(
+                                    egui::Slider::new(&mut self.settings.ui_scale, 0.75..=1.5)
+                                        .step_by(0.05),
+                                )
+                                .changed()
+                            {
+                                ctx.set_zoom_factor(self.settings.ui_scale);
+                            }
+                            ui.end_row();
+
+                            ui.label("Sidebar");
+                            if ui
+                                .checkbox(
+                                    &mut self.settings.tree_status_badges,
+                                    "Show each request's last response status",
+                                )
+                                .changed()
+                                && self.settings.tree_status_badges
+                            {
+                                self.ensure_history_loaded();
+                            }
+                            ui.end_row();
+                        });
+
+                    section_heading(ui, "Keyboard Shortcuts");
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Preset").color(Colors::TEXT_SECONDARY));
+                        for preset in KeymapPreset::ALL {
+                            if ui.button(preset.label()).clicked() {
+                                self.settings.keybindings = Keymap::preset(preset);
+                                self.recording_binding = None;
+                            }
+                        }
+                    });
+                    ui.add_space(Spacing::XS);
+                    egui::Grid::new("settings_keybindings")
+                        .num_columns(3)
+                        .spacing([Spacing::XL, Spacing::XS])
+                        .show(ui, |ui| {
+                            for action in Action::ALL {
+                                ui.label(action.label());
+                                let recording = self.recording_binding == Some(action);
+                                let text = if recording {
+                                    "Press keys...".to_string()
+                                } else {
+                                    combo_label(&self.settings.keybindings.get(action))
+                                };
+                                if ui
+                                    .selectable_label(recording, RichText::new(text).monospace())
+                                    .on_hover_text("Click, then press the new shortcut")
+                                    .clicked()
+                                {
+                                    self.recording_binding = (!recording).then_some(action);
+                                }
+                                match self.settings.keybindings.conflict(action) {
+                                    Some(other) => {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "{} Same as {}",
+                                                Icons::WARNING,
+                                                other.label()
+                                            ))
+                                            .color(Colors::WARNING)
+                                            .size(FontSize::SM),
+                                        );
+                                    }
+                                    None => {
+                                        ui.label("");
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    if self.recording_binding.is_some() {
+                        ui.label(
+                            RichText::new("Hold ⌘/Ctrl or Alt with the key. Esc cancels.")
+                                .color(Colors::TEXT_MUTED)
+                                .size(FontSize::SM),
+                        );
+                    }
+                });
+
+            ui.add_space(Spacing::MD);
+            if ui.button("Done").clicked() {
+                *open = false;
+            }
+        });
+
+        // The response view caches its formatted body
+        if self.settings.json_format != json_format {
+            self.formatted_response_cache = None;
+        }
+
+        if was_open && !self.show_settings {
+            self.recording_binding = None;
+            self.apply_settings();
+        }
+    }
+}
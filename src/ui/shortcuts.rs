@@ -0,0 +1,85 @@
+//! Shortcuts Module
+//!
+//! Matches the configured key bindings against egui input, and records new ones
+//! from the Settings modal.
+
+use super::icons::Icons;
+use crate::core::keybindings::KeyCombo;
+use egui::{Context, InputState};
+
+/// Whether `combo` was pressed this frame. Modifiers must match exactly, so
+/// `Cmd+O` and `Cmd+Shift+O` can be bound to different actions.
+pub fn combo_pressed(input: &InputState, combo: &KeyCombo) -> bool {
+    let Some(key) = egui::Key::from_name(&combo.key) else {
+        return false;
+    };
+    input.key_pressed(key)
+        && input.modifiers.command == combo.command
+        && input.modifiers.alt == combo.alt
+        && input.modifiers.shift == combo.shift
+}
+
+/// Keys of `combo` as shown to the user, e.g. `["⌘", "Shift", "O"]`
+pub fn combo_keys(combo: &KeyCombo) -> Vec<String> {
+    let mut keys = Vec::new();
+    if combo.command {
+        keys.push(Icons::CMD_KEY);
+    }
+    if combo.alt {
+        keys.push("Alt");
+    }
+    if combo.shift {
+        keys.push("Shift");
+    }
+    keys.push(
+        egui::Key::from_name(&combo.key)
+            .map(|k| k.symbol_or_name())
+            .unwrap_or(&combo.key),
+    );
+    keys.into_iter().map(str::to_string).collect()
+}
+
+/// One-line form of `combo`, e.g. `⌘ + Shift + O`
+pub fn combo_label(combo: &KeyCombo) -> String {
+    combo_keys(combo).join(" + ")
+}
+
+/// What the binding recorder saw this frame
+pub enum Recorded {
+    Combo(KeyCombo),
+    /// Escape: keep the old binding
+    Cancelled,
+    /// A key without ⌘/Ctrl or Alt, which would fire while typing
+    NeedsModifier,
+}
+
+/// Take the next key press as a new binding. The press is consumed so it doesn't
+/// also trigger a shortcut or close the modal.
+pub fn record_combo(ctx: &Context) -> Option<Recorded> {
+    ctx.input_mut(|i| {
+        let (key, modifiers) = i.events.iter().find_map(|event| match event {
+            egui::Event::Key {
+                key,
+                pressed: true,
+                repeat: false,
+                modifiers,
+                ..
+            } => Some((*key, *modifiers)),
+            _ => None,
+        })?;
+        i.consume_key(modifiers, key);
+
+        Some(if key == egui::Key::Escape {
+            Recorded::Cancelled
+        } else if !modifiers.command && !modifiers.alt {
+            Recorded::NeedsModifier
+        } else {
+            Recorded::Combo(KeyCombo {
+                command: modifiers.command,
+                alt: modifiers.alt,
+                shift: modifiers.shift,
+                key: key.name().to_string(),
+            })
+        })
+    })
+}
@@ -86,6 +86,42 @@ impl Colors {
         }
     }
 
+    /// Color for a response status; 0 is a request that never got a response
+    pub fn status_color(status: u16) -> Color32 {
+        match status {
+            1..=299 => Self::SUCCESS,
+            300..=399 => Self::WARNING,
+            _ => Self::ERROR,
+        }
+    }
+
+    /// Color for an environment name: red for production, amber for staging
+    pub fn env_color(env_name: &str) -> Color32 {
+        if env_name.contains("prod") {
+            Self::ERROR
+        } else if env_name.contains("stag") {
+            Self::WARNING
+        } else {
+            Self::TEXT_SECONDARY
+        }
+    }
+
+    /// Stable color for a request tag chip, derived from the tag text
+    pub fn tag_color(tag: &str) -> Color32 {
+        const PALETTE: [Color32; 6] = [
+            Colors::METHOD_POST,
+            Colors::METHOD_GET,
+            Colors::METHOD_PUT,
+            Colors::METHOD_HEAD,
+            Colors::METHOD_OPTIONS,
+            Colors::JSON_BOOLEAN,
+        ];
+        let hash = tag.to_lowercase().bytes().fold(0usize, |acc, b| {
+            acc.wrapping_mul(31).wrapping_add(b as usize)
+        });
+        PALETTE[hash % PALETTE.len()]
+    }
+
     pub const SELECTED_ITEM: Color32 = Color32::from_rgb(97, 175, 239);
     pub const ERROR_FLASH: Color32 = Color32::from_rgb(220, 80, 80);
     pub const SUCCESS_FLASH: Color32 = Color32::from_rgb(100, 200, 100);
@@ -108,6 +144,7 @@ pub struct Animation;
 impl Animation {
     pub const PULSE_SPEED: f32 = 3.0; // Pulses per second
     pub const GLOW_INTENSITY: f32 = 0.4; // Max glow alpha
+    pub const SWEEP_SPEED: f32 = 0.8; // Sending bar sweeps per second
 }
 
 /// Spacing - 8px grid system
@@ -161,8 +198,6 @@ impl Indent {
     pub const SEND_BUTTON_RESERVE: f32 = 24.0;
 }
 
-pub const BYTES_PER_KB: f32 = 1024.0;
-
 /// Panel dimensions
 pub struct Layout;
 
@@ -177,10 +212,12 @@ impl Layout {
     pub const RESPONSE_MAX: f32 = 500.0;
     pub const RESPONSE_DEFAULT: f32 = 350.0;
 
+    // Request or response panel when collapsed
+    pub const COLLAPSED_PANEL_WIDTH: f32 = 28.0;
+
     // Fixed heights
     pub const TOPBAR_HEIGHT: f32 = 40.0;
     pub const STATUS_BAR_HEIGHT: f32 = 24.0;
-    pub const HEADERS_MAX_HEIGHT: f32 = 120.0;
 
     // Modal/Popup widths
     pub const MODAL_WIDTH: f32 = 420.0;